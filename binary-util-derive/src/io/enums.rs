@@ -1,20 +1,19 @@
 #![allow(dead_code)]
-use lazy_static::lazy_static;
 use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
-use quote::{format_ident, quote, ToTokens, TokenStreamExt};
-use regex::Regex;
+use quote::{format_ident, quote, TokenStreamExt};
+use std::collections::HashMap;
 use syn::{DataEnum, Error, Fields};
 
-use super::util::attrs::{parse_attribute, IoAttr};
+use super::util::attrs::{
+    bounded_generics, check_greedy_fields, doc_comment, parse_attribute, parse_fields, remote_type,
+    repr_wire, unless_skipped, validate_repr, ContainerAttr, FieldAttrs, IoAttr, VAR_REPR_TYPES,
+};
+use super::util::schema;
 
+use super::structs::deny_trailing;
 use super::AstContext;
 
-lazy_static! {
-    // this regex checks whether a repr attribute a valid repr attribute
-    static ref REG: regex::Regex = Regex::new(r"^(?:u|i)(?:size|8|16|32|32|64|128)$").unwrap();
-}
-
 /// A helper struct for parsing enum variants.
 /// This struct is used internally, however, for those who wish to modify this struct documentation is provided.
 ///
@@ -83,9 +82,15 @@ struct ParsedEnumVariant {
     /// }
     /// ```
     pub read_content: TokenStream2,
-    /// The discriminant of this variant.
+    /// Identical to `write_content`, without writing the discriminant.
+    /// This is used by `write_payload` when the enum has an external tag.
+    pub payload_write_content: TokenStream2,
+    /// The match arm returning the discriminant of this variant, used by `discriminant`.
+    /// This is empty for untagged enums.
+    pub discriminant_content: TokenStream2,
+    /// The name of the const holding the discriminant of this variant.
     /// This is automatically set by the parser.
-    pub discriminant: syn::LitInt,
+    pub discriminant: syn::Ident,
 }
 
 pub(crate) fn derive_enum(
@@ -96,6 +101,77 @@ pub(crate) fn derive_enum(
     // The name of our enum.
     let enum_name = ast_ctx.0;
 
+    // untagged enums are written without a discriminant, so they do not need a repr.
+    let container_attrs = ast_ctx.4;
+    let untagged = container_attrs.contains(&ContainerAttr::Untagged);
+    let external_tag = container_attrs.contains(&ContainerAttr::ExternalTag);
+
+    if container_attrs.contains(&ContainerAttr::DebugWire) {
+        error_stream.append_all(
+            Error::new_spanned(
+                enum_name,
+                "#[binary_io(debug_wire)] is only supported on structs.",
+            )
+            .to_compile_error(),
+        );
+        return TokenStream::new();
+    }
+
+    if container_attrs.contains(&ContainerAttr::DisplayWire) {
+        error_stream.append_all(
+            Error::new_spanned(
+                enum_name,
+                "#[binary_io(display_wire)] is only supported on structs.",
+            )
+            .to_compile_error(),
+        );
+        return TokenStream::new();
+    }
+
+    if container_attrs.contains(&ContainerAttr::Partial) {
+        error_stream.append_all(
+            Error::new_spanned(
+                enum_name,
+                "#[binary_io(partial)] is only supported on structs.",
+            )
+            .to_compile_error(),
+        );
+        return TokenStream::new();
+    }
+
+    if container_attrs.contains(&ContainerAttr::InPlace) {
+        error_stream.append_all(
+            Error::new_spanned(
+                enum_name,
+                "#[binary_io(in_place)] is only supported on structs.",
+            )
+            .to_compile_error(),
+        );
+        return TokenStream::new();
+    }
+
+    if remote_type(container_attrs).is_some() {
+        error_stream.append_all(
+            Error::new_spanned(
+                enum_name,
+                "#[binary_io(remote = \"...\")] is only supported on structs.",
+            )
+            .to_compile_error(),
+        );
+        return TokenStream::new();
+    }
+
+    if untagged && external_tag {
+        error_stream.append_all(
+            Error::new_spanned(
+                enum_name,
+                "#[binary_io(untagged)] and #[binary_io(external_tag)] can not be used together.",
+            )
+            .to_compile_error(),
+        );
+        return TokenStream::new();
+    }
+
     // get the repr attribute if it exists
     let repr = ast_ctx
         .1
@@ -110,6 +186,7 @@ pub(crate) fn derive_enum(
             // todo validate the repr
             repr
         }
+        None if untagged => format_ident!("u8"),
         None => {
             // we need to force the user to specify a repr attribute
             error_stream.append_all(
@@ -120,41 +197,55 @@ pub(crate) fn derive_enum(
         }
     };
 
-    if !REG.is_match(&repr_type.to_string()) {
+    if let Err(e) = validate_repr(&repr_type) {
+        error_stream.append_all(e.to_compile_error());
+        return TokenStream::new();
+    }
+
+    // the type the discriminant is encoded as, if it differs from the repr, and the integer type it holds.
+    let wire_repr = repr_wire(container_attrs);
+    if wire_repr.is_some() && untagged {
         error_stream.append_all(
             Error::new_spanned(
-                &repr_type,
-                "#[repr] attribute must contain a valid C type, one of: u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize"
+                enum_name,
+                "#[binary_io(repr_wire = \"...\")] can not be used on untagged enums, which have no discriminant.",
             )
-            .to_compile_error()
+            .to_compile_error(),
         );
         return TokenStream::new();
     }
+    let wire_inner = wire_repr.as_ref().map(|wire| {
+        let name = wire.to_string();
+        match VAR_REPR_TYPES.iter().find(|(var, _)| *var == name) {
+            Some((_, inner)) => format_ident!("{}", inner),
+            None => wire.clone(),
+        }
+    });
 
     let mut curr_discrim: Option<i128> = None;
+    // the last discriminant that could not be evaluated at derive time (IE: a const), if any.
+    // when this is set, `curr_discrim` is the offset from this expression instead.
+    let mut base_expr: Option<&syn::Expr> = None;
 
     let mut variants: Vec<ParsedEnumVariant> = Vec::new();
-
-    for variant in data.variants.iter() {
+    // every discriminant seen so far, along with the variant that uses it.
+    let mut discriminants: HashMap<i128, &syn::Ident> = HashMap::new();
+    // the discriminants are declared as consts so they can be used as match patterns.
+    let mut discriminant_consts = TokenStream2::new();
+    // the `VariantSchema` of every variant, used when `#[binary_io(schema)]` is set.
+    let mut schema_variants: Vec<TokenStream2> = Vec::new();
+    // the entries of `DISCRIMINANTS`, the discriminant const and name of every variant.
+    let mut discriminant_entries: Vec<TokenStream2> = Vec::new();
+
+    for (i, variant) in data.variants.iter().enumerate() {
         // parse the discriminant
         if let Some((_, expr)) = &variant.discriminant {
-            // check whether the expression is a syn::LitInt
-            match syn::parse::<syn::LitInt>(expr.to_token_stream().into()) {
-                Ok(v) => {
-                    if let Ok(discrim) = v.base10_parse::<i128>() {
-                        curr_discrim = Some(discrim);
-                    } else {
-                        error_stream.append_all(
-                            Error::new_spanned(
-                                expr,
-                                "Discriminant must be a primitive integer literal.",
-                            )
-                            .to_compile_error(),
-                        );
-                        return TokenStream::new();
-                    }
+            match literal_discriminant(expr) {
+                Some(Ok(discrim)) => {
+                    curr_discrim = Some(discrim);
+                    base_expr = None;
                 }
-                Err(_) => {
+                Some(Err(_)) => {
                     error_stream.append_all(
                         Error::new_spanned(
                             expr,
@@ -164,16 +255,88 @@ pub(crate) fn derive_enum(
                     );
                     return TokenStream::new();
                 }
+                None => {
+                    // this is an expression, which we leave to the compiler to evaluate.
+                    curr_discrim = Some(0);
+                    base_expr = Some(expr);
+                }
             }
         } else {
             // no discriminant, so we use the next discriminant
             // todo parse descend vs ascend, currently the order doesnt matter.
             curr_discrim = match curr_discrim {
-                Some(discrim) => Some(discrim + 1),
+                Some(discrim) => Some(discrim.saturating_add(1)),
                 None => Some(0),
             }
         }
 
+        let discrim = curr_discrim.unwrap();
+        let discrim_name = format_ident!("__BINARY_DISCRIMINANT_{}", i);
+
+        if untagged {
+            // untagged variants have no discriminant on the wire.
+        } else if let Some(expr) = base_expr {
+            // the value is unknown until compile time, where the compiler validates the range and uniqueness.
+            let offset = proc_macro2::Literal::i128_unsuffixed(discrim);
+            discriminant_consts.append_all(quote!(
+                const #discrim_name: #repr_type = (#expr) + #offset;
+            ));
+        } else {
+            // the discriminant must fit within the repr, otherwise it would be truncated when written.
+            let (min, max) = repr_bounds(&repr_type.to_string());
+            if discrim < min || discrim > max {
+                error_stream.append_all(
+                    Error::new_spanned(
+                        &variant.ident,
+                        format!(
+                            "Variant `{}` has the discriminant {}, which does not fit in #[repr({})] ({}..={}).",
+                            variant.ident, discrim, repr_type, min, max
+                        ),
+                    )
+                    .to_compile_error(),
+                );
+                return TokenStream::new();
+            }
+
+            // the discriminant must also fit within the type it is encoded as.
+            if let (Some(wire), Some(inner)) = (&wire_repr, &wire_inner) {
+                let (min, max) = repr_bounds(&inner.to_string());
+                if discrim < min || discrim > max {
+                    error_stream.append_all(
+                        Error::new_spanned(
+                            &variant.ident,
+                            format!(
+                                "Variant `{}` has the discriminant {}, which does not fit in #[binary_io(repr_wire = \"{}\")] ({}..={}).",
+                                variant.ident, discrim, wire, min, max
+                            ),
+                        )
+                        .to_compile_error(),
+                    );
+                    return TokenStream::new();
+                }
+            }
+
+            // two variants with the same discriminant can never be told apart when reading.
+            if let Some(existing) = discriminants.insert(discrim, &variant.ident) {
+                error_stream.append_all(
+                    Error::new_spanned(
+                        &variant.ident,
+                        format!(
+                            "Variant `{}` has the discriminant {}, which is already used by variant `{}`.",
+                            variant.ident, discrim, existing
+                        ),
+                    )
+                    .to_compile_error(),
+                );
+                return TokenStream::new();
+            }
+
+            let value = proc_macro2::Literal::i128_unsuffixed(discrim);
+            discriminant_consts.append_all(quote!(
+                const #discrim_name: #repr_type = #value;
+            ));
+        }
+
         // parse the attributes
         let attributes = variant
             .attrs
@@ -191,11 +354,20 @@ pub(crate) fn derive_enum(
         if let Some(attr) = attributes.first() {
             match *attr {
                 IoAttr::Skip => {}
-                IoAttr::Satisfy(_) | IoAttr::IfPresent(_) | IoAttr::Require(_) => {
+                IoAttr::Satisfy(_)
+                | IoAttr::IfPresent(_)
+                | IoAttr::Require(_)
+                | IoAttr::SkipIf(_)
+                | IoAttr::WriteIf(_)
+                | IoAttr::LenOf(_)
+                | IoAttr::CrcOf(_, _)
+                | IoAttr::OffsetFrom(_)
+                | IoAttr::MaxLen(_)
+                | IoAttr::AllowTrailing => {
                     error_stream.append_all(
                         Error::new_spanned(
                             &variant,
-                            "Attributes: #[satisfy], #[if_present], and #[require] are not valid on enum variants."
+                            "Attributes: #[satisfy], #[if_present], #[require], #[skip_if], #[write_if], #[len_of], #[crc_of], #[offset_from], #[max_len] and #[allow_trailing] are not valid on enum variants."
                         )
                         .to_compile_error()
                     );
@@ -221,9 +393,18 @@ pub(crate) fn derive_enum(
             return TokenStream::new();
         }
 
-        // we need to parse this indo an ident _ and a type
-        let di = format!("{}{}", curr_discrim.unwrap(), repr_type);
-        let discrim = syn::LitInt::new(&di, proc_macro2::Span::call_site());
+        let variant_name = variant.ident.to_string();
+        discriminant_entries.push(quote!((#discrim_name, #variant_name)));
+        let variant_doc = schema::doc_tokens(doc_comment(&variant.attrs));
+        let field_attrs = parse_fields(&variant.fields, error_stream);
+        let variant_fields = field_attrs
+            .iter()
+            .map(|attrs| schema::field(attrs.index.to_string(), attrs));
+        schema_variants.push(quote!(::binary_util::schema::VariantSchema {
+            name: #variant_name,
+            doc: #variant_doc,
+            fields: &[#(#variant_fields),*],
+        }));
 
         // we need to iterate through each field and parse it.
         // keep in mind, in this context we're inside of the expr within the variant
@@ -234,7 +415,9 @@ pub(crate) fn derive_enum(
         variants.push(parse_enum_variant(
             variant,
             &attributes,
-            &discrim,
+            &field_attrs,
+            &discrim_name,
+            !untagged,
             error_stream,
         ));
 
@@ -255,36 +438,251 @@ pub(crate) fn derive_enum(
         .map(|variant| variant.read_content.clone())
         .collect::<Vec<TokenStream2>>();
 
-    quote! {
-        impl ::binary_util::interfaces::Writer for #enum_name {
-            fn write(&self, _binary_writew: &mut ::binary_util::io::ByteWriter) -> ::std::result::Result<(), ::std::io::Error> {
-                match self {
-                    #(#write_streams)*
+    let read_body = if untagged {
+        // every variant is attempted in order, the first variant that reads successfully is returned.
+        quote! {
+            _binary_readerr.nested(|_binary_readerr| {
+                #(#read_streams)*
+                Err(_binary_readerr.error_at_offset(::std::io::ErrorKind::InvalidData, "No variant of untagged enum matched."))
+            })
+        }
+    } else {
+        let read_discriminant = match (&wire_repr, &wire_inner) {
+            (Some(wire), Some(inner)) => {
+                let (wire_ty, value) = match wire == inner {
+                    true => (quote!(#wire), quote!(__binary_wire)),
+                    false => (quote!(::binary_util::types::#wire), quote!(__binary_wire.0)),
+                };
+                quote! {{
+                    let __binary_wire = <#wire_ty as ::binary_util::interfaces::Reader<#wire_ty>>::read(_binary_readerr)?;
+                    <#repr_type as ::std::convert::TryFrom<#inner>>::try_from(#value).map_err(|_| {
+                        _binary_readerr.error_at_offset(::std::io::ErrorKind::InvalidData, "Invalid enum discriminant.")
+                    })?
+                }}
+            }
+            _ => quote!(<#repr_type>::read(_binary_readerr)?),
+        };
+        quote! {
+            #discriminant_consts
+            _binary_readerr.nested(|_binary_readerr| {
+                match #read_discriminant {
+                    #(#read_streams)*
+                    _ => Err(_binary_readerr.error_at_offset(::std::io::ErrorKind::InvalidData, "Invalid enum discriminant."))
+                }
+            })
+        }
+    };
+
+    let (_, ty_generics, _) = ast_ctx.2.split_for_impl();
+    let self_ty = quote!(#enum_name #ty_generics);
+    let deny_trailing = deny_trailing(container_attrs);
+    let write_generics = bounded_generics(
+        ast_ctx.2,
+        container_attrs,
+        |param| quote!(#param: ::binary_util::interfaces::Writer),
+    );
+    let (write_impl_generics, _, write_where) = write_generics.split_for_impl();
+    let read_generics = bounded_generics(
+        ast_ctx.2,
+        container_attrs,
+        |param| quote!(#param: ::binary_util::interfaces::Reader<#param>),
+    );
+    let (read_impl_generics, _, read_where) = read_generics.split_for_impl();
+
+    // untagged enums have no discriminants on the wire.
+    let discriminants_impl = if untagged {
+        TokenStream2::new()
+    } else {
+        let (impl_generics, _, where_clause) = ast_ctx.2.split_for_impl();
+        let discriminant_streams = variants
+            .iter()
+            .map(|variant| variant.discriminant_content.clone())
+            .collect::<Vec<TokenStream2>>();
+
+        quote! {
+            impl #impl_generics ::binary_util::interfaces::BinaryDiscriminant for #self_ty #where_clause {
+                type Repr = #repr_type;
+
+                const DISCRIMINANTS: &'static [(#repr_type, &'static str)] = {
+                    #discriminant_consts
+                    &[#(#discriminant_entries),*]
                 };
 
-                Ok(())
+                fn discriminant(&self) -> #repr_type {
+                    #discriminant_consts
+                    match self {
+                        #(#discriminant_streams)*
+                    }
+                }
+            }
+        }
+    };
+
+    let external_tag_impl = if external_tag {
+        let io_generics = bounded_generics(
+            ast_ctx.2,
+            container_attrs,
+            |param| quote!(#param: ::binary_util::interfaces::Writer + ::binary_util::interfaces::Reader<#param>),
+        );
+        let (io_impl_generics, _, io_where) = io_generics.split_for_impl();
+        let payload_write_streams = variants
+            .iter()
+            .map(|variant| variant.payload_write_content.clone())
+            .collect::<Vec<TokenStream2>>();
+        quote! {
+            impl #io_impl_generics #self_ty #io_where {
+                /// Writes the fields of this variant, without the discriminant.
+                pub fn write_payload(&self, _binary_writew: &mut ::binary_util::io::ByteWriter) -> ::std::result::Result<(), ::std::io::Error> {
+                    match self {
+                        #(#payload_write_streams)*
+                    };
+
+                    Ok(())
+                }
+
+                /// Reads the fields of the variant with the given discriminant, which was read separately.
+                pub fn read_variant(_binary_readerr: &mut ::binary_util::io::ByteReader, discriminant: #repr_type) -> ::std::result::Result<Self, ::std::io::Error> {
+                    #discriminant_consts
+                    _binary_readerr.nested(|_binary_readerr| {
+                        match discriminant {
+                            #(#read_streams)*
+                            _ => Err(_binary_readerr.error_at_offset(::std::io::ErrorKind::InvalidData, "Invalid enum discriminant."))
+                        }
+                    })
+                }
             }
         }
+    } else {
+        TokenStream2::new()
+    };
 
-        impl ::binary_util::interfaces::Reader<#enum_name> for #enum_name {
-            fn read(_binary_readerr: &mut ::binary_util::io::ByteReader) -> ::std::result::Result<#enum_name, ::std::io::Error> {
-                match <#repr_type>::read(_binary_readerr)? {
-                    #(#read_streams)*
-                    _ => Err(::std::io::Error::new(::std::io::ErrorKind::InvalidData, "Invalid enum discriminant."))
+    let schema_impl = if container_attrs.contains(&ContainerAttr::Schema) {
+        schema::schema_impl(
+            enum_name,
+            ast_ctx.1,
+            ast_ctx.2,
+            quote!(::binary_util::schema::SchemaKind::Enum(&[#(#schema_variants),*])),
+        )
+    } else {
+        TokenStream2::new()
+    };
+
+    // writes the discriminant of a variant, converting it to the type it is encoded as, if set.
+    let write_discriminant = match (&wire_repr, &wire_inner) {
+        _ if untagged => TokenStream2::new(),
+        (Some(wire), Some(inner)) => {
+            let wire_value = match wire == inner {
+                true => quote!(__binary_wire),
+                false => quote!(::binary_util::types::#wire(__binary_wire)),
+            };
+            quote! {
+                fn __binary_write_discriminant(_binary_writew: &mut ::binary_util::io::ByteWriter, discriminant: #repr_type) -> ::std::result::Result<(), ::std::io::Error> {
+                    let __binary_wire = <#inner as ::std::convert::TryFrom<#repr_type>>::try_from(discriminant).map_err(|_| {
+                        ::std::io::Error::new(::std::io::ErrorKind::InvalidInput, "Enum discriminant does not fit within its wire representation.")
+                    })?;
+                    _binary_writew.write_type(&#wire_value)
                 }
             }
         }
-    }.into()
+        _ => quote! {
+            fn __binary_write_discriminant(_binary_writew: &mut ::binary_util::io::ByteWriter, discriminant: #repr_type) -> ::std::result::Result<(), ::std::io::Error> {
+                _binary_writew.write(&mut ::binary_util::interfaces::Writer::write_to_bytes(&discriminant)?.as_slice())
+            }
+        },
+    };
+
+    let writer_impl = unless_skipped(
+        container_attrs,
+        ContainerAttr::SkipWriter,
+        quote! {
+            impl #write_impl_generics ::binary_util::interfaces::Writer for #self_ty #write_where {
+                fn write(&self, _binary_writew: &mut ::binary_util::io::ByteWriter) -> ::std::result::Result<(), ::std::io::Error> {
+                    #discriminant_consts
+                    #write_discriminant
+                    match self {
+                        #(#write_streams)*
+                    };
+
+                    Ok(())
+                }
+            }
+        },
+    );
+    let reader_impl = unless_skipped(
+        container_attrs,
+        ContainerAttr::SkipReader,
+        quote! {
+            impl #read_impl_generics ::binary_util::interfaces::Reader<#self_ty> for #self_ty #read_where {
+                fn read(_binary_readerr: &mut ::binary_util::io::ByteReader) -> ::std::result::Result<#self_ty, ::std::io::Error> {
+                    let _binary_value = { #read_body }?;
+                    #deny_trailing
+                    Ok(_binary_value)
+                }
+            }
+        },
+    );
+
+    quote! {
+        #discriminants_impl
+        #external_tag_impl
+        #schema_impl
+        #writer_impl
+        #reader_impl
+    }
+    .into()
+}
+
+/// Evaluates a discriminant that is an integer literal, optionally negated.
+/// Returns `None` if the discriminant is any other expression, such as a const.
+fn literal_discriminant(expr: &syn::Expr) -> Option<Result<i128, syn::Error>> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(lit),
+            ..
+        }) => Some(lit.base10_parse::<i128>()),
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => literal_discriminant(expr).map(|v| v.map(|v| -v)),
+        syn::Expr::Paren(syn::ExprParen { expr, .. })
+        | syn::Expr::Group(syn::ExprGroup { expr, .. }) => literal_discriminant(expr),
+        _ => None,
+    }
+}
+
+/// Returns the inclusive range of discriminants that fit within the given repr,
+/// which `validate_repr` has already checked is one of `REPR_TYPES`.
+fn repr_bounds(repr: &str) -> (i128, i128) {
+    match repr {
+        "u8" => (0, u8::MAX as i128),
+        "u16" => (0, u16::MAX as i128),
+        "u32" => (0, u32::MAX as i128),
+        "u64" => (0, u64::MAX as i128),
+        "i8" => (i8::MIN as i128, i8::MAX as i128),
+        "i16" => (i16::MIN as i128, i16::MAX as i128),
+        "i32" => (i32::MIN as i128, i32::MAX as i128),
+        "i64" => (i64::MIN as i128, i64::MAX as i128),
+        _ => unreachable!("#[repr({})] is not one of REPR_TYPES", repr),
+    }
 }
 
 fn parse_enum_variant(
     variant: &syn::Variant,
     _attributes: &Vec<super::util::attrs::IoAttr>,
-    curr_discrim: &syn::LitInt,
+    field_attrs: &[FieldAttrs],
+    curr_discrim: &syn::Ident,
+    tagged: bool,
     error_stream: &mut TokenStream2,
 ) -> ParsedEnumVariant {
-    let mut read_content = TokenStream2::new();
-    let mut write_content = TokenStream2::new();
+    let mut parsed = ParsedEnumVariant {
+        name: variant.ident.clone(),
+        read_content: TokenStream2::new(),
+        write_content: TokenStream2::new(),
+        payload_write_content: TokenStream2::new(),
+        discriminant_content: TokenStream2::new(),
+        discriminant: curr_discrim.clone(),
+    };
 
     let variant_name = &variant.ident;
 
@@ -296,20 +694,10 @@ fn parse_enum_variant(
 
             let mut args: Vec<syn::Ident> = Vec::new();
 
-            for (i, field) in fields.unnamed.iter().enumerate() {
-                let inner_attrs = field
-                    .attrs
-                    .iter()
-                    .filter_map(|att| match parse_attribute(&att, error_stream) {
-                        Ok(attr) => match attr {
-                            IoAttr::Unknown => None,
-                            _ => Some(attr),
-                        },
-                        Err(_) => None,
-                    })
-                    .collect::<Vec<super::util::attrs::IoAttr>>();
+            check_greedy_fields(&field_attrs.iter().collect::<Vec<_>>(), &[], error_stream);
 
-                if inner_attrs.len() != 0 {
+            for (i, (field, attrs)) in fields.unnamed.iter().zip(field_attrs).enumerate() {
+                if !attrs.io.is_empty() {
                     error_stream.append_all(
                         syn::Error::new_spanned(
                             field,
@@ -333,29 +721,25 @@ fn parse_enum_variant(
                 args.push(arg_name);
             }
 
-            write_content.append_all(quote!(
-                Self::#variant_name(#(#args),*) => {
-                    _binary_writew.write(&mut #curr_discrim.write_to_bytes()?.as_slice())?;
-                    #write_inner
-                }
-            ));
-            read_content.append_all(quote!(
-                #curr_discrim => {
-                    #read_inner
-                    Ok(Self::#variant_name(#(#args),*))
-                }
-            ));
+            append_variant(
+                quote!(Self::#variant_name(#(#args),*)),
+                quote!(Self::#variant_name(..)),
+                read_inner,
+                write_inner,
+                tagged,
+                &mut parsed,
+            );
         }
         Fields::Unit => {
             // Unit variants are easy, we just read/write the discriminant.
-            read_content.append_all(quote! {
-                #curr_discrim => Ok(Self::#variant_name),
-            });
-            write_content.append_all(quote! {
-                Self::#variant_name => {
-                    _binary_writew.write(&mut #curr_discrim.write_to_bytes()?.as_slice())?;
-                },
-            });
+            append_variant(
+                quote!(Self::#variant_name),
+                quote!(Self::#variant_name),
+                TokenStream2::new(),
+                TokenStream2::new(),
+                tagged,
+                &mut parsed,
+            );
         }
         _ => {
             error_stream.append_all(
@@ -365,10 +749,58 @@ fn parse_enum_variant(
         }
     }
 
-    ParsedEnumVariant {
-        name: variant.ident.clone(),
-        read_content,
-        write_content,
-        discriminant: syn::parse::<syn::LitInt>(quote!(#curr_discrim).into()).unwrap(),
+    parsed
+}
+
+/// Appends the match arms that read and write a single variant, where `construct` is both
+/// the pattern used when writing, and the expression used to build the variant when reading.
+/// `pattern` matches the variant without binding any of its fields.
+///
+/// Untagged variants are read by attempting to read the variant, and rolling the reader back if it fails.
+fn append_variant(
+    construct: TokenStream2,
+    pattern: TokenStream2,
+    read_inner: TokenStream2,
+    write_inner: TokenStream2,
+    tagged: bool,
+    parsed: &mut ParsedEnumVariant,
+) {
+    let curr_discrim = &parsed.discriminant;
+
+    parsed.payload_write_content.append_all(quote!(
+        #construct => {
+            #write_inner
+        }
+    ));
+
+    if tagged {
+        parsed.write_content.append_all(quote!(
+            #construct => {
+                __binary_write_discriminant(_binary_writew, #curr_discrim)?;
+                #write_inner
+            }
+        ));
+        parsed.read_content.append_all(quote!(
+            #curr_discrim => {
+                #read_inner
+                Ok(#construct)
+            }
+        ));
+        parsed.discriminant_content.append_all(quote!(
+            #pattern => #curr_discrim,
+        ));
+    } else {
+        parsed.write_content = parsed.payload_write_content.clone();
+        parsed.read_content.append_all(quote!(
+            let __binary_checkpoint = _binary_readerr.checkpoint();
+            let __binary_attempt = |_binary_readerr: &mut ::binary_util::io::ByteReader| -> ::std::result::Result<Self, ::std::io::Error> {
+                #read_inner
+                Ok(#construct)
+            };
+            match __binary_attempt(_binary_readerr) {
+                Ok(v) => return Ok(v),
+                Err(_) => _binary_readerr.restore(__binary_checkpoint),
+            }
+        ));
     }
 }
@@ -4,31 +4,90 @@ pub(crate) mod unions;
 pub(crate) mod util;
 
 use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
 use syn::{parse_macro_input, Data, DeriveInput};
+use util::attrs::{crate_path, parse_container_attributes, rewrite_crate_path, ContainerAttr};
 
+/// The name, attributes, generics and visibility of the derived type,
+/// along with its `#[binary_io(...)]` options, which are parsed once per derive.
 pub(crate) type AstContext<'a> = (
     &'a syn::Ident,
     &'a Vec<syn::Attribute>,
     &'a syn::Generics,
     &'a syn::Visibility,
+    &'a [ContainerAttr],
 );
 
 // BinaryEncoder is a derive macro that implements `::binary_util::interfaces::Reader<T>` and `::binary_util::interfaces::Writer<T>`
 pub(crate) fn binary_encoder(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
-    let ctx: AstContext = (&input.ident, &input.attrs, &input.generics, &input.vis);
-
     let mut err = proc_macro2::TokenStream::new();
 
+    let container_attrs = parse_container_attributes(&input.attrs, &mut err);
+    let ctx: AstContext = (
+        &input.ident,
+        &input.attrs,
+        &input.generics,
+        &input.vis,
+        &container_attrs,
+    );
+    let crate_path = crate_path(&container_attrs);
+    let conversions = match container_attrs.contains(&ContainerAttr::Conversions) {
+        true => conversions_impl(ctx),
+        false => TokenStream2::new(),
+    };
+
     let stream = match input.data {
         Data::Struct(d) => structs::derive_struct(ctx, d, &mut err),
         Data::Enum(d) => enums::derive_enum(ctx, d, &mut err),
         Data::Union(d) => unions::derive_union(ctx, d, &mut err),
     };
+    let stream: TokenStream =
+        TokenStream2::from_iter([TokenStream2::from(stream), conversions]).into();
 
     if err.is_empty() {
-        stream.into()
+        match crate_path {
+            Some(path) => rewrite_crate_path(stream.into(), &path).into(),
+            None => stream,
+        }
     } else {
         err.into()
     }
 }
+
+/// Implements `TryFrom<&[u8]>` for the type and `TryFrom<&Type>` for `Vec<u8>`, if `#[binary_io(conversions)]` is set.
+/// These are bounded on `Reader` and `Writer` rather than the derived impls, so they also work with `skip_reader` and `skip_writer`.
+fn conversions_impl(ctx: AstContext) -> TokenStream2 {
+    let (name, _, generics, _, _) = ctx;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let self_ty = quote!(#name #ty_generics);
+    let predicates = where_clause.map(|w| &w.predicates).into_iter().flatten();
+    let predicates = quote!(#(#predicates,)*);
+
+    quote! {
+        impl #impl_generics ::std::convert::TryFrom<&[u8]> for #self_ty
+        where
+            #self_ty: ::binary_util::interfaces::Reader<#self_ty>,
+            #predicates
+        {
+            type Error = ::std::io::Error;
+
+            fn try_from(bytes: &[u8]) -> ::std::result::Result<Self, Self::Error> {
+                <#self_ty as ::binary_util::interfaces::Reader<#self_ty>>::read_from_slice(bytes)
+            }
+        }
+
+        impl #impl_generics ::std::convert::TryFrom<&#self_ty> for ::std::vec::Vec<u8>
+        where
+            #self_ty: ::binary_util::interfaces::Writer,
+            #predicates
+        {
+            type Error = ::std::io::Error;
+
+            fn try_from(value: &#self_ty) -> ::std::result::Result<Self, Self::Error> {
+                ::binary_util::interfaces::Writer::write_to_bytes(value).map(|buf| buf.as_slice().to_vec())
+            }
+        }
+    }
+}
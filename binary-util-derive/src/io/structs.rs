@@ -1,16 +1,15 @@
-use lazy_static::lazy_static;
 use proc_macro::TokenStream;
-use proc_macro2::TokenStream as TokenStream2;
+use proc_macro2::{Group, Span, TokenStream as TokenStream2, TokenTree};
 use quote::{format_ident, quote, ToTokens, TokenStreamExt};
-use regex::Regex;
+use std::collections::HashMap;
 use syn::{DataStruct, Fields};
 
-use crate::io::util::attrs::IoAttr;
+use crate::io::util::attrs::{
+    bounded_generics, check_greedy_fields, parse_fields, remote_type, unless_skipped, wire_order,
+    BoolWidth, ContainerAttr, FieldAttrs, FieldHistory, IoAttr,
+};
 
-use super::{util::attrs::resolve_generic_type, AstContext};
-lazy_static! {
-    static ref REG: regex::Regex = Regex::new(r"((?:self\.)([\u0041-\u323AF_0-9]*))").unwrap();
-}
+use super::{util::attrs::resolve_generic_type, util::schema, AstContext};
 
 /// Derive structs will automatically implement the `BinaryReader` and `BinaryWriter` traits for the struct.
 ///
@@ -41,6 +40,43 @@ pub(crate) fn derive_struct(
     let struct_name = ast_ctx.0;
     let mut writer = TokenStream2::new();
     let mut reader = TokenStream2::new();
+    // the writer of `WireDebug`, which records the range each field was written to.
+    let mut wire = TokenStream2::new();
+    // the body of `Writer::write_into_stream`, which writes each field to `_binary_stream` in turn.
+    let mut stream = TokenStream2::new();
+    let container_attrs = ast_ctx.4;
+    let debug_wire = container_attrs.contains(&ContainerAttr::DebugWire);
+    let partial = container_attrs.contains(&ContainerAttr::Partial);
+    let in_place = container_attrs.contains(&ContainerAttr::InPlace);
+    // the body of `Reader::read_into`, used when `#[binary_io(in_place)]` is set.
+    let mut in_place_reader = TokenStream2::new();
+    let deny_trailing = deny_trailing(container_attrs);
+    // the name and value of every written field, used when `#[binary_io(display_wire)]` is set.
+    let mut display_fields: Vec<(String, TokenStream2)> = Vec::new();
+    // the `FieldSchema` of every field that is encoded, used when `#[binary_io(schema)]` is set.
+    let mut schema_fields: Vec<TokenStream2> = Vec::new();
+    // adjacent `#[bool_width(bit)]` fields waiting to be packed into a shared byte.
+    let mut bits: Vec<(TokenStream2, syn::Ident, String)> = Vec::new();
+    let remote_impl = remote_glue(&ast_ctx, container_attrs, &data.fields);
+    let (_, ty_generics, _) = ast_ctx.2.split_for_impl();
+    let self_ty = quote!(#struct_name #ty_generics);
+    let write_generics = bounded_generics(
+        ast_ctx.2,
+        container_attrs,
+        |param| quote!(#param: ::binary_util::interfaces::Writer),
+    );
+    let (write_impl_generics, _, write_where) = write_generics.split_for_impl();
+    let read_generics = bounded_generics(
+        ast_ctx.2,
+        container_attrs,
+        |param| quote!(#param: ::binary_util::interfaces::Reader<#param>),
+    );
+    let (read_impl_generics, _, read_where) = read_generics.split_for_impl();
+    // the fields are only recorded by `read_partial`, so `read` uses a trace that records nothing.
+    let trace = match partial {
+        true => quote!(let mut _binary_trace = ::binary_util::interfaces::ReadTrace::disabled();),
+        false => TokenStream2::new(),
+    };
 
     match data.fields {
         Fields::Named(ref fields) => {
@@ -62,21 +98,45 @@ pub(crate) fn derive_struct(
                 })
                 .collect::<Vec<&syn::Ident>>();
 
-            for field in fields.named.iter() {
-                let attributes = field
-                    .attrs
-                    .iter()
-                    .filter_map(|att| {
-                        match super::util::attrs::parse_attribute(&att, error_stream) {
-                            Ok(attr) => match attr {
-                                IoAttr::Unknown => None,
-                                IoAttr::Doc(_) => None,
-                                _ => Some(attr),
-                            },
-                            Err(_) => None,
-                        }
-                    })
-                    .collect::<Vec<super::util::attrs::IoAttr>>();
+            let field_attrs = parse_fields(&fields.named, error_stream);
+            // fields whose length is stored in another field with `#[len_of]`, mapped to that field.
+            let len_targets = field_references(
+                &field_attrs,
+                &field_names,
+                |attr| match attr {
+                    IoAttr::LenOf(target) => Some(target),
+                    _ => None,
+                },
+                "length",
+                error_stream,
+            );
+            // fields referenced by `#[crc_of]`, whose bytes are captured to compute their checksum.
+            let crc_targets = field_references(
+                &field_attrs,
+                &field_names,
+                |attr| match attr {
+                    IoAttr::CrcOf(target, _) => Some(target),
+                    _ => None,
+                },
+                "checksum",
+                error_stream,
+            );
+            // the writers of the fields referenced by `#[crc_of]`, which `display_wire` replays to compute their checksum.
+            let mut crc_writers: HashMap<&syn::Ident, TokenStream2> = HashMap::new();
+            // the fields that have been encoded so far.
+            let mut encoded: Vec<&syn::Ident> = Vec::new();
+            // checksums and offsets depend on the bytes written before them, which are not kept when streaming.
+            let mut streamable = crc_targets.is_empty();
+
+            let ordered = wire_order(&field_attrs, error_stream);
+            check_greedy_fields(
+                &ordered,
+                &len_targets.keys().collect::<Vec<_>>(),
+                error_stream,
+            );
+
+            for attrs in ordered {
+                let (field, attributes) = (attrs.field, &attrs.io);
 
                 if attributes.len() > 1 {
                     error_stream.append_all(
@@ -104,7 +164,134 @@ pub(crate) fn derive_struct(
                     return quote!().into();
                 }
 
-                if let Some(attr) = attributes.first() {
+                if !matches!(attributes.first(), Some(IoAttr::Skip | IoAttr::FromCtx)) {
+                    let name = field_name.as_ref().unwrap().to_string();
+                    schema_fields.push(schema::field(name, attrs));
+                }
+                let history = &attrs.history;
+                if !matches!(attributes.first(), Some(IoAttr::Skip | IoAttr::FromCtx))
+                    && history.removed_in.is_none()
+                {
+                    let n = field_name.as_ref().unwrap();
+                    let value = display_value(
+                        quote!(self.#n),
+                        field_type,
+                        attributes.first(),
+                        &crc_writers,
+                    );
+                    display_fields.push((n.to_string(), value));
+                }
+                if in_place {
+                    let n = field_name.as_ref().unwrap();
+                    match in_place_read(field, quote!(_binary_out.#n), attributes, history) {
+                        Ok(read) => in_place_reader.append_all(read),
+                        Err(e) => {
+                            error_stream.append_all(e.to_compile_error());
+                            return quote!().into();
+                        }
+                    }
+                }
+
+                if let Some(IoAttr::BoolWidth(BoolWidth::Bit)) = attributes.first() {
+                    if !is_bool(field_type, field.to_token_stream(), error_stream)
+                        || !is_unversioned_bit(history, field, error_stream)
+                    {
+                        return quote!().into();
+                    }
+                    let n = field_name.clone().unwrap();
+                    let wire_name = n.to_string();
+                    bits.push((quote!(self.#n), n, wire_name));
+                    continue;
+                }
+                flush_bits(
+                    &mut bits,
+                    &mut writer,
+                    &mut reader,
+                    &mut wire,
+                    &mut stream,
+                    partial,
+                );
+                let n = field_name.as_ref().unwrap();
+                encoded.push(n);
+
+                let mut field_writer = TokenStream2::new();
+                let mut field_reader = TokenStream2::new();
+                let mut direct_writer = None;
+                let checksummed = crc_targets.contains_key(n);
+                if history.removed_in.is_some() && (checksummed || len_targets.contains_key(n)) {
+                    error_stream.append_all(
+                        syn::Error::new_spanned(
+                            field,
+                            "A field removed with #[removed_in] can not have its length or checksum stored in another field!",
+                        )
+                        .to_compile_error(),
+                    );
+                    return quote!().into();
+                }
+                let (crc_start, crc_end, crc_bytes) = (
+                    format_ident!("_binary_crc_start_{}", n),
+                    format_ident!("_binary_crc_end_{}", n),
+                    format_ident!("_binary_crc_bytes_{}", n),
+                );
+                if checksummed {
+                    field_writer.append_all(quote!(let #crc_start = _binary_writew.mark();));
+                    reader.append_all(quote!(let #crc_start = _binary_readerr.checkpoint();));
+                }
+                if let Some(IoAttr::OffsetFrom(target)) = attributes.first() {
+                    streamable = false;
+                    // the offset is read from `target`, so it must have been read already.
+                    if target == n || !encoded.contains(&target) {
+                        error_stream.append_all(
+                            syn::Error::new_spanned(
+                                field,
+                                format!("A field read from the offset stored in `{}` must be encoded after `{}`!", target, target),
+                            )
+                            .to_compile_error(),
+                        );
+                        return quote!().into();
+                    }
+                }
+                if let Some(IoAttr::CrcOf(target, _)) = attributes.first() {
+                    // the checksum is computed from the bytes of `target`, so it must have been encoded already.
+                    if target == n || !encoded.contains(&target) {
+                        error_stream.append_all(
+                            syn::Error::new_spanned(
+                                field,
+                                format!("A field storing the checksum of `{}` must be encoded after `{}`!", target, target),
+                            )
+                            .to_compile_error(),
+                        );
+                        return quote!().into();
+                    }
+                }
+
+                if let Some(len_field) = len_targets.get(n) {
+                    // the length of this field is read from `len_field`, so it must have been read already.
+                    if !attributes.is_empty() || !encoded.contains(&len_field) {
+                        error_stream.append_all(
+                            syn::Error::new_spanned(
+                                field,
+                                format!("A field with its length stored in `{}` can not have other attributes, and must be encoded after `{}`!", len_field, len_field),
+                            )
+                            .to_compile_error(),
+                        );
+                        return quote!().into();
+                    }
+                    let msg = format!("Length of `{}` does not fit within `usize`.", n);
+                    field_writer.append_all(quote!(
+                        ::binary_util::interfaces::Counted::write_items(&self.#n, _binary_writew)?;
+                    ));
+                    field_reader.append_all(quote!(
+                        let _binary_count = <usize as ::std::convert::TryFrom<_>>::try_from(#len_field).map_err(|e| -> ::std::io::Error {
+                            ::binary_util::io::BinaryError::new(::std::io::ErrorKind::InvalidData, #msg)
+                                .at_offset(_binary_readerr.offset())
+                                .caused_by(e)
+                                .into()
+                        })?;
+                        let _binary_count = _binary_readerr.check_alloc(_binary_count)?;
+                        let #n = <#field_type as ::binary_util::interfaces::Counted>::read_items(_binary_readerr, _binary_count)?;
+                    ));
+                } else if let Some(attr) = attributes.first() {
                     let name = field_name.clone();
                     let n = name.clone().unwrap();
                     if let Some(v) = parse_attributes(
@@ -113,57 +300,128 @@ pub(crate) fn derive_struct(
                         field_type,
                         quote!(self.#n),
                         name.unwrap(),
-                        &mut writer,
-                        &mut reader,
+                        &mut field_writer,
+                        &mut field_reader,
                         error_stream,
                     ) {
                         return v.into();
                     }
                 } else {
                     // we don't have an attribute, so we just parse the field as normal interface type.
-                    writer.append_all(quote!(
+                    field_writer.append_all(quote!(
                         _binary_writew.write(&mut self.#field_name.write_to_bytes()?.as_slice())?;
                     ));
-                    reader.append_all(quote!(
+                    direct_writer = Some(quote!(self.#field_name));
+                    field_reader.append_all(quote!(
                         let #field_name = <#field_type>::read(_binary_readerr)?;
                     ));
                 }
+                let traced =
+                    partial && !matches!(attributes.first(), Some(IoAttr::Skip | IoAttr::FromCtx));
+                reader.append_all(traced_read(
+                    &n.to_string(),
+                    versioned_read(n, field_type, history, field_reader),
+                    traced,
+                ));
+                if checksummed {
+                    field_writer.append_all(quote!(let #crc_end = _binary_writew.mark();));
+                    reader.append_all(
+                        quote!(let #crc_bytes = _binary_readerr.read_since(&#crc_start);),
+                    );
+                    crc_writers.insert(n, field_writer.clone());
+                }
+                // only the latest layout is written, which no longer includes removed fields.
+                if history.removed_in.is_none() {
+                    let wire_name = field_name.as_ref().unwrap().to_string();
+                    append_field(
+                        wire_name,
+                        field_writer,
+                        direct_writer,
+                        &mut writer,
+                        &mut wire,
+                        &mut stream,
+                    );
+                }
             }
-            quote! {
-                impl ::binary_util::interfaces::Writer for #struct_name {
-                    fn write(&self, _binary_writew: &mut ::binary_util::io::ByteWriter) -> Result<(), ::std::io::Error> {
-                        #writer
-                        Ok(())
+            flush_bits(
+                &mut bits,
+                &mut writer,
+                &mut reader,
+                &mut wire,
+                &mut stream,
+                partial,
+            );
+            let wire_impl = wire_debug_impl(&self_ty, &write_generics, debug_wire, wire);
+            let display_impl = wire_display_impl(&ast_ctx, container_attrs, &display_fields);
+            let schema_impl = struct_schema_impl(&ast_ctx, container_attrs, &schema_fields);
+            let stream_impl = stream_impl(streamable, stream);
+            let writer_impl = unless_skipped(
+                container_attrs,
+                ContainerAttr::SkipWriter,
+                quote! {
+                    impl #write_impl_generics ::binary_util::interfaces::Writer for #self_ty #write_where {
+                        fn write(&self, _binary_writew: &mut ::binary_util::io::ByteWriter) -> Result<(), ::std::io::Error> {
+                            #writer
+                            Ok(())
+                        }
+                        #stream_impl
                     }
-                }
-                impl ::binary_util::interfaces::Reader<#struct_name> for #struct_name {
-                    fn read(_binary_readerr: &mut ::binary_util::io::ByteReader) -> Result<#struct_name, ::std::io::Error> {
-                        // println!("impl Reader for {} called!\n-> {}", stringify!(#struct_name), stringify!(#reader));
-                        #reader
-                        Ok(Self {
-                            #(#field_names),*
-                        })
+                },
+            );
+            let in_place_fn = in_place_fn(in_place, &in_place_reader, &deny_trailing);
+            let partial_impl = partial_impl(
+                partial,
+                &read_generics,
+                &self_ty,
+                &reader,
+                &deny_trailing,
+                quote!(Self { #(#field_names),* }),
+            );
+            let reader_impl = unless_skipped(
+                container_attrs,
+                ContainerAttr::SkipReader,
+                quote! {
+                    impl #read_impl_generics ::binary_util::interfaces::Reader<#self_ty> for #self_ty #read_where {
+                        fn read(_binary_readerr: &mut ::binary_util::io::ByteReader) -> Result<#self_ty, ::std::io::Error> {
+                            // println!("impl Reader for {} called!\n-> {}", stringify!(#struct_name), stringify!(#reader));
+                            #trace
+                            _binary_readerr.nested(|_binary_readerr| {
+                                #reader
+                                #deny_trailing
+                                Ok(Self {
+                                    #(#field_names),*
+                                })
+                            })
+                        }
+                        #in_place_fn
                     }
-                }
-            }.into()
+                },
+            );
+            quote! {
+                #writer_impl
+                #reader_impl
+                #partial_impl
+                #wire_impl
+                #display_impl
+                #schema_impl
+                #remote_impl
+            }
+            .into()
         }
         Fields::Unnamed(ref fields) => {
-            let mut read_names: Vec<syn::Ident> = Vec::new();
-
-            for (i, field) in fields.unnamed.iter().enumerate() {
-                let attributes = field
-                    .attrs
-                    .iter()
-                    .filter_map(|att| {
-                        match super::util::attrs::parse_attribute(&att, error_stream) {
-                            Ok(attr) => match attr {
-                                IoAttr::Unknown => None,
-                                _ => Some(attr),
-                            },
-                            Err(_) => None,
-                        }
-                    })
-                    .collect::<Vec<super::util::attrs::IoAttr>>();
+            // the names the fields are read into, in the order they are declared.
+            let read_names = (0..fields.unnamed.len())
+                .map(|i| {
+                    format_ident!("__{}_unnamed_{}", struct_name.to_string().to_lowercase(), i)
+                })
+                .collect::<Vec<syn::Ident>>();
+
+            let field_attrs = parse_fields(&fields.unnamed, error_stream);
+            let ordered = wire_order(&field_attrs, error_stream);
+            check_greedy_fields(&ordered, &[], error_stream);
+
+            for attrs in ordered {
+                let (i, field, attributes) = (attrs.index, attrs.field, &attrs.io);
 
                 if attributes.len() > 1 {
                     error_stream.append_all(
@@ -179,22 +437,63 @@ pub(crate) fn derive_struct(
                 // parse the field type
                 let field_type = &field.ty;
                 let index = syn::Index::from(i);
-                let field_name = format_ident!(
-                    "__{}_unnamed_{}",
-                    struct_name.to_string().to_lowercase(),
-                    index
-                );
+                let field_name = read_names[i].clone();
 
-                read_names.push(field_name.clone());
+                if !matches!(attributes.first(), Some(IoAttr::Skip | IoAttr::FromCtx)) {
+                    schema_fields.push(schema::field(i.to_string(), attrs));
+                }
+                let history = &attrs.history;
+                if !matches!(attributes.first(), Some(IoAttr::Skip | IoAttr::FromCtx))
+                    && history.removed_in.is_none()
+                {
+                    display_fields.push((i.to_string(), quote!(self.#index)));
+                }
+                if in_place {
+                    match in_place_read(field, quote!(_binary_out.#index), attributes, history) {
+                        Ok(read) => in_place_reader.append_all(read),
+                        Err(e) => {
+                            error_stream.append_all(e.to_compile_error());
+                            return quote!().into();
+                        }
+                    }
+                }
+
+                if let Some(IoAttr::BoolWidth(BoolWidth::Bit)) = attributes.first() {
+                    if !is_bool(field_type, field.to_token_stream(), error_stream)
+                        || !is_unversioned_bit(history, field, error_stream)
+                    {
+                        return quote!().into();
+                    }
+                    bits.push((quote!(self.#index), field_name, i.to_string()));
+                    continue;
+                }
+                flush_bits(
+                    &mut bits,
+                    &mut writer,
+                    &mut reader,
+                    &mut wire,
+                    &mut stream,
+                    partial,
+                );
 
+                let mut field_writer = TokenStream2::new();
+                let mut field_reader = TokenStream2::new();
+                let mut direct_writer = None;
                 if let Some(attr) = attributes.first() {
                     match *attr {
-                        IoAttr::Skip => {}
+                        IoAttr::Skip
+                        | IoAttr::FromCtx
+                        | IoAttr::AllowTrailing
+                        | IoAttr::MaxLen(_)
+                        | IoAttr::BoolWidth(_)
+                        | IoAttr::SkipIf(_)
+                        | IoAttr::WriteIf(_)
+                        | IoAttr::With(_) => {}
                         _ => {
                             error_stream.append_all(
                                 syn::Error::new_spanned(
                                     field,
-                                    "Unnamed fields only support the 'skip' attribute!",
+                                    "Unnamed fields only support the 'skip', 'from_ctx', 'allow_trailing', 'max_len', 'skip_if', 'write_if', 'with' and 'bool_width' attributes!",
                                 )
                                 .to_compile_error(),
                             );
@@ -206,43 +505,109 @@ pub(crate) fn derive_struct(
                         attr,
                         field_type,
                         quote!(self.#index),
-                        field_name,
-                        &mut writer,
-                        &mut reader,
+                        field_name.clone(),
+                        &mut field_writer,
+                        &mut field_reader,
                         error_stream,
                     ) {
                         return v.into();
                     }
                 } else {
                     // we don't have an attribute, so we just parse the field as normal interface type.
-                    writer.append_all(quote!(
+                    field_writer.append_all(quote!(
                         _binary_writew.write(&mut self.#index.write_to_bytes()?.as_slice())?;
                     ));
-                    reader.append_all(quote!(
+                    direct_writer = Some(quote!(self.#index));
+                    field_reader.append_all(quote!(
                         let #field_name = <#field_type>::read(_binary_readerr)?;
                     ));
                 }
+                let traced =
+                    partial && !matches!(attributes.first(), Some(IoAttr::Skip | IoAttr::FromCtx));
+                reader.append_all(traced_read(
+                    &i.to_string(),
+                    versioned_read(&field_name, field_type, history, field_reader),
+                    traced,
+                ));
+                // only the latest layout is written, which no longer includes removed fields.
+                if history.removed_in.is_none() {
+                    append_field(
+                        i.to_string(),
+                        field_writer,
+                        direct_writer,
+                        &mut writer,
+                        &mut wire,
+                        &mut stream,
+                    );
+                }
             }
+            flush_bits(
+                &mut bits,
+                &mut writer,
+                &mut reader,
+                &mut wire,
+                &mut stream,
+                partial,
+            );
+            let wire_impl = wire_debug_impl(&self_ty, &write_generics, debug_wire, wire);
+            let display_impl = wire_display_impl(&ast_ctx, container_attrs, &display_fields);
+            let schema_impl = struct_schema_impl(&ast_ctx, container_attrs, &schema_fields);
+            let stream_impl = stream_impl(true, stream);
             // let read_names: Vec<syn::Ident> = (0..fields.unnamed.len())
             //     .map(|i| syn::Ident::new(&format!("__unnamed_{}", i), proc_macro2::Span::call_site()))
             //     .collect();
-            quote! {
-                impl ::binary_util::interfaces::Writer for #struct_name {
-                    fn write(&self, _binary_writew: &mut ::binary_util::io::ByteWriter) -> ::std::result::Result<(), ::std::io::Error> {
-                        #writer
-                        Ok(())
+            let writer_impl = unless_skipped(
+                container_attrs,
+                ContainerAttr::SkipWriter,
+                quote! {
+                    impl #write_impl_generics ::binary_util::interfaces::Writer for #self_ty #write_where {
+                        fn write(&self, _binary_writew: &mut ::binary_util::io::ByteWriter) -> ::std::result::Result<(), ::std::io::Error> {
+                            #writer
+                            Ok(())
+                        }
+                        #stream_impl
                     }
-                }
-                impl ::binary_util::interfaces::Reader<#struct_name> for #struct_name {
-                    fn read(_binary_readerr: &mut ::binary_util::io::ByteReader) -> ::std::result::Result<#struct_name, ::std::io::Error> {
-                        // println!("impl Reader for {} called!\n-> {}", stringify!(#struct_name), stringify!(#reader));
-                        #reader
-                        Ok(Self(
-                            #(#read_names),*
-                        ))
+                },
+            );
+            let in_place_fn = in_place_fn(in_place, &in_place_reader, &deny_trailing);
+            let partial_impl = partial_impl(
+                partial,
+                &read_generics,
+                &self_ty,
+                &reader,
+                &deny_trailing,
+                quote!(Self(#(#read_names),*)),
+            );
+            let reader_impl = unless_skipped(
+                container_attrs,
+                ContainerAttr::SkipReader,
+                quote! {
+                    impl #read_impl_generics ::binary_util::interfaces::Reader<#self_ty> for #self_ty #read_where {
+                        fn read(_binary_readerr: &mut ::binary_util::io::ByteReader) -> ::std::result::Result<#self_ty, ::std::io::Error> {
+                            // println!("impl Reader for {} called!\n-> {}", stringify!(#struct_name), stringify!(#reader));
+                            #trace
+                            _binary_readerr.nested(|_binary_readerr| {
+                                #reader
+                                #deny_trailing
+                                Ok(Self(
+                                    #(#read_names),*
+                                ))
+                            })
+                        }
+                        #in_place_fn
                     }
-                }
-            }.into()
+                },
+            );
+            quote! {
+                #writer_impl
+                #reader_impl
+                #partial_impl
+                #wire_impl
+                #display_impl
+                #schema_impl
+                #remote_impl
+            }
+            .into()
         }
         Fields::Unit => {
             error_stream.append_all(syn::Error::new_spanned(
@@ -288,7 +653,7 @@ fn parse_attributes<'a>(
             ));
             reader.append_all(quote!(
                 if #id.is_none() {
-                    return Err(::std::io::Error::new(::std::io::ErrorKind::InvalidData, "Cannot read a field that is required but not present!"));
+                    return Err(_binary_readerr.error_at_offset(::std::io::ErrorKind::InvalidData, "Cannot read a field that is required but not present!"));
                 }
                 let #read_name = <#forced_type>::read(_binary_readerr).ok();
             ));
@@ -331,16 +696,9 @@ fn parse_attributes<'a>(
             }
 
             // this is a conditional field! it requires the expression to be true when reading or writing.
-            let expr_tokens = expr.to_token_stream().to_string();
-            let p_wexp = expr_tokens.as_str();
-
-            let (write_capture, read_capture) = (
-                &REG.replace_all(p_wexp.clone(), r"self.$2"),
-                &REG.replace_all(p_wexp.clone(), r"$2"),
-            );
             let (write_expr, read_expr) = (
-                syn::parse_str::<syn::Expr>(write_capture.as_ref()).unwrap(),
-                syn::parse_str::<syn::Expr>(read_capture.as_ref()).unwrap(),
+                call_site_expr(expr.to_token_stream(), false),
+                local_expr(expr),
             );
 
             writer.append_all(quote!(
@@ -361,6 +719,170 @@ fn parse_attributes<'a>(
             ));
             None
         }
+        IoAttr::SkipIf(expr) | IoAttr::WriteIf(expr) => {
+            // only the write side is conditional, the field is always read.
+            let condition = match attr {
+                IoAttr::SkipIf(_) => quote!(!(#expr)),
+                _ => quote!(#expr),
+            };
+            writer.append_all(quote!(
+                if #condition {
+                    _binary_writew.write(&mut #write_name.write_to_bytes()?.as_slice())?;
+                }
+            ));
+            reader.append_all(quote!(
+                let #read_name = <#ty>::read(_binary_readerr)?;
+            ));
+            None
+        }
+        IoAttr::LenOf(target) => {
+            // the value of this field is ignored when writing, the length of `target` is written instead.
+            let msg = format!(
+                "Length of `{}` does not fit within `{}`.",
+                target, read_name
+            );
+            writer.append_all(quote!(
+                let _binary_len = <#ty as ::std::convert::TryFrom<usize>>::try_from(
+                    ::binary_util::interfaces::Counted::count(&self.#target),
+                )
+                .map_err(|e| -> ::std::io::Error {
+                    ::binary_util::io::BinaryError::new(::std::io::ErrorKind::InvalidData, #msg)
+                        .caused_by(e)
+                        .into()
+                })?;
+                _binary_writew.write(&mut _binary_len.write_to_bytes()?.as_slice())?;
+            ));
+            reader.append_all(quote!(
+                let #read_name = <#ty>::read(_binary_readerr)?;
+            ));
+            None
+        }
+        IoAttr::CrcOf(target, algorithm) => {
+            // the value of this field is ignored when writing, the checksum of `target` is written instead.
+            let (start, end, bytes) = (
+                format_ident!("_binary_crc_start_{}", target),
+                format_ident!("_binary_crc_end_{}", target),
+                format_ident!("_binary_crc_bytes_{}", target),
+            );
+            let msg = format!("Checksum of `{}` does not match `{}`.", target, read_name);
+            writer.append_all(quote!(
+                let _binary_crc = ::binary_util::checksum::#algorithm(_binary_writew.written_between(#start, #end));
+                _binary_writew.write(&mut <#ty as ::std::convert::From<u32>>::from(_binary_crc).write_to_bytes()?.as_slice())?;
+            ));
+            reader.append_all(quote!(
+                let #read_name = <#ty>::read(_binary_readerr)?;
+                if #read_name != <#ty as ::std::convert::From<u32>>::from(::binary_util::checksum::#algorithm(&#bytes)) {
+                    return Err(_binary_readerr.error_at_offset(::std::io::ErrorKind::InvalidData, #msg));
+                }
+            ));
+            None
+        }
+        IoAttr::MaxLen(max) => {
+            // the length prefix is checked before the collection is read, so an oversized one is never allocated.
+            let msg = format!(
+                "`{}` holds more than {} items, the maximum set with #[max_len].",
+                read_name, max
+            );
+            writer.append_all(quote!(
+                if ::binary_util::interfaces::Counted::count(&#write_name) > #max {
+                    return Err(::std::io::Error::new(::std::io::ErrorKind::InvalidInput, #msg));
+                }
+                _binary_writew.write(&mut #write_name.write_to_bytes()?.as_slice())?;
+            ));
+            reader.append_all(quote!(
+                let _binary_count = <#ty as ::binary_util::interfaces::Counted>::read_count(_binary_readerr)?;
+                if _binary_count > #max {
+                    return Err(_binary_readerr.error_at_offset(::std::io::ErrorKind::InvalidData, #msg));
+                }
+                let _binary_count = _binary_readerr.check_alloc(_binary_count)?;
+                let #read_name = <#ty as ::binary_util::interfaces::Counted>::read_items(_binary_readerr, _binary_count)?;
+            ));
+            None
+        }
+        IoAttr::OffsetFrom(target) => {
+            // the field is written at the offset stored in `target`, padding the gap before it with zeros.
+            let msg = format!("Offset of `{}` does not fit within `usize`.", read_name);
+            let overlap_msg = format!(
+                "`{}` overlaps the bytes written before the offset stored in `{}`.",
+                read_name, target
+            );
+            writer.append_all(quote!(
+                let _binary_offset = <usize as ::std::convert::TryFrom<_>>::try_from(self.#target)
+                    .map_err(|e| -> ::std::io::Error {
+                    ::binary_util::io::BinaryError::new(::std::io::ErrorKind::InvalidData, #msg)
+                        .caused_by(e)
+                        .into()
+                })?;
+                let _binary_gap = _binary_offset
+                    .checked_sub(_binary_writew.as_slice().len())
+                    .ok_or_else(|| ::std::io::Error::new(::std::io::ErrorKind::InvalidData, #overlap_msg))?;
+                _binary_writew.write(&::std::vec![0u8; _binary_gap])?;
+                _binary_writew.write(&mut #write_name.write_to_bytes()?.as_slice())?;
+            ));
+            // the field is read from a section, and the reader is moved past it if it ends after the current position,
+            // so a field that directly follows the bytes before it is read as if it was not offset.
+            reader.append_all(quote!(
+                let _binary_offset = <usize as ::std::convert::TryFrom<_>>::try_from(#target).map_err(|e| -> ::std::io::Error {
+                    ::binary_util::io::BinaryError::new(::std::io::ErrorKind::InvalidData, #msg)
+                        .at_offset(_binary_readerr.offset())
+                        .caused_by(e)
+                        .into()
+                })?;
+                let _binary_end = _binary_readerr.offset() + _binary_readerr.as_slice().len();
+                let mut _binary_section = _binary_readerr
+                    .section(_binary_offset, _binary_end.saturating_sub(_binary_offset))?;
+                let #read_name = <#ty>::read(&mut _binary_section)?;
+                if _binary_section.offset() > _binary_readerr.offset() {
+                    _binary_readerr.seek_to(_binary_section.offset())?;
+                }
+            ));
+            None
+        }
+        IoAttr::With(mirror) => {
+            writer.append_all(quote!(
+                #mirror::write_remote(&#write_name, _binary_writew)?;
+            ));
+            reader.append_all(quote!(
+                let #read_name: #ty = #mirror::read_remote(_binary_readerr)?;
+            ));
+            None
+        }
+        IoAttr::BoolWidth(width) => {
+            if !is_bool(ty, tokens, error_stream) {
+                return quote!().into();
+            }
+            // bit widths are packed by the caller, so only byte widths are handled here.
+            match width {
+                BoolWidth::U32 => {
+                    writer.append_all(quote!(
+                        _binary_writew.write_u32(#write_name as u32)?;
+                    ));
+                    // like `read_bool`, only `0` and `1` are accepted when the reader has strict bools.
+                    reader.append_all(quote!(
+                        let #read_name = match _binary_readerr.read_u32()? {
+                            0 => false,
+                            1 => true,
+                            _ if !_binary_readerr.strict_bools() => true,
+                            _ => {
+                                return Err(_binary_readerr.error_at_offset(
+                                    ::std::io::ErrorKind::InvalidData,
+                                    ::binary_util::io::ERR_INVALID_BOOL,
+                                ));
+                            }
+                        };
+                    ));
+                }
+                _ => {
+                    writer.append_all(quote!(
+                        _binary_writew.write_bool(#write_name)?;
+                    ));
+                    reader.append_all(quote!(
+                        let #read_name = _binary_readerr.read_bool()?;
+                    ));
+                }
+            }
+            None
+        }
         IoAttr::Skip => {
             // we skip this
             writer.append_all(quote!(
@@ -372,9 +894,550 @@ fn parse_attributes<'a>(
             ));
             None
         }
+        IoAttr::AllowTrailing => {
+            writer.append_all(quote!(
+                _binary_writew.write(&mut #write_name.write_to_bytes()?.as_slice())?;
+            ));
+            reader.append_all(quote!(
+                let #read_name = _binary_readerr.allow_trailing(<#ty>::read)?;
+            ));
+            None
+        }
+        IoAttr::FromCtx => {
+            reader.append_all(quote!(
+                let #read_name: #ty = _binary_readerr
+                    .context::<#ty>()
+                    .cloned()
+                    .unwrap_or_default();
+            ));
+            None
+        }
         _ => {
             // we don't have an attribute, so we just return the tokens.
             None
         }
     }
 }
+
+/// Rewrites an expression referencing `self.field` to reference the local binding `field` instead.
+/// This is used when reading, where fields are read into local variables before `Self` is constructed.
+pub(crate) fn local_expr(expr: &syn::Expr) -> TokenStream2 {
+    call_site_expr(expr.to_token_stream(), true)
+}
+
+/// Re-spans `tokens` to the call site, as if they were written by the macro, so lints on the
+/// expression are reported the same way as the rest of the generated code.
+/// When `strip_self` is set, the `self.` prefix is removed from every field access, including within groups.
+fn call_site_expr(tokens: TokenStream2, strip_self: bool) -> TokenStream2 {
+    let tokens = tokens.into_iter().collect::<Vec<TokenTree>>();
+    let mut out = TokenStream2::new();
+    let mut i = 0;
+
+    while i < tokens.len() {
+        match (&tokens[i], tokens.get(i + 1), tokens.get(i + 2)) {
+            (
+                TokenTree::Ident(this),
+                Some(TokenTree::Punct(dot)),
+                Some(TokenTree::Ident(_) | TokenTree::Literal(_)),
+            ) if strip_self && this == "self" && dot.as_char() == '.' => {
+                // skip `self.`, the field itself is appended on the next iteration.
+                i += 2;
+                continue;
+            }
+            (TokenTree::Group(group), _, _) => {
+                let mut stripped = Group::new(
+                    group.delimiter(),
+                    call_site_expr(group.stream(), strip_self),
+                );
+                stripped.set_span(Span::call_site());
+                out.append(stripped);
+            }
+            (token, _, _) => {
+                let mut token = token.clone();
+                token.set_span(Span::call_site());
+                out.append(token);
+            }
+        }
+        i += 1;
+    }
+    out
+}
+
+/// Wraps the code reading a field, so the range of bytes it is read from is recorded as `name`
+/// in `_binary_trace`, when `#[binary_io(partial)]` is set.
+fn traced_read(name: &str, read: TokenStream2, traced: bool) -> TokenStream2 {
+    if !traced {
+        return read;
+    }
+    quote!(
+        _binary_trace.enter(#name, _binary_readerr.offset());
+        #read
+        _binary_trace.exit(_binary_readerr.offset());
+    )
+}
+
+/// Returns the code reading a field into `target` in place, for `#[binary_io(in_place)]`.
+/// Skipped fields are reset to their default, while other attributes are only supported by `read`.
+fn in_place_read(
+    field: &syn::Field,
+    target: TokenStream2,
+    attributes: &[IoAttr],
+    history: &FieldHistory,
+) -> syn::Result<TokenStream2> {
+    let ty = &field.ty;
+    match attributes.first() {
+        None if !history.is_versioned() => Ok(quote!(
+            <#ty as ::binary_util::interfaces::Reader<#ty>>::read_into(&mut #target, _binary_readerr)?;
+        )),
+        Some(IoAttr::Skip) => Ok(quote!(
+            #target = ::std::default::Default::default();
+        )),
+        _ => Err(syn::Error::new_spanned(
+            field,
+            "#[binary_io(in_place)] only supports fields without attributes, other than #[skip]!",
+        )),
+    }
+}
+
+/// Returns the `Reader::read_into` method of the struct, if `#[binary_io(in_place)]` is set.
+fn in_place_fn(
+    in_place: bool,
+    reader: &TokenStream2,
+    deny_trailing: &TokenStream2,
+) -> TokenStream2 {
+    if !in_place {
+        return TokenStream2::new();
+    }
+    quote! {
+        fn read_into(_binary_out: &mut Self, _binary_readerr: &mut ::binary_util::io::ByteReader) -> ::std::result::Result<(), ::std::io::Error> {
+            _binary_readerr.nested(|_binary_readerr| {
+                #reader
+                #deny_trailing
+                Ok(())
+            })
+        }
+    }
+}
+
+/// Implements `ReadPartial` for the struct, if `#[binary_io(partial)]` is set, where `construct`
+/// builds `Self` from the fields read by `reader`.
+fn partial_impl(
+    partial: bool,
+    read_generics: &syn::Generics,
+    self_ty: &TokenStream2,
+    reader: &TokenStream2,
+    deny_trailing: &TokenStream2,
+    construct: TokenStream2,
+) -> TokenStream2 {
+    if !partial {
+        return TokenStream2::new();
+    }
+    let (impl_generics, _, where_clause) = read_generics.split_for_impl();
+    quote! {
+        impl #impl_generics ::binary_util::interfaces::ReadPartial for #self_ty #where_clause {
+            fn read_partial(_binary_readerr: &mut ::binary_util::io::ByteReader) -> ::std::result::Result<Self, ::binary_util::interfaces::PartialRead> {
+                let mut _binary_trace = ::binary_util::interfaces::ReadTrace::recording();
+                let _binary_result = _binary_readerr.nested(|_binary_readerr| {
+                    #reader
+                    #deny_trailing
+                    Ok(#construct)
+                });
+                _binary_result.map_err(|e| _binary_trace.into_partial(e))
+            }
+        }
+    }
+}
+
+/// Wraps the code reading a field, so it is only read in the versions the field is present in.
+/// When the field is not present, it is `Default::default()`.
+///
+/// If the reader has no version set, the latest layout is read, where removed fields are not present.
+fn versioned_read(
+    name: &syn::Ident,
+    ty: &syn::Type,
+    history: &FieldHistory,
+    read: TokenStream2,
+) -> TokenStream2 {
+    if !history.is_versioned() {
+        return read;
+    }
+    let latest = history.removed_in.is_none();
+    let present = match (history.added_in, history.removed_in) {
+        (Some(added), Some(removed)) => quote!((#added..#removed).contains(&_binary_version)),
+        (Some(added), None) => quote!(_binary_version >= #added),
+        (None, Some(removed)) => quote!(_binary_version < #removed),
+        (None, None) => unreachable!(),
+    };
+    quote!(
+        let #name: #ty = if _binary_readerr.version().map_or(#latest, |_binary_version| #present) {
+            #read
+            #name
+        } else {
+            ::std::default::Default::default()
+        };
+    )
+}
+
+/// Validates that a `#[bool_width(bit)]` field is not versioned, as it shares a byte with adjacent fields.
+fn is_unversioned_bit(
+    history: &FieldHistory,
+    field: &syn::Field,
+    error_stream: &mut TokenStream2,
+) -> bool {
+    if !history.is_versioned() {
+        return true;
+    }
+    error_stream.append_all(
+        syn::Error::new_spanned(
+            field,
+            "Cannot have a field with a 'bool_width(bit)' attribute that is added or removed in a version!",
+        )
+        .to_compile_error(),
+    );
+    false
+}
+
+/// Validates that a field with the `bool_width` attribute is a `bool`.
+fn is_bool(ty: &syn::Type, tokens: TokenStream2, error_stream: &mut TokenStream2) -> bool {
+    if let syn::Type::Path(ref tp) = ty {
+        if tp.path.is_ident("bool") {
+            return true;
+        }
+    }
+    error_stream.append_all(
+        syn::Error::new_spanned(
+            tokens,
+            "Cannot have a field with a 'bool_width' attribute that is not of type 'bool'!",
+        )
+        .to_compile_error(),
+    );
+    false
+}
+
+/// Packs the pending `#[bool_width(bit)]` fields into bytes, where the first field
+/// is the least significant bit. Each byte holds up to 8 fields.
+fn flush_bits(
+    bits: &mut Vec<(TokenStream2, syn::Ident, String)>,
+    writer: &mut TokenStream2,
+    reader: &mut TokenStream2,
+    wire: &mut TokenStream2,
+    stream: &mut TokenStream2,
+    partial: bool,
+) {
+    for chunk in bits.chunks(8) {
+        let write_names = chunk.iter().map(|(name, _, _)| name);
+        let read_names = chunk.iter().map(|(_, name, _)| name);
+        let wire_name = chunk
+            .iter()
+            .map(|(_, _, name)| name.as_str())
+            .collect::<Vec<&str>>()
+            .join(", ");
+        let shifts = (0..chunk.len() as u8).collect::<Vec<u8>>();
+
+        append_field(
+            wire_name.clone(),
+            quote!(
+                let mut __binary_bits: u8 = 0;
+                #( __binary_bits |= (#write_names as u8) << #shifts; )*
+                _binary_writew.write_u8(__binary_bits)?;
+            ),
+            None,
+            writer,
+            wire,
+            stream,
+        );
+        reader.append_all(traced_read(
+            &wire_name,
+            quote!(
+                let __binary_bits = _binary_readerr.read_u8()?;
+                #( let #read_names = __binary_bits & (1 << #shifts) != 0; )*
+            ),
+            partial,
+        ));
+    }
+    bits.clear();
+}
+
+/// Appends the code writing a field to `writer`, and to `wire`, where the range of bytes
+/// it was written to is recorded as `name` for `WireDebug`.
+///
+/// The field is also appended to `stream`, which calls `write_into_stream` on `direct` if it is set,
+/// otherwise the field is written to a scratch `ByteWriter` that is then written to the stream.
+fn append_field(
+    name: String,
+    write: TokenStream2,
+    direct: Option<TokenStream2>,
+    writer: &mut TokenStream2,
+    wire: &mut TokenStream2,
+    stream: &mut TokenStream2,
+) {
+    stream.append_all(match direct {
+        Some(field) => quote!(
+            ::binary_util::interfaces::Writer::write_into_stream(&#field, _binary_stream)?;
+        ),
+        None => quote!({
+            let _binary_writew = &mut ::binary_util::io::ByteWriter::new();
+            #write
+            ::std::io::Write::write_all(_binary_stream, _binary_writew.as_slice())?;
+        }),
+    });
+    wire.append_all(quote!(
+        let __binary_start = _binary_writew.as_slice().len();
+        #write
+        _binary_fields.push(::binary_util::interfaces::WireField {
+            name: #name,
+            range: __binary_start.._binary_writew.as_slice().len(),
+        });
+    ));
+    writer.append_all(write);
+}
+
+/// Overrides `Writer::write_into_stream` with `stream`, and `write_into_dyn_stream` with it,
+/// unless the struct can not be streamed.
+fn stream_impl(streamable: bool, stream: TokenStream2) -> TokenStream2 {
+    if !streamable {
+        return TokenStream2::new();
+    }
+    quote! {
+        fn write_into_stream<__BinaryStream: ::std::io::Write + ?Sized>(&self, _binary_stream: &mut __BinaryStream) -> ::std::result::Result<(), ::std::io::Error> {
+            #stream
+            Ok(())
+        }
+
+        fn write_into_dyn_stream(&self, _binary_stream: &mut dyn ::std::io::Write) -> ::std::result::Result<(), ::std::io::Error> {
+            ::binary_util::interfaces::Writer::write_into_stream(self, _binary_stream)
+        }
+    }
+}
+
+/// Returns the check failing `read` if bytes remain after the last field, when `#[binary_io(deny_trailing)]` is set.
+pub(crate) fn deny_trailing(container_attrs: &[ContainerAttr]) -> TokenStream2 {
+    if !container_attrs.contains(&ContainerAttr::DenyTrailing) {
+        return TokenStream2::new();
+    }
+    quote! {
+        if !_binary_readerr.trailing_allowed() {
+            _binary_readerr.expect_eof()?;
+        }
+    }
+}
+
+/// Implements `WireDebug` for the struct, if it has the `#[binary_io(debug_wire)]` attribute.
+fn wire_debug_impl(
+    self_ty: &TokenStream2,
+    generics: &syn::Generics,
+    debug_wire: bool,
+    wire: TokenStream2,
+) -> TokenStream2 {
+    if !debug_wire {
+        return TokenStream2::new();
+    }
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    quote! {
+        impl #impl_generics ::binary_util::interfaces::WireDebug for #self_ty #where_clause {
+            fn write_fields(&self, _binary_writew: &mut ::binary_util::io::ByteWriter) -> ::std::result::Result<::std::vec::Vec<::binary_util::interfaces::WireField>, ::std::io::Error> {
+                let mut _binary_fields = ::std::vec::Vec::new();
+                #wire
+                Ok(_binary_fields)
+            }
+        }
+    }
+}
+
+/// Returns the value `display_wire` shows for a field, which is `field` itself unless the field is written
+/// with a computed value: the length of the `#[len_of]` target, or the checksum of the `#[crc_of]` target,
+/// computed by replaying the target's writer from `crc_writers`.
+fn display_value(
+    field: TokenStream2,
+    ty: &syn::Type,
+    attr: Option<&IoAttr>,
+    crc_writers: &HashMap<&syn::Ident, TokenStream2>,
+) -> TokenStream2 {
+    match attr {
+        Some(IoAttr::LenOf(target)) => quote!({
+            let Ok(_binary_len) = <#ty as ::std::convert::TryFrom<usize>>::try_from(
+                ::binary_util::interfaces::Counted::count(&self.#target),
+            ) else {
+                return Err(::std::fmt::Error);
+            };
+            _binary_len
+        }),
+        Some(IoAttr::CrcOf(target, algorithm)) => {
+            let Some(target_writer) = crc_writers.get(target) else {
+                return field;
+            };
+            quote!({
+                let _binary_write = || -> ::std::result::Result<u32, ::std::io::Error> {
+                    let _binary_writew = &mut ::binary_util::io::ByteWriter::new();
+                    #target_writer
+                    Ok(::binary_util::checksum::#algorithm(_binary_writew.as_slice()))
+                };
+                let _binary_crc = _binary_write().map_err(|_| ::std::fmt::Error)?;
+                <#ty as ::std::convert::From<u32>>::from(_binary_crc)
+            })
+        }
+        _ => field,
+    }
+}
+
+/// Implements `WireDisplay` for the struct, if it has the `#[binary_io(display_wire)]` attribute.
+/// The name of the struct is written, followed by every field in `fields` as ` name=value`.
+fn wire_display_impl(
+    ast_ctx: &AstContext,
+    container_attrs: &[ContainerAttr],
+    fields: &[(String, TokenStream2)],
+) -> TokenStream2 {
+    if !container_attrs.contains(&ContainerAttr::DisplayWire) {
+        return TokenStream2::new();
+    }
+    let struct_name = ast_ctx.0;
+    let (_, ty_generics, _) = ast_ctx.2.split_for_impl();
+    let generics = bounded_generics(
+        ast_ctx.2,
+        container_attrs,
+        |param| quote!(#param: ::binary_util::interfaces::WireDisplay),
+    );
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let name = struct_name.to_string();
+    let labels = fields.iter().map(|(field, _)| format!(" {}=", field));
+    let values = fields.iter().map(|(_, value)| value);
+    quote! {
+        impl #impl_generics ::binary_util::interfaces::WireDisplay for #struct_name #ty_generics #where_clause {
+            #[allow(clippy::redundant_closure_call)]
+            fn fmt_wire(&self, out: &mut dyn ::std::fmt::Write) -> ::std::fmt::Result {
+                out.write_str(#name)?;
+                #(
+                    out.write_str(#labels)?;
+                    ::binary_util::interfaces::WireDisplay::fmt_wire(&#values, out)?;
+                )*
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Implements `Schema` for the struct, if `#[binary_io(schema)]` is set.
+fn struct_schema_impl(
+    ast_ctx: &AstContext,
+    container_attrs: &[ContainerAttr],
+    fields: &[TokenStream2],
+) -> TokenStream2 {
+    if !container_attrs.contains(&ContainerAttr::Schema) {
+        return TokenStream2::new();
+    }
+    schema::schema_impl(
+        ast_ctx.0,
+        ast_ctx.1,
+        ast_ctx.2,
+        quote!(::binary_util::schema::SchemaKind::Struct(&[#(#fields),*])),
+    )
+}
+
+/// Implements the conversions between the struct and the foreign type it mirrors, if `#[binary_io(remote = "...")]` is set.
+/// `write_remote` and `read_remote` are generated on the struct, which fields use with `#[with]`.
+fn remote_glue(
+    ast_ctx: &AstContext,
+    container_attrs: &[ContainerAttr],
+    fields: &Fields,
+) -> TokenStream2 {
+    let Some(remote) = remote_type(container_attrs) else {
+        return TokenStream2::new();
+    };
+    let (mirror, vis) = (ast_ctx.0, ast_ctx.3);
+    let (impl_generics, ty_generics, where_clause) = ast_ctx.2.split_for_impl();
+    let io_generics = bounded_generics(
+        ast_ctx.2,
+        container_attrs,
+        |param| quote!(#param: ::binary_util::interfaces::Writer + ::binary_util::interfaces::Reader<#param>),
+    );
+    let (io_impl_generics, _, io_where) = io_generics.split_for_impl();
+    let (to_remote, to_mirror) = match fields {
+        Fields::Named(fields) => {
+            let names = fields
+                .named
+                .iter()
+                .filter_map(|field| field.ident.as_ref())
+                .collect::<Vec<&syn::Ident>>();
+            (
+                quote!(Self { #(#names: mirror.#names),* }),
+                quote!(Self { #(#names: ::std::clone::Clone::clone(&remote.#names)),* }),
+            )
+        }
+        Fields::Unnamed(fields) => {
+            let indexes = (0..fields.unnamed.len())
+                .map(syn::Index::from)
+                .collect::<Vec<syn::Index>>();
+            (
+                quote!(Self(#(mirror.#indexes),*)),
+                quote!(Self(#(::std::clone::Clone::clone(&remote.#indexes)),*)),
+            )
+        }
+        // reported by `derive_struct`.
+        Fields::Unit => return TokenStream2::new(),
+    };
+
+    quote! {
+        impl #impl_generics ::std::convert::From<#mirror #ty_generics> for #remote #where_clause {
+            fn from(mirror: #mirror #ty_generics) -> Self {
+                #to_remote
+            }
+        }
+        impl #impl_generics ::std::convert::From<&#remote> for #mirror #ty_generics #where_clause {
+            fn from(remote: &#remote) -> Self {
+                #to_mirror
+            }
+        }
+        impl #io_impl_generics #mirror #ty_generics #io_where {
+            /// Writes the foreign type this struct mirrors.
+            #vis fn write_remote(remote: &#remote, buf: &mut ::binary_util::io::ByteWriter) -> ::std::result::Result<(), ::std::io::Error> {
+                ::binary_util::interfaces::Writer::write(&Self::from(remote), buf)
+            }
+            /// Reads the foreign type this struct mirrors.
+            #vis fn read_remote(buf: &mut ::binary_util::io::ByteReader) -> ::std::result::Result<#remote, ::std::io::Error> {
+                <Self as ::binary_util::interfaces::Reader<Self>>::read(buf).map(::std::convert::From::from)
+            }
+        }
+    }
+}
+
+/// Returns the fields referenced by `reference` (IE: `#[len_of]` or `#[crc_of]`), mapped to the field referencing them.
+/// `property` describes what the referencing field stores, and is used in errors.
+fn field_references(
+    fields: &[FieldAttrs],
+    field_names: &[&syn::Ident],
+    reference: impl Fn(&IoAttr) -> Option<&syn::Ident>,
+    property: &str,
+    error_stream: &mut TokenStream2,
+) -> HashMap<syn::Ident, syn::Ident> {
+    let mut targets: HashMap<syn::Ident, syn::Ident> = HashMap::new();
+
+    for attrs in fields.iter() {
+        for target in attrs.io.iter().filter_map(&reference) {
+            let Some(len_field) = &attrs.field.ident else {
+                continue;
+            };
+            if !field_names.contains(&target) || target == len_field {
+                error_stream.append_all(
+                    syn::Error::new_spanned(
+                        target,
+                        format!("`{}` is not another field of this struct!", target),
+                    )
+                    .to_compile_error(),
+                );
+            } else if let Some(existing) = targets.insert(target.clone(), len_field.clone()) {
+                error_stream.append_all(
+                    syn::Error::new_spanned(
+                        target,
+                        format!(
+                            "The {} of `{}` is already stored in `{}`!",
+                            property, target, existing
+                        ),
+                    )
+                    .to_compile_error(),
+                );
+            }
+        }
+    }
+
+    targets
+}
@@ -1,24 +1,606 @@
 pub(crate) mod attrs {
-    use proc_macro2::TokenStream as TokenStream2;
-    use quote::TokenStreamExt;
-
-    use lazy_static::lazy_static;
-    use regex::Regex;
-
-    lazy_static! {
-        static ref REG: regex::Regex = Regex::new(r"\$([\u0041-\u323AF|_]*)").unwrap();
-    }
+    use proc_macro2::{Group, Spacing, TokenStream as TokenStream2, TokenTree};
+    use quote::{ToTokens, TokenStreamExt};
 
     #[derive(Clone)]
     pub enum IoAttr {
         Satisfy(syn::Expr),
+        SkipIf(syn::Expr),
+        WriteIf(syn::Expr),
         Require(syn::Ident),
         IfPresent(syn::Ident),
+        BoolWidth(BoolWidth),
+        LenOf(syn::Ident),
+        /// The field checksummed, and the checksum algorithm.
+        CrcOf(syn::Ident, syn::Ident),
+        /// The maximum amount of items (or bytes) of a collection.
+        MaxLen(usize),
+        /// The field holding the absolute offset the field is read from.
+        OffsetFrom(syn::Ident),
+        /// The `#[binary_io(remote)]` mirror the field is encoded with.
+        With(syn::Path),
         Doc(syn::Attribute),
         Skip,
+        /// The field is skipped, and read from the value of its type attached to the reader with `with_context`.
+        FromCtx,
+        /// The field is read with trailing bytes allowed, see `ByteReader::allow_trailing`.
+        AllowTrailing,
         Unknown,
     }
 
+    /// The width of a boolean on the wire, specified with `#[bool_width(bit | u8 | u32)]`.
+    #[derive(Clone, Copy, PartialEq)]
+    pub enum BoolWidth {
+        /// Packed with adjacent `bit` fields into a shared byte.
+        Bit,
+        U8,
+        U32,
+    }
+
+    /// The `#[repr]` types an enum discriminant may be encoded as.
+    /// Pointer sized types are not supported, because their width differs between platforms.
+    pub const REPR_TYPES: &[&str] = &["u8", "u16", "u32", "u64", "i8", "i16", "i32", "i64"];
+
+    /// Returns an error if `repr` is not one of `REPR_TYPES`.
+    pub fn validate_repr(repr: &syn::Ident) -> syn::Result<()> {
+        let name = repr.to_string();
+        if REPR_TYPES.contains(&name.as_str()) {
+            return Ok(());
+        }
+        let reason = match name.as_str() {
+            "usize" | "isize" => format!(
+                "#[repr({})] can not be encoded, because its width differs between platforms, which would corrupt traffic between them.",
+                name
+            ),
+            _ => "#[repr] attribute must contain a fixed width integer type.".to_string(),
+        };
+        Err(syn::Error::new_spanned(
+            repr,
+            format!("{} Use one of: {}", reason, REPR_TYPES.join(", ")),
+        ))
+    }
+
+    /// The var-int types a discriminant may be encoded as with `#[binary_io(repr_wire = "...")]`,
+    /// along with the integer type they wrap. Any of `REPR_TYPES` may also be used.
+    pub const VAR_REPR_TYPES: &[(&str, &str)] = &[
+        ("varu32", "u32"),
+        ("vari32", "i32"),
+        ("varu64", "u64"),
+        ("vari64", "i64"),
+    ];
+
+    /// The checksum algorithms supported by `#[crc_of]`, which are functions of `binary_util::checksum`.
+    pub const CHECKSUMS: &[&str] = &["crc32"];
+
+    /// Options set on a struct or enum with `#[binary_io(...)]`.
+    #[derive(Clone, PartialEq)]
+    pub enum ContainerAttr {
+        /// The enum is written without a discriminant, and read by trying each variant in order.
+        Untagged,
+        /// The discriminant of the enum is stored elsewhere, so `discriminant`, `write_payload`
+        /// and `read_variant` methods are generated.
+        ExternalTag,
+        /// `WireDebug` is implemented for the struct.
+        DebugWire,
+        /// `WireDisplay` is implemented for the struct.
+        DisplayWire,
+        /// `ReadPartial` is implemented for the struct, recording the fields read before a failure.
+        Partial,
+        /// `Reader::read_into` is implemented for the struct, reading each field in place.
+        InPlace,
+        /// `read` fails if any bytes remain after the last field, unless trailing bytes are allowed.
+        DenyTrailing,
+        /// `Schema` is implemented for the struct or enum, describing its fields and variants.
+        Schema,
+        /// The struct mirrors the layout of a foreign type, which is the path stored.
+        Remote(String),
+        /// The where predicates replacing the `Reader`/`Writer` bounds added to every type parameter.
+        Bound(String),
+        /// `Reader` is not implemented, so it can be implemented by hand.
+        SkipReader,
+        /// `Writer` is not implemented, so it can be implemented by hand.
+        SkipWriter,
+        /// The path `binary_util` is reachable at, when it is renamed or re-exported by another crate.
+        Crate(String),
+        /// `TryFrom<&[u8]>` is implemented for the type, and `TryFrom<&Type>` for `Vec<u8>`.
+        Conversions,
+        /// The type the discriminant of an enum is encoded as, when it differs from its `#[repr]`.
+        ReprWire(String),
+    }
+
+    /// Returns `impl_tokens`, unless the trait it implements was opted out of with `skip`, IE: `ContainerAttr::SkipReader`.
+    pub fn unless_skipped(
+        container_attrs: &[ContainerAttr],
+        skip: ContainerAttr,
+        impl_tokens: TokenStream2,
+    ) -> TokenStream2 {
+        match container_attrs.contains(&skip) {
+            true => TokenStream2::new(),
+            false => impl_tokens,
+        }
+    }
+
+    /// Returns the foreign type set with `#[binary_io(remote = "Type")]`, if any.
+    pub fn remote_type(container_attrs: &[ContainerAttr]) -> Option<syn::Path> {
+        container_attrs.iter().find_map(|attr| match attr {
+            // the path is validated when parsed.
+            ContainerAttr::Remote(path) => syn::parse_str::<syn::Path>(path).ok(),
+            _ => None,
+        })
+    }
+
+    /// Returns the type set with `#[binary_io(repr_wire = "type")]`, if any.
+    pub fn repr_wire(container_attrs: &[ContainerAttr]) -> Option<syn::Ident> {
+        container_attrs.iter().find_map(|attr| match attr {
+            // the type is validated when parsed.
+            ContainerAttr::ReprWire(ty) => {
+                Some(syn::Ident::new(ty, proc_macro2::Span::call_site()))
+            }
+            _ => None,
+        })
+    }
+
+    /// Returns the path set with `#[binary_io(crate = "path")]`, if any.
+    pub fn crate_path(container_attrs: &[ContainerAttr]) -> Option<syn::Path> {
+        container_attrs.iter().find_map(|attr| match attr {
+            // the path is validated when parsed.
+            ContainerAttr::Crate(path) => syn::parse_str::<syn::Path>(path).ok(),
+            _ => None,
+        })
+    }
+
+    /// Replaces every `::binary_util` path within the generated `tokens` with `path`.
+    ///
+    /// The impls are generated against `::binary_util`, so this is applied once they have been generated,
+    /// rather than threading the path through every part of the derive.
+    pub fn rewrite_crate_path(tokens: TokenStream2, path: &syn::Path) -> TokenStream2 {
+        let mut out: Vec<TokenTree> = Vec::new();
+        for token in tokens {
+            match token {
+                TokenTree::Ident(ident) if ident == "binary_util" && ends_with_path_sep(&out) => {
+                    out.truncate(out.len() - 2);
+                    out.extend(path.to_token_stream());
+                }
+                TokenTree::Group(group) => {
+                    let mut rewritten =
+                        Group::new(group.delimiter(), rewrite_crate_path(group.stream(), path));
+                    rewritten.set_span(group.span());
+                    out.push(TokenTree::Group(rewritten));
+                }
+                token => out.push(token),
+            }
+        }
+        out.into_iter().collect()
+    }
+
+    /// Returns whether `tokens` end with a leading `::`, IE: one which does not follow a path segment.
+    fn ends_with_path_sep(tokens: &[TokenTree]) -> bool {
+        let (before, sep) = match tokens {
+            [rest @ .., TokenTree::Punct(first), TokenTree::Punct(second)] => {
+                (rest.last(), [first, second])
+            }
+            _ => return false,
+        };
+        if sep[0].as_char() != ':' || sep[0].spacing() != Spacing::Joint || sep[1].as_char() != ':'
+        {
+            return false;
+        }
+        match before {
+            // keywords such as `as` or `for` can precede a leading `::`, other identifiers are path segments.
+            Some(TokenTree::Ident(ident)) => {
+                let ident = ident.to_string();
+                syn::parse_str::<syn::Ident>(&ident).is_err()
+                    && !matches!(ident.as_str(), "crate" | "self" | "super" | "Self")
+            }
+            _ => true,
+        }
+    }
+
+    /// Parses a comma separated list of where predicates, IE: `T: Writer, U: Default`.
+    fn where_predicates(
+        input: syn::parse::ParseStream,
+    ) -> syn::Result<syn::punctuated::Punctuated<syn::WherePredicate, syn::Token![,]>> {
+        syn::punctuated::Punctuated::parse_terminated(input)
+    }
+
+    /// Returns `generics` with the bounds the impls generated for the type require.
+    ///
+    /// Every type parameter is bounded with `bound`, IE: `T: Writer`, unless `#[binary_io(bound = "...")]`
+    /// is set, in which case its predicates are added instead.
+    pub fn bounded_generics(
+        generics: &syn::Generics,
+        container_attrs: &[ContainerAttr],
+        bound: impl Fn(&syn::Ident) -> TokenStream2,
+    ) -> syn::Generics {
+        let mut generics = generics.clone();
+        let predicates = match container_attrs.iter().find_map(|attr| match attr {
+            ContainerAttr::Bound(bound) => Some(bound),
+            _ => None,
+        }) {
+            // the predicates are validated when parsed.
+            Some(custom) => syn::parse::Parser::parse_str(where_predicates, custom)
+                .map(|predicates| predicates.into_iter().collect())
+                .unwrap_or_default(),
+            None => generics
+                .type_params()
+                .map(|param| syn::parse2::<syn::WherePredicate>(bound(&param.ident)))
+                .collect::<syn::Result<Vec<syn::WherePredicate>>>()
+                .unwrap_or_default(),
+        };
+        generics.make_where_clause().predicates.extend(predicates);
+        generics
+    }
+
+    /// Parses every `#[binary_io(...)]` attribute on a struct or enum.
+    pub fn parse_container_attributes(
+        attrs: &[syn::Attribute],
+        error_stream: &mut TokenStream2,
+    ) -> Vec<ContainerAttr> {
+        let mut parsed: Vec<ContainerAttr> = Vec::new();
+
+        for attr in attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("binary_io"))
+        {
+            let result = attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("untagged") {
+                    parsed.push(ContainerAttr::Untagged);
+                    Ok(())
+                } else if meta.path.is_ident("external_tag") {
+                    parsed.push(ContainerAttr::ExternalTag);
+                    Ok(())
+                } else if meta.path.is_ident("debug_wire") {
+                    parsed.push(ContainerAttr::DebugWire);
+                    Ok(())
+                } else if meta.path.is_ident("display_wire") {
+                    parsed.push(ContainerAttr::DisplayWire);
+                    Ok(())
+                } else if meta.path.is_ident("in_place") {
+                    parsed.push(ContainerAttr::InPlace);
+                    Ok(())
+                } else if meta.path.is_ident("partial") {
+                    parsed.push(ContainerAttr::Partial);
+                    Ok(())
+                } else if meta.path.is_ident("deny_trailing") {
+                    parsed.push(ContainerAttr::DenyTrailing);
+                    Ok(())
+                } else if meta.path.is_ident("schema") {
+                    parsed.push(ContainerAttr::Schema);
+                    Ok(())
+                } else if meta.path.is_ident("skip_reader") {
+                    parsed.push(ContainerAttr::SkipReader);
+                    Ok(())
+                } else if meta.path.is_ident("skip_writer") {
+                    parsed.push(ContainerAttr::SkipWriter);
+                    Ok(())
+                } else if meta.path.is_ident("conversions") {
+                    parsed.push(ContainerAttr::Conversions);
+                    Ok(())
+                } else if meta.path.is_ident("remote") {
+                    let path = meta.value()?.parse::<syn::LitStr>()?;
+                    path.parse::<syn::Path>()?;
+                    parsed.push(ContainerAttr::Remote(path.value()));
+                    Ok(())
+                } else if meta.path.is_ident("repr_wire") {
+                    let ty = meta.value()?.parse::<syn::LitStr>()?;
+                    let name = ty.value();
+                    if !REPR_TYPES.contains(&name.as_str())
+                        && !VAR_REPR_TYPES.iter().any(|(var, _)| *var == name)
+                    {
+                        let types = REPR_TYPES
+                            .iter()
+                            .chain(VAR_REPR_TYPES.iter().map(|(var, _)| var))
+                            .copied()
+                            .collect::<Vec<_>>();
+                        return Err(syn::Error::new_spanned(
+                            ty,
+                            format!("repr_wire must be one of: {}", types.join(", ")),
+                        ));
+                    }
+                    parsed.push(ContainerAttr::ReprWire(name));
+                    Ok(())
+                } else if meta.path.is_ident("crate") {
+                    let path = meta.value()?.parse::<syn::LitStr>()?;
+                    path.parse::<syn::Path>()?;
+                    parsed.push(ContainerAttr::Crate(path.value()));
+                    Ok(())
+                } else if meta.path.is_ident("bound") {
+                    let bound = meta.value()?.parse::<syn::LitStr>()?;
+                    bound.parse_with(where_predicates)?;
+                    parsed.push(ContainerAttr::Bound(bound.value()));
+                    Ok(())
+                } else {
+                    Err(meta
+                        .error("Unknown binary_io option, expected one of: untagged, external_tag, debug_wire, display_wire, partial, in_place, deny_trailing, schema, skip_reader, skip_writer, conversions, repr_wire, remote, bound, crate"))
+                }
+            });
+
+            if let Err(e) = result {
+                error_stream.append_all(e.to_compile_error());
+            }
+        }
+
+        parsed
+    }
+
+    /// The attributes of a field, which are parsed once per derive by `parse_fields` and shared by every generator.
+    pub struct FieldAttrs<'a> {
+        pub field: &'a syn::Field,
+        /// The index of the field in its declaration.
+        pub index: usize,
+        /// The attributes changing how the field is encoded, without doc comments or unknown attributes.
+        pub io: Vec<IoAttr>,
+        pub history: FieldHistory,
+        /// The position of the field on the wire, set with `#[order(n)]`.
+        pub order: Option<u32>,
+    }
+
+    impl FieldAttrs<'_> {
+        /// Returns the attribute the field is encoded with, as a field may only have one.
+        pub fn attr(&self) -> Option<&IoAttr> {
+            self.io.first()
+        }
+
+        /// Returns whether the field is never encoded, IE: `#[skip]` or `#[from_ctx]`.
+        pub fn is_skipped(&self) -> bool {
+            self.io
+                .iter()
+                .any(|attr| matches!(attr, IoAttr::Skip | IoAttr::FromCtx))
+        }
+    }
+
+    /// Parses the attributes of every field in `fields`, reporting malformed attributes to `error_stream`.
+    pub fn parse_fields<'a>(
+        fields: impl IntoIterator<Item = &'a syn::Field>,
+        error_stream: &mut TokenStream2,
+    ) -> Vec<FieldAttrs<'a>> {
+        fields
+            .into_iter()
+            .enumerate()
+            .map(|(index, field)| {
+                let mut order: Option<u32> = None;
+                let mut io: Vec<IoAttr> = Vec::new();
+                for attr in field.attrs.iter() {
+                    if attr.path().is_ident("order") {
+                        match attr.parse_args::<syn::LitInt>().and_then(|lit| lit.base10_parse::<u32>()) {
+                            Ok(n) if order.is_none() => order = Some(n),
+                            Ok(_) => error_stream.append_all(
+                                syn::Error::new_spanned(attr, "A field can only have one #[order] attribute!")
+                                    .to_compile_error(),
+                            ),
+                            Err(_) => error_stream.append_all(
+                                syn::Error::new_spanned(attr, "'order' attribute requires a positive integer! \n Example: #[order(0)]")
+                                    .to_compile_error(),
+                            ),
+                        }
+                        continue;
+                    }
+                    match parse_attribute(attr, error_stream) {
+                        Ok(IoAttr::Unknown | IoAttr::Doc(_)) | Err(_) => {}
+                        Ok(attr) => io.push(attr),
+                    }
+                }
+                FieldAttrs {
+                    field,
+                    index,
+                    io,
+                    history: field_history(field, error_stream),
+                    order,
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the fields in the order they are encoded.
+    ///
+    /// Fields are encoded in the order they are declared, unless `#[order(n)]` is used,
+    /// in which case every field that is not skipped must have a unique order.
+    /// Skipped fields are never encoded, so they are placed last.
+    pub fn wire_order<'f, 'a>(
+        fields: &'f [FieldAttrs<'a>],
+        error_stream: &mut TokenStream2,
+    ) -> Vec<&'f FieldAttrs<'a>> {
+        let mut ordered = fields.iter().collect::<Vec<&FieldAttrs>>();
+
+        if ordered.iter().all(|attrs| attrs.order.is_none()) {
+            return ordered;
+        }
+
+        for attrs in ordered.iter() {
+            if attrs.order.is_none() && !attrs.is_skipped() {
+                error_stream.append_all(
+                    syn::Error::new_spanned(
+                        attrs.field,
+                        "Every field that is not skipped must have an #[order] attribute when #[order] is used!",
+                    )
+                    .to_compile_error(),
+                );
+            }
+        }
+
+        ordered.sort_by_key(|attrs| (attrs.order.is_none(), attrs.order));
+        for pair in ordered.windows(2) {
+            if let (Some(a), Some(b)) = (pair[0].order, pair[1].order) {
+                if a == b {
+                    error_stream.append_all(
+                        syn::Error::new_spanned(
+                            pair[1].field,
+                            format!("#[order({})] is already used by another field!", a),
+                        )
+                        .to_compile_error(),
+                    );
+                }
+            }
+        }
+
+        ordered
+    }
+
+    /// The types which consume every remaining byte of the buffer when read, IE: `binary_util::types::Raw`.
+    ///
+    /// Types are matched by the last segment of their path, since a derive can not resolve them,
+    /// so aliases of these types are missed, and unrelated types sharing their names are matched.
+    const GREEDY_TYPES: &[&str] = &["Raw", "RawVec"];
+
+    /// Reports the first field that follows a greedy field in `fields`, which are in wire order,
+    /// since the greedy field leaves no bytes for it to be read from.
+    ///
+    /// Fields listed in `sized` have their length stored with `#[len_of]`, so they are not greedy.
+    pub fn check_greedy_fields(
+        fields: &[&FieldAttrs],
+        sized: &[&syn::Ident],
+        error_stream: &mut TokenStream2,
+    ) {
+        let name = |i: usize, field: &syn::Field| match field.ident {
+            Some(ref ident) => ident.to_string(),
+            None => i.to_string(),
+        };
+        let mut greedy: Option<(usize, &syn::Field)> = None;
+        for attrs in fields {
+            let (i, field) = (attrs.index, attrs.field);
+            if attrs.is_skipped() {
+                continue;
+            }
+            if let Some((greedy_index, greedy_field)) = greedy {
+                let greedy_name = name(greedy_index, greedy_field);
+                let mut error = syn::Error::new_spanned(
+                    field,
+                    format!(
+                        "`{}` can never be read, because `{}` consumes every remaining byte before it!",
+                        name(i, field),
+                        greedy_name
+                    ),
+                );
+                error.combine(syn::Error::new_spanned(
+                    greedy_field,
+                    format!(
+                        "`{}` must be the last field, or have its length stored in another field with #[len_of]",
+                        greedy_name
+                    ),
+                ));
+                error_stream.append_all(error.to_compile_error());
+                return;
+            }
+            let is_greedy = match field.ty {
+                syn::Type::Path(ref path) => path
+                    .path
+                    .segments
+                    .last()
+                    .is_some_and(|segment| GREEDY_TYPES.iter().any(|ty| segment.ident == ty)),
+                _ => false,
+            };
+            if is_greedy
+                && !field
+                    .ident
+                    .as_ref()
+                    .is_some_and(|ident| sized.contains(&ident))
+            {
+                greedy = Some((i, field));
+            }
+        }
+    }
+
+    /// The changes made to a field across protocol versions, see `field_history`.
+    #[derive(Default)]
+    pub struct FieldHistory {
+        pub renamed_from: Vec<String>,
+        pub added_in: Option<u32>,
+        pub removed_in: Option<u32>,
+    }
+
+    impl FieldHistory {
+        /// Returns whether the field is only encoded in some versions.
+        pub fn is_versioned(&self) -> bool {
+            self.added_in.is_some() || self.removed_in.is_some()
+        }
+    }
+
+    /// Parses the `#[renamed_from(name)]`, `#[added_in(N)]` and `#[removed_in(N)]` attributes of a field.
+    ///
+    /// These are not `IoAttr`s, as they describe the history of a field rather than how it is encoded,
+    /// so they may be combined with any other attribute.
+    fn field_history(field: &syn::Field, error_stream: &mut TokenStream2) -> FieldHistory {
+        let mut history = FieldHistory::default();
+
+        for attr in field.attrs.iter() {
+            if attr.path().is_ident("renamed_from") {
+                match attr.parse_args::<syn::Ident>() {
+                    Ok(name) => history.renamed_from.push(name.to_string()),
+                    Err(_) => error_stream.append_all(
+                        syn::Error::new_spanned(attr, "'renamed_from' attribute requires the previous name of the field! \n Example: #[renamed_from(old_name)]")
+                            .to_compile_error(),
+                    ),
+                }
+                continue;
+            }
+            let version = if attr.path().is_ident("added_in") {
+                &mut history.added_in
+            } else if attr.path().is_ident("removed_in") {
+                &mut history.removed_in
+            } else {
+                continue;
+            };
+            match attr.parse_args::<syn::LitInt>().and_then(|lit| lit.base10_parse::<u32>()) {
+                Ok(n) if version.is_none() => *version = Some(n),
+                Ok(_) => error_stream.append_all(
+                    syn::Error::new_spanned(attr, "A field can only be added or removed once!")
+                        .to_compile_error(),
+                ),
+                Err(_) => error_stream.append_all(
+                    syn::Error::new_spanned(attr, "'added_in' and 'removed_in' attributes require a version! \n Example: #[removed_in(3)]")
+                        .to_compile_error(),
+                ),
+            }
+        }
+
+        if let (Some(added), Some(removed)) = (history.added_in, history.removed_in) {
+            if added >= removed {
+                error_stream.append_all(
+                    syn::Error::new_spanned(
+                        field,
+                        "A field must be added in an earlier version than it is removed in!",
+                    )
+                    .to_compile_error(),
+                );
+            }
+        }
+
+        history
+    }
+
+    /// Joins the rustdoc comments within `attrs` into a single string, one line per `#[doc]` attribute.
+    /// Returns `None` if there are no doc comments.
+    pub fn doc_comment(attrs: &[syn::Attribute]) -> Option<String> {
+        let lines = attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("doc"))
+            .filter_map(|attr| match &attr.meta {
+                syn::Meta::NameValue(syn::MetaNameValue {
+                    value:
+                        syn::Expr::Lit(syn::ExprLit {
+                            lit: syn::Lit::Str(doc),
+                            ..
+                        }),
+                    ..
+                }) => Some(doc.value()),
+                _ => None,
+            })
+            .map(|line| {
+                line.strip_prefix(' ')
+                    .unwrap_or(&line)
+                    .trim_end()
+                    .to_string()
+            })
+            .collect::<Vec<String>>();
+
+        let doc = lines.join("\n").trim().to_string();
+        if doc.is_empty() {
+            None
+        } else {
+            Some(doc)
+        }
+    }
+
     /// Parses the attributes of a struct or enum.
     /// The attributes are returned in the order they were parsed in, you can return errors if you want to.
     /// Some attributes do not allow conflicting attributes, such as #[skip]
@@ -46,6 +628,20 @@ pub(crate) mod attrs {
                     );
                 }
             }
+        } else if path.is_ident("skip_if") || path.is_ident("write_if") {
+            // skip_if and write_if only control whether the field is written,
+            // the field is always read.
+            let name = path.get_ident().unwrap();
+            match attr.parse_args::<syn::Expr>() {
+                Ok(expr) if name == "skip_if" => return Ok(IoAttr::SkipIf(expr)),
+                Ok(expr) => return Ok(IoAttr::WriteIf(expr)),
+                Err(e) => {
+                    error_stream.append_all(
+                        syn::Error::new_spanned(attr, format!("'{}' attribute requires an Expression!\n Example: #[{}(self.field == 0)]\n Error: {}", name, name, e))
+                            .to_compile_error(),
+                    );
+                }
+            }
         } else if path.is_ident("require") {
             // Require is an attribute that allows an identifier to be specified
             // this is polyfilled later with `self.IDENTIFIER.is_some()`
@@ -74,11 +670,95 @@ pub(crate) mod attrs {
                     );
                 }
             }
+        } else if path.is_ident("len_of") {
+            // len_of populates this field with the length of another field when writing,
+            // and uses it to read that field.
+            match attr.parse_args::<syn::Ident>() {
+                Ok(ident) => return Ok(IoAttr::LenOf(ident)),
+                Err(_) => {
+                    error_stream.append_all(
+                        syn::Error::new_spanned(attr, "'len_of' attribute requires an Identifier! \n Example: #[len_of(payload)]")
+                            .to_compile_error(),
+                    );
+                }
+            }
+        } else if path.is_ident("max_len") {
+            // max_len bounds the length prefix of a collection, which is checked before the collection is read.
+            match attr
+                .parse_args::<syn::LitInt>()
+                .and_then(|lit| lit.base10_parse::<usize>())
+            {
+                Ok(max) => return Ok(IoAttr::MaxLen(max)),
+                Err(_) => {
+                    error_stream.append_all(
+                        syn::Error::new_spanned(attr, "'max_len' attribute requires a positive integer! \n Example: #[max_len(256)]")
+                            .to_compile_error(),
+                    );
+                }
+            }
+        } else if path.is_ident("offset_from") {
+            // offset_from reads this field from the absolute offset stored in another field.
+            match attr.parse_args::<syn::Ident>() {
+                Ok(ident) => return Ok(IoAttr::OffsetFrom(ident)),
+                Err(_) => {
+                    error_stream.append_all(
+                        syn::Error::new_spanned(attr, "'offset_from' attribute requires an Identifier! \n Example: #[offset_from(body_offset)]")
+                            .to_compile_error(),
+                    );
+                }
+            }
+        } else if path.is_ident("crc_of") {
+            // crc_of populates this field with the checksum of another field's encoded bytes when writing,
+            // and validates it when reading.
+            let args = attr.parse_args_with(
+                syn::punctuated::Punctuated::<syn::Ident, syn::Token![,]>::parse_terminated,
+            );
+            match args.map(|args| args.into_iter().collect::<Vec<syn::Ident>>()) {
+                Ok(args)
+                    if args.len() == 2 && CHECKSUMS.contains(&args[1].to_string().as_str()) =>
+                {
+                    return Ok(IoAttr::CrcOf(args[0].clone(), args[1].clone()));
+                }
+                _ => {
+                    error_stream.append_all(
+                        syn::Error::new_spanned(attr, format!("'crc_of' attribute requires a field and one of: {}! \n Example: #[crc_of(payload, crc32)]", CHECKSUMS.join(", ")))
+                            .to_compile_error(),
+                    );
+                }
+            }
+        } else if path.is_ident("bool_width") {
+            // bool_width changes how many bytes (or bits) a bool takes up on the wire.
+            match attr.parse_args::<syn::Ident>() {
+                Ok(ident) if ident == "bit" => return Ok(IoAttr::BoolWidth(BoolWidth::Bit)),
+                Ok(ident) if ident == "u8" => return Ok(IoAttr::BoolWidth(BoolWidth::U8)),
+                Ok(ident) if ident == "u32" => return Ok(IoAttr::BoolWidth(BoolWidth::U32)),
+                _ => {
+                    error_stream.append_all(
+                        syn::Error::new_spanned(attr, "'bool_width' attribute requires one of: bit, u8, u32! \n Example: #[bool_width(bit)]")
+                            .to_compile_error(),
+                    );
+                }
+            }
+        } else if path.is_ident("with") {
+            // with encodes a foreign type through a mirror struct derived with `#[binary_io(remote = "...")]`.
+            match attr.parse_args::<syn::Path>() {
+                Ok(mirror) => return Ok(IoAttr::With(mirror)),
+                Err(_) => {
+                    error_stream.append_all(
+                        syn::Error::new_spanned(attr, "'with' attribute requires the path of a remote mirror! \n Example: #[with(Vec3Def)]")
+                            .to_compile_error(),
+                    );
+                }
+            }
         } else if path.is_ident("skip") {
             // skip is a special attribute, it cannot be used with any other attribute
             // therefore we can just return early, however we need to validate that
             // there are no other attributes
             return Ok(IoAttr::Skip);
+        } else if path.is_ident("from_ctx") {
+            return Ok(IoAttr::FromCtx);
+        } else if path.is_ident("allow_trailing") {
+            return Ok(IoAttr::AllowTrailing);
         } else {
             return Ok(IoAttr::Unknown);
             // error_stream.append_all(
@@ -123,3 +803,87 @@ pub(crate) mod attrs {
         }
     }
 }
+
+/// Code generation shared by the `Schema` implementations of structs and enums.
+pub(crate) mod schema {
+    use proc_macro2::TokenStream as TokenStream2;
+    use quote::{quote, ToTokens};
+
+    /// Formats a type the way it was written, IE: `Vec<u8>` rather than `Vec < u8 >`.
+    fn type_name(ty: &syn::Type) -> String {
+        let mut name = ty.to_token_stream().to_string();
+        for (from, to) in [
+            (" <", "<"),
+            ("< ", "<"),
+            (" >", ">"),
+            (" :: ", "::"),
+            (":: ", "::"),
+            (" ,", ","),
+            ("& ", "&"),
+        ] {
+            name = name.replace(from, to);
+        }
+        name
+    }
+
+    /// Returns an `Option<&'static str>` expression of `doc`.
+    pub fn doc_tokens(doc: Option<String>) -> TokenStream2 {
+        match doc {
+            Some(doc) => quote!(::std::option::Option::Some(#doc)),
+            None => quote!(::std::option::Option::None),
+        }
+    }
+
+    /// Returns an `Option<u32>` expression of `version`.
+    fn version_tokens(version: Option<u32>) -> TokenStream2 {
+        match version {
+            Some(version) => quote!(::std::option::Option::Some(#version)),
+            None => quote!(::std::option::Option::None),
+        }
+    }
+
+    /// Returns a `FieldSchema` expression for a field.
+    pub fn field(name: String, attrs: &super::attrs::FieldAttrs) -> TokenStream2 {
+        let ty = type_name(&attrs.field.ty);
+        let doc = doc_tokens(super::attrs::doc_comment(&attrs.field.attrs));
+        let history = &attrs.history;
+        let renamed_from = &history.renamed_from;
+        let (added_in, removed_in) = (
+            version_tokens(history.added_in),
+            version_tokens(history.removed_in),
+        );
+        quote!(::binary_util::schema::FieldSchema {
+            name: #name,
+            ty: #ty,
+            doc: #doc,
+            history: ::binary_util::schema::FieldHistory {
+                renamed_from: &[#(#renamed_from),*],
+                added_in: #added_in,
+                removed_in: #removed_in,
+            },
+        })
+    }
+
+    /// Implements `Schema` for `name`, where `kind` is a `SchemaKind` expression.
+    pub fn schema_impl(
+        name: &syn::Ident,
+        attrs: &[syn::Attribute],
+        generics: &syn::Generics,
+        kind: TokenStream2,
+    ) -> TokenStream2 {
+        let type_name = name.to_string();
+        let doc = doc_tokens(super::attrs::doc_comment(attrs));
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        quote! {
+            impl #impl_generics ::binary_util::schema::Schema for #name #ty_generics #where_clause {
+                fn schema() -> ::binary_util::schema::TypeSchema {
+                    ::binary_util::schema::TypeSchema {
+                        name: #type_name,
+                        doc: #doc,
+                        kind: #kind,
+                    }
+                }
+            }
+        }
+    }
+}
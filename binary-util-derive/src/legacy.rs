@@ -1,3 +1,4 @@
+use crate::io::util::attrs::validate_repr;
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::{quote, TokenStreamExt};
 use syn::{Attribute, Data, DeriveInput, Error, Expr, ExprLit, Fields, Lit, LitInt, Result, Type};
@@ -55,21 +56,10 @@ pub fn stream_parse(input: DeriveInput) -> Result<TokenStream> {
             })
         }
         Data::Enum(data) => {
-            let representation =
-                find_one_attr("repr", attrs).expect("Enums must have a #[repr] attribute");
-            let enum_ty = representation
-                .parse_args::<Ident>()
-                .expect("Enums can only have types as attributes");
-
-            if !enum_ty
-                .to_string()
-                .starts_with(|v| v == 'u' || v == 'i' || v == 'f')
-            {
-                return Err(Error::new_spanned(
-                    representation,
-                    "Representation must be a primitive number",
-                ));
-            }
+            let representation = find_one_attr("repr", attrs)
+                .ok_or_else(|| Error::new_spanned(name, "Enums must have a #[repr] attribute"))?;
+            let enum_ty = representation.parse_args::<Ident>()?;
+            validate_repr(&enum_ty)?;
 
             let (mut writers, mut readers) = (Vec::<TokenStream>::new(), Vec::<TokenStream>::new());
             let mut new_writers = Vec::<TokenStream>::new();
@@ -219,7 +209,7 @@ pub fn stream_parse(input: DeriveInput) -> Result<TokenStream> {
 
                         match v {
                             #(#readers)*
-                            _ => panic!("Will not fit in enum!")
+                            _ => Err(::binary_util::error::BinaryError::RecoverableKnown("Invalid enum discriminant.".to_string()))
                         }
                     }
                 }
@@ -241,7 +231,7 @@ pub fn stream_parse(input: DeriveInput) -> Result<TokenStream> {
 
                         match v {
                             #(#readers)*
-                            _ => panic!("Will not fit in enum!")
+                            _ => Err(source.error_at_offset(::std::io::ErrorKind::InvalidData, "Invalid enum discriminant."))
                         }
                     }
                 }
@@ -1,11 +1,13 @@
 //! This crate provides a set of proc-macros that can be used to derive traits from `binary-util`.
 //! This crate is not intended to be used directly, and is only used as a dependency for `binary-util`.
 //! If you are looking for a way to implement the `Reader` and `Writer` traits, please see the documentation for `binary-util`.
+use io::util::attrs::{crate_path, parse_container_attributes, rewrite_crate_path};
 use proc_macro::TokenStream;
 use syn::{parse_macro_input, DeriveInput};
 
 mod io;
 mod legacy;
+mod mock;
 
 /// **DEPRECATED**.
 /// This is a legacy proc-macro that is used to generate a BufferStream.
@@ -95,15 +97,262 @@ pub fn derive_stream(input: TokenStream) -> TokenStream {
 /// #[derive(BinaryIo, Debug)]
 /// struct ABC(u8, Option<u8>, u8);
 /// ```
+///
+/// ### Debug Wire
+/// The `#[binary_io(debug_wire)]` attribute implements `binary_util::interfaces::WireDebug` for the struct,
+/// which records the range of bytes each field is written to. `wire_debug()` prints each field alongside its
+/// encoded bytes in hex, which makes it easy to spot where an encoding differs from a reference implementation.
+/// ```ignore
+/// use binary_util::interfaces::{Reader, WireDebug, Writer};
+/// use binary_util::BinaryIo;
+///
+/// #[derive(BinaryIo, Debug)]
+/// #[binary_io(debug_wire)]
+/// struct Handshake {
+///     protocol: u16,
+///     name: String,
+/// }
+///
+/// fn main() {
+///     let handshake = Handshake { protocol: 47, name: "abc".into() };
+///     // protocol [0..2] 00 2f
+///     // name     [2..6] 03 61 62 63
+///     println!("{}", handshake.wire_debug().unwrap());
+/// }
+/// ```
+///
+/// ### Display Wire
+/// The `#[binary_io(display_wire)]` attribute implements `binary_util::interfaces::WireDisplay` for the struct,
+/// which prints the struct and each written field on a single line, without allocating. This is intended for
+/// logging packet traffic, where `display_wire()` can be passed straight to a log macro.
+/// Fields with `#[len_of]` or `#[crc_of]` print the length or checksum that would be written, not the stored value.
+/// ```ignore
+/// use binary_util::interfaces::{Reader, WireDisplay, Writer};
+/// use binary_util::BinaryIo;
+///
+/// #[derive(BinaryIo, Debug)]
+/// #[binary_io(display_wire)]
+/// struct Handshake {
+///     protocol: u16,
+///     name: String,
+/// }
+///
+/// fn main() {
+///     let handshake = Handshake { protocol: 47, name: "abc".into() };
+///     // Handshake protocol=47 name="abc"
+///     println!("{}", handshake.display_wire());
+/// }
+/// ```
+///
+/// ### Deny Trailing
+/// The `#[binary_io(deny_trailing)]` attribute makes `read` return an `InvalidData` error if any bytes remain
+/// in the reader after the last field, which top-level packets use to reject trailing garbage.
+/// When such a type is nested within another, the field must be marked with `#[allow_trailing]`,
+/// unless it is the last field.
+/// ```ignore
+/// use binary_util::interfaces::{Reader, Writer};
+/// use binary_util::BinaryIo;
+///
+/// #[derive(BinaryIo, Debug)]
+/// #[binary_io(deny_trailing)]
+/// struct Ping {
+///     time: u64,
+/// }
+///
+/// fn main() {
+///     assert!(Ping::read_from_slice(&[0, 0, 0, 0, 0, 0, 0, 1]).is_ok());
+///     assert!(Ping::read_from_slice(&[0, 0, 0, 0, 0, 0, 0, 1, 0]).is_err());
+/// }
+/// ```
+///
+/// ### Partial
+/// The `#[binary_io(partial)]` attribute implements `binary_util::interfaces::ReadPartial` for the struct,
+/// whose `read_partial` returns the fields read before an error, the range of bytes each was read from,
+/// and the field that failed. This is useful for inspecting truncated or malformed captures.
+/// ```ignore
+/// use binary_util::interfaces::{ReadPartial, Reader, Writer};
+/// use binary_util::io::ByteReader;
+/// use binary_util::BinaryIo;
+///
+/// #[derive(BinaryIo)]
+/// #[binary_io(partial)]
+/// struct Ping {
+///     id: u8,
+///     time: u64,
+/// }
+///
+/// fn main() {
+///     let partial = Ping::read_partial(&mut ByteReader::from(&[1, 0, 0][..])).unwrap_err();
+///     assert_eq!(partial.fields[0].name, "id");
+///     assert_eq!(partial.failed, Some("time"));
+/// }
+/// ```
+///
+/// ### In Place
+/// The `#[binary_io(in_place)]` attribute implements `Reader::read_into` for the struct, reading each field
+/// into an existing value so the capacity of its `Vec`s is reused, IE: with `binary_util::pool::ObjectPool`.
+/// Fields may not have attributes other than `#[skip]`, which resets them to their default.
+/// ```ignore
+/// use binary_util::interfaces::{Reader, Writer};
+/// use binary_util::io::ByteReader;
+/// use binary_util::BinaryIo;
+///
+/// #[derive(BinaryIo, Default)]
+/// #[binary_io(in_place)]
+/// struct Chunk {
+///     x: i32,
+///     blocks: Vec<u16>,
+/// }
+///
+/// fn main() {
+///     let mut chunk = Chunk::default();
+///     Chunk::read_into(&mut chunk, &mut ByteReader::from(&[0, 0, 0, 1, 1, 0, 7][..])).unwrap();
+///     assert_eq!(chunk.blocks, vec![7]);
+/// }
+/// ```
+///
+/// ### Skip Reader / Skip Writer
+/// The `#[binary_io(skip_reader)]` and `#[binary_io(skip_writer)]` attributes opt out of implementing `Reader`
+/// or `Writer` respectively, so the other trait is derived while this one is implemented by hand,
+/// IE: to validate a packet while reading it.
+/// ```ignore
+/// use binary_util::interfaces::{Reader, Writer};
+/// use binary_util::io::ByteReader;
+/// use binary_util::BinaryIo;
+///
+/// #[derive(BinaryIo)]
+/// #[binary_io(skip_reader)]
+/// struct Resize {
+///     width: u16,
+///     height: u16,
+/// }
+///
+/// impl Reader<Resize> for Resize {
+///     fn read(buf: &mut ByteReader) -> Result<Resize, std::io::Error> {
+///         let (width, height) = (buf.read_u16()?, buf.read_u16()?);
+///         if width == 0 || height == 0 {
+///             return Err(buf.error_at_offset(std::io::ErrorKind::InvalidData, "Empty window"));
+///         }
+///         Ok(Resize { width, height })
+///     }
+/// }
+/// ```
+///
+/// ### Crate
+/// The generated impls refer to `::binary_util`, which does not resolve when the crate is renamed in `Cargo.toml`,
+/// or used through a crate re-exporting it. The `#[binary_io(crate = "path")]` attribute sets the path
+/// `binary_util` is reachable at instead, for both `BinaryIo` and `MockBinary`.
+/// ```ignore
+/// use my_facade::binary_util::BinaryIo;
+///
+/// #[derive(BinaryIo)]
+/// #[binary_io(crate = "my_facade::binary_util")]
+/// struct Ping {
+///     time: u64,
+/// }
+/// ```
+///
+/// ### Conversions
+/// The `#[binary_io(conversions)]` attribute implements `TryFrom<&[u8]>` for the struct or enum, and `TryFrom<&Type>`
+/// for `Vec<u8>`, so it can be passed to APIs expecting conversion traits, IE: storage layers. Both return a `std::io::Error`.
+/// ```ignore
+/// use binary_util::interfaces::{Reader, Writer};
+/// use binary_util::BinaryIo;
+///
+/// #[derive(BinaryIo)]
+/// #[binary_io(conversions)]
+/// struct Ping {
+///     time: u64,
+/// }
+///
+/// fn main() {
+///     let bytes = Vec::<u8>::try_from(&Ping { time: 1 }).unwrap();
+///     let ping = Ping::try_from(&bytes[..]).unwrap();
+///     assert_eq!(ping.time, 1);
+/// }
+/// ```
+///
+/// ### Schema
+/// The `#[binary_io(schema)]` attribute implements `binary_util::schema::Schema` for the struct or enum,
+/// which describes the name, type and doc comment of every encoded field, and every variant of an enum.
+/// ```ignore
+/// use binary_util::interfaces::{Reader, Writer};
+/// use binary_util::schema::Schema;
+/// use binary_util::BinaryIo;
+///
+/// #[derive(BinaryIo)]
+/// #[binary_io(schema)]
+/// #[repr(u8)]
+/// enum Status {
+///     /// The server is accepting players.
+///     Online,
+///     /// The server is full, with the maximum amount of players.
+///     Full(u32),
+/// }
+///
+/// fn main() {
+///     println!("{:#?}", Status::schema());
+/// }
+/// ```
+///
+/// ### Generics
+/// Generic structs and enums are supported, with every type parameter bounded by `Writer` in the `Writer` impl,
+/// and by `Reader` in the `Reader` impl. The `#[binary_io(bound = "...")]` attribute replaces these bounds
+/// with the given where predicates, IE: when a type parameter is only used in a skipped field.
+/// An empty bound, `#[binary_io(bound = "")]`, removes the bounds entirely.
+/// ```ignore
+/// use binary_util::interfaces::{Reader, Writer};
+/// use binary_util::BinaryIo;
+/// use std::marker::PhantomData;
+///
+/// #[derive(BinaryIo)]
+/// struct Envelope<T> {
+///     id: u16,
+///     body: T,
+/// }
+///
+/// #[derive(BinaryIo)]
+/// #[binary_io(bound = "")]
+/// struct Handle<T> {
+///     id: u32,
+///     #[skip]
+///     kind: PhantomData<T>,
+/// }
+/// ```
+///
+/// ### Conditional Fields
+/// Fields, tuple fields and variants may be gated with `#[cfg(...)]`, and attributes with `#[cfg_attr(...)]`.
+/// Conditional compilation is applied before the macro runs, so a disabled field is neither read, written,
+/// nor part of the schema, and the layout of the packet differs between builds with and without the feature.
+/// ```ignore
+/// use binary_util::interfaces::{Reader, Writer};
+/// use binary_util::BinaryIo;
+///
+/// #[derive(BinaryIo)]
+/// struct Handshake {
+///     protocol: u32,
+///     #[cfg(feature = "compression")]
+///     compression_threshold: u16,
+///     #[cfg_attr(not(feature = "encryption"), skip)]
+///     key: Vec<u8>,
+/// }
+/// ```
 /// ---
 ///
 /// ## Enums
 /// Enums function a bit differently than structs, and have a few more exclusive attributes that allow you to adjust the behavior of the macro.
 /// Identically to structs, this macro will encode/decode the fields of the enum in the order they are defined, as long as they are not skipped.
-/// > **Note:** Enums require the `#[repr]` attribute to be used, and the `#[repr]` attribute must be a primitive type.
+/// > **Note:** Enums require the `#[repr]` attribute to be used, and the `#[repr]` attribute must be a fixed width integer,
+/// > one of `u8`, `u16`, `u32`, `u64`, `i8`, `i16`, `i32` or `i64`. `usize` and `isize` are rejected, because their width
+/// > differs between platforms.
+///
+/// Every variant must have a unique discriminant, whether it is explicit or follows from the previous variant,
+/// otherwise the macro will fail with an error naming both variants.
+/// Discriminants may be negative when using a signed `#[repr]`, and may also be a const or any other
+/// constant expression, IE: `Login = PACKET_BASE + 1`, which are evaluated by the compiler.
 ///
 /// ### Unit Variants
-/// Unit variants are the simplest variant, of an enum and require the `#[repr(usize)]` attribute to be used. <br />
+/// Unit variants are the simplest variant of an enum. <br />
 ///
 /// **Example:**
 /// The following example will encode the `ProtcolEnum` enum as a `u8`, where each variant is encoded, by default, starting from 0.
@@ -160,6 +409,109 @@ pub fn derive_stream(input: TokenStream) -> TokenStream {
 /// }
 /// ```
 ///
+/// ### Untagged Enums
+/// The `#[binary_io(untagged)]` attribute writes an enum without a discriminant, only writing the fields of the variant. <br />
+/// When reading, each variant is attempted in the order it is defined, and the first variant that reads
+/// successfully is returned. If a variant fails to read, the reader is rolled back before the next variant is attempted.
+/// Untagged enums do not require a `#[repr]` attribute.
+///
+/// **Example:**
+/// In the following example, `Id::Short` is only read when there are not enough bytes to read `Id::Long`.
+/// ```ignore
+/// use binary_util::BinaryIo;
+///
+/// #[derive(BinaryIo, Debug)]
+/// #[binary_io(untagged)]
+/// pub enum Id {
+///     Long(u64),
+///     Short(u16),
+/// }
+/// ```
+///
+/// ### External Tags
+/// The `#[binary_io(external_tag)]` attribute is useful when the discriminant of an enum is stored elsewhere, like
+/// in a frame header that was read earlier. Along with `Reader`, `Writer` and the [discriminants](#discriminants),
+/// the following methods are generated:
+/// - `write_payload(&self, writer)`, which writes the fields of the variant without the discriminant.
+/// - `read_variant(reader, discriminant)`, which reads the fields of the variant with the given discriminant.
+///
+/// **Example:**
+/// ```ignore
+/// use binary_util::interfaces::BinaryDiscriminant;
+/// use binary_util::BinaryIo;
+///
+/// #[derive(BinaryIo, Debug)]
+/// #[binary_io(external_tag)]
+/// #[repr(u8)]
+/// pub enum Payload {
+///     Ping(u64) = 1,
+///     Disconnect(String),
+/// }
+///
+/// fn main() {
+///     let payload = Payload::Disconnect("Bye!".to_string());
+///     let mut writer = ByteWriter::new();
+///     writer.write_u8(payload.discriminant()).unwrap();
+///     writer.write_u16(0).unwrap(); // some other header field
+///     payload.write_payload(&mut writer).unwrap();
+///
+///     let mut reader = ByteReader::from(writer.as_slice());
+///     let tag = reader.read_u8().unwrap();
+///     reader.read_u16().unwrap();
+///     let payload = Payload::read_variant(&mut reader, tag).unwrap();
+/// }
+/// ```
+///
+/// ### Discriminants
+/// Every enum that is not untagged implements `BinaryDiscriminant`, so registries, metrics and logs can map
+/// discriminants to variant names without maintaining a second table:
+/// - `DISCRIMINANTS`, the discriminant and name of every variant, in the order they are declared.
+/// - `discriminant(&self)`, which returns the discriminant of the variant as the `#[repr]` type.
+///
+/// **Example:**
+/// ```ignore
+/// use binary_util::interfaces::BinaryDiscriminant;
+/// use binary_util::BinaryIo;
+///
+/// #[derive(BinaryIo, Debug)]
+/// #[repr(u8)]
+/// pub enum Packet {
+///     Ping(u64) = 1,
+///     Pong(u64),
+///     Disconnect(String) = 10,
+/// }
+///
+/// fn main() {
+///     assert_eq!(Packet::DISCRIMINANTS, &[(1, "Ping"), (2, "Pong"), (10, "Disconnect")]);
+///     assert_eq!(Packet::Pong(0).discriminant(), 2);
+/// }
+/// ```
+///
+/// ### Wire Repr
+/// By default the discriminant is encoded as the `#[repr]` type. The `#[binary_io(repr_wire = "...")]` attribute
+/// encodes it as another type instead, so the enum can keep a wide `#[repr]` while the discriminant is sent as a var-int.
+/// Any of the `#[repr]` types, or `varu32`, `vari32`, `varu64` and `vari64` may be used, and every discriminant must fit within both.
+/// `DISCRIMINANTS` and `discriminant(&self)` still use the `#[repr]` type.
+///
+/// **Example:**
+/// ```ignore
+/// use binary_util::interfaces::{Reader, Writer};
+/// use binary_util::BinaryIo;
+///
+/// #[derive(BinaryIo, Debug)]
+/// #[binary_io(repr_wire = "varu32")]
+/// #[repr(u32)]
+/// pub enum Packet {
+///     Ping(u64) = 1,
+///     Disconnect(String) = 300,
+/// }
+///
+/// fn main() {
+///     let bytes = Packet::Disconnect("bye".into()).write_to_bytes().unwrap();
+///     assert_eq!(&bytes.as_slice()[..2], &[0xac, 0x02]);
+/// }
+/// ```
+///
 /// ---
 ///
 /// ## Attributes
@@ -194,6 +546,76 @@ pub fn derive_stream(input: TokenStream) -> TokenStream {
 /// }
 /// ```
 ///
+/// ### From Ctx
+/// The `#[from_ctx]` attribute skips a field like `#[skip]`, however when decoding, the field is populated with the value
+/// of its type attached to the reader with `ByteReader::with_context`, IE: the address a packet was received from.
+/// If no value of the type is attached, the field is `Default::default()`. <br />
+/// The type of the field must implement `Clone` and `Default`.
+///
+/// **Syntax:**
+/// ```ignore
+/// #[from_ctx]
+/// ```
+///
+/// **Compatibility:**
+/// - ✅ Named Structs
+/// - ✅ Unnamed Structs
+/// - ❌ Enums
+///
+/// **Example:**
+/// ```ignore
+/// use binary_util::interfaces::{Reader, Writer};
+/// use binary_util::io::ByteReader;
+/// use binary_util::BinaryIo;
+///
+/// #[derive(Clone, Default)]
+/// struct ConnectionId(u64);
+///
+/// #[derive(BinaryIo)]
+/// struct Chat {
+///     message: String,
+///     #[from_ctx]
+///     sender: ConnectionId,
+/// }
+///
+/// fn main() {
+///     let mut buf = ByteReader::from(&[2, b'h', b'i'][..]).with_context(ConnectionId(7));
+///     let chat = Chat::read(&mut buf).unwrap();
+///     assert_eq!(chat.sender.0, 7);
+/// }
+/// ```
+///
+/// ### Allow Trailing
+/// The `#[allow_trailing]` attribute reads a field with `ByteReader::allow_trailing`, so a type derived with
+/// `#[binary_io(deny_trailing)]` can be nested within another type, and is followed by the fields after it.
+///
+/// **Syntax:**
+/// ```ignore
+/// #[allow_trailing]
+/// ```
+///
+/// **Compatibility:**
+/// - ✅ Named Structs
+/// - ✅ Unnamed Structs
+/// - ❌ Enums
+///
+/// **Example:**
+/// ```ignore
+/// use binary_util::interfaces::{Reader, Writer};
+/// use binary_util::BinaryIo;
+///
+/// #[derive(BinaryIo)]
+/// #[binary_io(deny_trailing)]
+/// struct Ping(u64);
+///
+/// #[derive(BinaryIo)]
+/// struct Batch {
+///     #[allow_trailing]
+///     ping: Ping,
+///     count: u8,
+/// }
+/// ```
+///
 /// ### Require
 /// This attribute explicitly requires a field to be present when either encoding, or decoding; and will fail if the field is not present. <br />
 /// This can be useful if you want to ensure that an optional field is present when encoding, or decoding it.
@@ -252,9 +674,412 @@ pub fn derive_stream(input: TokenStream) -> TokenStream {
 ///     c: u8,
 /// }
 /// ```
+///
+/// ### Skip If / Write If
+/// These attributes only control whether a field is **written**, the field is always read. <br />
+/// This is useful for asymmetric protocols, where a server always sends a field, but a client may omit it.
+/// `#[skip_if(EXPR)]` omits the field when the expression is `true`, while `#[write_if(EXPR)]` only writes the field
+/// when the expression is `true`.
+///
+/// **Syntax:**
+/// ```ignore
+/// #[skip_if(EXPR)]
+/// #[write_if(EXPR)]
+/// ```
+///
+/// **Compatibility:**
+/// - ✅ Named Structs
+/// - ✅ Unnamed Structs
+/// - ❌ Enums
+///
+/// **Example:**
+/// In the following example, `token` is always read, but is only written when `is_server` is `true`.
+/// ```ignore
+/// #[derive(BinaryIo, Debug)]
+/// struct Handshake {
+///     #[skip]
+///     is_server: bool,
+///     #[write_if(self.is_server)]
+///     token: u64,
+/// }
+/// ```
+///
+/// ### Bool Width
+/// By default a `bool` is encoded as a single byte. The `#[bool_width]` attribute allows you to change
+/// how many bytes a `bool` takes up on the wire. <br />
+/// The `bit` width packs adjacent `bit` fields into a shared byte, where the first field is the least significant bit.
+/// A byte holds up to 8 fields, after which a new byte is started.
+///
+/// **Syntax:**
+/// ```ignore
+/// #[bool_width(bit | u8 | u32)]
+/// ```
+///
+/// **Compatibility:**
+/// - ✅ Named Structs
+/// - ✅ Unnamed Structs
+/// - ❌ Enums
+///
+/// **Example:**
+/// In the following example, `a` and `b` are packed into a single byte, while `c` is encoded as a `u32`.
+/// ```ignore
+/// use binary_util::interfaces::{Reader, Writer};
+/// use binary_util::BinaryIo;
+///
+/// #[derive(BinaryIo, Debug)]
+/// struct Flags {
+///     #[bool_width(bit)]
+///     a: bool,
+///     #[bool_width(bit)]
+///     b: bool,
+///     #[bool_width(u32)]
+///     c: bool
+/// }
+/// ```
 /// ---
 ///
-#[proc_macro_derive(BinaryIo, attributes(skip, require, if_present, satisfy))]
+/// ### Len Of
+/// This attribute stores the length of another field in an integer field, instead of prefixing that field with a var-int length. <br />
+/// When writing, the value of the integer field is ignored, and the length of the other field is written in its place.
+/// When reading, the integer field is read first and determines how many items (or bytes) of the other field are read.
+/// The other field must implement `binary_util::interfaces::Counted`, which is implemented for `Vec<T>` and `String`,
+/// and must be encoded after the integer field.
+///
+/// **Syntax:**
+/// ```ignore
+/// #[len_of(FIELD)]
+/// ```
+///
+/// **Compatibility:**
+/// - ✅ Named Structs
+/// - ❌ Unnamed Structs
+/// - ❌ Enums
+///
+/// **Example:**
+/// In the following example, the amount of entries is written as a `u16` before the entries.
+/// ```ignore
+/// use binary_util::interfaces::{Reader, Writer};
+/// use binary_util::BinaryIo;
+///
+/// #[derive(BinaryIo, Debug)]
+/// struct PlayerList {
+///     #[len_of(entries)]
+///     count: u16,
+///     entries: Vec<u64>,
+/// }
+/// ```
+///
+/// ### Max Len
+/// This attribute bounds the amount of items (or bytes) of a collection, such as a `Vec<T>` or `String`. <br />
+/// When reading, the length prefix is checked before the collection is read, so an oversized length fails with
+/// `InvalidData` without being allocated. When writing, a collection over the bound fails with `InvalidInput`.
+/// The field must implement `binary_util::interfaces::Counted`, whose `read_count` reads the length prefix the field
+/// is written with, IE: a var-int for `Vec<T>` and `String`, or `L` for `Prefixed<L, T>`.
+///
+/// **Syntax:**
+/// ```ignore
+/// #[max_len(N)]
+/// ```
+///
+/// **Compatibility:**
+/// - ✅ Named Structs
+/// - ✅ Unnamed Structs
+/// - ❌ Enums
+///
+/// **Example:**
+/// ```ignore
+/// use binary_util::interfaces::{Reader, Writer};
+/// use binary_util::BinaryIo;
+///
+/// #[derive(BinaryIo, Debug)]
+/// struct Chat {
+///     #[max_len(256)]
+///     message: String,
+///     #[max_len(16)]
+///     mentions: Vec<u64>,
+/// }
+/// ```
+///
+/// ### Crc Of
+/// This attribute stores the checksum of another field's encoded bytes in an integer field. <br />
+/// When writing, the value of the integer field is ignored, and the checksum of the other field is written in its place.
+/// When reading, the checksum is validated against the bytes the other field was read from, failing with `InvalidData` if they do not match.
+/// The integer field must implement `From<u32>`, and must be encoded after the other field.
+/// The supported algorithms are the functions of `binary_util::checksum`, currently `crc32`.
+///
+/// **Syntax:**
+/// ```ignore
+/// #[crc_of(FIELD, ALGORITHM)]
+/// ```
+///
+/// **Compatibility:**
+/// - ✅ Named Structs
+/// - ❌ Unnamed Structs
+/// - ❌ Enums
+///
+/// **Example:**
+/// ```ignore
+/// use binary_util::interfaces::{Reader, Writer};
+/// use binary_util::BinaryIo;
+///
+/// #[derive(BinaryIo, Debug)]
+/// struct Chunk {
+///     data: Vec<u8>,
+///     #[crc_of(data, crc32)]
+///     checksum: u32,
+/// }
+/// ```
+///
+/// ### Offset From
+/// This attribute reads a field from the absolute offset stored in an integer field, for formats with a header of offsets,
+/// such as region or asset files. <br />
+/// When reading, the field is read from `ByteReader::section` at the offset, rather than the current position.
+/// If the field ends after the current position, the reader is moved past it, so fields laid out one after another
+/// are read as usual. When writing, the field is written at the offset, and the gap before it is filled with zeros,
+/// which fails if bytes were already written past the offset.
+/// Offsets are relative to the start of the outermost reader or writer, and the integer field must be encoded first.
+///
+/// **Syntax:**
+/// ```ignore
+/// #[offset_from(FIELD)]
+/// ```
+///
+/// **Compatibility:**
+/// - ✅ Named Structs
+/// - ❌ Unnamed Structs
+/// - ❌ Enums
+///
+/// **Example:**
+/// In the following example, the chunk is aligned to 16 bytes.
+/// ```ignore
+/// use binary_util::interfaces::{Reader, Writer};
+/// use binary_util::BinaryIo;
+///
+/// #[derive(BinaryIo, Debug)]
+/// struct Region {
+///     chunk_offset: u32,
+///     #[offset_from(chunk_offset)]
+///     chunk: Vec<u8>,
+/// }
+/// ```
+///
+/// ### Order
+/// By default fields are encoded in the order they are declared. The `#[order]` attribute encodes fields in an explicit
+/// order instead, which allows the layout of a struct to differ from a fixed wire layout. <br />
+/// When `#[order]` is used, every field that is not skipped must have a unique order. Fields are encoded from the lowest
+/// order to the highest, and the numbers do not need to be contiguous. Attributes that reference other fields, such as
+/// `#[require]`, follow the wire order, meaning the referenced field must have a lower order.
+///
+/// **Syntax:**
+/// ```ignore
+/// #[order(N)]
+/// ```
+///
+/// **Compatibility:**
+/// - ✅ Named Structs
+/// - ✅ Unnamed Structs
+/// - ❌ Enums
+///
+/// **Example:**
+/// In the following example, `id` is encoded first, followed by `name` and then `flags`.
+/// ```ignore
+/// use binary_util::interfaces::{Reader, Writer};
+/// use binary_util::BinaryIo;
+///
+/// #[derive(BinaryIo, Debug)]
+/// struct Player {
+///     #[order(2)]
+///     flags: u8,
+///     #[order(1)]
+///     name: String,
+///     #[order(0)]
+///     id: u64,
+/// }
+/// ```
+/// ---
+///
+/// ### Versioning
+/// Long lived protocols change over time. The `#[added_in]` and `#[removed_in]` attributes describe the versions a field
+/// is encoded in, allowing a single struct to read several historical layouts. <br />
+/// The version is set on the reader with `ByteReader::with_version`. A field is read when the version is at least the
+/// version it was added in, and lower than the version it was removed in, otherwise it is `Default::default()`.
+/// When no version is set, the latest layout is read. The latest layout is always written, meaning removed fields are never written.
+///
+/// `#[renamed_from]` records the previous names of a field. It does not change the encoding, however it is included in the
+/// `FieldHistory` of the field when `#[binary_io(schema)]` is used, along with the versions the field was added and removed in.
+///
+/// **Syntax:**
+/// ```ignore
+/// #[added_in(VERSION)]
+/// #[removed_in(VERSION)]
+/// #[renamed_from(OLD_NAME)]
+/// ```
+///
+/// **Compatibility:**
+/// - ✅ Named Structs
+/// - ✅ Unnamed Structs
+/// - ❌ Enums
+///
+/// **Example:**
+/// In the following example, `skin` is only read from version 2, and `legacy_id` is only read before version 3.
+/// ```ignore
+/// use binary_util::interfaces::{Reader, Writer};
+/// use binary_util::io::ByteReader;
+/// use binary_util::BinaryIo;
+///
+/// #[derive(BinaryIo, Debug)]
+/// struct Login {
+///     #[renamed_from(username)]
+///     name: String,
+///     #[removed_in(3)]
+///     legacy_id: u32,
+///     #[added_in(2)]
+///     skin: Vec<u8>,
+/// }
+///
+/// fn main() {
+///     let mut buf = ByteReader::from(&[3, b'a', b'b', b'c', 0, 0, 0, 7][..]).with_version(1);
+///     let login = Login::read(&mut buf).unwrap();
+///     assert_eq!(login.legacy_id, 7);
+///     assert!(login.skin.is_empty());
+/// }
+/// ```
+/// ---
+///
+/// ### Remote
+/// Foreign types, IE: types from other crates, can not implement `Reader` and `Writer` with this macro.
+/// `#[binary_io(remote = "Type")]` derives a local mirror struct instead, which declares the wire layout of the foreign type
+/// with the same fields. Conversions between the mirror and the foreign type are generated, along with `write_remote`
+/// and `read_remote` functions on the mirror. Fields of the foreign type are then encoded with `#[with(Mirror)]`. <br />
+/// The fields of the foreign type must be public, and are cloned when writing.
+///
+/// **Syntax:**
+/// ```ignore
+/// #[binary_io(remote = "path::to::Type")]
+/// #[with(Mirror)]
+/// ```
+///
+/// **Compatibility:**
+/// - ✅ Named Structs
+/// - ✅ Unnamed Structs
+/// - ❌ Enums
+///
+/// **Example:**
+/// ```ignore
+/// use binary_util::interfaces::{Reader, Writer};
+/// use binary_util::BinaryIo;
+///
+/// #[derive(BinaryIo)]
+/// #[binary_io(remote = "glam::Vec3")]
+/// struct Vec3Def {
+///     x: f32,
+///     y: f32,
+///     z: f32,
+/// }
+///
+/// #[derive(BinaryIo)]
+/// struct Teleport {
+///     entity: u64,
+///     #[with(Vec3Def)]
+///     position: glam::Vec3,
+/// }
+/// ```
+/// ---
+///
+#[proc_macro_derive(
+    BinaryIo,
+    attributes(
+        binary_io,
+        skip,
+        require,
+        if_present,
+        satisfy,
+        skip_if,
+        write_if,
+        bool_width,
+        order,
+        len_of,
+        crc_of,
+        offset_from,
+        max_len,
+        renamed_from,
+        added_in,
+        removed_in,
+        with,
+        from_ctx,
+        allow_trailing
+    )
+)]
 pub fn derive_binary_io(input: TokenStream) -> TokenStream {
     io::binary_encoder(input)
 }
+
+/// This proc-macro implements `binary_util::testing::Mock`, which constructs randomized but wire-valid
+/// instances of a type, to feed integration tests and fuzzers.
+///
+/// Fields are built with the same attributes as `BinaryIo`, meaning:
+/// - `#[skip]` fields are always `Default::default()`.
+/// - `#[satisfy(EXPR)]` fields are only present when the expression is true.
+/// - `#[require(FIELD)]` fields, and the fields they require, are always present.
+/// - `#[if_present(FIELD)]` fields are only present when `FIELD` is present.
+/// - Collections with their length stored in a `#[len_of]` field never hold more items than that field can store,
+///   and `#[max_len(N)]` collections never hold more than `N`, which requires the collection to implement
+///   `binary_util::testing::MockLen`.
+///
+/// `#[skip_if]` and `#[write_if]` fields are built like any other field, so values where the field is not written
+/// will not read back the same.
+///
+/// **Example:**
+/// ```ignore
+/// use binary_util::{BinaryIo, MockBinary};
+/// use binary_util::testing::random;
+///
+/// #[derive(BinaryIo, MockBinary, Debug)]
+/// struct ABC {
+///     a: u8,
+///     #[satisfy(self.a == 10)]
+///     b: Option<u8>,
+///     c: u8,
+/// }
+///
+/// fn main() {
+///     let abc: ABC = random();
+///     assert!(abc.write_to_bytes().is_ok());
+/// }
+/// ```
+#[proc_macro_derive(
+    MockBinary,
+    attributes(
+        binary_io,
+        skip,
+        require,
+        if_present,
+        satisfy,
+        skip_if,
+        write_if,
+        bool_width,
+        order,
+        len_of,
+        crc_of,
+        offset_from,
+        max_len,
+        renamed_from,
+        added_in,
+        removed_in,
+        with,
+        from_ctx,
+        allow_trailing
+    )
+)]
+pub fn derive_mock_binary(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let crate_path = crate_path(&parse_container_attributes(
+        &input.attrs,
+        &mut proc_macro2::TokenStream::new(),
+    ));
+    let stream = mock::derive_mock(input);
+    match crate_path {
+        Some(path) => rewrite_crate_path(stream, &path).into(),
+        None => stream.into(),
+    }
+}
@@ -0,0 +1,235 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote, ToTokens, TokenStreamExt};
+use std::collections::HashMap;
+use syn::{Data, DeriveInput, Fields};
+
+use crate::io::structs::local_expr;
+use crate::io::util::attrs::{parse_fields, resolve_generic_type, wire_order, FieldAttrs, IoAttr};
+
+/// Implements `::binary_util::testing::Mock` for a struct or enum.
+///
+/// Fields are built in the order they are encoded, so the `#[satisfy]`, `#[require]` and `#[if_present]`
+/// attributes can reference previously built fields, the same way they do when reading.
+pub(crate) fn derive_mock(input: DeriveInput) -> TokenStream2 {
+    let name = &input.ident;
+    let mut error_stream = TokenStream2::new();
+
+    let body = match input.data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(ref fields) => {
+                let field_attrs = parse_fields(&fields.named, &mut error_stream);
+                // fields referenced by #[require] must be present, otherwise the struct can not be written.
+                let required = field_attrs
+                    .iter()
+                    .flat_map(|attrs| attrs.io.iter())
+                    .filter_map(|attr| match attr {
+                        IoAttr::Require(id) => Some(id),
+                        _ => None,
+                    })
+                    .collect::<Vec<&syn::Ident>>();
+                // collections whose length is stored with #[len_of], mapped to the type storing it.
+                let len_types = field_attrs
+                    .iter()
+                    .filter_map(|attrs| match attrs.attr() {
+                        Some(IoAttr::LenOf(target)) => Some((target, &attrs.field.ty)),
+                        _ => None,
+                    })
+                    .collect::<HashMap<&syn::Ident, &syn::Type>>();
+
+                let mut builder = TokenStream2::new();
+                let mut names: Vec<&syn::Ident> = Vec::new();
+
+                for attrs in wire_order(&field_attrs, &mut error_stream) {
+                    let field_name = attrs.field.ident.as_ref().unwrap();
+                    let forced = required.contains(&field_name);
+                    builder.append_all(mock_field(
+                        attrs,
+                        quote!(#field_name),
+                        forced,
+                        len_types.get(field_name).copied(),
+                        &mut error_stream,
+                    ));
+                    names.push(field_name);
+                }
+
+                // `#[len_of]` fields are populated from the length of their collection, as they are when writing.
+                for attrs in field_attrs.iter() {
+                    if let Some(IoAttr::LenOf(target)) = attrs.attr() {
+                        let (name, ty) = (&attrs.field.ident, &attrs.field.ty);
+                        builder.append_all(quote!(
+                            let #name = <#ty as ::std::convert::TryFrom<usize>>::try_from(
+                                ::binary_util::interfaces::Counted::count(&#target),
+                            )
+                            .expect("Mocked collection is bounded by its #[len_of] field.");
+                        ));
+                    }
+                }
+
+                // `#[crc_of]` fields are populated from the checksum of their field, as they are when writing.
+                for attrs in field_attrs.iter() {
+                    if let Some(IoAttr::CrcOf(target, algorithm)) = attrs.attr() {
+                        let (name, ty) = (&attrs.field.ident, &attrs.field.ty);
+                        builder.append_all(quote!(
+                            let #name = <#ty as ::std::convert::From<u32>>::from(::binary_util::checksum::#algorithm(
+                                ::binary_util::interfaces::Writer::write_to_bytes(&#target)
+                                    .expect("Mocked field could not be written.")
+                                    .as_slice(),
+                            ));
+                        ));
+                    }
+                }
+
+                quote! {
+                    #builder
+                    Self { #(#names),* }
+                }
+            }
+            Fields::Unnamed(ref fields) => {
+                let mut builder = TokenStream2::new();
+                let names = (0..fields.unnamed.len())
+                    .map(|i| format_ident!("__mock_unnamed_{}", i))
+                    .collect::<Vec<syn::Ident>>();
+
+                let field_attrs = parse_fields(&fields.unnamed, &mut error_stream);
+                for attrs in wire_order(&field_attrs, &mut error_stream) {
+                    let field_name = &names[attrs.index];
+                    builder.append_all(mock_field(
+                        attrs,
+                        quote!(#field_name),
+                        false,
+                        None,
+                        &mut error_stream,
+                    ));
+                }
+
+                quote! {
+                    #builder
+                    Self(#(#names),*)
+                }
+            }
+            Fields::Unit => {
+                return syn::Error::new_spanned(
+                    name,
+                    "Unit structs are not supported by binary_util because they have no fields to parse or write.",
+                )
+                .to_compile_error();
+            }
+        },
+        Data::Enum(ref data) => {
+            let count = data.variants.len();
+            if count == 0 {
+                return syn::Error::new_spanned(
+                    name,
+                    "Enums must have at least one variant to be mocked.",
+                )
+                .to_compile_error();
+            }
+            let arms = data.variants.iter().enumerate().map(|(i, variant)| {
+                let variant_name = &variant.ident;
+                let construct = match variant.fields {
+                    Fields::Unnamed(ref fields) => {
+                        let args = fields
+                            .unnamed
+                            .iter()
+                            .map(|field| {
+                                let ty = &field.ty;
+                                quote!(<#ty as ::binary_util::testing::Mock>::mock(rng))
+                            })
+                            .collect::<Vec<TokenStream2>>();
+                        quote!(Self::#variant_name(#(#args),*))
+                    }
+                    _ => quote!(Self::#variant_name),
+                };
+                // the last variant catches every remaining index.
+                if i == count - 1 {
+                    quote!(_ => #construct,)
+                } else {
+                    quote!(#i => #construct,)
+                }
+            });
+
+            quote! {
+                match rng.below(#count) {
+                    #(#arms)*
+                }
+            }
+        }
+        Data::Union(_) => {
+            return syn::Error::new_spanned(name, "Unions are not supported by binary_util.")
+                .to_compile_error();
+        }
+    };
+
+    if !error_stream.is_empty() {
+        return error_stream;
+    }
+
+    quote! {
+        impl ::binary_util::testing::Mock for #name {
+            fn mock(rng: &mut ::binary_util::testing::MockRng) -> Self {
+                #body
+            }
+        }
+    }
+}
+
+/// Builds a single field as a local binding named `name`.
+/// If `forced` is true, an `Option` field will always be present.
+/// If `len_ty` is set, the field is a collection whose length is stored in a `#[len_of]` field of that type.
+fn mock_field(
+    attrs: &FieldAttrs,
+    name: TokenStream2,
+    forced: bool,
+    len_ty: Option<&syn::Type>,
+    error_stream: &mut TokenStream2,
+) -> TokenStream2 {
+    let (field, ty) = (attrs.field, &attrs.field.ty);
+    let inner = resolve_generic_type(ty, "Option", error_stream);
+    let mock_inner = quote!(Some(<#inner as ::binary_util::testing::Mock>::mock(rng)));
+
+    // removed fields are never written, so they are always read back as their default.
+    if attrs.history.removed_in.is_some() {
+        return quote!(let #name: #ty = Default::default(););
+    }
+
+    // the collection is bounded by the largest length its #[len_of] field can store.
+    if let Some(len_ty) = len_ty {
+        return quote!(
+            let #name = <#ty as ::binary_util::testing::MockLen>::mock_with_max_len(
+                rng,
+                ::binary_util::testing::max_count::<#len_ty>(),
+            );
+        );
+    }
+
+    match attrs.attr() {
+        Some(IoAttr::Skip | IoAttr::FromCtx) => quote!(let #name: #ty = Default::default();),
+        // populated once every field is built, see `derive_mock`.
+        Some(IoAttr::LenOf(_)) | Some(IoAttr::CrcOf(_, _)) => TokenStream2::new(),
+        Some(IoAttr::MaxLen(max)) => quote!(
+            let #name = <#ty as ::binary_util::testing::MockLen>::mock_with_max_len(rng, #max);
+        ),
+        // the offset of the field depends on the encoded size of every field before it.
+        Some(IoAttr::OffsetFrom(_)) => syn::Error::new_spanned(
+            field,
+            "MockBinary can not build a field with #[offset_from], implement `Mock` by hand instead!",
+        )
+        .to_compile_error(),
+        Some(IoAttr::Satisfy(expr)) => {
+            let expr = local_expr(expr);
+            quote!(let #name = if #expr { #mock_inner } else { None };)
+        }
+        Some(IoAttr::Require(_)) => quote!(let #name = #mock_inner;),
+        Some(IoAttr::With(mirror)) => {
+            quote!(let #name: #ty = <#mirror as ::binary_util::testing::Mock>::mock(rng).into();)
+        }
+        Some(IoAttr::IfPresent(id)) => {
+            quote!(let #name = if #id.is_some() { #mock_inner } else { None };)
+        }
+        _ if forced && inner.is_some() => quote!(let #name = #mock_inner;),
+        _ => {
+            let ty = ty.to_token_stream();
+            quote!(let #name = <#ty as ::binary_util::testing::Mock>::mock(rng);)
+        }
+    }
+}
@@ -0,0 +1,51 @@
+//! Measures how long the 300 derived types of `tests/derive_fixture.rs` take to compile,
+//! which is dominated by the expansion of the derive macros.
+//!
+//! Run with `cargo bench --bench derive`.
+use std::fs::File;
+use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, Instant, SystemTime};
+
+const RUNS: u32 = 3;
+
+/// Builds the fixture into `target_dir`, returning the time taken.
+fn build_fixture(target_dir: &Path) -> Duration {
+    let start = Instant::now();
+    let status = Command::new(env!("CARGO"))
+        .args([
+            "build",
+            "--quiet",
+            "--test",
+            "derive_fixture",
+            "--manifest-path",
+        ])
+        .arg(Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml"))
+        .arg("--target-dir")
+        .arg(target_dir)
+        .status()
+        .expect("cargo could not be run");
+    assert!(status.success(), "the fixture failed to build");
+    start.elapsed()
+}
+
+fn main() {
+    // a separate target directory, since the one running this bench is locked while it runs.
+    let target_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("derive_fixture");
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/derive_fixture.rs");
+    // builds the dependencies of the fixture, which are not measured.
+    build_fixture(&target_dir);
+
+    let mut total = Duration::ZERO;
+    for _ in 0..RUNS {
+        // only the fixture is rebuilt, as its sources appear to have changed.
+        File::options()
+            .write(true)
+            .open(&fixture)
+            .and_then(|file| file.set_modified(SystemTime::now()))
+            .expect("the fixture could not be touched");
+        total += build_fixture(&target_dir);
+    }
+
+    println!("derive fixture: {:?} per build", total / RUNS);
+}
@@ -0,0 +1,34 @@
+//! Compares the cost of rejecting malformed input with and without detailed errors.
+//!
+//! Run with `cargo bench --bench errors`.
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use binary_util::io::ByteReader;
+use bytes::Bytes;
+
+const ITERATIONS: u32 = 1_000_000;
+
+/// Reads a `u32` and a var-int from buffers that are too short, returning the time taken per failure.
+fn reject_malformed(detailed: bool) -> Duration {
+    let truncated = Bytes::from_static(&[0, 1]);
+    let overlong = Bytes::from_static(&[255, 255, 255, 255, 255, 255]);
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        let mut reader =
+            ByteReader::from(black_box(truncated.clone())).with_detailed_errors(detailed);
+        black_box(reader.read_u32().unwrap_err());
+        let mut reader =
+            ByteReader::from(black_box(overlong.clone())).with_detailed_errors(detailed);
+        black_box(reader.read_var_u32().unwrap_err());
+    }
+    start.elapsed() / (ITERATIONS * 2)
+}
+
+fn main() {
+    let detailed = reject_malformed(true);
+    let lean = reject_malformed(false);
+
+    println!("detailed errors: {:?} per error", detailed);
+    println!("lean errors:     {:?} per error", lean);
+}
@@ -0,0 +1,66 @@
+//! Drives a `Driver` over a `TcpStream`, with a client sending chat messages and a server echoing them back.
+//!
+//! Run with `cargo run --example driver`.
+use std::io::Read;
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+
+use binary_util::driver::{Driver, Framing};
+use binary_util::BinaryIo;
+
+#[derive(BinaryIo, Debug, PartialEq)]
+#[repr(u8)]
+enum Packet {
+    Chat(String),
+    Echo(String),
+    Disconnect,
+}
+
+/// Feeds bytes read from `stream` to `driver` until a packet is decoded, returning `None` once the stream ends.
+fn next_packet(
+    stream: &mut TcpStream,
+    driver: &mut Driver<Packet>,
+) -> Result<Option<Packet>, std::io::Error> {
+    let mut buf = [0; 512];
+    loop {
+        if let Some(packet) = driver.poll_packet()? {
+            return Ok(Some(packet));
+        }
+        match stream.read(&mut buf)? {
+            0 => return Ok(None),
+            read => driver.feed(&buf[..read]),
+        }
+    }
+}
+
+fn serve(mut stream: TcpStream) -> Result<(), std::io::Error> {
+    let mut driver = Driver::<Packet>::new().with_framing(Framing::VarU32);
+    while let Some(packet) = next_packet(&mut stream, &mut driver)? {
+        match packet {
+            Packet::Chat(message) => driver.send(&Packet::Echo(message.to_uppercase()))?,
+            Packet::Disconnect => break,
+            Packet::Echo(_) => {}
+        }
+        driver.flush_to(&mut stream)?;
+    }
+    Ok(())
+}
+
+fn main() -> Result<(), std::io::Error> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?;
+    let server = thread::spawn(move || serve(listener.accept()?.0));
+
+    let mut stream = TcpStream::connect(addr)?;
+    let mut driver = Driver::<Packet>::new().with_framing(Framing::VarU32);
+    for message in ["hello", "sans-io", "world"] {
+        driver.send(&Packet::Chat(message.into()))?;
+        driver.flush_to(&mut stream)?;
+        if let Some(Packet::Echo(echo)) = next_packet(&mut stream, &mut driver)? {
+            println!("{} -> {}", message, echo);
+        }
+    }
+    driver.send(&Packet::Disconnect)?;
+    driver.flush_to(&mut stream)?;
+    server.join().unwrap()
+}
@@ -0,0 +1,242 @@
+use std::io::ErrorKind;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::time::Duration;
+
+use bytes::Bytes;
+
+use crate::io::ByteReader;
+
+const MAGIC_MICROS: u32 = 0xa1b2_c3d4;
+const MAGIC_NANOS: u32 = 0xa1b2_3c4d;
+const MAGIC_PCAPNG: u32 = 0x0a0d_0d0a;
+
+const ERR_NOT_PCAP: &str = "Not a pcap file, the magic number is unknown";
+const ERR_PCAPNG: &str = "pcapng files are not supported, convert the capture to pcap first";
+
+/// The link types a capture can be read from, which are the values of `Capture::link_type`.
+pub mod link_type {
+    /// BSD loopback, where frames start with the address family of the host.
+    pub const NULL: u32 = 0;
+    /// Ethernet, including frames tagged with 802.1Q VLANs.
+    pub const ETHERNET: u32 = 1;
+    /// Raw IPv4 or IPv6, without a link layer header.
+    pub const RAW: u32 = 101;
+    /// Linux "cooked" capture, IE: captures on the `any` interface.
+    pub const LINUX_SLL: u32 = 113;
+    /// Linux "cooked" capture, version 2.
+    pub const LINUX_SLL2: u32 = 276;
+}
+
+/// The transport protocol a `Packet` was captured from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Transport {
+    Udp,
+    Tcp,
+}
+
+/// The payload of a UDP datagram or TCP segment read from a capture.
+#[derive(Debug)]
+pub struct Packet {
+    /// The time the packet was captured, since the unix epoch.
+    pub timestamp: Duration,
+    pub src: SocketAddr,
+    pub dst: SocketAddr,
+    pub transport: Transport,
+    /// The payload of the datagram or segment, without any of its headers.
+    pub payload: ByteReader,
+}
+
+/// An iterator over the UDP and TCP payloads of a pcap file, which allows captured traffic
+/// to be replayed through decoders in tests.
+///
+/// Frames that are not UDP or TCP over IPv4 or IPv6, such as ARP, are skipped, as are fragmented IPv4 packets.
+/// TCP segments are yielded one by one, without reassembling the stream.
+/// Only the classic pcap format is supported, pcapng files must be converted first, IE: with `editcap -F pcap`.
+///
+/// ```rust no_run
+/// use binary_util::capture::Capture;
+///
+/// fn main() {
+///     for packet in Capture::open("tests/fixtures/login.pcap").unwrap() {
+///         let mut packet = packet.unwrap();
+///         if packet.dst.port() == 19132 {
+///             println!("{} -> {}: {:?}", packet.src, packet.dst, packet.payload.read_u8());
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Capture {
+    buf: ByteReader,
+    little_endian: bool,
+    nanos: bool,
+    link_type: u32,
+    failed: bool,
+}
+
+impl Capture {
+    /// Reads the pcap file at `path`.
+    pub fn open(path: impl AsRef<std::path::Path>) -> Result<Self, std::io::Error> {
+        Self::from_bytes(std::fs::read(path)?)
+    }
+
+    /// Reads a pcap file from memory.
+    pub fn from_bytes(data: impl Into<Bytes>) -> Result<Self, std::io::Error> {
+        let mut buf = ByteReader::from(data.into());
+        let (little_endian, nanos) = match buf.read_u32()? {
+            MAGIC_MICROS => (false, false),
+            MAGIC_NANOS => (false, true),
+            magic if magic.swap_bytes() == MAGIC_MICROS => (true, false),
+            magic if magic.swap_bytes() == MAGIC_NANOS => (true, true),
+            MAGIC_PCAPNG => return Err(buf.error_at_offset(ErrorKind::InvalidData, ERR_PCAPNG)),
+            _ => return Err(buf.error_at_offset(ErrorKind::InvalidData, ERR_NOT_PCAP)),
+        };
+        let mut capture = Self {
+            buf,
+            little_endian,
+            nanos,
+            link_type: 0,
+            failed: false,
+        };
+        // version, timezone, timestamp accuracy and snapshot length, which are not needed.
+        capture.buf.read_bytes(16)?;
+        capture.link_type = capture.read_u32()? & 0x0fff_ffff;
+        Ok(capture)
+    }
+
+    /// Returns the link type of the capture, see the [`link_type`] module.
+    /// Captures of other link types yield no packets.
+    pub fn link_type(&self) -> u32 {
+        self.link_type
+    }
+
+    fn read_u32(&mut self) -> Result<u32, std::io::Error> {
+        if self.little_endian {
+            self.buf.read_u32_le()
+        } else {
+            self.buf.read_u32()
+        }
+    }
+
+    fn read_record(&mut self) -> Result<(Duration, Bytes), std::io::Error> {
+        let seconds = self.read_u32()? as u64;
+        let fraction = self.read_u32()? as u64;
+        let len = self.read_u32()?;
+        let _original_len = self.read_u32()?;
+        let frame = self.buf.read_bytes(len as usize)?;
+        let fraction = match self.nanos {
+            true => Duration::from_nanos(fraction),
+            false => Duration::from_micros(fraction),
+        };
+        Ok((Duration::from_secs(seconds) + fraction, frame))
+    }
+}
+
+impl Iterator for Capture {
+    type Item = Result<Packet, std::io::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.failed && !self.buf.as_slice().is_empty() {
+            let (timestamp, frame) = match self.read_record() {
+                Ok(record) => record,
+                Err(e) => {
+                    // the records can not be found after a truncated one.
+                    self.failed = true;
+                    return Some(Err(e));
+                }
+            };
+            if let Some(packet) = parse_frame(self.link_type, timestamp, &frame) {
+                return Some(Ok(packet));
+            }
+        }
+        None
+    }
+}
+
+fn be16(frame: &[u8], at: usize) -> Option<u16> {
+    frame
+        .get(at..at + 2)
+        .map(|b| u16::from_be_bytes([b[0], b[1]]))
+}
+
+/// Returns the offset of the IP header within a frame of `link_type`.
+fn ip_offset(link_type: u32, frame: &[u8]) -> Option<usize> {
+    let (ether_type, offset) = match link_type {
+        link_type::NULL => return Some(4),
+        link_type::RAW => return Some(0),
+        link_type::LINUX_SLL => (be16(frame, 14)?, 16),
+        link_type::LINUX_SLL2 => (be16(frame, 0)?, 20),
+        link_type::ETHERNET => {
+            let mut offset = 12;
+            // skip any 802.1Q and 802.1ad VLAN tags.
+            while matches!(be16(frame, offset)?, 0x8100 | 0x88a8) {
+                offset += 4;
+            }
+            (be16(frame, offset)?, offset + 2)
+        }
+        _ => return None,
+    };
+    matches!(ether_type, 0x0800 | 0x86dd).then_some(offset)
+}
+
+fn parse_frame(link_type: u32, timestamp: Duration, frame: &Bytes) -> Option<Packet> {
+    let ip = ip_offset(link_type, frame)?;
+    let header = frame.get(ip..)?;
+    // (source, destination, protocol, start of the transport header, end of the ip packet)
+    let (src, dst, protocol, start, end): (IpAddr, IpAddr, u8, usize, usize) =
+        match header.first()? >> 4 {
+            4 => {
+                let header_len = ((header[0] & 0x0f) as usize) * 4;
+                let fragment = be16(header, 6)?;
+                // only the first fragment has the transport header, and the payload is incomplete.
+                if fragment & 0x3fff != 0 || header_len < 20 {
+                    return None;
+                }
+                let octets = header.get(12..20)?;
+                (
+                    Ipv4Addr::new(octets[0], octets[1], octets[2], octets[3]).into(),
+                    Ipv4Addr::new(octets[4], octets[5], octets[6], octets[7]).into(),
+                    header[9],
+                    header_len,
+                    be16(header, 2)? as usize,
+                )
+            }
+            6 => {
+                let src: [u8; 16] = header.get(8..24)?.try_into().ok()?;
+                let dst: [u8; 16] = header.get(24..40)?.try_into().ok()?;
+                (
+                    Ipv6Addr::from(src).into(),
+                    Ipv6Addr::from(dst).into(),
+                    *header.get(6)?,
+                    40,
+                    40 + be16(header, 4)? as usize,
+                )
+            }
+            _ => return None,
+        };
+    // link layers may pad short frames, and the capture may be cut short by its snapshot length.
+    let packet = header.get(..end.min(header.len()))?;
+    let segment = packet.get(start..)?;
+    let (transport, payload_start, payload_end) = match protocol {
+        17 => (
+            Transport::Udp,
+            8,
+            (be16(segment, 4)? as usize).min(segment.len()),
+        ),
+        6 => (
+            Transport::Tcp,
+            ((segment.get(12)? >> 4) as usize) * 4,
+            segment.len(),
+        ),
+        _ => return None,
+    };
+    let offset = ip + start;
+    let payload = frame.slice(offset + payload_start.min(payload_end)..offset + payload_end);
+    Some(Packet {
+        timestamp,
+        src: SocketAddr::new(src, be16(segment, 0)?),
+        dst: SocketAddr::new(dst, be16(segment, 2)?),
+        transport,
+        payload: ByteReader::from(payload),
+    })
+}
@@ -0,0 +1,38 @@
+/// The lookup table of the IEEE CRC-32 polynomial (reversed), computed at compile time.
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                0xEDB8_8320 ^ (crc >> 1)
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// Computes the CRC-32 (IEEE) checksum of `bytes`, as used by zlib, gzip and PNG.
+///
+/// This is the algorithm used by `#[crc_of(field, crc32)]`.
+///
+/// ```rust
+/// use binary_util::checksum::crc32;
+///
+/// fn main() {
+///     assert_eq!(crc32(b"123456789"), 0xCBF43926);
+///     assert_eq!(crc32(&[]), 0);
+/// }
+/// ```
+pub fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0u32, |crc, byte| {
+        CRC32_TABLE[((crc ^ *byte as u32) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
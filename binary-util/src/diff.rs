@@ -0,0 +1,162 @@
+use std::fmt;
+use std::ops::Range;
+
+use crate::interfaces::{WireDebug, WireField};
+use crate::io::ByteWriter;
+
+/// A run of bytes that differ between two buffers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffRange {
+    /// The range of the mismatch. If the buffers are different lengths, this may extend past the end of one of them.
+    pub range: Range<usize>,
+    /// The name of the field the range belongs to, if the diff is schema-aware.
+    pub field: Option<&'static str>,
+    /// The bytes of the left buffer within `range`.
+    pub left: Vec<u8>,
+    /// The bytes of the right buffer within `range`.
+    pub right: Vec<u8>,
+}
+
+/// The result of comparing two buffers with `compare`, `compare_fields` or `compare_type`.
+///
+/// The report implements `Display`, printing one line per mismatched range:
+/// ```text
+/// [2..4] name: 03 61 != 03 62
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffReport {
+    pub left_len: usize,
+    pub right_len: usize,
+    pub ranges: Vec<DiffRange>,
+}
+
+impl DiffReport {
+    /// Returns whether both buffers were identical.
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+}
+
+impl fmt::Display for DiffReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.left_len != self.right_len {
+            writeln!(f, "length {} != {}", self.left_len, self.right_len)?;
+        }
+        for diff in &self.ranges {
+            write!(f, "[{}..{}]", diff.range.start, diff.range.end)?;
+            if let Some(field) = diff.field {
+                write!(f, " {}", field)?;
+            }
+            writeln!(f, ": {} != {}", hex(&diff.left), hex(&diff.right))?;
+        }
+        Ok(())
+    }
+}
+
+/// Formats `bytes` as hex, or `(none)` if the range is past the end of the buffer.
+fn hex(bytes: &[u8]) -> String {
+    if bytes.is_empty() {
+        return "(none)".to_string();
+    }
+    bytes
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Compares two buffers byte by byte, returning every run of mismatched bytes.
+///
+/// ```rust
+/// use binary_util::diff::compare;
+///
+/// fn main() {
+///     let report = compare(&[1, 2, 3, 4], &[1, 9, 9, 4, 5]);
+///     assert_eq!(report.ranges.len(), 2);
+///     assert_eq!(report.ranges[0].range, 1..3);
+///     assert_eq!(report.ranges[1].range, 4..5);
+///     assert_eq!(report.to_string(), "length 4 != 5\n[1..3]: 02 03 != 09 09\n[4..5]: (none) != 05\n");
+/// }
+/// ```
+pub fn compare(left: &[u8], right: &[u8]) -> DiffReport {
+    compare_fields(left, right, &[])
+}
+
+/// Compares two buffers byte by byte like `compare`, labelling each mismatched range with the
+/// field it belongs to. Ranges are split at field boundaries, so each belongs to at most one field.
+///
+/// `fields` are usually retrieved from `WireDebug::write_fields`, see `compare_type`.
+pub fn compare_fields(left: &[u8], right: &[u8], fields: &[WireField]) -> DiffReport {
+    let len = left.len().max(right.len());
+    let field_at = |pos: usize| {
+        fields
+            .iter()
+            .find(|field| field.range.contains(&pos))
+            .map(|field| field.name)
+    };
+
+    let mut ranges: Vec<DiffRange> = Vec::new();
+    for pos in 0..len {
+        if left.get(pos) == right.get(pos) {
+            continue;
+        }
+        let field = field_at(pos);
+        match ranges.last_mut() {
+            Some(last) if last.range.end == pos && last.field == field => last.range.end += 1,
+            _ => ranges.push(DiffRange {
+                range: pos..pos + 1,
+                field,
+                left: Vec::new(),
+                right: Vec::new(),
+            }),
+        }
+    }
+
+    for diff in ranges.iter_mut() {
+        diff.left = slice_within(left, &diff.range).to_vec();
+        diff.right = slice_within(right, &diff.range).to_vec();
+    }
+
+    DiffReport {
+        left_len: left.len(),
+        right_len: right.len(),
+        ranges,
+    }
+}
+
+/// Encodes `expected` and compares it against `actual`, labelling mismatched ranges with the
+/// names of the fields of `expected`.
+///
+/// ```rust
+/// use binary_util::diff::compare_type;
+/// use binary_util::interfaces::{Reader, Writer};
+/// use binary_util::BinaryIo;
+///
+/// #[derive(BinaryIo)]
+/// #[binary_io(debug_wire)]
+/// struct Handshake {
+///     protocol: u16,
+///     name: String,
+/// }
+///
+/// fn main() {
+///     let expected = Handshake { protocol: 47, name: "abc".into() };
+///     let report = compare_type(&expected, &[0, 47, 3, b'a', b'b', b'd']).unwrap();
+///     assert_eq!(report.to_string(), "[5..6] name: 63 != 64\n");
+/// }
+/// ```
+pub fn compare_type<T: WireDebug>(
+    expected: &T,
+    actual: &[u8],
+) -> Result<DiffReport, std::io::Error> {
+    let mut buf = ByteWriter::new();
+    let fields = expected.write_fields(&mut buf)?;
+    Ok(compare_fields(buf.as_slice(), actual, &fields))
+}
+
+/// Returns the part of `range` that lies within `buf`.
+fn slice_within<'a>(buf: &'a [u8], range: &Range<usize>) -> &'a [u8] {
+    let start = range.start.min(buf.len());
+    let end = range.end.min(buf.len());
+    &buf[start..end]
+}
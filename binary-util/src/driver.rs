@@ -0,0 +1,331 @@
+use std::collections::VecDeque;
+use std::io::{ErrorKind, Write};
+use std::marker::PhantomData;
+
+use bytes::{Buf, Bytes, BytesMut};
+
+use crate::interfaces::{Reader, Writer};
+use crate::io::{BinaryError, BinaryErrorExt, ByteReader, ByteWriter, MAX_VAR_U32_LEN};
+use crate::pool::BytePool;
+use crate::varint::{self, Decode, Decoder, MAX_VAR_U64_LEN};
+
+/// The capacity of the buffers `Driver` encodes packets into, unless a pool is given with `with_pool`.
+const DEFAULT_BUFFER_CAPACITY: usize = 1024;
+
+const ERR_TRUNCATED_FRAME: &str = "Packet ends before the frame it was sent in";
+const ERR_FRAME_TOO_LONG: &str = "Frame is longer than the maximum frame length";
+const ERR_FRAME_OVERFLOW: &str = "Packet is too long to be framed";
+
+/// How packets are delimited on the wire, see `Driver::with_framing`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Framing {
+    /// Packets are sent back to back, and must encode their own length, IE: a `BinaryIo` enum of packets.
+    /// Once a packet fails to decode, the rest of the buffered bytes are discarded, as the next packet can not be found.
+    #[default]
+    Delimited,
+    /// Every packet is prefixed with its length as a `var_u32`.
+    /// A packet which fails to decode is skipped, and decoding continues with the next frame.
+    /// A frame longer than `Driver::with_max_frame_len` is skipped without being buffered.
+    VarU32,
+}
+
+/// A minimal sans-io packet loop, which decodes packets of type `P` from bytes fed to it,
+/// and encodes packets queued with `send` into buffers for the caller to transmit.
+///
+/// The driver performs no io itself, so the same driver works with blocking sockets, non-blocking sockets,
+/// async runtimes or tests, which feed it bytes however they were received. `P` is typically a `BinaryIo` enum
+/// of every packet of the protocol.
+///
+/// Packets are decoded incrementally: a packet which has not fully arrived is kept buffered, and is decoded
+/// once `feed` has been given the rest of it. Encoded packets are written to buffers taken from a `BytePool`,
+/// which `recycle` returns them to once they have been transmitted.
+///
+/// ```rust
+/// use binary_util::driver::Driver;
+/// use binary_util::BinaryIo;
+///
+/// #[derive(BinaryIo, Debug, PartialEq)]
+/// #[repr(u8)]
+/// enum Packet {
+///     Ping(u64),
+///     Chat(String),
+/// }
+///
+/// fn main() {
+///     let mut client = Driver::<Packet>::new();
+///     client.send(&Packet::Chat("hello".into())).unwrap();
+///     client.send(&Packet::Ping(7)).unwrap();
+///
+///     let mut wire = Vec::new();
+///     client.flush_to(&mut wire).unwrap();
+///
+///     // the bytes arrive split at an arbitrary point.
+///     let mut server = Driver::<Packet>::new();
+///     server.feed(&wire[..4]);
+///     assert_eq!(server.poll_packet().unwrap(), None);
+///     server.feed(&wire[4..]);
+///     assert_eq!(server.poll_packet().unwrap(), Some(Packet::Chat("hello".into())));
+///     assert_eq!(server.poll_packet().unwrap(), Some(Packet::Ping(7)));
+///     assert_eq!(server.poll_packet().unwrap(), None);
+/// }
+/// ```
+///
+/// See `examples/driver.rs` for a loop driving a `TcpStream`.
+#[derive(Debug)]
+pub struct Driver<P> {
+    /// The bytes fed to the driver which have not been decoded yet.
+    inbound: BytesMut,
+    /// The amount of buffered bytes needed before decoding is attempted again.
+    awaiting: usize,
+    /// The amount of bytes of a skipped frame which have not been fed to the driver yet.
+    discarding: usize,
+    max_frame_len: Option<usize>,
+    decoded: VecDeque<P>,
+    /// The error to return once the packets decoded before it have been polled.
+    error: Option<std::io::Error>,
+    outbound: VecDeque<ByteWriter>,
+    pool: BytePool,
+    framing: Framing,
+    _packet: PhantomData<fn() -> P>,
+}
+
+impl<P> Default for Driver<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P> Driver<P> {
+    pub fn new() -> Self {
+        Self {
+            inbound: BytesMut::new(),
+            awaiting: 0,
+            discarding: 0,
+            max_frame_len: None,
+            decoded: VecDeque::new(),
+            error: None,
+            outbound: VecDeque::new(),
+            pool: BytePool::new(DEFAULT_BUFFER_CAPACITY),
+            framing: Framing::default(),
+            _packet: PhantomData,
+        }
+    }
+
+    /// Sets how packets are delimited on the wire, which is `Framing::Delimited` by default.
+    pub fn with_framing(mut self, framing: Framing) -> Self {
+        self.framing = framing;
+        self
+    }
+
+    /// Limits the length of frames received with `Framing::VarU32`, which are unlimited by default.
+    ///
+    /// A frame with a longer length prefix fails with `InvalidData` as soon as its prefix arrives,
+    /// and the rest of it is discarded as it is fed to the driver, rather than buffered.
+    pub fn with_max_frame_len(mut self, max_frame_len: usize) -> Self {
+        self.max_frame_len = Some(max_frame_len);
+        self
+    }
+
+    /// Encodes packets into buffers taken from `pool`, IE: one with a `DecayPolicy`.
+    pub fn with_pool(mut self, pool: BytePool) -> Self {
+        self.pool = pool;
+        self
+    }
+
+    /// Buffers bytes received from the peer, which are decoded by `poll_packet`.
+    pub fn feed(&mut self, bytes: &[u8]) {
+        let skipped = self.discarding.min(bytes.len());
+        self.discarding -= skipped;
+        self.inbound.extend_from_slice(&bytes[skipped..]);
+    }
+
+    /// Returns the amount of bytes fed to the driver which are not part of a decoded packet yet.
+    pub fn buffered(&self) -> usize {
+        self.inbound.len()
+    }
+
+    /// Returns the amount of encoded packets waiting to be transmitted.
+    pub fn queued(&self) -> usize {
+        self.outbound.len()
+    }
+
+    /// Returns the next encoded packet to transmit, if any.
+    /// Once it has been transmitted, the buffer should be returned to the driver with `recycle`.
+    pub fn poll_transmit(&mut self) -> Option<ByteWriter> {
+        self.outbound.pop_front()
+    }
+
+    /// Returns a buffer returned by `poll_transmit` to the pool, so it is reused by the next packet sent.
+    pub fn recycle(&mut self, buf: ByteWriter) {
+        self.pool.put(buf);
+    }
+
+    /// Writes every queued packet to `w`, recycling their buffers.
+    ///
+    /// If `w` fails, the packet which could not be written is queued again, and may be partially written.
+    pub fn flush_to<W: Write + ?Sized>(&mut self, w: &mut W) -> Result<(), std::io::Error> {
+        while let Some(buf) = self.outbound.pop_front() {
+            if let Err(e) = w.write_all(buf.as_slice()) {
+                self.outbound.push_front(buf);
+                return Err(e);
+            }
+            self.pool.put(buf);
+        }
+        Ok(())
+    }
+}
+
+impl<P: Reader<P>> Driver<P> {
+    /// Returns the next packet decoded from the bytes fed to the driver,
+    /// or `None` if more bytes are needed to decode it.
+    ///
+    /// Errors are returned once the packets decoded before them have been returned, see `Framing`
+    /// for how decoding continues afterwards.
+    pub fn poll_packet(&mut self) -> Result<Option<P>, std::io::Error> {
+        if self.decoded.is_empty() && self.error.is_none() && self.inbound.len() >= self.awaiting {
+            match self.framing {
+                Framing::Delimited => self.decode_delimited(),
+                Framing::VarU32 => self.decode_frames(),
+            }
+        }
+        match self.decoded.pop_front() {
+            Some(packet) => Ok(Some(packet)),
+            None => self.error.take().map_or(Ok(None), Err),
+        }
+    }
+
+    /// Decodes every complete packet within the buffered bytes, keeping the bytes of an incomplete one.
+    fn decode_delimited(&mut self) {
+        let buffered = self.inbound.split().freeze();
+        let mut reader = ByteReader::from(buffered.clone());
+        self.awaiting = 0;
+        while !reader.as_slice().is_empty() {
+            let checkpoint = reader.checkpoint();
+            match reader.read_type::<P>() {
+                Ok(packet) => self.decoded.push_back(packet),
+                Err(e) if e.is_eof() => {
+                    // the rest of the packet has not arrived yet, so it is not decoded again until it might have.
+                    reader.restore(checkpoint);
+                    self.awaiting = reader.as_slice().len() + e.needed_bytes().unwrap_or(1).max(1);
+                    break;
+                }
+                Err(e) => {
+                    // the next packet can not be found, so the buffered bytes are discarded.
+                    self.error = Some(e);
+                    return;
+                }
+            }
+        }
+        let rest = buffered.slice(reader.offset()..);
+        drop((reader, buffered));
+        // the bytes are only copied if a decoded packet still references them.
+        self.inbound = rest
+            .try_into_mut()
+            .unwrap_or_else(|rest| BytesMut::from(&rest[..]));
+    }
+
+    /// Decodes every complete frame, splitting them off the buffered bytes.
+    fn decode_frames(&mut self) {
+        self.awaiting = 0;
+        while !self.inbound.is_empty() {
+            let (prefix, len) = match Self::frame_header(&self.inbound) {
+                Ok(Some(header)) => header,
+                Ok(None) => {
+                    self.awaiting = self.inbound.len() + 1;
+                    break;
+                }
+                Err(e) => {
+                    // the next frame can not be found, so the buffered bytes are discarded.
+                    self.inbound.clear();
+                    self.error = Some(e);
+                    break;
+                }
+            };
+            let size = prefix.saturating_add(len);
+            if matches!(self.max_frame_len, Some(max) if len > max) {
+                let skipped = size.min(self.inbound.len());
+                self.inbound.advance(skipped);
+                self.discarding = size - skipped;
+                self.error =
+                    Some(BinaryError::new(ErrorKind::InvalidData, ERR_FRAME_TOO_LONG).into());
+                break;
+            }
+            if self.inbound.len() < size {
+                // the rest of the frame has not arrived yet.
+                self.awaiting = size;
+                break;
+            }
+            let mut frame = self.inbound.split_to(size).freeze();
+            frame.advance(prefix);
+            match Self::read_frame(frame) {
+                Ok(packet) => self.decoded.push_back(packet),
+                Err(e) => {
+                    self.error = Some(e);
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Returns the length of the prefix of the next frame and the length of the frame after it,
+    /// or `None` if the prefix has not fully arrived yet.
+    fn frame_header(buf: &[u8]) -> Result<Option<(usize, usize)>, std::io::Error> {
+        let mut decoder = Decoder::u32();
+        for (i, byte) in buf.iter().enumerate() {
+            if let Decode::Complete(len) = decoder.push(*byte)? {
+                return Ok(Some((i + 1, len as usize)));
+            }
+        }
+        Ok(None)
+    }
+
+    fn read_frame(frame: Bytes) -> Result<P, std::io::Error> {
+        // the frame is complete, so running out of bytes within it means the packet is corrupt.
+        ByteReader::from(frame)
+            .read_exact_type::<P>()
+            .map_err(|e| match e.is_eof() {
+                true => BinaryError::new(ErrorKind::InvalidData, ERR_TRUNCATED_FRAME)
+                    .caused_by(e)
+                    .into(),
+                false => e,
+            })
+    }
+}
+
+impl<P: Writer> Driver<P> {
+    /// Encodes `packet`, and queues it to be transmitted.
+    pub fn send(&mut self, packet: &P) -> Result<(), std::io::Error> {
+        let mut buf = self.pool.get();
+        let encoded = match self.framing {
+            Framing::Delimited => packet.write(&mut buf),
+            Framing::VarU32 => Self::write_frame(&mut buf, packet),
+        };
+        match encoded {
+            Ok(()) => {
+                self.outbound.push_back(buf);
+                Ok(())
+            }
+            Err(e) => {
+                self.pool.put(buf);
+                Err(e)
+            }
+        }
+    }
+
+    /// Writes `packet` after a slot for its length prefix, which is filled in once the length is known.
+    /// The part of the slot a shorter prefix does not need is discarded, so the body is never copied.
+    fn write_frame(buf: &mut ByteWriter, packet: &P) -> Result<(), std::io::Error> {
+        buf.write(&[0; MAX_VAR_U32_LEN])?;
+        packet.write(buf)?;
+        let len = u32::try_from(buf.len() - MAX_VAR_U32_LEN).map_err(|_| -> std::io::Error {
+            BinaryError::new(ErrorKind::InvalidInput, ERR_FRAME_OVERFLOW).into()
+        })?;
+
+        let mut prefix = [0; MAX_VAR_U64_LEN];
+        let prefix = varint::encode(len as u64, &mut prefix);
+        let start = MAX_VAR_U32_LEN - prefix.len();
+        buf.buf[start..MAX_VAR_U32_LEN].copy_from_slice(prefix);
+        buf.buf.advance(start);
+        Ok(())
+    }
+}
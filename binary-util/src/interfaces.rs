@@ -3,8 +3,48 @@
 
 use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
 
-use crate::io::{ByteReader, ByteWriter};
-use crate::types::{i24, u24, vari32, vari64, varu32, varu64, BE, LE};
+use bytes::{Bytes, BytesMut};
+
+#[cfg(feature = "prost")]
+use crate::io::ERR_EOM;
+use crate::io::{BinaryError, ByteReader, ByteWriter, ERR_INVALID_UTF8};
+use crate::pool::{BytePool, PooledBytes};
+#[cfg(feature = "json")]
+use crate::types::Json;
+#[cfg(feature = "msgpack")]
+use crate::types::MsgPack;
+#[cfg(feature = "prost")]
+use crate::types::Proto;
+use crate::types::{
+    i24, i40, i48, i56, u24, u40, u48, u56, vari32, vari64, varu32, varu64, Angle16, Angle8,
+    ByteArray, Ordered, OrderedMap, Prefixed, Raw, Reserved, Sequenced, U32Char, UnitVec24,
+    Utf16Char, Utf8Char, WriterFn, BE, LE,
+};
+#[cfg(feature = "bitflags")]
+use crate::types::{BitFlags, Flags};
+use crate::util::Secret;
+
+/// The derive macros implementing `Reader`, `Writer` and `Mock`, also available from the crate root.
+pub use crate::{BinaryIo, MockBinary};
+
+/// The amount of bytes `Vec::write_into_stream` buffers before flushing them to the sink.
+const STREAM_CHUNK: usize = 8 * 1024;
+
+const ERR_RESULT_TAG: &str = "Invalid Result tag, expected 0 (Ok) or 1 (Err)";
+#[cfg(feature = "bitflags")]
+const ERR_FLAGS_WIDTH: &str = "Flags do not fit within the width they are encoded as";
+#[cfg(feature = "json")]
+const ERR_JSON: &str = "Invalid JSON payload";
+#[cfg(feature = "msgpack")]
+const ERR_MSGPACK: &str = "Invalid MessagePack payload";
+#[cfg(feature = "prost")]
+const ERR_PROTO: &str = "Invalid Protobuf payload";
+
+const ERR_PREFIX_OVERFLOW: &str = "Length does not fit within its length prefix";
+
+const ERR_RESERVED_NOT_ZERO: &str = "Reserved bytes are not zero";
+
+const ERR_MAP_ORDER: &str = "Map keys are not in strictly ascending order";
 
 macro_rules! impl_reader {
     ($(LE<$t:ty>, $method:ident),*) => {
@@ -25,12 +65,17 @@ macro_rules! impl_reader {
             }
         )*
     };
-    ($($t:ty, $method: tt),*) => {
+    ($($t:ty, $method: tt $(=> $into: ident)?),*) => {
         $(
             impl Reader<$t> for $t {
                 fn read(buf: &mut ByteReader) -> Result<$t, std::io::Error> {
                     buf.$method()
                 }
+                $(
+                    fn read_into(out: &mut $t, buf: &mut ByteReader) -> Result<(), std::io::Error> {
+                        buf.$into(out)
+                    }
+                )?
             }
         )*
     };
@@ -98,12 +143,55 @@ pub trait Reader<Output> {
     /// For automatic implementations, use the `#[derive(BinaryIo)]` macro.
     fn read(buf: &mut ByteReader) -> Result<Output, std::io::Error>;
 
+    /// Reads `Self` into an existing value, reusing its allocations where possible, IE: the capacity of a `Vec`.
+    /// This is used with `ObjectPool` to avoid allocating a new value for every packet.
+    ///
+    /// By default, the value is replaced with one read by `read`. `Vec` reads its items in place, `String` reuses its capacity, and
+    /// `#[derive(BinaryIo)]` reads each field in place when the struct has the `#[binary_io(in_place)]` attribute.
+    /// If reading fails, `out` may be left partially overwritten.
+    fn read_into(out: &mut Output, buf: &mut ByteReader) -> Result<(), std::io::Error> {
+        *out = Self::read(buf)?;
+        Ok(())
+    }
+
     /// Reads `Self` from a `&[u8]`.
     ///
     /// This is a convenience method that creates a `ByteReader` from the slice and calls `read`.
     fn read_from_slice(buf: &[u8]) -> Result<Output, std::io::Error> {
         let mut reader = ByteReader::from(buf);
-        Self::read(&mut reader)
+        let value = Self::read(&mut reader)?;
+        #[cfg(feature = "stats")]
+        reader.stats.frame(reader.offset());
+        Ok(value)
+    }
+}
+
+/// Reads a value from a `ByteReader` into `self`, reusing its allocations where possible.
+///
+/// This is the method form of `Reader::read_into`, and is implemented for every type implementing `Reader`,
+/// including primitives, `Vec`, `String`, and types deriving `BinaryIo`. It is useful for consumers with
+/// strict allocation budgets, which decode every packet into the same value.
+///
+/// ```rust
+/// use binary_util::interfaces::{ReadInPlace, Writer};
+/// use binary_util::io::ByteReader;
+///
+/// let mut names: Vec<String> = Vec::with_capacity(16);
+/// let bytes = vec!["a".to_string(), "b".to_string()].write_to_bytes().unwrap();
+///
+/// names.read_in_place(&mut ByteReader::from(bytes.as_slice())).unwrap();
+/// assert_eq!(names, ["a", "b"]);
+/// assert!(names.capacity() >= 16);
+/// ```
+pub trait ReadInPlace {
+    /// Reads `Self` from a `ByteReader` into `self`.
+    /// If reading fails, `self` may be left partially overwritten.
+    fn read_in_place(&mut self, buf: &mut ByteReader) -> Result<(), std::io::Error>;
+}
+
+impl<T: Reader<T>> ReadInPlace for T {
+    fn read_in_place(&mut self, buf: &mut ByteReader) -> Result<(), std::io::Error> {
+        T::read_into(self, buf)
     }
 }
 
@@ -135,10 +223,11 @@ impl_reader!(
     read_f64,
     bool,
     read_bool,
+    // `char` is encoded as a u32 code point, which is deprecated in favor of the explicit `U32Char`.
     char,
     read_char,
     String,
-    read_string
+    read_string => read_string_into
 );
 
 // little endian implementations on primitive types.
@@ -194,13 +283,81 @@ where
     T: Reader<T> + Sized,
 {
     fn read(buf: &mut ByteReader) -> Result<Vec<T>, std::io::Error> {
-        let len = buf.read_var_u32()?;
-        let mut vec = Vec::with_capacity(len as usize);
+        let len = buf.read_var_u32()? as usize;
+        let len = buf.check_alloc(len)?;
+        // every item takes up at least a byte, so this bounds the allocation by the size of the buffer.
+        let mut vec = Vec::with_capacity(len.min(buf.as_slice().len()));
         for _ in 0..len {
             vec.push(T::read(buf)?);
         }
         Ok(vec)
     }
+
+    fn read_into(out: &mut Vec<T>, buf: &mut ByteReader) -> Result<(), std::io::Error> {
+        let len = buf.read_var_u32()? as usize;
+        let len = buf.check_alloc(len)?;
+        out.truncate(len);
+        for item in out.iter_mut() {
+            T::read_into(item, buf)?;
+        }
+        for _ in out.len()..len {
+            out.push(T::read(buf)?);
+        }
+        Ok(())
+    }
+}
+
+impl<K, V> Reader<OrderedMap<K, V>> for OrderedMap<K, V>
+where
+    K: Reader<K> + Ord + std::hash::Hash,
+    V: Reader<V>,
+{
+    fn read(buf: &mut ByteReader) -> Result<OrderedMap<K, V>, std::io::Error> {
+        let len = buf.read_var_u32()? as usize;
+        let len = buf.check_alloc(len)?;
+        read_map_entries(buf, len)
+    }
+}
+
+/// Reads `count` entries of an `OrderedMap`, failing if a key is not greater than the key before it.
+fn read_map_entries<K, V>(
+    buf: &mut ByteReader,
+    count: usize,
+) -> Result<OrderedMap<K, V>, std::io::Error>
+where
+    K: Reader<K> + Ord + std::hash::Hash,
+    V: Reader<V>,
+{
+    // every entry takes up at least a byte, so this bounds the allocation by the size of the buffer.
+    let mut entries: Vec<(K, V)> = Vec::with_capacity(count.min(buf.as_slice().len()));
+    for _ in 0..count {
+        let offset = buf.offset();
+        let key = K::read(buf)?;
+        if entries.last().is_some_and(|(last, _)| *last >= key) {
+            return Err(
+                BinaryError::new(std::io::ErrorKind::InvalidData, ERR_MAP_ORDER)
+                    .at_offset(offset)
+                    .into(),
+            );
+        }
+        entries.push((key, V::read(buf)?));
+    }
+    Ok(entries.into_iter().collect())
+}
+
+/// `Bytes` is encoded like `Vec<u8>`, with a varu32 length prefix.
+/// The bytes are not copied, and share the buffer of the reader.
+impl Reader<Bytes> for Bytes {
+    fn read(buf: &mut ByteReader) -> Result<Bytes, std::io::Error> {
+        buf.read_sized_slice()
+    }
+}
+
+/// `BytesMut` is encoded like `Vec<u8>`, with a varu32 length prefix.
+impl Reader<BytesMut> for BytesMut {
+    fn read(buf: &mut ByteReader) -> Result<BytesMut, std::io::Error> {
+        Ok(BytesMut::from(&buf.read_sized_slice()?[..]))
+    }
 }
 
 impl<T> Reader<Option<T>> for Option<T>
@@ -217,6 +374,175 @@ where
     }
 }
 
+/// `()` is encoded as nothing, so generic types can use it as an empty payload, IE: `Ack<T = ()>`.
+impl Reader<()> for () {
+    fn read(_: &mut ByteReader) -> Result<(), std::io::Error> {
+        Ok(())
+    }
+}
+
+/// `Result<T, E>` is encoded as a `u8` tag, `0` for `Ok` or `1` for `Err`, followed by the payload of the variant.
+/// Any other tag is rejected with an `InvalidData` error.
+impl<T, E> Reader<Result<T, E>> for Result<T, E>
+where
+    T: Reader<T> + Sized,
+    E: Reader<E> + Sized,
+{
+    fn read(buf: &mut ByteReader) -> Result<Result<T, E>, std::io::Error> {
+        match buf.read_u8()? {
+            0 => Ok(Ok(T::read(buf)?)),
+            1 => Ok(Err(E::read(buf)?)),
+            _ => Err(buf.error_at_offset(std::io::ErrorKind::InvalidData, ERR_RESULT_TAG)),
+        }
+    }
+}
+
+#[cfg(feature = "bitflags")]
+impl<F, W> Reader<BitFlags<F, W>> for BitFlags<F, W>
+where
+    F: Flags,
+    W: Reader<W> + TryInto<F::Bits>,
+{
+    fn read(buf: &mut ByteReader) -> Result<BitFlags<F, W>, std::io::Error> {
+        let bits = W::read(buf)?
+            .try_into()
+            .map_err(|_| buf.error_at_offset(std::io::ErrorKind::InvalidData, ERR_FLAGS_WIDTH))?;
+        Ok(BitFlags::new(F::from_bits_retain(bits)))
+    }
+}
+
+#[cfg(feature = "json")]
+impl<T: serde::de::DeserializeOwned> Reader<Json<T>> for Json<T> {
+    fn read(buf: &mut ByteReader) -> Result<Json<T>, std::io::Error> {
+        let offset = buf.offset();
+        let json = buf.read_string()?;
+        serde_json::from_str(&json).map(Json).map_err(|e| {
+            BinaryError::new(std::io::ErrorKind::InvalidData, ERR_JSON)
+                .at_offset(offset)
+                .caused_by(e)
+                .into()
+        })
+    }
+}
+
+#[cfg(feature = "msgpack")]
+impl<T: serde::de::DeserializeOwned> Reader<MsgPack<T>> for MsgPack<T> {
+    fn read(buf: &mut ByteReader) -> Result<MsgPack<T>, std::io::Error> {
+        let offset = buf.offset();
+        let bytes = buf.read_sized_slice()?;
+        rmp_serde::from_slice(&bytes).map(MsgPack).map_err(|e| {
+            BinaryError::new(std::io::ErrorKind::InvalidData, ERR_MSGPACK)
+                .at_offset(offset)
+                .caused_by(e)
+                .into()
+        })
+    }
+}
+
+#[cfg(feature = "prost")]
+impl<T: prost::Message + Default> Reader<Proto<T>> for Proto<T> {
+    fn read(buf: &mut ByteReader) -> Result<Proto<T>, std::io::Error> {
+        let offset = buf.offset();
+        let bytes = buf.read_sized_slice()?;
+        T::decode(bytes).map(Proto).map_err(|e| {
+            BinaryError::new(std::io::ErrorKind::InvalidData, ERR_PROTO)
+                .at_offset(offset)
+                .caused_by(e)
+                .into()
+        })
+    }
+}
+
+impl<T: Reader<T>> Reader<Secret<T>> for Secret<T> {
+    fn read(buf: &mut ByteReader) -> Result<Secret<T>, std::io::Error> {
+        Ok(Secret::new(T::read(buf)?))
+    }
+}
+
+impl<T: Reader<T>> Reader<Sequenced<T>> for Sequenced<T> {
+    fn read(buf: &mut ByteReader) -> Result<Sequenced<T>, std::io::Error> {
+        let index = u24(buf.read_u24_le()?);
+        Ok(Sequenced::new(index, T::read(buf)?))
+    }
+}
+
+impl<T: Reader<T>> Reader<Ordered<T>> for Ordered<T> {
+    fn read(buf: &mut ByteReader) -> Result<Ordered<T>, std::io::Error> {
+        let index = u24(buf.read_u24_le()?);
+        let channel = buf.read_u8()?;
+        Ok(Ordered::new(index, channel, T::read(buf)?))
+    }
+}
+
+impl<L: LengthPrefix, T: Counted> Reader<Prefixed<L, T>> for Prefixed<L, T> {
+    fn read(buf: &mut ByteReader) -> Result<Prefixed<L, T>, std::io::Error> {
+        let count = Self::read_count(buf)?;
+        let count = buf.check_alloc(count)?;
+        Self::read_items(buf, count)
+    }
+}
+
+impl<const N: usize> Reader<ByteArray<N>> for ByteArray<N> {
+    fn read(buf: &mut ByteReader) -> Result<ByteArray<N>, std::io::Error> {
+        let mut bytes = [0; N];
+        bytes.copy_from_slice(&buf.read_bytes(N)?);
+        Ok(ByteArray(bytes))
+    }
+}
+
+impl<const N: usize, const STRICT: bool> Reader<Reserved<N, STRICT>> for Reserved<N, STRICT> {
+    fn read(buf: &mut ByteReader) -> Result<Reserved<N, STRICT>, std::io::Error> {
+        let offset = buf.offset();
+        let bytes = buf.read_bytes(N)?;
+        if STRICT && bytes.iter().any(|&b| b != 0) {
+            return Err(
+                BinaryError::new(std::io::ErrorKind::InvalidData, ERR_RESERVED_NOT_ZERO)
+                    .at_offset(offset)
+                    .into(),
+            );
+        }
+        Ok(Reserved)
+    }
+}
+
+macro_rules! impl_raw {
+    ($($t:ty => $from_bytes:expr),*) => {
+        $(
+            impl Reader<Raw<$t>> for Raw<$t> {
+                fn read(buf: &mut ByteReader) -> Result<Raw<$t>, std::io::Error> {
+                    let len = buf.as_slice().len();
+                    Ok(Raw($from_bytes(buf.read_bytes(len)?)))
+                }
+            }
+
+            impl Counted for Raw<$t> {
+                fn count(&self) -> usize {
+                    self.0.len()
+                }
+
+                fn write_items(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+                    buf.write(&self.0)
+                }
+
+                fn read_items(buf: &mut ByteReader, count: usize) -> Result<Self, std::io::Error> {
+                    Ok(Raw($from_bytes(buf.read_bytes(count)?)))
+                }
+
+                /// `Raw` has no length prefix, as it consumes every remaining byte.
+                fn read_count(buf: &mut ByteReader) -> Result<usize, std::io::Error> {
+                    Ok(buf.as_slice().len())
+                }
+            }
+        )*
+    };
+}
+
+impl_raw!(
+    Bytes => |bytes| bytes,
+    BytesMut => |bytes: Bytes| BytesMut::from(&bytes[..]),
+    Vec<u8> => |bytes: Bytes| bytes.to_vec()
+);
+
 impl Reader<SocketAddr> for SocketAddr {
     fn read(buf: &mut ByteReader) -> Result<SocketAddr, std::io::Error> {
         match buf.read_u8()? {
@@ -255,14 +581,41 @@ impl Reader<SocketAddr> for SocketAddr {
                     address, port, flow, scope,
                 )))
             }
-            _ => Err(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                "Invalid IP version",
-            )),
+            _ => Err(buf.error_at_offset(std::io::ErrorKind::InvalidData, "Invalid IP version")),
         }
     }
 }
 
+impl Reader<Utf8Char> for Utf8Char {
+    fn read(buf: &mut ByteReader) -> Result<Utf8Char, std::io::Error> {
+        Ok(Utf8Char(buf.read_char_utf8()?))
+    }
+}
+
+impl Reader<Utf16Char> for Utf16Char {
+    fn read(buf: &mut ByteReader) -> Result<Utf16Char, std::io::Error> {
+        Ok(Utf16Char(buf.read_char_utf16()?))
+    }
+}
+
+impl Reader<BE<Utf16Char>> for BE<Utf16Char> {
+    fn read(buf: &mut ByteReader) -> Result<BE<Utf16Char>, std::io::Error> {
+        Ok(BE(Utf16Char(buf.read_char_utf16()?)))
+    }
+}
+
+impl Reader<LE<Utf16Char>> for LE<Utf16Char> {
+    fn read(buf: &mut ByteReader) -> Result<LE<Utf16Char>, std::io::Error> {
+        Ok(LE(Utf16Char(buf.read_char_utf16_le()?)))
+    }
+}
+
+impl Reader<U32Char> for U32Char {
+    fn read(buf: &mut ByteReader) -> Result<U32Char, std::io::Error> {
+        Ok(U32Char(buf.read_char()?))
+    }
+}
+
 impl Reader<varu32> for varu32 {
     fn read(buf: &mut ByteReader) -> Result<varu32, std::io::Error> {
         Ok(varu32(buf.read_var_u32()?))
@@ -287,6 +640,45 @@ impl Reader<vari64> for vari64 {
     }
 }
 
+macro_rules! impl_var_order {
+    ($($t:ident, $read:ident, $read_be:ident, $write:ident, $write_be:ident);*) => {
+        $(
+            /// Encoded like the bare var-int, which is LEB128, IE: the least significant group first.
+            impl Reader<LE<$t>> for LE<$t> {
+                fn read(buf: &mut ByteReader) -> Result<LE<$t>, std::io::Error> {
+                    Ok(LE($t(buf.$read()?)))
+                }
+            }
+
+            /// Encoded with the most significant group first.
+            impl Reader<BE<$t>> for BE<$t> {
+                fn read(buf: &mut ByteReader) -> Result<BE<$t>, std::io::Error> {
+                    Ok(BE($t(buf.$read_be()?)))
+                }
+            }
+
+            impl Writer for LE<$t> {
+                fn write(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+                    buf.$write(self.0 .0)
+                }
+            }
+
+            impl Writer for BE<$t> {
+                fn write(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+                    buf.$write_be(self.0 .0)
+                }
+            }
+        )*
+    };
+}
+
+impl_var_order!(
+    varu32, read_var_u32, read_var_u32_be, write_var_u32, write_var_u32_be;
+    vari32, read_var_i32, read_var_i32_be, write_var_i32, write_var_i32_be;
+    varu64, read_var_u64, read_var_u64_be, write_var_u64, write_var_u64_be;
+    vari64, read_var_i64, read_var_i64_be, write_var_i64, write_var_i64_be
+);
+
 impl Reader<LE<u24>> for LE<u24> {
     fn read(buf: &mut ByteReader) -> Result<LE<u24>, std::io::Error> {
         Ok(LE(buf.read_u24()?.into()))
@@ -323,6 +715,94 @@ impl Reader<i24> for i24 {
     }
 }
 
+/// Implements `Reader` and `Writer` for a wide integer type, as well as its `LE` and `BE` wrappers.
+macro_rules! impl_wide_int_io {
+    ($($name: ident, $read: ident, $read_le: ident, $write: ident, $write_le: ident);* $(;)?) => {
+        $(
+            impl Reader<$name> for $name {
+                fn read(buf: &mut ByteReader) -> Result<$name, std::io::Error> {
+                    Ok($name(buf.$read($name::BYTES)?))
+                }
+            }
+
+            impl Reader<BE<$name>> for BE<$name> {
+                fn read(buf: &mut ByteReader) -> Result<BE<$name>, std::io::Error> {
+                    Ok(BE($name::read(buf)?))
+                }
+            }
+
+            impl Reader<LE<$name>> for LE<$name> {
+                fn read(buf: &mut ByteReader) -> Result<LE<$name>, std::io::Error> {
+                    Ok(LE($name(buf.$read_le($name::BYTES)?)))
+                }
+            }
+
+            impl Writer for $name {
+                fn write(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+                    buf.$write(self.0, $name::BYTES)
+                }
+            }
+
+            impl Writer for BE<$name> {
+                fn write(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+                    self.0.write(buf)
+                }
+            }
+
+            impl Writer for LE<$name> {
+                fn write(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+                    buf.$write_le(self.0 .0, $name::BYTES)
+                }
+            }
+        )*
+    };
+}
+
+impl Reader<Angle8> for Angle8 {
+    fn read(buf: &mut ByteReader) -> Result<Angle8, std::io::Error> {
+        Ok(Angle8(buf.read_u8()?))
+    }
+}
+
+impl Reader<Angle16> for Angle16 {
+    fn read(buf: &mut ByteReader) -> Result<Angle16, std::io::Error> {
+        Ok(Angle16(buf.read_u16()?))
+    }
+}
+
+impl Reader<UnitVec24> for UnitVec24 {
+    fn read(buf: &mut ByteReader) -> Result<UnitVec24, std::io::Error> {
+        Ok(UnitVec24(buf.read_u24()?))
+    }
+}
+
+impl Writer for Angle8 {
+    fn write(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+        buf.write_u8(self.0)
+    }
+}
+
+impl Writer for Angle16 {
+    fn write(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+        buf.write_u16(self.0)
+    }
+}
+
+impl Writer for UnitVec24 {
+    fn write(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+        buf.write_u24(self.0)
+    }
+}
+
+impl_wide_int_io! {
+    u40, read_uint, read_uint_le, write_uint, write_uint_le;
+    u48, read_uint, read_uint_le, write_uint, write_uint_le;
+    u56, read_uint, read_uint_le, write_uint, write_uint_le;
+    i40, read_int, read_int_le, write_int, write_int_le;
+    i48, read_int, read_int_le, write_int, write_int_le;
+    i56, read_int, read_int_le, write_int, write_int_le;
+}
+
 /// Allows you to write to a `ByteWriter` without needing to know the type.
 ///
 /// ```ignore
@@ -352,60 +832,898 @@ pub trait Writer {
     fn write_to_bytes(&self) -> Result<ByteWriter, std::io::Error> {
         let mut buf = ByteWriter::new();
         self.write(&mut buf)?;
+        #[cfg(feature = "stats")]
+        buf.stats.frame(buf.buf.len());
         Ok(buf)
     }
-}
 
-// default implementations on primitive types.
-impl_writer!(
-    u8,
-    write_u8,
-    i8,
-    write_i8,
-    u16,
-    write_u16,
-    i16,
-    write_i16,
-    u24,
-    write_u24,
-    i24,
-    write_i24,
-    u32,
-    write_u32,
-    i32,
-    write_i32,
-    u64,
-    write_u64,
-    i64,
-    write_i64,
-    u128,
-    write_u128,
-    i128,
-    write_i128,
-    f32,
-    write_f32,
-    f64,
-    write_f64,
-    bool,
-    write_bool,
-    &str,
-    write_string
-);
+    /// Writes `Self` directly to `w`, producing the same bytes as `write`.
+    ///
+    /// By default, `Self` is written to a `ByteWriter` first. Byte payloads, IE: `Bytes`, `Raw` and `&[u8]`,
+    /// are written to `w` without being copied, `Vec` is flushed to `w` in chunks, and `#[derive(BinaryIo)]` structs
+    /// write each of their fields to `w` in turn. This avoids buffering the entire encoding of multi-megabyte packets,
+    /// such as world data, before sending them.
+    ///
+    /// `w` receives many small writes, so it should be buffered, IE: with `std::io::BufWriter`.
+    ///
+    /// ```rust
+    /// use binary_util::interfaces::Writer;
+    /// use binary_util::types::Raw;
+    /// use binary_util::BinaryIo;
+    ///
+    /// #[derive(BinaryIo)]
+    /// struct LevelChunk {
+    ///     x: i32,
+    ///     z: i32,
+    ///     data: Raw,
+    /// }
+    ///
+    /// fn main() {
+    ///     let chunk = LevelChunk { x: 1, z: 2, data: Raw::new(vec![7; 4096].into()) };
+    ///     let mut sink = Vec::new();
+    ///     chunk.write_into_stream(&mut sink).unwrap();
+    ///     assert_eq!(sink, chunk.write_to_bytes().unwrap().as_slice());
+    /// }
+    /// ```
+    fn write_into_stream<W: std::io::Write + ?Sized>(&self, w: &mut W) -> Result<(), std::io::Error>
+    where
+        Self: Sized,
+    {
+        w.write_all(self.write_to_bytes()?.as_slice())
+    }
 
-// little endian implementations on primitive types.
-impl_writer!(
-    LE<u16>,
-    write_u16_le,
-    LE<u32>,
-    write_u32_le,
-    LE<u64>,
-    write_u64_le,
-    LE<u128>,
-    write_u128_le,
-    LE<i16>,
-    write_i16_le,
-    LE<i32>,
-    write_i32_le,
+    /// Writes `Self` directly to `w`, like `write_into_stream`, which is only callable on sized types.
+    ///
+    /// References and boxes, IE: `&dyn Writer`, forward `write_into_stream` to this, so types which override
+    /// `write_into_stream` should implement this by calling it, which `#[derive(BinaryIo)]` does.
+    fn write_into_dyn_stream(&self, w: &mut dyn std::io::Write) -> Result<(), std::io::Error> {
+        w.write_all(self.write_to_bytes()?.as_slice())
+    }
+
+    /// Writes `Self` to a buffer taken from `pool`, which is returned to the pool once the handle is dropped.
+    ///
+    /// This avoids allocating a buffer for every packet in a "serialize, send, drop" loop.
+    ///
+    /// ```rust
+    /// use binary_util::interfaces::Writer;
+    /// use binary_util::pool::BytePool;
+    ///
+    /// fn main() {
+    ///     let mut pool = BytePool::new(64);
+    ///     for id in 0..3u32 {
+    ///         let packet = id.write_pooled(&mut pool).unwrap();
+    ///         assert_eq!(&packet[..], &id.to_be_bytes());
+    ///         // the buffer returns to the pool here, and is reused by the next packet.
+    ///     }
+    ///     assert_eq!(pool.len(), 1);
+    /// }
+    /// ```
+    fn write_pooled<'a>(&self, pool: &'a mut BytePool) -> Result<PooledBytes<'a>, std::io::Error> {
+        let mut buf = pool.get();
+        if let Err(e) = self.write(&mut buf) {
+            pool.put(buf);
+            return Err(e);
+        }
+        #[cfg(feature = "stats")]
+        buf.stats.frame(buf.buf.len());
+        Ok(PooledBytes::new(buf, pool))
+    }
+
+    /// Boxes `Self` as a `dyn Writer`, so packets of different types can be queued together,
+    /// IE: in a `Vec<Box<dyn Writer>>`.
+    ///
+    /// ```rust
+    /// use binary_util::interfaces::Writer;
+    /// use binary_util::io::ByteWriter;
+    ///
+    /// fn main() {
+    ///     let queue: Vec<Box<dyn Writer>> = vec![1u8.boxed(), "hi".to_string().boxed()];
+    ///     let mut buf = ByteWriter::new();
+    ///     for packet in &queue {
+    ///         packet.write(&mut buf).unwrap();
+    ///     }
+    ///     assert_eq!(buf.as_slice(), &[1, 2, b'h', b'i']);
+    /// }
+    /// ```
+    fn boxed(self) -> Box<dyn Writer>
+    where
+        Self: Sized + 'static,
+    {
+        Box::new(self)
+    }
+
+    /// Clones `Self` into a `Box<dyn Writer>`, see `boxed`.
+    fn boxed_clone(&self) -> Box<dyn Writer>
+    where
+        Self: Clone + 'static,
+    {
+        Box::new(self.clone())
+    }
+}
+
+/// Forwards the methods of `Writer` to the value behind a reference or box, except `boxed` and `boxed_clone`,
+/// which box the pointer itself, as the value may not be `Clone` or `'static`.
+macro_rules! forward_writer {
+    () => {
+        fn write(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+            (**self).write(buf)
+        }
+
+        fn write_to_bytes(&self) -> Result<ByteWriter, std::io::Error> {
+            (**self).write_to_bytes()
+        }
+
+        fn write_into_stream<W: std::io::Write + ?Sized>(
+            &self,
+            mut w: &mut W,
+        ) -> Result<(), std::io::Error> {
+            // the value may not be sized, so it can only be streamed through `dyn Write`.
+            (**self).write_into_dyn_stream(&mut w)
+        }
+
+        fn write_into_dyn_stream(&self, w: &mut dyn std::io::Write) -> Result<(), std::io::Error> {
+            (**self).write_into_dyn_stream(w)
+        }
+
+        fn write_pooled<'a>(
+            &self,
+            pool: &'a mut BytePool,
+        ) -> Result<PooledBytes<'a>, std::io::Error> {
+            (**self).write_pooled(pool)
+        }
+    };
+}
+
+/// References are written like the value they refer to.
+impl<T: Writer + ?Sized> Writer for &T {
+    forward_writer!();
+}
+
+/// Boxes are written like the value they hold, which allows `Box<dyn Writer>` to be written.
+impl<T: Writer + ?Sized> Writer for Box<T> {
+    forward_writer!();
+}
+
+/// Implements `Writer::write_into_dyn_stream` with `write_into_stream`, for the types overriding it.
+macro_rules! dyn_stream {
+    () => {
+        fn write_into_dyn_stream(&self, w: &mut dyn std::io::Write) -> Result<(), std::io::Error> {
+            self.write_into_stream(w)
+        }
+    };
+}
+
+/// Writes `bytes` to `w`, prefixed with their length as a `var_u32` like `ByteWriter::write_slice`.
+fn stream_slice<W: std::io::Write + ?Sized>(w: &mut W, bytes: &[u8]) -> Result<(), std::io::Error> {
+    let len = crate::io::writer::slice_len(bytes.len())?;
+    let mut prefix = [0; crate::varint::MAX_VAR_U64_LEN];
+    w.write_all(crate::varint::encode(len as u64, &mut prefix))?;
+    w.write_all(bytes)
+}
+
+/// A field of a type implementing `WireDebug`, and the range of bytes it was written to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WireField {
+    pub name: &'static str,
+    pub range: std::ops::Range<usize>,
+}
+
+/// Describes which bytes each field of a type is encoded to,
+/// which is useful when comparing an encoder against a reference implementation byte by byte.
+///
+/// This is implemented by `#[derive(BinaryIo)]` when the struct has the `#[binary_io(debug_wire)]` attribute.
+///
+/// ```rust
+/// use binary_util::interfaces::{Reader, WireDebug, Writer};
+/// use binary_util::BinaryIo;
+///
+/// #[derive(BinaryIo)]
+/// #[binary_io(debug_wire)]
+/// struct Handshake {
+///     protocol: u16,
+///     name: String,
+/// }
+///
+/// fn main() {
+///     let handshake = Handshake { protocol: 47, name: "abc".into() };
+///     assert_eq!(
+///         handshake.wire_debug().unwrap(),
+///         "protocol [0..2] 00 2f\nname     [2..6] 03 61 62 63\n"
+///     );
+/// }
+/// ```
+pub trait WireDebug: Writer {
+    /// Writes `Self` to a `ByteWriter`, returning the range of bytes each field was written to.
+    fn write_fields(&self, buf: &mut ByteWriter) -> Result<Vec<WireField>, std::io::Error>;
+
+    /// Writes `Self`, and formats each field alongside its encoded bytes in hex, one field per line.
+    fn wire_debug(&self) -> Result<String, std::io::Error> {
+        let mut buf = ByteWriter::new();
+        let fields = self.write_fields(&mut buf)?;
+        let width = fields.iter().map(|f| f.name.len()).max().unwrap_or(0);
+
+        let mut out = String::new();
+        for field in fields {
+            let hex = buf.as_slice()[field.range.clone()]
+                .iter()
+                .map(|b| format!("{:02x}", b))
+                .collect::<Vec<String>>()
+                .join(" ");
+            let line = format!(
+                "{:width$} [{}..{}] {}",
+                field.name, field.range.start, field.range.end, hex
+            );
+            out.push_str(line.trim_end());
+            out.push('\n');
+        }
+        Ok(out)
+    }
+}
+
+/// The fields of a type that were read before reading it failed, returned by `ReadPartial::read_partial`.
+#[derive(Debug)]
+pub struct PartialRead {
+    /// The fields read successfully, in the order they were read, and the range of bytes each was read from.
+    pub fields: Vec<WireField>,
+    /// The field being read when the error occurred, `None` if every field was read, IE: bytes remained after them.
+    pub failed: Option<&'static str>,
+    pub error: std::io::Error,
+}
+
+impl std::fmt::Display for PartialRead {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.failed {
+            Some(name) => write!(f, "{} (while reading `{}`", self.error, name)?,
+            None => write!(f, "{} (after reading every field", self.error)?,
+        }
+        write!(f, ", {} fields read)", self.fields.len())
+    }
+}
+
+impl std::error::Error for PartialRead {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl From<PartialRead> for std::io::Error {
+    fn from(partial: PartialRead) -> Self {
+        partial.error
+    }
+}
+
+/// Records the range of bytes each field is read from, used by the code generated for `#[binary_io(partial)]`.
+///
+/// A disabled trace records nothing, so `Reader::read` of the same type does not allocate.
+#[derive(Debug)]
+pub struct ReadTrace {
+    enabled: bool,
+    fields: Vec<WireField>,
+    current: Option<(&'static str, usize)>,
+}
+
+impl ReadTrace {
+    pub fn recording() -> Self {
+        Self {
+            enabled: true,
+            fields: Vec::new(),
+            current: None,
+        }
+    }
+
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            fields: Vec::new(),
+            current: None,
+        }
+    }
+
+    /// Marks the start of the field `name`, at the given offset of the reader.
+    pub fn enter(&mut self, name: &'static str, offset: usize) {
+        if self.enabled {
+            self.current = Some((name, offset));
+        }
+    }
+
+    /// Marks the end of the field last entered, at the given offset of the reader.
+    pub fn exit(&mut self, offset: usize) {
+        if let Some((name, start)) = self.current.take() {
+            self.fields.push(WireField {
+                name,
+                range: start..offset,
+            });
+        }
+    }
+
+    /// Returns the fields recorded so far, along with the error that stopped the read.
+    pub fn into_partial(self, error: std::io::Error) -> PartialRead {
+        PartialRead {
+            fields: self.fields,
+            failed: self.current.map(|(name, _)| name),
+            error,
+        }
+    }
+}
+
+/// Reads a type, returning the fields that were read successfully if it fails, IE: to inspect truncated captures.
+///
+/// This is implemented by `#[derive(BinaryIo)]` when the struct has the `#[binary_io(partial)]` attribute.
+/// The ranges of the fields are offsets of the reader, like `ByteReader::offset`.
+///
+/// ```rust
+/// use binary_util::interfaces::{ReadPartial, Reader, Writer};
+/// use binary_util::io::ByteReader;
+/// use binary_util::BinaryIo;
+///
+/// #[derive(BinaryIo, Debug)]
+/// #[binary_io(partial)]
+/// struct Login {
+///     protocol: u16,
+///     name: String,
+///     token: u64,
+/// }
+///
+/// fn main() {
+///     // the capture was cut off within `token`.
+///     let mut buf = ByteReader::from(&[0, 47, 3, b'a', b'b', b'c', 0, 0][..]);
+///     let partial = Login::read_partial(&mut buf).unwrap_err();
+///     assert_eq!(partial.fields.iter().map(|f| f.name).collect::<Vec<_>>(), ["protocol", "name"]);
+///     assert_eq!(partial.fields[1].range, 2..6);
+///     assert_eq!(partial.failed, Some("token"));
+/// }
+/// ```
+pub trait ReadPartial: Sized {
+    fn read_partial(buf: &mut ByteReader) -> Result<Self, PartialRead>;
+}
+
+/// Formats a value as a compact, single line dump of its fields, for logging packet traffic.
+///
+/// Values are formatted straight into any `fmt::Write`, such as the `Formatter` of a log line,
+/// without allocating intermediate `String`s. `display_wire()` adapts the value to `Display`.
+///
+/// This is implemented by `#[derive(BinaryIo)]` when the struct has the `#[binary_io(display_wire)]` attribute,
+/// which prints the name of the struct followed by each field that is written, as `name=value`.
+///
+/// ```rust
+/// use binary_util::interfaces::{Reader, WireDisplay, Writer};
+/// use binary_util::BinaryIo;
+///
+/// #[derive(BinaryIo)]
+/// #[binary_io(display_wire)]
+/// struct Handshake {
+///     protocol: u16,
+///     name: String,
+///     token: Option<u32>,
+/// }
+///
+/// fn main() {
+///     let handshake = Handshake { protocol: 47, name: "abc".into(), token: None };
+///     assert_eq!(
+///         handshake.display_wire().to_string(),
+///         "Handshake protocol=47 name=\"abc\" token=None"
+///     );
+/// }
+/// ```
+pub trait WireDisplay {
+    /// Writes the compact representation of `self` to `out`.
+    fn fmt_wire(&self, out: &mut dyn std::fmt::Write) -> std::fmt::Result;
+
+    /// Returns an adapter implementing `Display` with `fmt_wire`, IE: `log::trace!("{}", packet.display_wire())`.
+    fn display_wire(&self) -> DisplayWire<'_, Self> {
+        DisplayWire(self)
+    }
+}
+
+/// Implements `Display` for a `WireDisplay` value, created with `WireDisplay::display_wire`.
+pub struct DisplayWire<'a, T: ?Sized>(&'a T);
+
+impl<T: WireDisplay + ?Sized> std::fmt::Display for DisplayWire<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt_wire(f)
+    }
+}
+
+macro_rules! impl_wire_display {
+    ($($t:ty),*) => {
+        $(
+            impl WireDisplay for $t {
+                fn fmt_wire(&self, out: &mut dyn std::fmt::Write) -> std::fmt::Result {
+                    write!(out, "{}", self)
+                }
+            }
+        )*
+    };
+}
+
+impl_wire_display!(
+    u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64, bool, char, u24, i24, u40, u48,
+    u56, i40, i48, i56, SocketAddr, Angle8, Angle16, UnitVec24
+);
+
+macro_rules! impl_wire_display_inner {
+    ($($t:ty),*) => {
+        $(
+            impl WireDisplay for $t {
+                fn fmt_wire(&self, out: &mut dyn std::fmt::Write) -> std::fmt::Result {
+                    write!(out, "{}", self.0)
+                }
+            }
+        )*
+    };
+}
+
+impl_wire_display_inner!(varu32, vari32, varu64, vari64, Utf8Char, Utf16Char, U32Char);
+
+impl WireDisplay for str {
+    fn fmt_wire(&self, out: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        write!(out, "{:?}", self)
+    }
+}
+
+impl WireDisplay for String {
+    fn fmt_wire(&self, out: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        self.as_str().fmt_wire(out)
+    }
+}
+
+impl WireDisplay for () {
+    fn fmt_wire(&self, out: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        out.write_str("()")
+    }
+}
+
+impl<T: WireDisplay> WireDisplay for [T] {
+    fn fmt_wire(&self, out: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        out.write_char('[')?;
+        for (i, item) in self.iter().enumerate() {
+            if i > 0 {
+                out.write_str(", ")?;
+            }
+            item.fmt_wire(out)?;
+        }
+        out.write_char(']')
+    }
+}
+
+impl<K: WireDisplay + Ord, V: WireDisplay> WireDisplay for OrderedMap<K, V> {
+    fn fmt_wire(&self, out: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        out.write_char('{')?;
+        for (i, (key, value)) in self.sorted().into_iter().enumerate() {
+            if i > 0 {
+                out.write_str(", ")?;
+            }
+            key.fmt_wire(out)?;
+            out.write_str(": ")?;
+            value.fmt_wire(out)?;
+        }
+        out.write_char('}')
+    }
+}
+
+impl<T: WireDisplay> WireDisplay for Vec<T> {
+    fn fmt_wire(&self, out: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        self.as_slice().fmt_wire(out)
+    }
+}
+
+impl WireDisplay for Bytes {
+    fn fmt_wire(&self, out: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        self[..].fmt_wire(out)
+    }
+}
+
+impl WireDisplay for BytesMut {
+    fn fmt_wire(&self, out: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        self[..].fmt_wire(out)
+    }
+}
+
+impl<T: WireDisplay> WireDisplay for Option<T> {
+    fn fmt_wire(&self, out: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        match self {
+            Some(value) => value.fmt_wire(out),
+            None => out.write_str("None"),
+        }
+    }
+}
+
+impl<T: WireDisplay, E: WireDisplay> WireDisplay for Result<T, E> {
+    fn fmt_wire(&self, out: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        match self {
+            Ok(value) => {
+                out.write_str("Ok(")?;
+                value.fmt_wire(out)?;
+            }
+            Err(err) => {
+                out.write_str("Err(")?;
+                err.fmt_wire(out)?;
+            }
+        }
+        out.write_char(')')
+    }
+}
+
+impl<T: WireDisplay> WireDisplay for LE<T> {
+    fn fmt_wire(&self, out: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        self.0.fmt_wire(out)
+    }
+}
+
+impl<T: WireDisplay> WireDisplay for BE<T> {
+    fn fmt_wire(&self, out: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        self.0.fmt_wire(out)
+    }
+}
+
+impl<T: WireDisplay> WireDisplay for Sequenced<T> {
+    fn fmt_wire(&self, out: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        write!(out, "#{} ", self.index)?;
+        self.value.fmt_wire(out)
+    }
+}
+
+impl<T: WireDisplay> WireDisplay for Ordered<T> {
+    fn fmt_wire(&self, out: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        write!(out, "#{}/{} ", self.index, self.channel)?;
+        self.value.fmt_wire(out)
+    }
+}
+
+impl<T: AsRef<[u8]>> WireDisplay for Raw<T> {
+    fn fmt_wire(&self, out: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        self.0.as_ref().fmt_wire(out)
+    }
+}
+
+impl<L, T: WireDisplay> WireDisplay for Prefixed<L, T> {
+    fn fmt_wire(&self, out: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        (**self).fmt_wire(out)
+    }
+}
+
+impl<const N: usize> WireDisplay for ByteArray<N> {
+    fn fmt_wire(&self, out: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        self.0.iter().try_for_each(|b| write!(out, "{:02x}", b))
+    }
+}
+
+impl<const N: usize, const STRICT: bool> WireDisplay for Reserved<N, STRICT> {
+    fn fmt_wire(&self, out: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        write!(out, "[{} reserved]", N)
+    }
+}
+
+impl<T> WireDisplay for Secret<T> {
+    fn fmt_wire(&self, out: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        out.write_str("[REDACTED]")
+    }
+}
+
+/// The discriminants of an enum, which is implemented by `#[derive(BinaryIo)]` for every enum that is not untagged.
+///
+/// These are trait items rather than inherent ones, so they never collide with a `discriminant` method
+/// or `DISCRIMINANTS` const the enum already has.
+///
+/// ```rust
+/// use binary_util::interfaces::BinaryDiscriminant;
+/// use binary_util::BinaryIo;
+///
+/// #[derive(BinaryIo, Debug)]
+/// #[repr(u8)]
+/// enum Packet {
+///     Ping(u64) = 1,
+///     Pong(u64),
+///     Disconnect(String) = 10,
+/// }
+///
+/// fn main() {
+///     assert_eq!(Packet::DISCRIMINANTS, &[(1, "Ping"), (2, "Pong"), (10, "Disconnect")]);
+///     assert_eq!(Packet::Pong(0).discriminant(), 2);
+/// }
+/// ```
+///
+/// Every discriminant must be unique, since two variants sharing one could never be told apart when reading.
+/// Deriving `BinaryIo` on an enum where two variants have the same explicit discriminant is a compile error:
+/// ```compile_fail
+/// use binary_util::BinaryIo;
+///
+/// #[derive(BinaryIo)]
+/// #[repr(u8)]
+/// enum Packet {
+///     Ping(u64) = 1,
+///     Pong(u64) = 1,
+/// }
+/// ```
+///
+/// As is a discriminant which follows from the previous variant, and collides with a later one:
+/// ```compile_fail
+/// use binary_util::BinaryIo;
+///
+/// #[derive(BinaryIo)]
+/// #[repr(u8)]
+/// enum Packet {
+///     Ping(u64) = 1,
+///     Pong(u64),
+///     Disconnect(String) = 2,
+/// }
+/// ```
+///
+/// Discriminants computed from a const, IE: `BASE + 1`, are checked by the compiler instead:
+/// ```compile_fail
+/// use binary_util::BinaryIo;
+///
+/// const BASE: u8 = 10;
+///
+/// #[derive(BinaryIo)]
+/// #[repr(u8)]
+/// enum Packet {
+///     Ping(u64) = BASE,
+///     Pong(u64),
+///     Disconnect(String) = BASE + 1,
+/// }
+/// ```
+pub trait BinaryDiscriminant {
+    /// The `#[repr]` type of the enum.
+    type Repr: Copy + 'static;
+
+    /// The discriminant and name of every variant, in the order they are declared.
+    const DISCRIMINANTS: &'static [(Self::Repr, &'static str)];
+
+    /// Returns the discriminant of this variant, which is written before its fields.
+    fn discriminant(&self) -> Self::Repr;
+}
+
+/// A collection whose length is stored in a separate field with `#[len_of]`, instead of a var-int prefix.
+///
+/// ```rust
+/// use binary_util::interfaces::{Reader, Writer};
+/// use binary_util::BinaryIo;
+///
+/// #[derive(BinaryIo, Debug, PartialEq)]
+/// struct Chat {
+///     #[len_of(message)]
+///     len: u16,
+///     message: String,
+/// }
+///
+/// fn main() {
+///     // `len` is populated with the length of `message` when writing.
+///     let chat = Chat { len: 0, message: "hi".into() };
+///     let buf = chat.write_to_bytes().unwrap();
+///     assert_eq!(buf.as_slice(), &[0, 2, b'h', b'i']);
+///     assert_eq!(Chat::read_from_slice(buf.as_slice()).unwrap().message, "hi");
+/// }
+/// ```
+pub trait Counted: Sized {
+    /// The length stored in the length field, which is the amount of bytes in a `String`,
+    /// or the amount of items in a `Vec`.
+    fn count(&self) -> usize;
+
+    /// Writes the contents of the collection, without a length prefix.
+    fn write_items(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error>;
+
+    /// Reads a collection of `count` items (or bytes), which was written with `write_items`.
+    fn read_items(buf: &mut ByteReader, count: usize) -> Result<Self, std::io::Error>;
+
+    /// Reads the length prefix `Writer::write` writes before the items, which is a `var_u32` by default.
+    /// This is used by `#[max_len]` to check the length before the items are read.
+    fn read_count(buf: &mut ByteReader) -> Result<usize, std::io::Error> {
+        Ok(buf.read_var_u32()? as usize)
+    }
+}
+
+impl<L: LengthPrefix, T: Counted> Counted for Prefixed<L, T> {
+    fn count(&self) -> usize {
+        T::count(self)
+    }
+
+    fn write_items(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+        T::write_items(self, buf)
+    }
+
+    fn read_items(buf: &mut ByteReader, count: usize) -> Result<Self, std::io::Error> {
+        T::read_items(buf, count).map(Prefixed::new)
+    }
+
+    fn read_count(buf: &mut ByteReader) -> Result<usize, std::io::Error> {
+        let offset = buf.offset();
+        L::read(buf)?.to_len().ok_or_else(|| -> std::io::Error {
+            BinaryError::new(std::io::ErrorKind::InvalidData, ERR_PREFIX_OVERFLOW)
+                .at_offset(offset)
+                .into()
+        })
+    }
+}
+
+impl<K, V> Counted for OrderedMap<K, V>
+where
+    K: Reader<K> + Writer + Ord + std::hash::Hash,
+    V: Reader<V> + Writer,
+{
+    fn count(&self) -> usize {
+        self.len()
+    }
+
+    fn write_items(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+        for (key, value) in self.sorted() {
+            key.write(buf)?;
+            value.write(buf)?;
+        }
+        Ok(())
+    }
+
+    fn read_items(buf: &mut ByteReader, count: usize) -> Result<Self, std::io::Error> {
+        read_map_entries(buf, count)
+    }
+}
+
+impl<T> Counted for Vec<T>
+where
+    T: Reader<T> + Writer + Sized,
+{
+    fn count(&self) -> usize {
+        self.len()
+    }
+
+    fn write_items(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+        for item in self {
+            item.write(buf)?;
+        }
+        Ok(())
+    }
+
+    fn read_items(buf: &mut ByteReader, count: usize) -> Result<Self, std::io::Error> {
+        // every item takes up at least a byte, so this bounds the allocation by the size of the buffer.
+        let mut vec = Vec::with_capacity(count.min(buf.as_slice().len()));
+        for _ in 0..count {
+            vec.push(T::read(buf)?);
+        }
+        Ok(vec)
+    }
+}
+
+impl Counted for String {
+    fn count(&self) -> usize {
+        self.len()
+    }
+
+    fn write_items(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+        buf.write(self.as_bytes())
+    }
+
+    fn read_items(buf: &mut ByteReader, count: usize) -> Result<Self, std::io::Error> {
+        let offset = buf.offset();
+        String::from_utf8(buf.read_bytes(count)?.to_vec()).map_err(|e| {
+            BinaryError::new(std::io::ErrorKind::InvalidData, ERR_INVALID_UTF8)
+                .at_offset(offset)
+                .caused_by(e.utf8_error())
+                .into()
+        })
+    }
+}
+
+impl Counted for Bytes {
+    fn count(&self) -> usize {
+        self.len()
+    }
+
+    fn write_items(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+        buf.write(self)
+    }
+
+    fn read_items(buf: &mut ByteReader, count: usize) -> Result<Self, std::io::Error> {
+        buf.read_bytes(count)
+    }
+}
+
+impl Counted for BytesMut {
+    fn count(&self) -> usize {
+        self.len()
+    }
+
+    fn write_items(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+        buf.write(self)
+    }
+
+    fn read_items(buf: &mut ByteReader, count: usize) -> Result<Self, std::io::Error> {
+        Ok(BytesMut::from(&buf.read_bytes(count)?[..]))
+    }
+}
+
+/// A type a collection can be prefixed with the length of, see `Prefixed`.
+///
+/// This is implemented for unsigned integers in either byte order, and unsigned var-ints.
+pub trait LengthPrefix: Reader<Self> + Writer + Sized {
+    /// Returns the prefix of a collection of `len` items, or `None` if it does not fit.
+    fn from_len(len: usize) -> Option<Self>;
+
+    /// Returns the length stored in the prefix, or `None` if it does not fit within `usize`.
+    fn to_len(&self) -> Option<usize>;
+}
+
+macro_rules! impl_length_prefix {
+    ($($t: ty => $new: expr, |$prefix: ident| $len: expr);* $(;)?) => {
+        $(
+            impl LengthPrefix for $t {
+                fn from_len(len: usize) -> Option<Self> {
+                    TryFrom::try_from(len).ok().map($new)
+                }
+
+                fn to_len(&self) -> Option<usize> {
+                    let $prefix = self;
+                    usize::try_from($len).ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_length_prefix!(
+    u8 => |len| len, |prefix| *prefix;
+    u16 => |len| len, |prefix| *prefix;
+    u32 => |len| len, |prefix| *prefix;
+    u64 => |len| len, |prefix| *prefix;
+    LE<u16> => LE, |prefix| prefix.0;
+    LE<u32> => LE, |prefix| prefix.0;
+    LE<u64> => LE, |prefix| prefix.0;
+    BE<u16> => BE, |prefix| prefix.0;
+    BE<u32> => BE, |prefix| prefix.0;
+    BE<u64> => BE, |prefix| prefix.0;
+    varu32 => varu32, |prefix| prefix.0;
+    varu64 => varu64, |prefix| prefix.0;
+);
+
+// default implementations on primitive types.
+impl_writer!(
+    u8,
+    write_u8,
+    i8,
+    write_i8,
+    u16,
+    write_u16,
+    i16,
+    write_i16,
+    u24,
+    write_u24,
+    i24,
+    write_i24,
+    u32,
+    write_u32,
+    i32,
+    write_i32,
+    u64,
+    write_u64,
+    i64,
+    write_i64,
+    u128,
+    write_u128,
+    i128,
+    write_i128,
+    f32,
+    write_f32,
+    f64,
+    write_f64,
+    bool,
+    write_bool,
+    &str,
+    write_string
+);
+
+// little endian implementations on primitive types.
+impl_writer!(
+    LE<u16>,
+    write_u16_le,
+    LE<u32>,
+    write_u32_le,
+    LE<u64>,
+    write_u64_le,
+    LE<u128>,
+    write_u128_le,
+    LE<i16>,
+    write_i16_le,
+    LE<i32>,
+    write_i32_le,
     LE<i64>,
     write_i64_le,
     LE<i128>,
@@ -445,23 +1763,351 @@ impl Writer for String {
     }
 }
 
+/// `char` is encoded as a u32 code point, which is deprecated in favor of the explicit `U32Char`.
 impl Writer for char {
     fn write(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
         buf.write_char(*self)
     }
 }
 
+impl<K: Writer + Ord, V: Writer> Writer for OrderedMap<K, V> {
+    fn write(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+        let len = u32::try_from(self.len()).map_err(|_| {
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, ERR_PREFIX_OVERFLOW)
+        })?;
+        buf.write_var_u32(len)?;
+        for (key, value) in self.sorted() {
+            key.write(buf)?;
+            value.write(buf)?;
+        }
+        Ok(())
+    }
+}
+
 impl<T> Writer for Vec<T>
 where
     T: Writer + Sized,
 {
     fn write(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
-        buf.write_var_u32(self.len() as u32)?;
+        buf.write_var_u32(crate::io::writer::slice_len(self.len())?)?;
         for item in self {
             item.write(buf)?;
         }
         Ok(())
     }
+
+    fn write_into_stream<W: std::io::Write + ?Sized>(
+        &self,
+        w: &mut W,
+    ) -> Result<(), std::io::Error> {
+        let mut buf = ByteWriter::new();
+        buf.write_var_u32(crate::io::writer::slice_len(self.len())?)?;
+        for item in self {
+            item.write(&mut buf)?;
+            if buf.as_slice().len() >= STREAM_CHUNK {
+                w.write_all(buf.as_slice())?;
+                buf.clear();
+            }
+        }
+        w.write_all(buf.as_slice())
+    }
+
+    dyn_stream!();
+}
+
+impl Writer for Bytes {
+    fn write(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+        buf.write_slice(self)
+    }
+
+    fn write_into_stream<W: std::io::Write + ?Sized>(
+        &self,
+        w: &mut W,
+    ) -> Result<(), std::io::Error> {
+        stream_slice(w, self)
+    }
+
+    dyn_stream!();
+}
+
+impl Writer for BytesMut {
+    fn write(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+        buf.write_slice(self)
+    }
+
+    fn write_into_stream<W: std::io::Write + ?Sized>(
+        &self,
+        w: &mut W,
+    ) -> Result<(), std::io::Error> {
+        stream_slice(w, self)
+    }
+
+    dyn_stream!();
+}
+
+/// Borrowed bytes are written like `Vec<u8>`, so they can be read back as a `Vec<u8>` or `Bytes`.
+impl Writer for &[u8] {
+    fn write(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+        buf.write_slice(self)
+    }
+
+    fn write_into_stream<W: std::io::Write + ?Sized>(
+        &self,
+        w: &mut W,
+    ) -> Result<(), std::io::Error> {
+        stream_slice(w, self)
+    }
+
+    dyn_stream!();
+}
+
+#[cfg(feature = "bitflags")]
+impl<F, W> Writer for BitFlags<F, W>
+where
+    F: Flags,
+    W: Writer + TryFrom<F::Bits>,
+{
+    fn write(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+        W::try_from(self.0.bits())
+            .map_err(|_| std::io::Error::new(std::io::ErrorKind::InvalidData, ERR_FLAGS_WIDTH))?
+            .write(buf)
+    }
+}
+
+#[cfg(feature = "json")]
+impl<T: serde::Serialize> Writer for Json<T> {
+    fn write(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+        let json = serde_json::to_string(&self.0).map_err(|e| {
+            BinaryError::new(std::io::ErrorKind::InvalidData, ERR_JSON).caused_by(e)
+        })?;
+        buf.write_string(&json)
+    }
+}
+
+#[cfg(feature = "msgpack")]
+impl<T: serde::Serialize> Writer for MsgPack<T> {
+    fn write(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+        let bytes = rmp_serde::to_vec_named(&self.0).map_err(|e| {
+            BinaryError::new(std::io::ErrorKind::InvalidData, ERR_MSGPACK).caused_by(e)
+        })?;
+        buf.write_slice(&bytes)
+    }
+}
+
+#[cfg(feature = "prost")]
+impl<T: prost::Message> Writer for Proto<T> {
+    fn write(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+        // the message is encoded directly into the writer, after its length.
+        buf.write_var_u32(self.0.encoded_len() as u32)?;
+        self.0.encode(buf).map_err(|e| {
+            BinaryError::new(std::io::ErrorKind::OutOfMemory, ERR_EOM)
+                .caused_by(e)
+                .into()
+        })
+    }
+}
+
+/// Implements `Reader` and `Writer` for a vector math type, as well as its `LE` and `BE` wrappers.
+/// Each component is encoded in order as `$scalar`, and the type is constructed by calling `$new` with every component.
+#[cfg(any(feature = "glam", feature = "nalgebra"))]
+macro_rules! impl_vector_io {
+    ($($ty: ty, $scalar: ty, $new: expr, [$($c: ident),*]);* $(;)?) => {
+        $(
+            impl Reader<$ty> for $ty {
+                fn read(buf: &mut ByteReader) -> Result<$ty, std::io::Error> {
+                    $( let $c = <$scalar>::read(buf)?; )*
+                    Ok(($new)($($c),*))
+                }
+            }
+
+            impl Reader<BE<$ty>> for BE<$ty> {
+                fn read(buf: &mut ByteReader) -> Result<BE<$ty>, std::io::Error> {
+                    Ok(BE(<$ty>::read(buf)?))
+                }
+            }
+
+            impl Reader<LE<$ty>> for LE<$ty> {
+                fn read(buf: &mut ByteReader) -> Result<LE<$ty>, std::io::Error> {
+                    $( let $c = LE::<$scalar>::read(buf)?.0; )*
+                    Ok(LE(($new)($($c),*)))
+                }
+            }
+
+            impl Writer for $ty {
+                fn write(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+                    $( self.$c.write(buf)?; )*
+                    Ok(())
+                }
+            }
+
+            impl Writer for BE<$ty> {
+                fn write(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+                    self.0.write(buf)
+                }
+            }
+
+            impl Writer for LE<$ty> {
+                fn write(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+                    $( LE(self.0.$c).write(buf)?; )*
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "glam")]
+impl_vector_io! {
+    glam::Vec2, f32, glam::Vec2::new, [x, y];
+    glam::Vec3, f32, glam::Vec3::new, [x, y, z];
+    glam::Vec3A, f32, glam::Vec3A::new, [x, y, z];
+    glam::Vec4, f32, glam::Vec4::new, [x, y, z, w];
+    glam::Quat, f32, glam::Quat::from_xyzw, [x, y, z, w];
+    glam::DVec2, f64, glam::DVec2::new, [x, y];
+    glam::DVec3, f64, glam::DVec3::new, [x, y, z];
+    glam::DVec4, f64, glam::DVec4::new, [x, y, z, w];
+    glam::DQuat, f64, glam::DQuat::from_xyzw, [x, y, z, w];
+}
+
+// quaternions are encoded as `i, j, k, w`, the same order as `glam::Quat`.
+// unit quaternions are normalized when read, as the encoded components may have lost precision.
+#[cfg(feature = "nalgebra")]
+impl_vector_io! {
+    nalgebra::Vector2<f32>, f32, nalgebra::Vector2::new, [x, y];
+    nalgebra::Vector3<f32>, f32, nalgebra::Vector3::new, [x, y, z];
+    nalgebra::Vector4<f32>, f32, nalgebra::Vector4::new, [x, y, z, w];
+    nalgebra::Point2<f32>, f32, nalgebra::Point2::new, [x, y];
+    nalgebra::Point3<f32>, f32, nalgebra::Point3::new, [x, y, z];
+    nalgebra::Quaternion<f32>, f32, |i, j, k, w| nalgebra::Quaternion::new(w, i, j, k), [i, j, k, w];
+    nalgebra::UnitQuaternion<f32>, f32, |i, j, k, w| nalgebra::UnitQuaternion::new_normalize(nalgebra::Quaternion::new(w, i, j, k)), [i, j, k, w];
+    nalgebra::Vector2<f64>, f64, nalgebra::Vector2::new, [x, y];
+    nalgebra::Vector3<f64>, f64, nalgebra::Vector3::new, [x, y, z];
+    nalgebra::Vector4<f64>, f64, nalgebra::Vector4::new, [x, y, z, w];
+    nalgebra::Point2<f64>, f64, nalgebra::Point2::new, [x, y];
+    nalgebra::Point3<f64>, f64, nalgebra::Point3::new, [x, y, z];
+    nalgebra::Quaternion<f64>, f64, |i, j, k, w| nalgebra::Quaternion::new(w, i, j, k), [i, j, k, w];
+    nalgebra::UnitQuaternion<f64>, f64, |i, j, k, w| nalgebra::UnitQuaternion::new_normalize(nalgebra::Quaternion::new(w, i, j, k)), [i, j, k, w];
+}
+
+/// Implements `Reader` and `Writer` for a type generated by the `bitflags!` macro,
+/// optionally encoding the bits as a different integer type.
+///
+/// ```rust ignore
+/// bitflags::bitflags! {
+///     #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+///     pub struct Capabilities: u64 {
+///         const COMPRESSION = 1;
+///         const ENCRYPTION = 1 << 1;
+///     }
+/// }
+///
+/// // encoded as a u64
+/// binary_util::impl_bitflags_io!(Capabilities);
+/// // or, encoded as a u16
+/// binary_util::impl_bitflags_io!(Capabilities as u16);
+/// ```
+#[cfg(feature = "bitflags")]
+#[macro_export]
+macro_rules! impl_bitflags_io {
+    ($ty: ty) => {
+        $crate::impl_bitflags_io!($ty as <$ty as $crate::types::Flags>::Bits);
+    };
+    ($ty: ty as $width: ty) => {
+        impl $crate::interfaces::Reader<$ty> for $ty {
+            fn read(
+                buf: &mut $crate::io::ByteReader,
+            ) -> ::std::result::Result<$ty, ::std::io::Error> {
+                Ok(
+                    <$crate::types::BitFlags<$ty, $width> as $crate::interfaces::Reader<_>>::read(
+                        buf,
+                    )?
+                    .0,
+                )
+            }
+        }
+
+        impl $crate::interfaces::Writer for $ty {
+            fn write(
+                &self,
+                buf: &mut $crate::io::ByteWriter,
+            ) -> ::std::result::Result<(), ::std::io::Error> {
+                let flags = <$ty as $crate::types::Flags>::from_bits_retain(
+                    $crate::types::Flags::bits(self),
+                );
+                $crate::interfaces::Writer::write(
+                    &$crate::types::BitFlags::<$ty, $width>::new(flags),
+                    buf,
+                )
+            }
+        }
+    };
+}
+
+impl<T: Writer> Writer for Sequenced<T> {
+    fn write(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+        buf.write_u24_le(self.index.0)?;
+        self.value.write(buf)
+    }
+}
+
+impl<T: Writer> Writer for Ordered<T> {
+    fn write(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+        buf.write_u24_le(self.index.0)?;
+        buf.write_u8(self.channel)?;
+        self.value.write(buf)
+    }
+}
+
+impl<T: AsRef<[u8]>> Writer for Raw<T> {
+    fn write(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+        buf.write(self.0.as_ref())
+    }
+
+    fn write_into_stream<W: std::io::Write + ?Sized>(
+        &self,
+        w: &mut W,
+    ) -> Result<(), std::io::Error> {
+        w.write_all(self.0.as_ref())
+    }
+
+    dyn_stream!();
+}
+
+impl<F> Writer for WriterFn<F>
+where
+    F: Fn(&mut ByteWriter) -> Result<(), std::io::Error>,
+{
+    fn write(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+        (self.0)(buf)
+    }
+}
+
+impl<L: LengthPrefix, T: Counted> Writer for Prefixed<L, T> {
+    fn write(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+        let prefix = L::from_len(self.count()).ok_or_else(|| -> std::io::Error {
+            BinaryError::new(std::io::ErrorKind::InvalidInput, ERR_PREFIX_OVERFLOW).into()
+        })?;
+        prefix.write(buf)?;
+        self.write_items(buf)
+    }
+}
+
+impl<const N: usize> Writer for ByteArray<N> {
+    fn write(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+        buf.write(&self.0)
+    }
+}
+
+impl<const N: usize, const STRICT: bool> Writer for Reserved<N, STRICT> {
+    fn write(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+        buf.write(&[0; N])
+    }
+}
+
+impl<T: Writer> Writer for Secret<T> {
+    fn write(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+        self.expose().write(buf)
+    }
 }
 
 impl<T> Writer for Option<T>
@@ -480,6 +2126,51 @@ where
         }
         Ok(())
     }
+
+    fn write_into_stream<W: std::io::Write + ?Sized>(
+        &self,
+        w: &mut W,
+    ) -> Result<(), std::io::Error> {
+        w.write_all(&[self.is_some() as u8])?;
+        match self {
+            Some(item) => item.write_into_stream(w),
+            None => Ok(()),
+        }
+    }
+
+    dyn_stream!();
+}
+
+impl Writer for () {
+    fn write(&self, _: &mut ByteWriter) -> Result<(), std::io::Error> {
+        Ok(())
+    }
+}
+
+/// `Infallible` can never be constructed, so it is never written, IE: as the error of a `Result<T, Infallible>`.
+impl Writer for std::convert::Infallible {
+    fn write(&self, _: &mut ByteWriter) -> Result<(), std::io::Error> {
+        match *self {}
+    }
+}
+
+impl<T, E> Writer for Result<T, E>
+where
+    T: Writer + Sized,
+    E: Writer + Sized,
+{
+    fn write(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+        match self {
+            Ok(value) => {
+                buf.write_u8(0)?;
+                value.write(buf)
+            }
+            Err(err) => {
+                buf.write_u8(1)?;
+                err.write(buf)
+            }
+        }
+    }
 }
 
 impl Writer for SocketAddr {
@@ -532,6 +2223,36 @@ impl Writer for BE<i24> {
     }
 }
 
+impl Writer for Utf8Char {
+    fn write(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+        buf.write_char_utf8(self.0)
+    }
+}
+
+impl Writer for Utf16Char {
+    fn write(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+        buf.write_char_utf16(self.0)
+    }
+}
+
+impl Writer for BE<Utf16Char> {
+    fn write(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+        buf.write_char_utf16(self.0 .0)
+    }
+}
+
+impl Writer for LE<Utf16Char> {
+    fn write(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+        buf.write_char_utf16_le(self.0 .0)
+    }
+}
+
+impl Writer for U32Char {
+    fn write(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+        buf.write_char(self.0)
+    }
+}
+
 impl Writer for varu32 {
     fn write(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
         buf.write_var_u32(self.0)
@@ -0,0 +1,217 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::io::{Error, ErrorKind};
+use std::sync::Arc;
+
+/// The structured payload attached to errors returned by `ByteReader`, `ByteWriter` and their extension traits.
+///
+/// This is stored within a `std::io::Error`, and can be inspected with `BinaryErrorExt`.
+///
+/// ```rust
+/// use binary_util::io::{BinaryError, BinaryErrorExt};
+/// use std::io::ErrorKind;
+///
+/// fn main() {
+///     let err: std::io::Error = BinaryError::new(ErrorKind::UnexpectedEof, "Not enough bytes")
+///         .at_offset(12)
+///         .needing(4)
+///         .into();
+///
+///     assert!(err.is_eof());
+///     assert_eq!(err.needed_bytes(), Some(4));
+///     assert_eq!(err.to_string(), "UnexpectedEof at offset 12 needing 4 more bytes: Not enough bytes");
+/// }
+/// ```
+///
+/// When a `BinaryError` wraps another error, IE: one returned by a nested `Reader` or by `serde`,
+/// the original error is kept as its `source`, so it can be downcast and reported as part of the chain:
+///
+/// ```rust
+/// use binary_util::io::{BinaryError, BinaryErrorExt};
+/// use std::error::Error;
+/// use std::io::ErrorKind;
+///
+/// fn main() {
+///     let inner = std::io::Error::new(ErrorKind::Other, "socket closed");
+///     let err: std::io::Error = BinaryError::new(ErrorKind::Other, "Failed to read packet")
+///         .caused_by(inner)
+///         .into();
+///
+///     let source = err.binary_error().unwrap().source().unwrap();
+///     assert_eq!(source.downcast_ref::<std::io::Error>().unwrap().to_string(), "socket closed");
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct BinaryError {
+    kind: ErrorKind,
+    message: Cow<'static, str>,
+    offset: Option<usize>,
+    needed: Option<usize>,
+    overflow: bool,
+    source: Option<Arc<dyn std::error::Error + Send + Sync>>,
+}
+
+impl BinaryError {
+    pub fn new(kind: ErrorKind, message: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            offset: None,
+            needed: None,
+            overflow: false,
+            source: None,
+        }
+    }
+
+    /// Sets the offset of the reader when the error occurred.
+    pub fn at_offset(mut self, offset: usize) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Sets the amount of bytes still missing for the failed operation to succeed,
+    /// IE: the bytes a reader has yet to receive, or the space a writer lacks.
+    pub fn needing(mut self, needed: usize) -> Self {
+        self.needed = Some(needed);
+        self
+    }
+
+    /// Marks the error as an overflow, IE: a var-int that is too long, or a full buffer.
+    pub fn overflowing(mut self) -> Self {
+        self.overflow = true;
+        self
+    }
+
+    /// Sets the error which caused this one, which is returned by `std::error::Error::source`.
+    pub fn caused_by(
+        mut self,
+        source: impl Into<Box<dyn std::error::Error + Send + Sync>>,
+    ) -> Self {
+        self.source = Some(Arc::from(source.into()));
+        self
+    }
+
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn offset(&self) -> Option<usize> {
+        self.offset
+    }
+
+    pub fn needed(&self) -> Option<usize> {
+        self.needed
+    }
+
+    pub fn is_overflow(&self) -> bool {
+        self.overflow
+    }
+}
+
+impl fmt::Display for BinaryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.offset, self.needed) {
+            (Some(offset), Some(needed)) => write!(
+                f,
+                "{:?} at offset {} needing {} more bytes: {}",
+                self.kind, offset, needed, self.message
+            ),
+            (Some(offset), None) => {
+                write!(f, "{:?} at offset {}: {}", self.kind, offset, self.message)
+            }
+            _ => f.write_str(&self.message),
+        }
+    }
+}
+
+/// Errors are compared by their sources' messages, as the sources themselves can not be compared.
+impl PartialEq for BinaryError {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind == other.kind
+            && self.message == other.message
+            && self.offset == other.offset
+            && self.needed == other.needed
+            && self.overflow == other.overflow
+            && self.source.as_ref().map(|e| e.to_string())
+                == other.source.as_ref().map(|e| e.to_string())
+    }
+}
+
+impl Eq for BinaryError {}
+
+impl std::error::Error for BinaryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source
+            .as_deref()
+            .map(|e| e as &(dyn std::error::Error + 'static))
+    }
+}
+
+impl From<BinaryError> for Error {
+    fn from(err: BinaryError) -> Self {
+        Error::new(err.kind, err)
+    }
+}
+
+/// Allows a `std::io::Error` to be inspected programmatically, for example to distinguish
+/// "wait for more data" from "the data is corrupt".
+///
+/// ```rust
+/// use binary_util::io::{BinaryErrorExt, ByteReader};
+///
+/// fn main() {
+///     let mut buf = ByteReader::from(&[0, 1][..]);
+///     let err = buf.read_u32().unwrap_err();
+///     assert!(err.is_eof());
+///     assert_eq!(err.needed_bytes(), Some(2));
+///
+///     let mut buf = ByteReader::from(&[255, 255, 255, 255, 255, 255][..]);
+///     assert!(buf.read_var_u32().unwrap_err().is_overflow());
+/// }
+/// ```
+pub trait BinaryErrorExt {
+    /// Returns the structured payload of the error, if it was created by this crate.
+    fn binary_error(&self) -> Option<&BinaryError>;
+
+    /// Returns whether the error was caused by running out of bytes,
+    /// meaning the operation may succeed once more data is available.
+    fn is_eof(&self) -> bool;
+
+    /// Returns whether the error was caused by an overflow, such as a var-int that is too long.
+    fn is_overflow(&self) -> bool;
+
+    /// Returns the amount of bytes still missing for the failed operation to succeed, if known.
+    ///
+    /// For an `UnexpectedEof` error, this is how many more bytes must be received before retrying,
+    /// or a lower bound if the full length is not known yet, IE: for a truncated var-int.
+    fn needed_bytes(&self) -> Option<usize>;
+
+    /// Returns the offset of the reader when the error occurred, if known.
+    fn offset(&self) -> Option<usize>;
+}
+
+impl BinaryErrorExt for Error {
+    fn binary_error(&self) -> Option<&BinaryError> {
+        self.get_ref()?.downcast_ref::<BinaryError>()
+    }
+
+    fn is_eof(&self) -> bool {
+        self.kind() == ErrorKind::UnexpectedEof
+    }
+
+    fn is_overflow(&self) -> bool {
+        self.binary_error().is_some_and(|e| e.is_overflow())
+    }
+
+    fn needed_bytes(&self) -> Option<usize> {
+        self.binary_error()?.needed()
+    }
+
+    fn offset(&self) -> Option<usize> {
+        self.binary_error()?.offset()
+    }
+}
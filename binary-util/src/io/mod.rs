@@ -0,0 +1,251 @@
+pub const ERR_EOB: &str = "No more bytes left to be read in buffer";
+pub const ERR_INVALID_UTF8: &str = "String is not valid UTF-8";
+pub const ERR_EOM: &str = "Buffer is full, cannot write more bytes";
+pub const ERR_VARINT_TOO_LONG: &str = "Varint is too long to be written to buffer";
+pub const ERR_VARINT_SLOT: &str = "Varint slot is out of bounds of the written buffer";
+pub const ERR_MAX_DEPTH: &str = "Maximum nesting depth exceeded while reading";
+pub const ERR_DEADLINE: &str = "Decode deadline exceeded while reading";
+pub const ERR_TRAILING_BYTES: &str = "Bytes remain in buffer after reading";
+pub const ERR_WRITE_CAP: &str = "Write exceeded the maximum allowed size";
+pub const ERR_STRING_TOO_LONG: &str = "String is too long to fit within its length prefix";
+pub const ERR_SLICE_TOO_LONG: &str = "Slice is too long to fit within its length prefix";
+pub const ERR_SEEK_OUT_OF_BOUNDS: &str = "Seek position is outside of the buffer";
+pub const ERR_READ_TIMEOUT: &str = "Timed out waiting for the source to become readable";
+pub const ERR_NESTED_READ: &str = "Failed to read from the underlying buffer";
+pub const ERR_INVALID_BOOL: &str = "Bool is not 0 or 1";
+pub const ERR_INVALID_CHAR: &str = "Char is not a valid unicode scalar value";
+pub const ERR_TOO_MANY_ITEMS: &str = "More items remain in buffer than the maximum allowed";
+pub const ERR_EMPTY_ITEM: &str = "Item was read without consuming any bytes";
+pub const ERR_INVALID_VALUE: &str = "Value read is not valid";
+pub const ERR_NON_CANONICAL_VARINT: &str = "Varint is not encoded in its shortest form";
+pub const ERR_MAX_ALLOC: &str = "Length exceeds the maximum allocation of the reader";
+
+/// The maximum amount of bytes a var-int 32-bit integer can take up on the wire.
+pub const MAX_VAR_U32_LEN: usize = 5;
+
+/// The maximum nesting depth of a reader created with `ByteReader::hardened`.
+pub const HARDENED_MAX_DEPTH: usize = 64;
+
+/// The maximum allocation of a reader created with `ByteReader::hardened`, see `ByteReader::with_max_alloc`.
+pub const HARDENED_MAX_ALLOC: usize = 1 << 20;
+
+macro_rules! can_read {
+    ($buf: expr, $size: expr) => {
+        $buf.remaining() >= $size
+    };
+}
+
+macro_rules! can_write {
+    ($buf: expr, $size: expr) => {
+        $buf.remaining_mut() >= $size
+    };
+}
+
+/// Updates the `read`, `written` or `frame` counters of `$self`, when the `stats` feature is enabled.
+macro_rules! track {
+    ($self: ident, $counter: ident, $($arg: expr),*) => {
+        #[cfg(feature = "stats")]
+        $self.stats.$counter($($arg),*);
+    };
+}
+
+/// Panics with `$msg` if `$cond` holds, when the `instrumented` feature is enabled in a debug build.
+/// This is used to catch misuse of the io types, which otherwise fails silently.
+macro_rules! misuse {
+    ($cond: expr, $($msg: tt)+) => {
+        #[cfg(all(feature = "instrumented", debug_assertions))]
+        if $cond {
+            panic!("binary_util misuse: {}", format_args!($($msg)+));
+        }
+    };
+}
+
+/// Implements a read method on `RawReadExt`, where `self` is a `bytes::Buf`.
+macro_rules! read_fn {
+    ($name: ident, $typ: ident, $fn_name: ident, $byte_size: literal) => {
+        #[inline]
+        fn $name(&mut self) -> Result<$typ, BinaryError> {
+            if can_read!(self, $byte_size) {
+                return Ok(self.$fn_name());
+            } else {
+                return Err(BinaryError::new(std::io::ErrorKind::UnexpectedEof, ERR_EOB)
+                    .needing($byte_size - self.remaining()));
+            }
+        }
+    };
+}
+
+/// Implements read methods on `ReadExt`, converting the `BinaryError` returned by `RawReadExt` into an `std::io::Error`.
+macro_rules! forward_read_fn {
+    ($($(#[$meta: meta])* $name: ident($($arg: ident: $arg_ty: ty),*) -> $typ: ty;)*) => {
+        $(
+            $(#[$meta])*
+            #[inline]
+            fn $name(&mut self, $($arg: $arg_ty),*) -> Result<$typ, std::io::Error> {
+                crate::io::raw::RawReadExt::$name(self, $($arg),*).map_err(Into::into)
+            }
+        )*
+    };
+}
+
+/// Implements a write method on `WriteExt`, where `self` is a `bytes::BufMut`.
+macro_rules! write_fn {
+    ($name: ident, $typ: ident, $fn_name: ident, $byte_size: literal) => {
+        #[inline]
+        fn $name(&mut self, num: $typ) -> Result<(), std::io::Error> {
+            if can_write!(self, $byte_size) {
+                self.$fn_name(num);
+                return Ok(());
+            } else {
+                return Err(BinaryError::new(std::io::ErrorKind::OutOfMemory, ERR_EOM)
+                    .needing($byte_size - self.remaining_mut())
+                    .overflowing()
+                    .into());
+            }
+        }
+    };
+}
+
+/// Forwards read methods on `ByteReader` to the `RawReadExt` implementation of its buffer,
+/// attaching the offset of the read to any error, which is only allocated if the reader has detailed errors.
+macro_rules! delegate_read_fn {
+    ($($(#[$meta: meta])* $name: ident, $typ: ty);* $(;)?) => {
+        $(
+            $(#[$meta])*
+            #[inline]
+            #[allow(deprecated)]
+            pub fn $name(&mut self) -> Result<$typ, std::io::Error> {
+                let offset = self.offset();
+                let value = crate::io::raw::RawReadExt::$name(&mut self.buf).map_err(|e| self.error(e.at_offset(offset)))?;
+                track!(self, read, self.offset() - offset, stringify!($name).starts_with("read_var"));
+                Ok(value)
+            }
+        )*
+    };
+}
+
+/// Forwards var-int read methods on `ByteReader` like `delegate_read_fn`,
+/// rejecting encodings which are not the shortest form of their value if the reader has canonical var-ints.
+macro_rules! delegate_var_fn {
+    ($($name: ident, $typ: ty, $unsigned: expr, $big_endian: literal);* $(;)?) => {
+        $(
+            #[inline]
+            pub fn $name(&mut self) -> Result<$typ, std::io::Error> {
+                let offset = self.offset();
+                let remaining = self.buf.remaining();
+                let value = crate::io::raw::RawReadExt::$name(&mut self.buf).map_err(|e| self.error(e.at_offset(offset)))?;
+                if self.canonical_varints {
+                    self.check_canonical(remaining, $unsigned(value), $big_endian)?;
+                }
+                track!(self, read, self.offset() - offset, true);
+                Ok(value)
+            }
+        )*
+    };
+}
+
+/// Forwards write methods on `ByteWriter` to the `WriteExt` implementation of its buffer.
+macro_rules! delegate_write_fn {
+    ($($(#[$meta: meta])* $name: ident, $typ: ty);* $(;)?) => {
+        $(
+            $(#[$meta])*
+            #[inline]
+            #[allow(deprecated)]
+            pub fn $name(&mut self, num: $typ) -> Result<(), std::io::Error> {
+                #[cfg(feature = "stats")]
+                let len = self.buf.len();
+                WriteExt::$name(&mut self.buf, num)?;
+                track!(self, written, self.buf.len() - len, stringify!($name).starts_with("write_var"));
+                Ok(())
+            }
+        )*
+    };
+}
+
+/// Implements bulk numeric slice reads on `ByteReader`, decoding every element in a single pass.
+macro_rules! read_slice_fn {
+    ($($name: ident, $typ: ty, $from: ident, $endian: literal);* $(;)?) => {
+        $(
+            #[doc = concat!("Reads `len` ", $endian, " `", stringify!($typ), "`s from the stream.")]
+            ///
+            /// This is considerably faster than reading each element individually,
+            /// which is useful for large numeric arrays, IE: chunk data.
+            pub fn $name(&mut self, len: usize) -> Result<Vec<$typ>, std::io::Error> {
+                const SIZE: usize = std::mem::size_of::<$typ>();
+                let bytes = self.read_bytes(len.saturating_mul(SIZE))?;
+                Ok(bytes
+                    .chunks_exact(SIZE)
+                    .map(|chunk| {
+                        let mut raw = [0u8; SIZE];
+                        raw.copy_from_slice(chunk);
+                        <$typ>::$from(raw)
+                    })
+                    .collect())
+            }
+        )*
+    };
+}
+
+/// Implements bulk numeric slice writes on `ByteWriter`, encoding every element in a single pass.
+macro_rules! write_slice_fn {
+    ($($name: ident, $typ: ty, $to: ident, $endian: literal);* $(;)?) => {
+        $(
+            #[doc = concat!("Writes every `", stringify!($typ), "` in `values` to the buffer in ", $endian, ".")]
+            ///
+            /// The length of the slice is not written, see `write_slice` if this is needed.
+            pub fn $name(&mut self, values: &[$typ]) -> Result<(), std::io::Error> {
+                let len = std::mem::size_of_val(values);
+                if !can_write!(self.buf, len) {
+                    return Err(BinaryError::new(std::io::ErrorKind::OutOfMemory, ERR_EOM)
+                        .needing(len - self.buf.remaining_mut())
+                        .overflowing()
+                        .into());
+                }
+                self.buf.reserve(len);
+                for value in values {
+                    self.buf.put_slice(&value.$to());
+                }
+                track!(self, written, len, false);
+                Ok(())
+            }
+        )*
+    };
+}
+
+pub mod error;
+mod raw;
+pub mod reader;
+pub mod record;
+pub mod ring;
+pub mod stream;
+pub mod text;
+pub mod writer;
+
+pub use error::{BinaryError, BinaryErrorExt};
+pub use reader::{ByteReader, ReadCheckpoint, ReadExt};
+pub use record::{read_capture, ReplayStream};
+pub use ring::RingBuffer;
+pub use stream::{ByteStream, SocketTimeouts};
+pub use text::Lines;
+#[cfg(feature = "inline")]
+pub use writer::INLINE_CAPACITY;
+pub use writer::{ByteWriter, ChainedWriter, WriteExt, WriteMark};
+
+/// The io prelude, which re-exports the types and traits you will need when
+/// reading or writing binary data manually.
+///
+/// ```rust
+/// use binary_util::io::prelude::*;
+///
+/// fn main() {
+///     let mut writer = ByteWriter::new();
+///     writer.write_u16(300).unwrap();
+///
+///     let mut reader = ByteReader::from(writer.as_slice());
+///     assert_eq!(reader.read_u16().unwrap(), 300);
+/// }
+/// ```
+pub mod prelude {
+    pub use super::{BinaryErrorExt, ByteReader, ByteStream, ByteWriter, ReadExt, WriteExt};
+    pub use crate::interfaces::{Reader, Writer};
+}
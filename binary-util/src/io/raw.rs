@@ -0,0 +1,256 @@
+use bytes::Buf;
+
+use super::{BinaryError, ERR_EOB, ERR_INVALID_BOOL, ERR_INVALID_CHAR};
+use crate::varint::{zigzag_decode, Decode, Decoder};
+
+/// The implementation of `ReadExt`, which returns the `BinaryError` itself rather than an `std::io::Error`.
+///
+/// Converting a `BinaryError` into an `std::io::Error` allocates, so `ByteReader` reads through this trait,
+/// and only converts the error if it has detailed errors, see `ByteReader::with_detailed_errors`.
+pub(crate) trait RawReadExt: Buf {
+    read_fn!(read_u8, u8, get_u8, 1);
+    read_fn!(read_i8, i8, get_i8, 1);
+    read_fn!(read_u16, u16, get_u16, 2);
+    read_fn!(read_u16_le, u16, get_u16_le, 2);
+    read_fn!(read_i16, i16, get_i16, 2);
+    read_fn!(read_i16_le, i16, get_i16_le, 2);
+    read_fn!(read_u32, u32, get_u32, 4);
+    read_fn!(read_u32_le, u32, get_u32_le, 4);
+    read_fn!(read_f32, f32, get_f32, 4);
+    read_fn!(read_f32_le, f32, get_f32_le, 4);
+    read_fn!(read_i32, i32, get_i32, 4);
+    read_fn!(read_i32_le, i32, get_i32_le, 4);
+    read_fn!(read_u64, u64, get_u64, 8);
+    read_fn!(read_u64_le, u64, get_u64_le, 8);
+    read_fn!(read_i64, i64, get_i64, 8);
+    read_fn!(read_i64_le, i64, get_i64_le, 8);
+    read_fn!(read_f64, f64, get_f64, 8);
+    read_fn!(read_f64_le, f64, get_f64_le, 8);
+    read_fn!(read_u128, u128, get_u128, 16);
+    read_fn!(read_u128_le, u128, get_u128_le, 16);
+    read_fn!(read_i128, i128, get_i128, 16);
+    read_fn!(read_i128_le, i128, get_i128_le, 16);
+
+    fn read_u24(&mut self) -> Result<u32, BinaryError> {
+        Ok(self.read_uint(3)? as u32)
+    }
+
+    fn read_u24_le(&mut self) -> Result<u32, BinaryError> {
+        Ok(self.read_uint_le(3)? as u32)
+    }
+
+    fn read_i24(&mut self) -> Result<i32, BinaryError> {
+        Ok(self.read_int(3)? as i32)
+    }
+
+    fn read_i24_le(&mut self) -> Result<i32, BinaryError> {
+        Ok(self.read_int_le(3)? as i32)
+    }
+
+    #[inline]
+    fn read_var_u32(&mut self) -> Result<u32, BinaryError> {
+        Ok(read_var(self, Decoder::u32())? as u32)
+    }
+
+    fn read_var_i32(&mut self) -> Result<i32, BinaryError> {
+        Ok(zigzag_decode(self.read_var_u32()? as u64) as i32)
+    }
+
+    #[inline]
+    fn read_var_u64(&mut self) -> Result<u64, BinaryError> {
+        read_var(self, Decoder::u64())
+    }
+
+    #[inline]
+    fn read_var_i64(&mut self) -> Result<i64, BinaryError> {
+        Ok(zigzag_decode(self.read_var_u64()?))
+    }
+
+    #[inline]
+    fn read_var_u32_be(&mut self) -> Result<u32, BinaryError> {
+        Ok(read_var(self, Decoder::u32_be())? as u32)
+    }
+
+    fn read_var_i32_be(&mut self) -> Result<i32, BinaryError> {
+        Ok(zigzag_decode(self.read_var_u32_be()? as u64) as i32)
+    }
+
+    #[inline]
+    fn read_var_u64_be(&mut self) -> Result<u64, BinaryError> {
+        read_var(self, Decoder::u64_be())
+    }
+
+    fn read_var_i64_be(&mut self) -> Result<i64, BinaryError> {
+        Ok(zigzag_decode(self.read_var_u64_be()?))
+    }
+
+    fn read_uint(&mut self, size: usize) -> Result<u64, BinaryError> {
+        if can_read!(self, size) {
+            Ok(self.get_uint(size))
+        } else {
+            Err(BinaryError::new(std::io::ErrorKind::UnexpectedEof, ERR_EOB)
+                .needing(size - self.remaining()))
+        }
+    }
+
+    fn read_uint_le(&mut self, size: usize) -> Result<u64, BinaryError> {
+        if can_read!(self, size) {
+            Ok(self.get_uint_le(size))
+        } else {
+            Err(BinaryError::new(std::io::ErrorKind::UnexpectedEof, ERR_EOB)
+                .needing(size - self.remaining()))
+        }
+    }
+
+    fn read_int(&mut self, size: usize) -> Result<i64, BinaryError> {
+        if can_read!(self, size) {
+            Ok(self.get_int(size))
+        } else {
+            Err(BinaryError::new(std::io::ErrorKind::UnexpectedEof, ERR_EOB)
+                .needing(size - self.remaining()))
+        }
+    }
+
+    fn read_int_le(&mut self, size: usize) -> Result<i64, BinaryError> {
+        if can_read!(self, size) {
+            Ok(self.get_int_le(size))
+        } else {
+            Err(BinaryError::new(std::io::ErrorKind::UnexpectedEof, ERR_EOB)
+                .needing(size - self.remaining()))
+        }
+    }
+
+    fn read_char(&mut self) -> Result<char, BinaryError> {
+        let c = self.read_u32()?;
+
+        char::from_u32(c)
+            .ok_or_else(|| BinaryError::new(std::io::ErrorKind::InvalidData, ERR_INVALID_CHAR))
+    }
+
+    fn read_char_utf8(&mut self) -> Result<char, BinaryError> {
+        let len = match self.chunk().first() {
+            // the length of the char is determined by its leading byte.
+            Some(0x00..=0x7f) => 1,
+            Some(0xc2..=0xdf) => 2,
+            Some(0xe0..=0xef) => 3,
+            Some(0xf0..=0xf4) => 4,
+            Some(_) => {
+                return Err(BinaryError::new(
+                    std::io::ErrorKind::InvalidData,
+                    ERR_INVALID_CHAR,
+                ))
+            }
+            None => 1,
+        };
+        if !can_read!(self, len) {
+            return Err(BinaryError::new(std::io::ErrorKind::UnexpectedEof, ERR_EOB)
+                .needing(len - self.remaining()));
+        }
+        let mut bytes = [0; 4];
+        self.copy_to_slice(&mut bytes[..len]);
+        match std::str::from_utf8(&bytes[..len]) {
+            Ok(s) => Ok(s.chars().next().unwrap_or_default()),
+            Err(e) => Err(
+                BinaryError::new(std::io::ErrorKind::InvalidData, ERR_INVALID_CHAR).caused_by(e),
+            ),
+        }
+    }
+
+    fn read_char_utf16(&mut self) -> Result<char, BinaryError> {
+        read_utf16(self, u16::from_be_bytes)
+    }
+
+    fn read_char_utf16_le(&mut self) -> Result<char, BinaryError> {
+        read_utf16(self, u16::from_le_bytes)
+    }
+
+    fn read_bool(&mut self) -> Result<bool, BinaryError> {
+        Ok(self.read_u8()? != 0)
+    }
+
+    fn read_bool_strict(&mut self) -> Result<bool, BinaryError> {
+        match self.read_u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(BinaryError::new(
+                std::io::ErrorKind::InvalidData,
+                ERR_INVALID_BOOL,
+            )),
+        }
+    }
+}
+
+impl<B: Buf + ?Sized> RawReadExt for B {}
+
+/// Reads a UTF-16 char from `buf`, where each code unit is decoded with `from_bytes`.
+///
+/// The leading unit is peeked from the current chunk, so if the buffer ends before the trailing unit
+/// of a surrogate pair, nothing is consumed. Buffers that split the leading unit across chunks
+/// fall back to consuming it.
+fn read_utf16<B: Buf + ?Sized>(
+    buf: &mut B,
+    from_bytes: fn([u8; 2]) -> u16,
+) -> Result<char, BinaryError> {
+    let is_leading = |unit: u16| (0xd800..=0xdbff).contains(&unit);
+    // a leading surrogate is followed by a trailing one, which together encode chars beyond U+FFFF.
+    let len = match buf.chunk() {
+        [a, b, ..] if is_leading(from_bytes([*a, *b])) => 4,
+        _ => 2,
+    };
+    let read_unit = |buf: &mut B| {
+        if !can_read!(buf, 2) {
+            return Err(BinaryError::new(std::io::ErrorKind::UnexpectedEof, ERR_EOB)
+                .needing(2 - buf.remaining()));
+        }
+        let mut unit = [0; 2];
+        buf.copy_to_slice(&mut unit);
+        Ok(from_bytes(unit))
+    };
+    if !can_read!(buf, len) {
+        return Err(BinaryError::new(std::io::ErrorKind::UnexpectedEof, ERR_EOB)
+            .needing(len - buf.remaining()));
+    }
+    let first = read_unit(buf)?;
+    let units = match is_leading(first) {
+        true => [first, read_unit(buf)?],
+        false => [first, 0],
+    };
+    match char::decode_utf16(units).next() {
+        Some(Ok(c)) => Ok(c),
+        Some(Err(e)) => {
+            Err(BinaryError::new(std::io::ErrorKind::InvalidData, ERR_INVALID_CHAR).caused_by(e))
+        }
+        None => Err(BinaryError::new(
+            std::io::ErrorKind::InvalidData,
+            ERR_INVALID_CHAR,
+        )),
+    }
+}
+
+/// Reads a var-int with `decoder`.
+///
+/// The var-int is peeked from the current chunk first, so nothing is consumed if the buffer ends early.
+/// Buffers that split the var-int across chunks fall back to reading byte by byte.
+fn read_var<B: Buf + ?Sized>(buf: &mut B, mut decoder: Decoder) -> Result<u64, BinaryError> {
+    let chunk = buf.chunk();
+
+    for (i, byte) in chunk.iter().enumerate() {
+        if let Decode::Complete(num) = decoder.push_raw(*byte)? {
+            buf.advance(i + 1);
+            return Ok(num);
+        }
+    }
+
+    if buf.remaining() > chunk.len() {
+        // the var-int continues in the next chunk.
+        decoder.reset();
+        loop {
+            if let Decode::Complete(num) = decoder.push_raw(buf.read_u8()?)? {
+                return Ok(num);
+            }
+        }
+    }
+
+    // the var-int needs at least one more byte, its full length is only known once it ends.
+    Err(BinaryError::new(std::io::ErrorKind::UnexpectedEof, ERR_EOB).needing(1))
+}
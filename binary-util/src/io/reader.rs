@@ -0,0 +1,1567 @@
+use bytes::{Buf, Bytes};
+use std::{
+    any::Any,
+    borrow::Cow,
+    collections::VecDeque,
+    io::Error,
+    mem::MaybeUninit,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use super::{
+    BinaryError, BinaryErrorExt, ByteWriter, ERR_DEADLINE, ERR_EMPTY_ITEM, ERR_EOB,
+    ERR_INVALID_UTF8, ERR_INVALID_VALUE, ERR_MAX_ALLOC, ERR_MAX_DEPTH, ERR_NON_CANONICAL_VARINT,
+    ERR_SEEK_OUT_OF_BOUNDS, ERR_TOO_MANY_ITEMS, ERR_TRAILING_BYTES, HARDENED_MAX_ALLOC,
+    HARDENED_MAX_DEPTH,
+};
+use crate::interfaces::Reader;
+#[cfg(feature = "stats")]
+use crate::stats::Stats;
+use crate::varint::{encode, encode_be, zigzag_encode, MAX_VAR_U64_LEN};
+
+/// Panic-free numeric read helpers for any type implementing `bytes::Buf`.
+///
+/// This trait is implemented for every `Buf`, so third-party buffer types can use the
+/// same helpers as `ByteReader` by importing it.
+///
+/// ```rust
+/// use binary_util::io::ReadExt;
+///
+/// fn main() {
+///     let mut buf = &[0, 0, 1, 44, 172, 2][..];
+///     assert_eq!(buf.read_u32().unwrap(), 300);
+///     assert_eq!(buf.read_var_u32().unwrap(), 300);
+///     assert!(buf.read_u8().is_err());
+/// }
+/// ```
+pub trait ReadExt: Buf {
+    forward_read_fn! {
+        read_u8() -> u8;
+        read_i8() -> i8;
+        read_u16() -> u16;
+        read_u16_le() -> u16;
+        read_i16() -> i16;
+        read_i16_le() -> i16;
+        /// Reads a 3-byte unsigned integer from the stream.
+        read_u24() -> u32;
+        /// Reads a 3-byte unsigned integer from the stream in little endian.
+        /// This is the same as `read_u24` but in little endian.
+        read_u24_le() -> u32;
+        read_i24() -> i32;
+        read_i24_le() -> i32;
+        read_u32() -> u32;
+        read_u32_le() -> u32;
+        read_f32() -> f32;
+        read_f32_le() -> f32;
+        /// Reads a var-int 32-bit unsigned integer from the stream.
+        /// This is a variable length integer that can be 1, 2, 3, 4 or 5 bytes long.
+        ///
+        /// This function is recoverable, meaning that if the stream ends before the
+        /// var-int is fully read, it will return an error, and will not consume the
+        /// bytes that were read.
+        read_var_u32() -> u32;
+        read_i32() -> i32;
+        read_i32_le() -> i32;
+        /// Reads a var-int 32-bit signed integer from the stream.
+        /// This method is the same as `read_var_u32` but it will return a signed integer.
+        read_var_i32() -> i32;
+        read_u64() -> u64;
+        read_u64_le() -> u64;
+        read_i64() -> i64;
+        read_i64_le() -> i64;
+        read_f64() -> f64;
+        read_f64_le() -> f64;
+        /// Reads a var-int 64-bit unsigned integer from the stream.
+        /// This is a variable length integer that can be up to 10 bytes long.
+        read_var_u64() -> u64;
+        /// Reads a var-int 64-bit signed integer from the stream.
+        /// This method is the same as `read_var_u64` but it will return a signed integer.
+        ///
+        /// For more information on how this works, see `read_var_i32`.
+        read_var_i64() -> i64;
+        /// Reads a var-int 32-bit unsigned integer with the most significant group first, IE: `BE<varu32>`.
+        /// This is recoverable like `read_var_u32`.
+        read_var_u32_be() -> u32;
+        /// Reads a zigzag encoded var-int 32-bit signed integer with the most significant group first, see `read_var_u32_be`.
+        read_var_i32_be() -> i32;
+        /// Reads a var-int 64-bit unsigned integer with the most significant group first, IE: `BE<varu64>`.
+        read_var_u64_be() -> u64;
+        /// Reads a zigzag encoded var-int 64-bit signed integer with the most significant group first, see `read_var_u64_be`.
+        read_var_i64_be() -> i64;
+        read_u128() -> u128;
+        read_u128_le() -> u128;
+        read_i128() -> i128;
+        read_i128_le() -> i128;
+        /// Reads an unsigned integer from the stream with a varying size
+        /// indicated by the `size` parameter.
+        read_uint(size: usize) -> u64;
+        /// Reads an unsigned integer from the stream with a varying size in little endian
+        /// indicated by the `size` parameter.
+        read_uint_le(size: usize) -> u64;
+        read_int(size: usize) -> i64;
+        read_int_le(size: usize) -> i64;
+        /// Reads a char encoded as its code point in a big endian `u32`, IE: `U32Char`.
+        #[deprecated(
+            note = "No common protocol encodes chars as a u32, use `read_char_utf8` or `read_char_utf16` instead"
+        )]
+        read_char() -> char;
+        /// Reads a char encoded as UTF-8, which takes 1 to 4 bytes, IE: `Utf8Char`.
+        ///
+        /// Nothing is consumed if the buffer ends before the char does.
+        read_char_utf8() -> char;
+        /// Reads a char encoded as big endian UTF-16, which takes 2 bytes, or 4 bytes for a surrogate pair, IE: `Utf16Char`.
+        read_char_utf16() -> char;
+        /// Reads a char encoded as little endian UTF-16, IE: `LE<Utf16Char>`.
+        /// This is the same as `read_char_utf16` but in little endian.
+        read_char_utf16_le() -> char;
+        /// Reads a bool, where any byte other than `0` is `true`.
+        read_bool() -> bool;
+        /// Reads a bool, failing with `InvalidData` if the byte is neither `0` nor `1`.
+        read_bool_strict() -> bool;
+    }
+}
+
+impl<B: Buf + ?Sized> ReadExt for B {}
+
+/// ByteReader is a panic-free way to read bytes from the `byte::Buf` trait.
+///
+/// ## Example
+/// ```rust
+/// use binary_util::io::ByteReader;
+///
+/// fn main() {
+///    let mut buf = ByteReader::from(&[0, 253, 255, 255, 255, 15][..]);
+///    assert_eq!(buf.read_u8().unwrap(), 0);
+///    assert_eq!(buf.read_var_i32().unwrap(), -2147483647);
+/// }
+/// ```
+///
+/// ## Peek Ahead
+/// `ByteReader` also provides a utility `peek_ahead` function that allows you to
+/// "peek ahead" at the next byte in the stream without advancing the stream.
+///
+/// Do not confuse this with any sort of "peek" function. This function does not
+/// increment the read position of the stream, but rather copies the byte at the
+/// specified position.
+/// ```rust
+/// use binary_util::io::ByteReader;
+///
+/// fn main() {
+///    let mut buf = ByteReader::from(&[253, 255, 14, 255, 255, 15][..]);
+///    if buf.peek_ahead(3).unwrap() != 255 {
+///        // buffer is corrupted!
+///    } else {
+///        // read the varint
+///        let num = buf.read_var_i32().unwrap();
+///    }
+/// }
+/// ```
+///
+/// ## Reading a struct without `BinaryDecoder`
+/// This is useful if you are trying to read a struct or optional type and validate the type before
+/// reading the rest of the struct.
+/// ```rust
+/// use binary_util::io::ByteReader;
+///
+/// struct PingPacket {
+///    pub id: u8,
+///    pub time: u64,
+///    pub ack_id: Option<i32>
+/// }
+///
+/// fn main() {
+///     let mut buf = ByteReader::from(&[0, 253, 255, 255, 255, 255, 255, 255, 255, 0][..]);
+///
+///     // Read the id
+///     let id = buf.read_u8().unwrap();
+///
+///     if id == 0 {
+///         // Read the time
+///        let time = buf.read_u64().unwrap();
+///        // read ack
+///        if buf.read_bool().unwrap() {
+///            let ack_id = buf.read_var_i32().unwrap();
+///            let packet = PingPacket { id, time, ack_id: Some(ack_id) };
+///        } else {
+///            let packet = PingPacket { id, time, ack_id: None };
+///        }
+///    }
+/// }
+/// ```
+///
+/// ## Nesting Depth
+/// Recursive types, like `struct Node { children: Vec<Node> }`, can overflow the stack when reading
+/// hostile input. `with_max_depth` limits how deeply nested types may be read, returning an error
+/// instead of overflowing the stack. Types implemented with `BinaryIo` respect this limit automatically.
+/// ```rust
+/// use binary_util::io::ByteReader;
+///
+/// fn main() {
+///     let mut buf = ByteReader::from(&[1, 1, 1, 0][..]).with_max_depth(2);
+///     buf.nested(|buf| buf.nested(|buf| buf.read_u8())).unwrap();
+///     assert!(buf.nested(|buf| buf.nested(|buf| buf.nested(|buf| buf.read_u8()))).is_err());
+/// }
+/// ```
+///
+/// ## Deadline
+/// A depth limit alone does not bound how long a decode may take, IE: a schema with many wide, shallow
+/// collections. `with_deadline` gives the reader a time budget, once it has elapsed every nested read
+/// fails with `ErrorKind::TimedOut`, allowing servers to bound the CPU time spent on a single packet.
+/// ```rust
+/// use binary_util::io::ByteReader;
+/// use std::time::Duration;
+///
+/// fn main() {
+///     let mut buf = ByteReader::from(&[1, 2][..]).with_deadline(Duration::ZERO);
+///     let err = buf.nested(|buf| buf.read_u8()).unwrap_err();
+///     assert_eq!(err.kind(), std::io::ErrorKind::TimedOut);
+/// }
+/// ```
+///
+/// ## Context
+/// Packets often need runtime metadata that is not on the wire, IE: the connection or address they were received from.
+/// `with_context` attaches a value to the reader, which hand written `Reader` implementations retrieve with `context`,
+/// and `BinaryIo` fields marked with `#[from_ctx]` are populated with.
+/// ```rust
+/// use binary_util::io::ByteReader;
+/// use std::net::SocketAddr;
+///
+/// fn main() {
+///     let peer: SocketAddr = "127.0.0.1:19132".parse().unwrap();
+///     let buf = ByteReader::from(&[1, 2][..]).with_context(peer);
+///     assert_eq!(buf.context::<SocketAddr>(), Some(&peer));
+///     assert_eq!(buf.context::<u64>(), None);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ByteReader {
+    pub(crate) buf: Bytes,
+    /// The whole buffer the reader was created over, which `seek_to` can move anywhere within.
+    /// `buf` is always a suffix of it.
+    pub(crate) full: Bytes,
+    /// The current nesting depth of the reader.
+    pub(crate) depth: usize,
+    /// The maximum nesting depth of the reader, `None` if unlimited.
+    pub(crate) max_depth: Option<usize>,
+    /// The instant after which nested reads fail, `None` if unlimited.
+    pub(crate) deadline: Option<Instant>,
+    /// The protocol version being read, `None` if the latest.
+    pub(crate) version: Option<u32>,
+    /// The length of the buffer when the reader was created, used to compute the read offset.
+    pub(crate) origin: usize,
+    /// The values attached with `with_context`, at most one of each type.
+    pub(crate) context: Vec<Arc<dyn Any + Send + Sync>>,
+    /// Whether types derived with `#[binary_io(deny_trailing)]` may leave bytes unread, see `allow_trailing`.
+    pub(crate) trailing_allowed: bool,
+    /// Whether `read_bool` fails on bytes other than `0` and `1`, see `with_strict_bools`.
+    pub(crate) strict_bools: bool,
+    /// Whether var-ints must be encoded in their shortest form, see `with_canonical_varints`.
+    pub(crate) canonical_varints: bool,
+    /// Whether errors returned by the reader carry a `BinaryError` payload, see `with_detailed_errors`.
+    pub(crate) detailed_errors: bool,
+    /// The largest length prefix a collection may be read with, `None` if unlimited, see `with_max_alloc`.
+    pub(crate) max_alloc: Option<usize>,
+    #[cfg(feature = "stats")]
+    pub(crate) stats: Stats,
+}
+
+impl From<ByteWriter> for ByteReader {
+    fn from(writer: ByteWriter) -> Self {
+        Self::from(writer.buf.freeze())
+    }
+}
+
+impl Into<Bytes> for ByteReader {
+    fn into(self) -> Bytes {
+        self.buf
+    }
+}
+
+impl Into<Vec<u8>> for ByteReader {
+    fn into(self) -> Vec<u8> {
+        self.buf.to_vec()
+    }
+}
+
+impl Into<VecDeque<u8>> for ByteReader {
+    fn into(self) -> VecDeque<u8> {
+        self.buf.to_vec().into()
+    }
+}
+
+impl From<Bytes> for ByteReader {
+    fn from(buf: Bytes) -> Self {
+        Self {
+            origin: buf.len(),
+            full: buf.clone(),
+            buf,
+            depth: 0,
+            max_depth: None,
+            deadline: None,
+            version: None,
+            context: Vec::new(),
+            trailing_allowed: false,
+            strict_bools: false,
+            canonical_varints: false,
+            detailed_errors: true,
+            max_alloc: None,
+            #[cfg(feature = "stats")]
+            stats: Stats::default(),
+        }
+    }
+}
+
+impl From<Vec<u8>> for ByteReader {
+    fn from(buf: Vec<u8>) -> Self {
+        Self::from(Bytes::from(buf))
+    }
+}
+
+impl From<&[u8]> for ByteReader {
+    fn from(buf: &[u8]) -> Self {
+        Self::from(Bytes::from(buf.to_vec()))
+    }
+}
+
+impl ByteReader {
+    /// Memory-maps the file at `path`, and creates a reader over it.
+    ///
+    /// The file is not copied into memory, and slices returned by `read_bytes` or `read_sized_slice`
+    /// reference the mapping directly, which keeps parsing large files such as packet capture dumps cheap.
+    /// The mapping is released once the reader and every slice taken from it are dropped.
+    ///
+    /// The file must not be modified while it is mapped, otherwise the bytes read are unspecified.
+    ///
+    /// ```rust
+    /// use binary_util::io::ByteReader;
+    ///
+    /// fn main() {
+    ///     let path = std::env::temp_dir().join("binary_util_from_mmap_doc.bin");
+    ///     std::fs::write(&path, [0, 0, 0, 42, 3, 1, 2, 3]).unwrap();
+    ///
+    ///     let mut buf = ByteReader::from_mmap(&path).unwrap();
+    ///     assert_eq!(buf.read_u32().unwrap(), 42);
+    ///     assert_eq!(&buf.read_sized_slice().unwrap()[..], &[1, 2, 3]);
+    ///     # drop(buf);
+    ///     # std::fs::remove_file(&path).unwrap();
+    /// }
+    /// ```
+    #[cfg(feature = "mmap")]
+    pub fn from_mmap(path: impl AsRef<std::path::Path>) -> Result<Self, std::io::Error> {
+        let file = std::fs::File::open(path)?;
+        // SAFETY: the caller must not modify the file while it is mapped, as documented above.
+        let map = unsafe { memmap2::Mmap::map(&file)? };
+        Ok(Self::from(Bytes::from_owner(map)))
+    }
+
+    /// Reads a named root compound of a tagged tree, as found at the start of an NBT file.
+    /// See [`crate::types::tagged`] for more information.
+    ///
+    /// ```rust
+    /// use binary_util::io::ByteReader;
+    /// use binary_util::types::tagged::Tag;
+    ///
+    /// fn main() {
+    ///     let mut buf = ByteReader::from(&[10, 0, 1, b'a', 1, 0, 1, b'b', 7, 0][..]);
+    ///     let nbt = buf.read_nbt().unwrap();
+    ///     assert_eq!(nbt.name, "a");
+    ///     assert_eq!(nbt.root.get("b"), Some(&Tag::Byte(7)));
+    /// }
+    /// ```
+    #[cfg(feature = "tagged")]
+    pub fn read_nbt(&mut self) -> Result<crate::types::tagged::Nbt, std::io::Error> {
+        crate::types::tagged::Nbt::read(self)
+    }
+
+    /// Returns the serialization counters of this reader.
+    /// See [`Stats`] for more information.
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+
+    /// Creates a reader over `buf` with every strictness setting enabled, for decoding untrusted input.
+    ///
+    /// This enables:
+    /// - `with_canonical_varints`, rejecting var-ints which are not in their shortest form,
+    ///   including length prefixes back-patched with `ByteWriter::write_var_u32_at`.
+    /// - `with_strict_bools`, rejecting bools other than `0` and `1`.
+    /// - `with_max_alloc`, limiting length prefixes to `HARDENED_MAX_ALLOC`.
+    /// - `with_max_depth`, limiting nesting to `HARDENED_MAX_DEPTH`.
+    ///
+    /// Strings are always validated as UTF-8. Settings added in the future are enabled here as well,
+    /// and each can still be relaxed afterwards, IE: `ByteReader::hardened(buf).with_max_alloc(1 << 24)`.
+    ///
+    /// ```rust
+    /// use binary_util::io::ByteReader;
+    ///
+    /// fn main() {
+    ///     let mut buf = ByteReader::hardened(&[1, 0x80, 0x00][..]);
+    ///     assert_eq!(buf.read_bool().unwrap(), true);
+    ///     assert!(buf.read_var_u32().is_err());
+    ///
+    ///     assert!(ByteReader::hardened(&[2][..]).read_bool().is_err());
+    /// }
+    /// ```
+    pub fn hardened(buf: impl Into<ByteReader>) -> Self {
+        buf.into()
+            .with_canonical_varints(true)
+            .with_strict_bools(true)
+            .with_max_alloc(HARDENED_MAX_ALLOC)
+            .with_max_depth(HARDENED_MAX_DEPTH)
+    }
+
+    /// Limits how deeply nested types may be read from this reader.
+    /// Once the limit is exceeded, `nested` will return an error instead of reading further.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Returns the maximum nesting depth of the reader, if any.
+    pub fn max_depth(&self) -> Option<usize> {
+        self.max_depth
+    }
+
+    /// Limits how long types may be read from this reader, starting from now.
+    /// Once `timeout` has elapsed, `nested` will return a `TimedOut` error instead of reading further.
+    pub fn with_deadline(mut self, timeout: Duration) -> Self {
+        self.deadline = Instant::now().checked_add(timeout);
+        self
+    }
+
+    /// Returns the instant after which the reader stops reading nested types, if any.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    /// Sets the protocol version being read.
+    ///
+    /// Types implemented with `BinaryIo` use this to read fields marked with `#[added_in]` or `#[removed_in]`
+    /// only when they are present in this version. If no version is set, the latest layout is read.
+    pub fn with_version(mut self, version: u32) -> Self {
+        self.version = Some(version);
+        self
+    }
+
+    /// Returns the protocol version being read, `None` if the latest.
+    pub fn version(&self) -> Option<u32> {
+        self.version
+    }
+
+    /// Sets whether `read_bool`, and therefore every `bool` read from this reader, fails with `InvalidData`
+    /// on bytes other than `0` and `1`, instead of reading them as `true`.
+    ///
+    /// Lenient bools can hide a corrupted or misaligned stream, as nearly any byte is a valid bool.
+    ///
+    /// ```rust
+    /// use binary_util::io::ByteReader;
+    ///
+    /// fn main() {
+    ///     assert_eq!(ByteReader::from(&[2][..]).read_bool().unwrap(), true);
+    ///
+    ///     let mut buf = ByteReader::from(&[1, 2][..]).with_strict_bools(true);
+    ///     assert_eq!(buf.read_bool().unwrap(), true);
+    ///     assert!(buf.read_bool().is_err());
+    /// }
+    /// ```
+    pub fn with_strict_bools(mut self, strict: bool) -> Self {
+        self.strict_bools = strict;
+        self
+    }
+
+    /// Returns whether `read_bool` only accepts `0` and `1`, see `with_strict_bools`.
+    pub fn strict_bools(&self) -> bool {
+        self.strict_bools
+    }
+
+    /// Sets whether errors returned by this reader carry a `BinaryError` payload, which is the default.
+    ///
+    /// Attaching the payload allocates for every error, which can dominate the cost of rejecting a packet
+    /// when a remote peer sends malformed data on purpose. When disabled, errors created by the reader are
+    /// built from their `ErrorKind` alone, which does not allocate, and the payload of errors returned by
+    /// nested reads is dropped. `BinaryErrorExt::is_eof` keeps working, however the offset, the amount
+    /// of bytes needed and whether the error was an overflow are no longer available.
+    ///
+    /// ```rust
+    /// use binary_util::io::{BinaryErrorExt, ByteReader};
+    ///
+    /// fn main() {
+    ///     let err = ByteReader::from(&[0][..]).with_detailed_errors(false).read_u32().unwrap_err();
+    ///     assert!(err.is_eof());
+    ///     assert!(err.binary_error().is_none());
+    ///
+    ///     // other readers are not affected.
+    ///     let err = ByteReader::from(&[0][..]).read_u32().unwrap_err();
+    ///     assert_eq!(err.needed_bytes(), Some(3));
+    /// }
+    /// ```
+    pub fn with_detailed_errors(mut self, detailed: bool) -> Self {
+        self.detailed_errors = detailed;
+        self
+    }
+
+    /// Returns whether errors returned by this reader carry a `BinaryError` payload, see `with_detailed_errors`.
+    pub fn detailed_errors(&self) -> bool {
+        self.detailed_errors
+    }
+
+    /// Converts `payload` into an error, which only carries the payload if the reader has detailed errors.
+    fn error(&self, payload: BinaryError) -> Error {
+        match self.detailed_errors {
+            true => payload.into(),
+            false => Error::from(payload.kind()),
+        }
+    }
+
+    /// Drops the `BinaryError` payload of `err` if the reader does not have detailed errors.
+    fn lean(&self, err: Error) -> Error {
+        match self.detailed_errors || err.binary_error().is_none() {
+            true => err,
+            false => Error::from(err.kind()),
+        }
+    }
+
+    /// Sets whether var-ints must be encoded in their shortest form, failing with `InvalidData` otherwise,
+    /// IE: `[0x80, 0x00]` for `0`, or a `varu32` with bits set beyond its 32 bits.
+    ///
+    /// Non canonical var-ints allow the same packet to be encoded in many ways, which can bypass signatures
+    /// or deduplication computed over the encoded bytes. The reader is left before the var-int if it is rejected.
+    ///
+    /// This rejects the padded length prefixes written by `ByteWriter::write_var_u32_at`, so peers reading with
+    /// canonical var-ints must be sent prefixes written with `ByteWriter::insert_var_u32_at` instead.
+    ///
+    /// ```rust
+    /// use binary_util::io::ByteReader;
+    ///
+    /// fn main() {
+    ///     assert_eq!(ByteReader::from(&[0x80, 0x00][..]).read_var_u32().unwrap(), 0);
+    ///
+    ///     let mut buf = ByteReader::from(&[0x80, 0x00][..]).with_canonical_varints(true);
+    ///     assert!(buf.read_var_u32().is_err());
+    ///     assert_eq!(buf.offset(), 0);
+    /// }
+    /// ```
+    pub fn with_canonical_varints(mut self, canonical: bool) -> Self {
+        self.canonical_varints = canonical;
+        self
+    }
+
+    /// Returns whether var-ints must be encoded in their shortest form, see `with_canonical_varints`.
+    pub fn canonical_varints(&self) -> bool {
+        self.canonical_varints
+    }
+
+    /// Limits the length prefix of collections read from this reader, IE: the amount of items in a `Vec`,
+    /// or the amount of bytes in a `String`, failing with `InvalidData` if it is exceeded.
+    ///
+    /// This limits the amount of items a forged length can make a reader allocate, not the amount of bytes:
+    /// a `Vec<T>` may still grow up to `max_alloc * size_of::<T>()` bytes while its items are read.
+    /// Collections of this crate only reserve as many items as there are bytes left in the buffer up front,
+    /// and hand written `Reader` implementations should check the lengths they read with `check_alloc`.
+    ///
+    /// ```rust
+    /// use binary_util::interfaces::Reader;
+    /// use binary_util::io::ByteReader;
+    ///
+    /// fn main() {
+    ///     let mut buf = ByteReader::from(&[3, 1, 2, 3][..]).with_max_alloc(2);
+    ///     assert!(Vec::<u8>::read(&mut buf).is_err());
+    /// }
+    /// ```
+    pub fn with_max_alloc(mut self, max_alloc: usize) -> Self {
+        self.max_alloc = Some(max_alloc);
+        self
+    }
+
+    /// Returns the largest length prefix a collection may be read with, if any.
+    pub fn max_alloc(&self) -> Option<usize> {
+        self.max_alloc
+    }
+
+    /// Returns `len` if it is within the maximum allocation of the reader, otherwise an `InvalidData` error,
+    /// see `with_max_alloc`.
+    pub fn check_alloc(&self, len: usize) -> Result<usize, std::io::Error> {
+        match self.max_alloc {
+            Some(max) if len > max => {
+                Err(self.error_at_offset(std::io::ErrorKind::InvalidData, ERR_MAX_ALLOC))
+            }
+            _ => Ok(len),
+        }
+    }
+
+    /// Reads a bool, which fails on bytes other than `0` and `1` if the reader has strict bools,
+    /// otherwise any byte other than `0` is `true`.
+    #[inline]
+    pub fn read_bool(&mut self) -> Result<bool, std::io::Error> {
+        match self.strict_bools {
+            true => self.read_bool_strict(),
+            false => Ok(self.read_u8()? != 0),
+        }
+    }
+
+    /// Attaches `value` to the reader, replacing any value of the same type attached before.
+    ///
+    /// Types implemented with `BinaryIo` populate fields marked with `#[from_ctx]` with the value of their type.
+    pub fn with_context<T: Any + Send + Sync>(mut self, value: T) -> Self {
+        self.context.retain(|attached| !attached.is::<T>());
+        self.context.push(Arc::new(value));
+        self
+    }
+
+    /// Returns the value of type `T` attached with `with_context`, if any.
+    pub fn context<T: Any>(&self) -> Option<&T> {
+        self.context
+            .iter()
+            .find_map(|attached| attached.downcast_ref::<T>())
+    }
+
+    /// Returns an error if the deadline of the reader has passed.
+    ///
+    /// `nested` already calls this, however long running loops in hand written `Reader`
+    /// implementations that do not read nested types may call it directly.
+    pub fn check_deadline(&self) -> Result<(), std::io::Error> {
+        match self.deadline {
+            Some(deadline) if Instant::now() >= deadline => {
+                Err(self.error_at_offset(std::io::ErrorKind::TimedOut, ERR_DEADLINE))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the amount of bytes consumed from the reader since it was created.
+    ///
+    /// ```rust
+    /// use binary_util::io::ByteReader;
+    ///
+    /// fn main() {
+    ///     let mut buf = ByteReader::from(&[0, 0, 0, 1, 0][..]);
+    ///     buf.read_u32().unwrap();
+    ///     assert_eq!(buf.offset(), 4);
+    ///
+    ///     let err = buf.read_u16().unwrap_err();
+    ///     assert!(err.to_string().starts_with("UnexpectedEof at offset 4 needing 1 more bytes"));
+    /// }
+    /// ```
+    pub fn offset(&self) -> usize {
+        self.origin - self.buf.remaining()
+    }
+
+    /// Saves the current position of the reader, which can be restored with `restore`.
+    ///
+    /// This allows a caller to attempt a read, and roll back if it fails.
+    /// ```rust
+    /// use binary_util::io::ByteReader;
+    ///
+    /// fn main() {
+    ///     let mut buf = ByteReader::from(&[0, 1, 2][..]);
+    ///     let checkpoint = buf.checkpoint();
+    ///     assert!(buf.read_u32().is_err());
+    ///     assert_eq!(buf.read_u16().unwrap(), 1);
+    ///
+    ///     buf.restore(checkpoint);
+    ///     assert_eq!(buf.read_u8().unwrap(), 0);
+    /// }
+    /// ```
+    pub fn checkpoint(&self) -> ReadCheckpoint {
+        ReadCheckpoint {
+            buf: self.buf.clone(),
+        }
+    }
+
+    /// Restores the reader to a position saved with `checkpoint`.
+    ///
+    /// The reader can not move before the bytes split off with `split_at_current`, so a checkpoint taken before
+    /// the split restores the reader to where the split was made instead, or panics in instrumented builds.
+    pub fn restore(&mut self, checkpoint: ReadCheckpoint) {
+        self.check_checkpoint(&checkpoint);
+        misuse!(
+            checkpoint.buf.len() > self.full.len(),
+            "checkpoint was taken before the reader was split"
+        );
+        self.buf = match checkpoint.buf.len() > self.full.len() {
+            true => self.full.clone(),
+            false => checkpoint.buf,
+        };
+    }
+
+    /// Returns the bytes read since `checkpoint` was taken.
+    /// If the reader was restored to an earlier position, this returns an empty buffer.
+    ///
+    /// ```rust
+    /// use binary_util::io::ByteReader;
+    ///
+    /// fn main() {
+    ///     let mut reader = ByteReader::from(&[0, 1, 44, 7][..]);
+    ///     reader.read_u8().unwrap();
+    ///
+    ///     let checkpoint = reader.checkpoint();
+    ///     reader.read_u16().unwrap();
+    ///     assert_eq!(&reader.read_since(&checkpoint)[..], &[1, 44]);
+    /// }
+    /// ```
+    pub fn read_since(&self, checkpoint: &ReadCheckpoint) -> Bytes {
+        self.check_checkpoint(checkpoint);
+        let read = checkpoint.buf.len().saturating_sub(self.buf.len());
+        checkpoint.buf.slice(..read)
+    }
+
+    /// Splits off the bytes read so far, IE: the header of a packet, and returns them.
+    ///
+    /// The reader keeps its position, offsets and settings, however `seek_to` and `rewind` can no longer
+    /// move before the current position. This lets a dispatcher keep the raw header for logging or forwarding,
+    /// while handing the same reader to the handler of the payload. No bytes are copied.
+    ///
+    /// ```rust
+    /// use binary_util::io::ByteReader;
+    ///
+    /// fn main() {
+    ///     let mut buf = ByteReader::from(&[0x01, 0x00, 0x2a, 0xff][..]);
+    ///     let id = buf.read_u8().unwrap();
+    ///     let header = buf.split_at_current();
+    ///     assert_eq!((id, &header[..]), (1, &[0x01][..]));
+    ///
+    ///     assert_eq!(buf.read_u16().unwrap(), 42);
+    ///     assert_eq!(buf.offset(), 3);
+    ///     buf.rewind();
+    ///     assert_eq!(buf.offset(), 1);
+    /// }
+    /// ```
+    pub fn split_at_current(&mut self) -> Bytes {
+        let read = self.full.len() - self.buf.len();
+        let consumed = self.full.slice(..read);
+        self.full = self.buf.clone();
+        consumed
+    }
+
+    /// Consumes the reader, returning the bytes before the current position and the bytes that remain, without copying.
+    /// For a reader returned by `window`, only the bytes within the window are returned.
+    ///
+    /// ```rust
+    /// use binary_util::io::ByteReader;
+    ///
+    /// fn main() {
+    ///     let mut buf = ByteReader::from(&[0x01, 0x00, 0x2a][..]);
+    ///     buf.read_u8().unwrap();
+    ///
+    ///     let (header, payload) = buf.into_parts();
+    ///     assert_eq!(&header[..], &[0x01]);
+    ///     assert_eq!(&payload[..], &[0x00, 0x2a]);
+    /// }
+    /// ```
+    pub fn into_parts(self) -> (Bytes, Bytes) {
+        let read = self.full.len() - self.buf.len();
+        (self.full.slice(..read), self.buf)
+    }
+
+    /// Moves the reader to the absolute offset `pos`, as returned by `offset`, which may be before or after
+    /// the current position. This allows offset tables, IE: the chunk locations of a region file, to be followed
+    /// instead of only reading the buffer linearly.
+    ///
+    /// Returns `InvalidInput` if `pos` is outside of the buffer, and leaves the position unchanged.
+    /// Within a window, `pos` may only be within the window.
+    ///
+    /// ```rust
+    /// use binary_util::io::ByteReader;
+    ///
+    /// fn main() {
+    ///     // a table of two offsets, followed by the entries they point to.
+    ///     let mut buf = ByteReader::from(&[4, 3, 0, 0xaa, 0xbb][..]);
+    ///     let table = [buf.read_u8().unwrap(), buf.read_u8().unwrap()];
+    ///
+    ///     buf.seek_to(table[0] as usize).unwrap();
+    ///     assert_eq!(buf.read_u8().unwrap(), 0xbb);
+    ///     buf.seek_to(table[1] as usize).unwrap();
+    ///     assert_eq!(buf.read_u8().unwrap(), 0xaa);
+    ///     assert!(buf.seek_to(6).is_err());
+    ///
+    ///     buf.rewind();
+    ///     assert_eq!(buf.offset(), 0);
+    /// }
+    /// ```
+    pub fn seek_to(&mut self, pos: usize) -> Result<(), std::io::Error> {
+        let start = self.origin - self.full.len();
+        if pos < start || pos > self.origin {
+            return Err(self.error(
+                BinaryError::new(std::io::ErrorKind::InvalidInput, ERR_SEEK_OUT_OF_BOUNDS)
+                    .at_offset(pos),
+            ));
+        }
+        self.buf = self.full.slice(pos - start..);
+        Ok(())
+    }
+
+    /// Moves the reader back to the start of its buffer, see `seek_to`.
+    pub fn rewind(&mut self) {
+        self.buf = self.full.clone();
+    }
+
+    /// Returns a reader over `len` bytes, starting `start` bytes after the current position, without consuming any bytes.
+    ///
+    /// The window shares the buffer of this reader, so no bytes are copied, and carries over its settings,
+    /// such as its context, version and limits. Offsets within the window, IE: in errors, continue from this reader.
+    /// This allows a span to be verified, IE: checksummed, before it is parsed by this reader.
+    ///
+    /// ```rust
+    /// use binary_util::io::ByteReader;
+    ///
+    /// fn main() {
+    ///     let mut buf = ByteReader::from(&[2, 0, 7, 0, 9][..]);
+    ///     // the checksum of the body is verified first.
+    ///     let body = buf.window(1, 4).unwrap();
+    ///     assert_eq!(body.as_slice().iter().map(|b| *b as u32).sum::<u32>(), 16);
+    ///     assert_eq!(body.offset(), 1);
+    ///
+    ///     // and the parent is then read as usual.
+    ///     assert_eq!(buf.read_u8().unwrap(), 2);
+    ///     assert_eq!(buf.read_u16().unwrap(), 7);
+    ///     assert!(buf.window(0, 3).is_err());
+    /// }
+    /// ```
+    pub fn window(&self, start: usize, len: usize) -> Result<ByteReader, std::io::Error> {
+        self.section(self.offset().saturating_add(start), len)
+    }
+
+    /// Returns a reader over `len` bytes at the absolute offset `offset`, as returned by `offset`, without moving this reader.
+    ///
+    /// This is `window` for formats with a header of absolute offsets, IE: region or asset files,
+    /// where a section may be before the current position. The section must be within the buffer of this reader,
+    /// see `seek_to`.
+    ///
+    /// ```rust
+    /// use binary_util::io::ByteReader;
+    ///
+    /// fn main() {
+    ///     // the offset and length of a section, followed by the section.
+    ///     let mut buf = ByteReader::from(&[2, 3, 1, 2, 3][..]);
+    ///     let (offset, len) = (buf.read_u8().unwrap(), buf.read_u8().unwrap());
+    ///
+    ///     let mut section = buf.section(offset as usize, len as usize).unwrap();
+    ///     assert_eq!(section.read_u8().unwrap(), 1);
+    ///     assert_eq!(section.offset(), 3);
+    ///     // the section can be read again, it was not consumed.
+    ///     assert_eq!(buf.section(0, 2).unwrap().read_u16().unwrap(), 0x0203);
+    ///     assert!(buf.section(4, 2).is_err());
+    /// }
+    /// ```
+    pub fn section(&self, offset: usize, len: usize) -> Result<ByteReader, std::io::Error> {
+        let start = self.origin - self.full.len();
+        if offset < start {
+            return Err(self.error(
+                BinaryError::new(std::io::ErrorKind::InvalidInput, ERR_SEEK_OUT_OF_BOUNDS)
+                    .at_offset(offset),
+            ));
+        }
+        let end = offset.saturating_add(len);
+        if end > self.origin {
+            return Err(self.error(
+                BinaryError::new(std::io::ErrorKind::UnexpectedEof, ERR_EOB)
+                    .at_offset(offset.min(self.origin))
+                    .needing(end - self.origin),
+            ));
+        }
+        let mut section = self.clone();
+        section.buf = self.full.slice(offset - start..end - start);
+        section.full = section.buf.clone();
+        section.origin = end;
+        #[cfg(feature = "stats")]
+        {
+            section.stats = Stats::default();
+        }
+        Ok(section)
+    }
+
+    /// Panics in instrumented builds if `checkpoint` does not point into the buffer of this reader,
+    /// IE: it was taken from a different reader.
+    #[inline]
+    fn check_checkpoint(&self, _checkpoint: &ReadCheckpoint) {
+        #[cfg(all(feature = "instrumented", debug_assertions))]
+        if !self.buf.is_empty() && !_checkpoint.buf.is_empty() {
+            let start = (self.buf.as_ptr() as usize).wrapping_sub(self.offset());
+            let at = (_checkpoint.buf.as_ptr() as usize).wrapping_sub(start);
+            misuse!(
+                at > self.origin || self.origin - at != _checkpoint.buf.len(),
+                "checkpoint was taken from a different reader"
+            );
+        }
+    }
+
+    /// Creates an error of the given kind, annotated with the current offset of the reader.
+    ///
+    /// This is useful when implementing `Reader` by hand, so validation errors carry
+    /// the same positional information as the errors returned by the reader itself.
+    pub fn error_at_offset(
+        &self,
+        kind: std::io::ErrorKind,
+        msg: impl Into<Cow<'static, str>>,
+    ) -> Error {
+        self.error(BinaryError::new(kind, msg).at_offset(self.offset()))
+    }
+
+    /// Calls `f` one nesting level deeper, failing if this exceeds the maximum depth of the reader,
+    /// or if the deadline of the reader has passed.
+    ///
+    /// This is used by `BinaryIo` when reading a type, however if you are implementing `Reader`
+    /// for a recursive type by hand, you should wrap your implementation with this method.
+    pub fn nested<T>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<T, std::io::Error>,
+    ) -> Result<T, std::io::Error> {
+        if let Some(max_depth) = self.max_depth {
+            if self.depth >= max_depth {
+                return Err(self.error_at_offset(std::io::ErrorKind::InvalidData, ERR_MAX_DEPTH));
+            }
+        }
+        self.check_deadline()?;
+        self.depth += 1;
+        let result = f(self);
+        self.depth -= 1;
+        result.map_err(|e| self.lean(e))
+    }
+
+    /// Calls `f` with trailing bytes allowed, so types derived with `#[binary_io(deny_trailing)]`
+    /// do not fail if bytes remain after them. This is what fields marked with `#[allow_trailing]` are read with.
+    ///
+    /// ```rust
+    /// use binary_util::interfaces::Reader;
+    /// use binary_util::io::ByteReader;
+    /// use binary_util::BinaryIo;
+    ///
+    /// #[derive(BinaryIo, Debug)]
+    /// #[binary_io(deny_trailing)]
+    /// struct Ping(u8);
+    ///
+    /// fn main() {
+    ///     let mut buf = ByteReader::from(&[1, 2][..]);
+    ///     assert!(Ping::read(&mut buf.clone()).is_err());
+    ///
+    ///     let ping = buf.allow_trailing(Ping::read).unwrap();
+    ///     assert_eq!(ping.0, 1);
+    ///     assert_eq!(buf.as_slice(), &[2]);
+    /// }
+    /// ```
+    pub fn allow_trailing<T>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<T, std::io::Error>,
+    ) -> Result<T, std::io::Error> {
+        let allowed = std::mem::replace(&mut self.trailing_allowed, true);
+        let result = f(self);
+        self.trailing_allowed = allowed;
+        result
+    }
+
+    /// Returns whether trailing bytes are allowed, IE: within `allow_trailing`.
+    pub fn trailing_allowed(&self) -> bool {
+        self.trailing_allowed
+    }
+
+    /// `ByteReader` also provides a utility `peek_ahead` function that allows you to
+    /// "peek ahead" at the next byte in the stream without advancing the stream.
+    ///
+    /// Do not confuse this with any sort of "peek" function. This function does not
+    /// increment the read position of the stream, but rather copies the byte at the
+    /// specified position.
+    /// ```rust
+    /// use binary_util::io::ByteReader;
+    ///
+    /// fn main() {
+    ///    let mut buf = ByteReader::from(&[253, 255, 14, 255, 255, 15][..]);
+    ///    if buf.peek_ahead(3).unwrap() != 255 {
+    ///        // buffer is corrupted, varints can never have a leading byte less than 255 if
+    ///        // Their are bytes remaining!
+    ///    } else {
+    ///        // read the varint
+    ///        let num = buf.read_var_i32().unwrap();
+    ///    }
+    /// }
+    /// ```
+    pub fn peek_ahead(&mut self, pos: usize) -> Result<u8, std::io::Error> {
+        if let Some(byte) = self.buf.chunk().get(pos) {
+            return Ok(*byte);
+        } else {
+            return Err(self.error_at_offset(std::io::ErrorKind::UnexpectedEof, ERR_EOB));
+        }
+    }
+
+    delegate_read_fn! {
+        read_u8, u8;
+        read_i8, i8;
+        read_u16, u16;
+        read_u16_le, u16;
+        read_i16, i16;
+        read_i16_le, i16;
+        read_u24, u32;
+        read_u24_le, u32;
+        read_i24, i32;
+        read_i24_le, i32;
+        read_u32, u32;
+        read_u32_le, u32;
+        read_i32, i32;
+        read_i32_le, i32;
+        read_f32, f32;
+        read_f32_le, f32;
+        read_u64, u64;
+        read_u64_le, u64;
+        read_i64, i64;
+        read_i64_le, i64;
+        read_f64, f64;
+        read_f64_le, f64;
+        read_u128, u128;
+        read_u128_le, u128;
+        read_i128, i128;
+        read_i128_le, i128;
+        #[deprecated(
+            note = "No common protocol encodes chars as a u32, use `read_char_utf8` or `read_char_utf16` instead"
+        )]
+        read_char, char;
+        read_char_utf8, char;
+        read_char_utf16, char;
+        read_char_utf16_le, char;
+        read_bool_strict, bool;
+    }
+
+    delegate_var_fn! {
+        read_var_u32, u32, u64::from, false;
+        read_var_i32, i32, |v| zigzag_encode(v as i64), false;
+        read_var_u64, u64, u64::from, false;
+        read_var_i64, i64, zigzag_encode, false;
+        read_var_u32_be, u32, u64::from, true;
+        read_var_i32_be, i32, |v| zigzag_encode(v as i64), true;
+        read_var_u64_be, u64, u64::from, true;
+        read_var_i64_be, i64, zigzag_encode, true;
+    }
+
+    /// Restores the reader and returns an error if the var-int just read, which started with `remaining` bytes left,
+    /// is not the shortest encoding of `num`, see `with_canonical_varints`.
+    fn check_canonical(
+        &mut self,
+        remaining: usize,
+        num: u64,
+        big_endian: bool,
+    ) -> Result<(), Error> {
+        let start = self.full.len() - remaining;
+        let end = self.full.len() - self.buf.remaining();
+        let mut out = [0; MAX_VAR_U64_LEN];
+        let canonical = match big_endian {
+            true => encode_be(num, &mut out),
+            false => encode(num, &mut out),
+        };
+        if canonical != &self.full[start..end] {
+            self.buf = self.full.slice(start..);
+            return Err(
+                self.error_at_offset(std::io::ErrorKind::InvalidData, ERR_NON_CANONICAL_VARINT)
+            );
+        }
+        Ok(())
+    }
+
+    read_slice_fn! {
+        read_u16_slice, u16, from_be_bytes, "big endian";
+        read_u16_le_slice, u16, from_le_bytes, "little endian";
+        read_i16_slice, i16, from_be_bytes, "big endian";
+        read_i16_le_slice, i16, from_le_bytes, "little endian";
+        read_u32_slice, u32, from_be_bytes, "big endian";
+        read_u32_le_slice, u32, from_le_bytes, "little endian";
+        read_i32_slice, i32, from_be_bytes, "big endian";
+        read_i32_le_slice, i32, from_le_bytes, "little endian";
+        read_f32_slice, f32, from_be_bytes, "big endian";
+        read_f32_le_slice, f32, from_le_bytes, "little endian";
+        read_u64_slice, u64, from_be_bytes, "big endian";
+        read_u64_le_slice, u64, from_le_bytes, "little endian";
+        read_i64_slice, i64, from_be_bytes, "big endian";
+        read_i64_le_slice, i64, from_le_bytes, "little endian";
+        read_f64_slice, f64, from_be_bytes, "big endian";
+        read_f64_le_slice, f64, from_le_bytes, "little endian";
+    }
+
+    /// Reads an unsigned integer from the stream with a varying size
+    /// indicated by the `size` parameter.
+    pub fn read_uint(&mut self, size: usize) -> Result<u64, std::io::Error> {
+        let offset = self.offset();
+        let value = crate::io::raw::RawReadExt::read_uint(&mut self.buf, size)
+            .map_err(|e| self.error(e.at_offset(offset)))?;
+        track!(self, read, size, false);
+        Ok(value)
+    }
+
+    /// Reads an unsigned integer from the stream with a varying size in little endian
+    /// indicated by the `size` parameter.
+    pub fn read_uint_le(&mut self, size: usize) -> Result<u64, std::io::Error> {
+        let offset = self.offset();
+        let value = crate::io::raw::RawReadExt::read_uint_le(&mut self.buf, size)
+            .map_err(|e| self.error(e.at_offset(offset)))?;
+        track!(self, read, size, false);
+        Ok(value)
+    }
+
+    pub fn read_int(&mut self, size: usize) -> Result<i64, std::io::Error> {
+        let offset = self.offset();
+        let value = crate::io::raw::RawReadExt::read_int(&mut self.buf, size)
+            .map_err(|e| self.error(e.at_offset(offset)))?;
+        track!(self, read, size, false);
+        Ok(value)
+    }
+
+    pub fn read_int_le(&mut self, size: usize) -> Result<i64, std::io::Error> {
+        let offset = self.offset();
+        let value = crate::io::raw::RawReadExt::read_int_le(&mut self.buf, size)
+            .map_err(|e| self.error(e.at_offset(offset)))?;
+        track!(self, read, size, false);
+        Ok(value)
+    }
+
+    /// Reads a string from the stream, prefixed with its length as a `var_u32`.
+    /// This is the same encoding `ByteWriter::write_string` and the `String` reader use.
+    /// This is a reversable operation, meaning if it fails,
+    /// the stream will be in the same state as before.
+    pub fn read_string(&mut self) -> Result<String, std::io::Error> {
+        self.read_string_varu32()
+    }
+
+    /// Reads a string prefixed with its length as a `var_u32`, which is the canonical encoding of `read_string`.
+    pub fn read_string_varu32(&mut self) -> Result<String, std::io::Error> {
+        self.read_prefixed_string(|buf| buf.read_var_u32().map(|len| len as usize))
+    }
+
+    /// Reads a string like `read_string` into `out`, replacing its contents while reusing its capacity.
+    /// This is a reversable operation, meaning if it fails,
+    /// the stream will be in the same state as before and `out` is left unchanged.
+    pub fn read_string_into(&mut self, out: &mut String) -> Result<(), std::io::Error> {
+        let checkpoint = self.checkpoint();
+        let result = self.read_var_u32().and_then(|len| {
+            let len = self.check_alloc(len as usize)?;
+            let offset = self.offset();
+            self.read_bytes(len).and_then(|bytes| {
+                std::str::from_utf8(&bytes)
+                    .map(|s| {
+                        out.clear();
+                        out.push_str(s);
+                    })
+                    .map_err(|e| {
+                        self.error(
+                            BinaryError::new(std::io::ErrorKind::InvalidData, ERR_INVALID_UTF8)
+                                .at_offset(offset)
+                                .caused_by(e),
+                        )
+                    })
+            })
+        });
+        if result.is_err() {
+            self.restore(checkpoint);
+        }
+        result
+    }
+
+    /// Reads a string prefixed with its length as a big endian `u16`.
+    /// This is a reversable operation, meaning if it fails,
+    /// the stream will be in the same state as before.
+    pub fn read_string_u16be(&mut self) -> Result<String, std::io::Error> {
+        self.read_prefixed_string(|buf| buf.read_u16().map(|len| len as usize))
+    }
+
+    /// Reads a string prefixed with its length as a little endian `u32`.
+    /// This is a reversable operation, meaning if it fails,
+    /// the stream will be in the same state as before.
+    pub fn read_string_u32le(&mut self) -> Result<String, std::io::Error> {
+        self.read_prefixed_string(|buf| buf.read_u32_le().map(|len| len as usize))
+    }
+
+    /// Reads a string whose length is read with `read_len`, restoring the reader if either fails.
+    fn read_prefixed_string(
+        &mut self,
+        read_len: impl FnOnce(&mut Self) -> Result<usize, std::io::Error>,
+    ) -> Result<String, std::io::Error> {
+        let checkpoint = self.checkpoint();
+        let result = read_len(self)
+            .and_then(|len| self.check_alloc(len))
+            .and_then(|len| self.read_string_bytes(len));
+        if result.is_err() {
+            self.restore(checkpoint);
+        }
+        result
+    }
+
+    fn read_string_bytes(&mut self, len: usize) -> Result<String, std::io::Error> {
+        if !can_read!(self.buf, len) {
+            return Err(self.error(
+                BinaryError::new(std::io::ErrorKind::UnexpectedEof, ERR_EOB)
+                    .at_offset(self.offset())
+                    .needing(len - self.buf.remaining()),
+            ));
+        }
+        let offset = self.offset();
+        let mut bytes = Vec::with_capacity(len);
+        let read = self
+            .read_into_uninit(&mut bytes.spare_capacity_mut()[..len])?
+            .len();
+        // SAFETY: `read_into_uninit` initialized the first `read` bytes of the spare capacity.
+        unsafe { bytes.set_len(read) };
+        String::from_utf8(bytes).map_err(|e| {
+            self.error(
+                BinaryError::new(std::io::ErrorKind::InvalidData, ERR_INVALID_UTF8)
+                    .at_offset(offset)
+                    .caused_by(e.utf8_error()),
+            )
+        })
+    }
+
+    /// Reads an `Option` of `T` from the stream.
+    /// `T` must implement the `Reader` trait and be sized.
+    ///
+    /// This operation is not recoverable and will corrupt the stream if it fails.
+    /// If this behavior is desired, you should use `peek_ahead` when implementing
+    /// the `Reader` trait.
+    ///
+    /// # Example
+    /// ```rust
+    /// use binary_util::io::ByteReader;
+    /// use binary_util::interfaces::Reader;
+    ///
+    /// pub struct HelloWorld {
+    ///     pub magic: u32
+    /// }
+    ///
+    /// impl Reader<HelloWorld> for HelloWorld {
+    ///     fn read(reader: &mut ByteReader) -> Result<HelloWorld, std::io::Error> {
+    ///         Ok(HelloWorld {
+    ///             magic: reader.read_u32()?
+    ///         })
+    ///     }
+    /// }
+    ///
+    /// fn main() {
+    ///     // Nothing is here!
+    ///     let mut reader = ByteReader::from(&[0x00][..]);
+    ///     let hello_world = reader.read_option::<HelloWorld>().unwrap();
+    ///     assert_eq!(hello_world.is_some(), false);
+    /// }
+    /// ```
+    pub fn read_option<T: Reader<T>>(&mut self) -> Result<Option<T>, std::io::Error> {
+        if self.read_bool()? {
+            return Ok(Some(T::read(self)?));
+        } else {
+            return Ok(None);
+        }
+    }
+
+    /// Reads an `Option` from the stream, using `f` to read the value if it is present.
+    /// This is useful for types that do not implement the `Reader` trait, such as foreign types.
+    ///
+    /// # Example
+    /// ```rust
+    /// use binary_util::io::ByteReader;
+    /// use std::time::Duration;
+    ///
+    /// fn main() {
+    ///     let mut reader = ByteReader::from(&[0x01, 0x00, 0x00, 0x03, 0xe8][..]);
+    ///     let timeout = reader
+    ///         .read_option_with(|r| Ok(Duration::from_millis(r.read_u32()? as u64)))
+    ///         .unwrap();
+    ///     assert_eq!(timeout, Some(Duration::from_secs(1)));
+    /// }
+    /// ```
+    pub fn read_option_with<T>(
+        &mut self,
+        f: impl FnOnce(&mut ByteReader) -> Result<T, std::io::Error>,
+    ) -> Result<Option<T>, std::io::Error> {
+        if self.read_bool()? {
+            Ok(Some(f(self)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Reads `T` from the stream, and converts it with `f`, IE: a raw id into an enum with `TryFrom`.
+    ///
+    /// If `f` fails, an `InvalidData` error at the offset `T` was read from is returned, caused by the error of `f`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use binary_util::io::ByteReader;
+    /// use std::num::NonZeroU16;
+    ///
+    /// fn main() {
+    ///     let mut reader = ByteReader::from(&[0x00, 0x05, 0x00, 0x00][..]);
+    ///     assert_eq!(reader.read_map::<u16, _, _>(NonZeroU16::try_from).unwrap().get(), 5);
+    ///     let err = reader.read_map::<u16, _, _>(NonZeroU16::try_from).unwrap_err();
+    ///     assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    /// }
+    /// ```
+    pub fn read_map<T, U, E>(
+        &mut self,
+        f: impl FnOnce(T) -> Result<U, E>,
+    ) -> Result<U, std::io::Error>
+    where
+        T: Reader<T>,
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        let offset = self.offset();
+        f(self.read_type::<T>()?).map_err(|e| {
+            self.error(
+                BinaryError::new(std::io::ErrorKind::InvalidData, ERR_INVALID_VALUE)
+                    .at_offset(offset)
+                    .caused_by(e),
+            )
+        })
+    }
+
+    /// Reads `T` from the stream, returning an `InvalidData` error at the offset it was read from
+    /// if `pred` does not hold for it.
+    ///
+    /// # Example
+    /// ```rust
+    /// use binary_util::io::ByteReader;
+    ///
+    /// fn main() {
+    ///     let mut reader = ByteReader::from(&[20, 200][..]);
+    ///     assert_eq!(reader.read_validate::<u8>(|players| *players <= 100).unwrap(), 20);
+    ///     assert!(reader.read_validate::<u8>(|players| *players <= 100).is_err());
+    /// }
+    /// ```
+    pub fn read_validate<T: Reader<T>>(
+        &mut self,
+        pred: impl FnOnce(&T) -> bool,
+    ) -> Result<T, std::io::Error> {
+        let offset = self.offset();
+        let value = self.read_type::<T>()?;
+        match pred(&value) {
+            true => Ok(value),
+            false => Err(self.error(
+                BinaryError::new(std::io::ErrorKind::InvalidData, ERR_INVALID_VALUE)
+                    .at_offset(offset),
+            )),
+        }
+    }
+
+    /// Reads `T` from the stream, or returns `T::default()` if no bytes remain,
+    /// IE: for a trailing field which older versions of a protocol do not send.
+    ///
+    /// Errors are still returned when `T` is only partially present.
+    ///
+    /// # Example
+    /// ```rust
+    /// use binary_util::io::ByteReader;
+    ///
+    /// fn main() {
+    ///     let mut reader = ByteReader::from(&[0x00, 0x2f][..]);
+    ///     assert_eq!(reader.read_or_default::<u16>().unwrap(), 47);
+    ///     assert_eq!(reader.read_or_default::<String>().unwrap(), "");
+    /// }
+    /// ```
+    pub fn read_or_default<T: Reader<T> + Default>(&mut self) -> Result<T, std::io::Error> {
+        match self.buf.has_remaining() {
+            true => self.read_type::<T>(),
+            false => Ok(T::default()),
+        }
+    }
+
+    /// Reads a varu32 sized slice from the stream.
+    /// For reading a slice of raw bytes, use `read` instead.
+    pub fn read_sized_slice(&mut self) -> Result<Bytes, std::io::Error> {
+        let len = self.read_var_u32()? as usize;
+        self.read_bytes(self.check_alloc(len)?)
+    }
+
+    /// Reads exactly `len` bytes from the stream, without a length prefix.
+    ///
+    /// The returned `Bytes` shares the buffer of the reader rather than copying it,
+    /// so this is cheap even for large records, such as those in a memory-mapped file.
+    pub fn read_bytes(&mut self, len: usize) -> Result<Bytes, std::io::Error> {
+        if can_read!(self.buf, len) {
+            track!(self, read, len, false);
+            return Ok(self.buf.split_to(len));
+        } else {
+            return Err(self.error(
+                BinaryError::new(std::io::ErrorKind::UnexpectedEof, ERR_EOB)
+                    .at_offset(self.offset())
+                    .needing(len - self.buf.remaining()),
+            ));
+        }
+    }
+
+    /// Reads a slice from the stream into the slice passed by the caller.
+    /// For reading a prefixed sized slice, use `read_sized_slice` instead.
+    pub fn read(&mut self, buffer: &mut [u8]) -> Result<(), std::io::Error> {
+        // SAFETY: `read_into_uninit` only ever writes initialized bytes to the buffer.
+        let buffer = unsafe { &mut *(buffer as *mut [u8] as *mut [MaybeUninit<u8>]) };
+        self.read_into_uninit(buffer)?;
+        Ok(())
+    }
+
+    /// Reads exactly `buffer.len()` bytes from the stream into uninitialized memory,
+    /// returning the now initialized bytes.
+    ///
+    /// This avoids having to zero a buffer before reading into it.
+    ///
+    /// # Example
+    /// ```rust
+    /// use binary_util::io::ByteReader;
+    /// use std::mem::MaybeUninit;
+    ///
+    /// fn main() {
+    ///     let mut reader = ByteReader::from(&[1, 2, 3, 4][..]);
+    ///     let mut buf = [MaybeUninit::<u8>::uninit(); 3];
+    ///     assert_eq!(reader.read_into_uninit(&mut buf).unwrap(), &[1, 2, 3]);
+    ///     assert_eq!(reader.as_slice(), &[4]);
+    /// }
+    /// ```
+    pub fn read_into_uninit<'a>(
+        &mut self,
+        buffer: &'a mut [MaybeUninit<u8>],
+    ) -> Result<&'a [u8], std::io::Error> {
+        let len = buffer.len();
+        if !can_read!(self.buf, len) {
+            return Err(self.error(
+                BinaryError::new(std::io::ErrorKind::UnexpectedEof, ERR_EOB)
+                    .at_offset(self.offset())
+                    .needing(len - self.buf.remaining()),
+            ));
+        }
+        // SAFETY: `MaybeUninit<u8>` has the same layout as `u8`, the source holds at least `len` bytes,
+        // and cannot overlap with the exclusively borrowed destination. Every byte of the destination
+        // is initialized by the copy before it is read as `u8`.
+        let read = unsafe {
+            std::ptr::copy_nonoverlapping(self.buf.as_ptr(), buffer.as_mut_ptr().cast::<u8>(), len);
+            std::slice::from_raw_parts(buffer.as_ptr().cast::<u8>(), len)
+        };
+        self.buf.advance(len);
+        track!(self, read, len, false);
+        Ok(read)
+    }
+
+    /// Reads `T` from the stream.
+    /// `T` must implement the `Reader` trait and be sized.
+    ///
+    /// # Deprecrated
+    ///
+    /// This function is deprecated and will be removed in `v0.3.4`.
+    #[deprecated(note = "Use `read_type` instead")]
+    pub fn read_struct<T: Reader<T>>(&mut self) -> Result<T, std::io::Error> {
+        return self.read_type::<T>();
+    }
+
+    /// Reads `T` from the stream.
+    /// `T` must implement the `Reader` trait and be sized.
+    pub fn read_type<T: Reader<T>>(&mut self) -> Result<T, std::io::Error> {
+        #[cfg(feature = "stats")]
+        let offset = self.offset();
+        let value = T::read(self)?;
+        track!(self, frame, self.offset() - offset);
+        return Ok(value);
+    }
+
+    /// Reads `T` from the stream, failing if any bytes remain afterwards.
+    ///
+    /// This is useful for strict servers, which must reject packets with trailing garbage.
+    /// ```rust
+    /// use binary_util::io::ByteReader;
+    ///
+    /// fn main() {
+    ///     let mut buf = ByteReader::from(&[0, 1][..]);
+    ///     assert_eq!(buf.read_exact_type::<u16>().unwrap(), 1);
+    ///
+    ///     let mut buf = ByteReader::from(&[0, 1, 2][..]);
+    ///     assert!(buf.read_exact_type::<u16>().is_err());
+    /// }
+    /// ```
+    pub fn read_exact_type<T: Reader<T>>(&mut self) -> Result<T, std::io::Error> {
+        let value = self.read_type::<T>()?;
+        self.expect_eof()?;
+        Ok(value)
+    }
+
+    /// Reads `T` from the stream until no bytes remain, IE: a trailing array without a length prefix,
+    /// such as the records of a flat file.
+    ///
+    /// At most `max` items are read, so the memory used is bounded even when the stream is not.
+    /// An `InvalidData` error is returned if more remain, or if an item is read without consuming any bytes,
+    /// and the error of the last item is returned if it is truncated.
+    /// ```rust
+    /// use binary_util::io::ByteReader;
+    ///
+    /// fn main() {
+    ///     let mut buf = ByteReader::from(&[0, 1, 0, 2, 0, 3][..]);
+    ///     assert_eq!(buf.read_to_end_vec::<u16>(16).unwrap(), vec![1, 2, 3]);
+    ///     assert!(buf.as_slice().is_empty());
+    ///
+    ///     let mut buf = ByteReader::from(&[0, 1, 0, 2, 0, 3][..]);
+    ///     assert!(buf.read_to_end_vec::<u16>(2).is_err());
+    /// }
+    /// ```
+    pub fn read_to_end_vec<T: Reader<T>>(&mut self, max: usize) -> Result<Vec<T>, std::io::Error> {
+        let mut items = Vec::new();
+        while self.buf.has_remaining() {
+            if items.len() == max {
+                return Err(
+                    self.error_at_offset(std::io::ErrorKind::InvalidData, ERR_TOO_MANY_ITEMS)
+                );
+            }
+            let remaining = self.buf.remaining();
+            items.push(self.read_type::<T>()?);
+            if self.buf.remaining() == remaining {
+                // a zero sized item would otherwise be read until `max` is reached.
+                return Err(self.error_at_offset(std::io::ErrorKind::InvalidData, ERR_EMPTY_ITEM));
+            }
+        }
+        Ok(items)
+    }
+
+    /// Returns an `InvalidData` error if any bytes remain in the stream.
+    pub fn expect_eof(&self) -> Result<(), std::io::Error> {
+        match self.buf.remaining() {
+            0 => Ok(()),
+            _ => Err(self.error_at_offset(std::io::ErrorKind::InvalidData, ERR_TRAILING_BYTES)),
+        }
+    }
+
+    /// Returns the remaining bytes in the stream.
+    pub fn as_slice(&self) -> &[u8] {
+        self.buf.chunk()
+    }
+}
+
+/// `ByteReader` implements `Buf`, so it can be passed to APIs of other crates expecting `impl Buf`,
+/// IE: `prost` or `tokio` codecs, without copying the remaining bytes out first.
+///
+/// Unlike the methods of `ByteReader`, the `get_*` methods of `Buf` panic if too few bytes remain,
+/// so prefer the `read_*` methods of the reader itself, or `ReadExt`, when reading manually.
+///
+/// ```rust
+/// use binary_util::io::ByteReader;
+/// use bytes::Buf;
+///
+/// fn main() {
+///     let mut reader = ByteReader::from(&[0, 1, 2, 3, 4][..]);
+///     reader.read_u8().unwrap();
+///     assert_eq!(reader.remaining(), 4);
+///     assert_eq!(&reader.copy_to_bytes(2)[..], &[1, 2]);
+///     assert_eq!(reader.offset(), 3);
+/// }
+/// ```
+impl Buf for ByteReader {
+    fn remaining(&self) -> usize {
+        self.buf.remaining()
+    }
+
+    fn chunk(&self) -> &[u8] {
+        self.buf.chunk()
+    }
+
+    fn advance(&mut self, cnt: usize) {
+        self.buf.advance(cnt);
+        track!(self, read, cnt, false);
+    }
+
+    fn copy_to_bytes(&mut self, len: usize) -> Bytes {
+        let bytes = self.buf.copy_to_bytes(len);
+        track!(self, read, len, false);
+        bytes
+    }
+}
+
+/// A saved position of a `ByteReader`, created with `ByteReader::checkpoint`.
+#[derive(Debug, Clone)]
+pub struct ReadCheckpoint {
+    buf: Bytes,
+}
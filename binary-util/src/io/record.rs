@@ -0,0 +1,219 @@
+use bytes::{Buf, Bytes};
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{BufWriter, Error, Read, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use super::{ByteReader, ByteWriter};
+
+/// The magic bytes every capture file starts with, followed by the format version.
+const CAPTURE_MAGIC: &[u8] = b"BUCAP\x01";
+const ERR_BAD_CAPTURE: &str = "Not a binary_util capture file.";
+const ERR_BAD_DIRECTION: &str = "Invalid capture record direction.";
+const ERR_REPLAY_MISMATCH: &str = "Written bytes do not match the capture.";
+
+/// Whether the bytes in a `CaptureRecord` were read from, or written to the stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Read,
+    Write,
+}
+
+/// A single chunk of bytes recorded by a `ByteStream`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaptureRecord {
+    pub direction: Direction,
+    /// The time since the recording started.
+    pub timestamp: Duration,
+    pub data: Bytes,
+}
+
+impl CaptureRecord {
+    fn write(&self, buf: &mut ByteWriter) -> Result<(), Error> {
+        buf.write_u8(match self.direction {
+            Direction::Read => 0,
+            Direction::Write => 1,
+        })?;
+        buf.write_var_u64(self.timestamp.as_micros() as u64)?;
+        buf.write_slice(&self.data)
+    }
+
+    fn read(buf: &mut ByteReader) -> Result<Self, Error> {
+        let direction = match buf.read_u8()? {
+            0 => Direction::Read,
+            1 => Direction::Write,
+            _ => {
+                let kind = std::io::ErrorKind::InvalidData;
+                return Err(buf.error_at_offset(kind, ERR_BAD_DIRECTION));
+            }
+        };
+        Ok(Self {
+            direction,
+            timestamp: Duration::from_micros(buf.read_var_u64()?),
+            data: buf.read_sized_slice()?,
+        })
+    }
+}
+
+/// Mirrors the traffic of a `ByteStream` to a capture file.
+///
+/// This is created with `ByteStream::with_recorder`, and the resulting file can be
+/// read back with `read_capture` or replayed with `ReplayStream`.
+#[derive(Debug)]
+pub struct Recorder {
+    out: BufWriter<File>,
+    start: Instant,
+}
+
+impl Recorder {
+    /// Creates (or truncates) the capture file at `path`.
+    pub fn create(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let mut out = BufWriter::new(File::create(path)?);
+        out.write_all(CAPTURE_MAGIC)?;
+        Ok(Self {
+            out,
+            start: Instant::now(),
+        })
+    }
+
+    /// Appends `data` to the capture, timestamped with the time since the recording started.
+    pub fn record(&mut self, direction: Direction, data: &[u8]) -> Result<(), Error> {
+        if data.is_empty() {
+            return Ok(());
+        }
+        let mut buf = ByteWriter::new();
+        CaptureRecord {
+            direction,
+            timestamp: self.start.elapsed(),
+            data: Bytes::copy_from_slice(data),
+        }
+        .write(&mut buf)?;
+        self.out.write_all(buf.as_slice())
+    }
+
+    /// Flushes any buffered records to the capture file.
+    pub fn flush(&mut self) -> Result<(), Error> {
+        self.out.flush()
+    }
+}
+
+/// Reads every record from a capture file created by `ByteStream::with_recorder`.
+pub fn read_capture(path: impl AsRef<Path>) -> Result<Vec<CaptureRecord>, Error> {
+    let mut data = Vec::new();
+    File::open(path)?.read_to_end(&mut data)?;
+    parse_capture(data.into())
+}
+
+fn parse_capture(data: Bytes) -> Result<Vec<CaptureRecord>, Error> {
+    if !data.starts_with(CAPTURE_MAGIC) {
+        return Err(Error::new(std::io::ErrorKind::InvalidData, ERR_BAD_CAPTURE));
+    }
+    let mut buf = ByteReader::from(data.slice(CAPTURE_MAGIC.len()..));
+    let mut records = Vec::new();
+    while !buf.as_slice().is_empty() {
+        records.push(CaptureRecord::read(&mut buf)?);
+    }
+    Ok(records)
+}
+
+/// Replays a capture file as the source of a `ByteStream`.
+///
+/// Reading yields the bytes that were read during the recording, in order.
+/// Writing checks the written bytes against those written during the recording,
+/// and fails with `InvalidData` as soon as they differ, which makes it simple to
+/// test that a protocol implementation still behaves the same against recorded traffic.
+///
+/// ```rust
+/// use binary_util::io::{ByteStream, ReplayStream};
+///
+/// fn main() {
+///     let path = std::env::temp_dir().join("binary_util_replay_doc.cap");
+///
+///     // record a session
+///     let mut stream = ByteStream::new(&[0, 1, 0, 2][..]).with_recorder(&path).unwrap();
+///     let a = stream.read_type::<u16>().unwrap();
+///     drop(stream);
+///     assert_eq!(a, 1);
+///
+///     // and replay it
+///     let mut stream = ByteStream::new(ReplayStream::open(&path).unwrap());
+///     assert_eq!(stream.read_type::<u16>().unwrap(), 1);
+///     assert_eq!(stream.read_type::<u16>().unwrap(), 2);
+///     # std::fs::remove_file(&path).unwrap();
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct ReplayStream {
+    reads: VecDeque<Bytes>,
+    writes: VecDeque<Bytes>,
+}
+
+impl ReplayStream {
+    /// Opens the capture file at `path` for replaying.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        Ok(Self::from_records(read_capture(path)?))
+    }
+
+    /// Creates a replay from records, IE: ones returned by `read_capture`.
+    pub fn from_records(records: impl IntoIterator<Item = CaptureRecord>) -> Self {
+        let mut replay = Self::default();
+        for record in records {
+            match record.direction {
+                Direction::Read => replay.reads.push_back(record.data),
+                Direction::Write => replay.writes.push_back(record.data),
+            }
+        }
+        replay
+    }
+
+    /// Returns whether every recorded byte has been read, and every expected byte written.
+    pub fn is_finished(&self) -> bool {
+        self.reads.is_empty() && self.writes.is_empty()
+    }
+}
+
+impl Read for ReplayStream {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let Some(chunk) = self.reads.front_mut() else {
+            return Ok(0);
+        };
+        let len = buf.len().min(chunk.len());
+        chunk.copy_to_slice(&mut buf[..len]);
+        if chunk.is_empty() {
+            self.reads.pop_front();
+        }
+        Ok(len)
+    }
+}
+
+impl Write for ReplayStream {
+    fn write(&mut self, mut buf: &[u8]) -> std::io::Result<usize> {
+        let written = buf.len();
+        while !buf.is_empty() {
+            let Some(expected) = self.writes.front_mut() else {
+                return Err(Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    ERR_REPLAY_MISMATCH,
+                ));
+            };
+            let len = buf.len().min(expected.len());
+            if buf[..len] != expected[..len] {
+                return Err(Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    ERR_REPLAY_MISMATCH,
+                ));
+            }
+            expected.advance(len);
+            buf = &buf[len..];
+            if expected.is_empty() {
+                self.writes.pop_front();
+            }
+        }
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,262 @@
+use bytes::{buf::UninitSlice, Buf, BufMut, Bytes};
+use std::io::{IoSlice, Read};
+
+use super::{BinaryErrorExt, ByteReader};
+use crate::interfaces::{Reader, Writer};
+
+/// The capacity a `RingBuffer` grows to when it is first written to.
+const MIN_CAPACITY: usize = 64;
+
+/// A growable ring buffer, intended as the receive buffer of a connection.
+///
+/// Bytes are produced at the back, IE: with `fill_from` or any `WriteExt` method, and consumed from the front,
+/// IE: with `read_type` or any `ReadExt` method. Consuming bytes frees space that later writes wrap around into,
+/// so the buffered bytes are never moved to the front of the buffer. The buffer only grows, and copies the buffered
+/// bytes, once it is full.
+///
+/// `RingBuffer` implements `Buf` and `BufMut`, which may return the buffered bytes, or free space,
+/// in two parts when they wrap around the end of the buffer.
+///
+/// ```rust
+/// use binary_util::io::{RingBuffer, WriteExt};
+///
+/// fn main() {
+///     let mut ring = RingBuffer::with_capacity(8);
+///     ring.write_u32(1).unwrap();
+///     ring.write_u16(2).unwrap();
+///     assert_eq!(ring.read_type::<u32>().unwrap(), 1);
+///
+///     // a string arrives split across two reads of the socket, and wraps around the end of the buffer.
+///     ring.extend_from_slice(&[5, b'h', b'e']);
+///     assert_eq!(ring.read_type::<u16>().unwrap(), 2);
+///     assert!(ring.read_type::<String>().is_err());
+///     ring.extend_from_slice(&[b'l', b'l', b'o']);
+///     assert_eq!(ring.read_type::<String>().unwrap(), "hello");
+///     assert_eq!(ring.capacity(), 8);
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RingBuffer {
+    storage: Vec<u8>,
+    /// The index of the first buffered byte.
+    head: usize,
+    len: usize,
+}
+
+impl RingBuffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            storage: vec![0; capacity],
+            head: 0,
+            len: 0,
+        }
+    }
+
+    /// Returns the amount of buffered bytes.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the amount of bytes the buffer can hold before it grows.
+    pub fn capacity(&self) -> usize {
+        self.storage.len()
+    }
+
+    /// Returns the buffered bytes, as two slices in the order they were written.
+    /// The second slice is empty unless the bytes wrap around the end of the buffer.
+    pub fn as_slices(&self) -> (&[u8], &[u8]) {
+        let end = self.head + self.len;
+        if end <= self.capacity() {
+            (&self.storage[self.head..end], &[])
+        } else {
+            (
+                &self.storage[self.head..],
+                &self.storage[..end - self.capacity()],
+            )
+        }
+    }
+
+    /// Discards every buffered byte.
+    pub fn clear(&mut self) {
+        self.head = 0;
+        self.len = 0;
+    }
+
+    /// Grows the buffer if needed, so at least `additional` more bytes can be written without growing.
+    pub fn reserve(&mut self, additional: usize) {
+        let needed = self.len + additional;
+        if needed <= self.capacity() {
+            return;
+        }
+        let mut storage = vec![0; needed.max(self.capacity() * 2).max(MIN_CAPACITY)];
+        let (front, back) = self.as_slices();
+        storage[..front.len()].copy_from_slice(front);
+        storage[front.len()..self.len].copy_from_slice(back);
+        self.storage = storage;
+        self.head = 0;
+    }
+
+    /// Appends `src` to the buffer, growing it if needed.
+    pub fn extend_from_slice(&mut self, src: &[u8]) {
+        self.put_slice(src);
+    }
+
+    /// Reads from `src` directly into the free space of the buffer, growing it first if it is full.
+    ///
+    /// Returns the amount of bytes read, which is `0` if `src` has ended.
+    /// `WouldBlock` errors of non-blocking sources are returned as is, and nothing is lost.
+    pub fn fill_from<R: Read>(&mut self, src: &mut R) -> Result<usize, std::io::Error> {
+        let free = self.free_mut();
+        loop {
+            match src.read(&mut free[..]) {
+                Ok(read) => {
+                    self.len += read;
+                    return Ok(read);
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Reads `T` from the front of the buffer.
+    ///
+    /// Bytes are only consumed once `T` has been read successfully, so if this returns `UnexpectedEof`,
+    /// it can be called again once more bytes are buffered.
+    ///
+    /// `T` is read straight from the buffer, unless it wraps around the end of the buffer, in which case
+    /// only the bytes of `T` are copied, growing the copy by the amount of bytes a failed read was missing.
+    /// As a result, types which read every remaining byte, IE: `Raw`, stop at the end of the buffer.
+    pub fn read_type<T: Reader<T>>(&mut self) -> Result<T, std::io::Error> {
+        let mut err = match self.read_front::<T>() {
+            Err(e) if e.is_eof() && !self.as_slices().1.is_empty() => e,
+            result => return result,
+        };
+        let mut window = self.as_slices().0.len();
+        loop {
+            // the copy grows by the bytes the last read reported missing, or doubles if it did not report them.
+            window = (window + err.needed_bytes().unwrap_or(window).max(1)).min(self.len);
+            let (front, back) = self.as_slices();
+            let mut buf = Vec::with_capacity(window);
+            buf.extend_from_slice(front);
+            buf.extend_from_slice(&back[..window - front.len()]);
+            let mut reader = ByteReader::from(Bytes::from(buf));
+            match reader.read_type::<T>() {
+                Ok(value) => {
+                    self.advance(reader.offset());
+                    return Ok(value);
+                }
+                Err(e) if e.is_eof() && window < self.len => err = e,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Reads `T` from the buffered bytes before the end of the buffer, without copying them.
+    ///
+    /// The storage is lent to the reader as `Bytes`, and reclaimed afterwards. It is only copied if `T`
+    /// still holds some of its bytes, IE: a `Bytes` field.
+    fn read_front<T: Reader<T>>(&mut self) -> Result<T, std::io::Error> {
+        let end = (self.head + self.len).min(self.capacity());
+        let storage = Bytes::from(std::mem::take(&mut self.storage));
+        let mut reader = ByteReader::from(storage.slice(self.head..end));
+        let result = reader.read_type::<T>();
+        let read = reader.offset();
+        drop(reader);
+        self.storage = match storage.try_into_mut() {
+            Ok(storage) => storage.into(),
+            Err(storage) => storage.to_vec(),
+        };
+        let value = result?;
+        self.advance(read);
+        Ok(value)
+    }
+
+    /// Encodes `T` and appends it to the buffer.
+    pub fn write_type<T: Writer>(&mut self, t: &T) -> Result<(), std::io::Error> {
+        let buf = t.write_to_bytes()?;
+        self.put_slice(buf.as_slice());
+        Ok(())
+    }
+
+    /// Returns the contiguous free space after the buffered bytes, growing the buffer first if it is full.
+    fn free_mut(&mut self) -> &mut [u8] {
+        if self.len == self.capacity() {
+            self.reserve(1);
+        }
+        let capacity = self.capacity();
+        let tail = (self.head + self.len) % capacity;
+        if tail < self.head {
+            &mut self.storage[tail..self.head]
+        } else {
+            &mut self.storage[tail..]
+        }
+    }
+}
+
+impl Buf for RingBuffer {
+    fn remaining(&self) -> usize {
+        self.len
+    }
+
+    fn chunk(&self) -> &[u8] {
+        self.as_slices().0
+    }
+
+    fn chunks_vectored<'a>(&'a self, dst: &mut [IoSlice<'a>]) -> usize {
+        let (front, back) = self.as_slices();
+        let mut filled = 0;
+        for (slot, slice) in dst.iter_mut().zip([front, back]) {
+            if slice.is_empty() {
+                break;
+            }
+            *slot = IoSlice::new(slice);
+            filled += 1;
+        }
+        filled
+    }
+
+    fn advance(&mut self, cnt: usize) {
+        assert!(
+            cnt <= self.len,
+            "advance: cnt ({}) exceeds the buffered bytes ({})",
+            cnt,
+            self.len
+        );
+        self.len -= cnt;
+        // the next write starts at the beginning of the buffer, where the most contiguous space is.
+        self.head = match self.len {
+            0 => 0,
+            _ => (self.head + cnt) % self.capacity(),
+        };
+    }
+}
+
+unsafe impl BufMut for RingBuffer {
+    fn remaining_mut(&self) -> usize {
+        isize::MAX as usize - self.len
+    }
+
+    unsafe fn advance_mut(&mut self, cnt: usize) {
+        let free = self.free_mut().len();
+        assert!(
+            cnt <= free,
+            "advance_mut: cnt ({}) exceeds the free space ({})",
+            cnt,
+            free
+        );
+        self.len += cnt;
+    }
+
+    fn chunk_mut(&mut self) -> &mut UninitSlice {
+        UninitSlice::new(self.free_mut())
+    }
+}
@@ -0,0 +1,323 @@
+use bytes::{Buf, Bytes, BytesMut};
+use std::io::{Read, Write};
+use std::time::{Duration, Instant};
+
+use super::record::{Direction, Recorder};
+use super::{BinaryError, BinaryErrorExt, ByteReader};
+use crate::interfaces::{Reader, Writer};
+
+/// The amount of bytes `ByteStream` attempts to read from its source at once.
+const READ_CHUNK: usize = 4096;
+
+/// ByteStream wraps a `std::io::Read` or `std::io::Write` source, such as a `TcpStream` or a `File`,
+/// allowing types implementing `Reader` and `Writer` to be read from, and written to it directly.
+///
+/// When reading, bytes are accumulated in an internal buffer until enough are available to read
+/// a frame or type. If the source is non-blocking, `WouldBlock` errors are returned to the caller
+/// and the buffered bytes are kept, so the call can simply be retried once the source is readable.
+///
+/// ## Example
+/// ```rust
+/// use binary_util::io::ByteStream;
+///
+/// fn main() {
+///     let mut stream = ByteStream::new(Vec::<u8>::new());
+///     stream.write_type(&String::from("Hello!")).unwrap();
+///     stream.write_type(&10u16).unwrap();
+///
+///     let buf = stream.into_inner();
+///     assert_eq!(buf, b"\x06Hello!\x00\x0a");
+///
+///     let mut stream = ByteStream::new(&buf[..]);
+///     assert_eq!(stream.read_type::<String>().unwrap(), "Hello!");
+///     assert_eq!(stream.read_type::<u16>().unwrap(), 10);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct ByteStream<S> {
+    inner: S,
+    buf: BytesMut,
+    recorder: Option<Recorder>,
+}
+
+impl<S> ByteStream<S> {
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            buf: BytesMut::new(),
+            recorder: None,
+        }
+    }
+
+    /// Mirrors every byte read from, or written to the source to a capture file at `path`.
+    ///
+    /// Each chunk is recorded with its direction and the time since recording started,
+    /// and the capture can later be replayed with `ReplayStream`.
+    pub fn with_recorder(
+        mut self,
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<Self, std::io::Error> {
+        self.recorder = Some(Recorder::create(path)?);
+        Ok(self)
+    }
+
+    fn record(&mut self, direction: Direction, data: &[u8]) -> Result<(), std::io::Error> {
+        match self.recorder.as_mut() {
+            Some(recorder) => recorder.record(direction, data),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns a reference to the underlying source.
+    pub fn get_ref(&self) -> &S {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying source.
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.inner
+    }
+
+    /// Returns the bytes that have been read from the source, but not yet consumed.
+    pub fn buffered(&self) -> &[u8] {
+        &self.buf
+    }
+
+    /// Consumes the stream, returning the underlying source.
+    /// Any buffered bytes are lost.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S: Read> ByteStream<S> {
+    /// Reads from the source until at least `n` bytes are buffered.
+    ///
+    /// Returns `UnexpectedEof` if the source ends before `n` bytes are available,
+    /// and propagates `WouldBlock` from non-blocking sources without losing any buffered bytes.
+    pub fn fill_at_least(&mut self, n: usize) -> Result<(), std::io::Error> {
+        let mut chunk = [0u8; READ_CHUNK];
+        while self.buf.len() < n {
+            match self.inner.read(&mut chunk) {
+                Ok(0) => {
+                    return Err(BinaryError::new(
+                        std::io::ErrorKind::UnexpectedEof,
+                        super::ERR_EOB,
+                    )
+                    .needing(n - self.buf.len())
+                    .into());
+                }
+                Ok(read) => {
+                    self.buf.extend_from_slice(&chunk[..read]);
+                    self.record(Direction::Read, &chunk[..read])?;
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads exactly `len` bytes from the stream.
+    pub fn read_frame(&mut self, len: usize) -> Result<Bytes, std::io::Error> {
+        self.fill_at_least(len)?;
+        #[cfg(feature = "stats")]
+        crate::stats::record_frame(len);
+        Ok(self.buf.split_to(len).freeze())
+    }
+
+    /// Reads `T` from the stream, reading from the source until enough bytes are buffered.
+    ///
+    /// Bytes are only consumed once `T` has been read successfully, so if this returns
+    /// `WouldBlock`, it can be called again once the source is readable.
+    pub fn read_type<T: Reader<T>>(&mut self) -> Result<T, std::io::Error> {
+        loop {
+            match self.read_buffered::<T>()? {
+                Ok(t) => return Ok(t),
+                Err(needed) => self.fill_at_least(self.buf.len() + needed)?,
+            }
+        }
+    }
+
+    /// Reads `T` from the buffered bytes, returning the amount of bytes missing if more are needed.
+    ///
+    /// The buffer is parsed in place rather than copied, and the missing bytes are taken from the
+    /// `UnexpectedEof` error, so a large type arriving in small chunks is not reparsed for every byte.
+    fn read_buffered<T: Reader<T>>(&mut self) -> Result<Result<T, usize>, std::io::Error> {
+        let buf = std::mem::take(&mut self.buf).freeze();
+        let mut reader = ByteReader::from(buf.clone());
+        let result = reader.read_type::<T>();
+        let offset = reader.offset();
+        drop(reader);
+
+        let (rest, result) = match result {
+            Ok(t) => (buf.slice(offset..), Ok(Ok(t))),
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                let needed = e.needed_bytes().unwrap_or(1).max(1);
+                (buf, Ok(Err(needed)))
+            }
+            Err(e) => (buf, Err(e)),
+        };
+        // the buffer is only copied if `T` kept a reference to it, IE: a `Bytes` field.
+        self.buf = rest
+            .try_into_mut()
+            .unwrap_or_else(|rest| BytesMut::from(&rest[..]));
+        result
+    }
+}
+
+impl<S: Read + SocketTimeouts> ByteStream<S> {
+    /// Reads `T` from the stream like `read_type`, but gives up once `timeout` has passed.
+    ///
+    /// A timeout is returned as a `WouldBlock` error, and the bytes buffered so far are kept,
+    /// so a synchronous server can check the liveness of the peer and call this again.
+    /// The read timeout of the source is only changed for the duration of the call.
+    ///
+    /// ```rust no_run
+    /// use binary_util::io::ByteStream;
+    /// use std::net::TcpStream;
+    /// use std::time::Duration;
+    ///
+    /// fn main() {
+    ///     let mut stream = ByteStream::new(TcpStream::connect("127.0.0.1:19132").unwrap());
+    ///     match stream.read_type_timeout::<u32>(Duration::from_secs(5)) {
+    ///         Ok(id) => println!("packet {}", id),
+    ///         Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => println!("peer is idle"),
+    ///         Err(e) => panic!("{}", e),
+    ///     }
+    /// }
+    /// ```
+    pub fn read_type_timeout<T: Reader<T>>(
+        &mut self,
+        timeout: Duration,
+    ) -> Result<T, std::io::Error> {
+        let previous = self.inner.read_timeout()?;
+        let result = self.read_type_until(Instant::now() + timeout);
+        self.inner.set_read_timeout(previous)?;
+        result
+    }
+
+    fn read_type_until<T: Reader<T>>(&mut self, deadline: Instant) -> Result<T, std::io::Error> {
+        loop {
+            let needed = match self.read_buffered::<T>()? {
+                Ok(t) => return Ok(t),
+                Err(needed) => needed,
+            };
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err(read_timeout());
+            }
+            self.inner.set_read_timeout(Some(remaining))?;
+            match self.fill_at_least(self.buf.len() + needed) {
+                // blocking sockets report timeouts as `WouldBlock` on unix, and `TimedOut` on windows.
+                Err(e)
+                    if matches!(
+                        e.kind(),
+                        std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+                    ) =>
+                {
+                    return Err(read_timeout())
+                }
+                result => result?,
+            }
+        }
+    }
+}
+
+fn read_timeout() -> std::io::Error {
+    BinaryError::new(std::io::ErrorKind::WouldBlock, super::ERR_READ_TIMEOUT).into()
+}
+
+impl<S: SocketTimeouts> ByteStream<S> {
+    /// Sets the read timeout of the underlying socket, see `TcpStream::set_read_timeout`.
+    pub fn set_read_timeout(&self, timeout: Option<Duration>) -> Result<(), std::io::Error> {
+        self.inner.set_read_timeout(timeout)
+    }
+
+    /// Sets the write timeout of the underlying socket, see `TcpStream::set_write_timeout`.
+    pub fn set_write_timeout(&self, timeout: Option<Duration>) -> Result<(), std::io::Error> {
+        self.inner.set_write_timeout(timeout)
+    }
+
+    pub fn read_timeout(&self) -> Result<Option<Duration>, std::io::Error> {
+        self.inner.read_timeout()
+    }
+
+    pub fn write_timeout(&self) -> Result<Option<Duration>, std::io::Error> {
+        self.inner.write_timeout()
+    }
+}
+
+/// Sources with read and write timeouts, which `ByteStream` exposes for sockets.
+pub trait SocketTimeouts {
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> Result<(), std::io::Error>;
+    fn set_write_timeout(&self, timeout: Option<Duration>) -> Result<(), std::io::Error>;
+    fn read_timeout(&self) -> Result<Option<Duration>, std::io::Error>;
+    fn write_timeout(&self) -> Result<Option<Duration>, std::io::Error>;
+}
+
+macro_rules! impl_socket_timeouts {
+    ($($ty:ty),*) => {
+        $(
+            impl SocketTimeouts for $ty {
+                fn set_read_timeout(&self, timeout: Option<Duration>) -> Result<(), std::io::Error> {
+                    <$ty>::set_read_timeout(self, timeout)
+                }
+
+                fn set_write_timeout(&self, timeout: Option<Duration>) -> Result<(), std::io::Error> {
+                    <$ty>::set_write_timeout(self, timeout)
+                }
+
+                fn read_timeout(&self) -> Result<Option<Duration>, std::io::Error> {
+                    <$ty>::read_timeout(self)
+                }
+
+                fn write_timeout(&self) -> Result<Option<Duration>, std::io::Error> {
+                    <$ty>::write_timeout(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_socket_timeouts!(std::net::TcpStream);
+#[cfg(unix)]
+impl_socket_timeouts!(std::os::unix::net::UnixStream);
+
+impl<S: Write> ByteStream<S> {
+    /// Encodes `T` and writes it to the underlying source in a single call.
+    pub fn write_type<T: Writer>(&mut self, t: &T) -> Result<(), std::io::Error> {
+        let buf = t.write_to_bytes()?;
+        self.inner.write_all(buf.as_slice())?;
+        self.record(Direction::Write, buf.as_slice())
+    }
+}
+
+impl<S: Read> Read for ByteStream<S> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        // buffered bytes must be drained before reading from the source.
+        if !self.buf.is_empty() {
+            let len = buf.len().min(self.buf.len());
+            self.buf.copy_to_slice(&mut buf[..len]);
+            return Ok(len);
+        }
+        let read = self.inner.read(buf)?;
+        self.record(Direction::Read, &buf[..read])?;
+        Ok(read)
+    }
+}
+
+impl<S: Write> Write for ByteStream<S> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.record(Direction::Write, &buf[..written])?;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        if let Some(recorder) = self.recorder.as_mut() {
+            recorder.flush()?;
+        }
+        self.inner.flush()
+    }
+}
@@ -0,0 +1,138 @@
+//! Helpers for text based protocols, such as query or handshake protocols that mix
+//! lines of text with binary data, allowing both to be read from the same `ByteReader`.
+//!
+//! ```rust
+//! use binary_util::io::{ByteReader, ByteWriter};
+//!
+//! fn main() {
+//!     let mut writer = ByteWriter::new();
+//!     writer.write_line("HELLO 1.0").unwrap();
+//!     writer.write_ascii_u32(3).unwrap();
+//!     writer.write_u8(b' ').unwrap();
+//!     writer.write_u16(300).unwrap();
+//!
+//!     let mut reader = ByteReader::from(writer.as_slice());
+//!     assert_eq!(reader.read_line().unwrap(), "HELLO 1.0");
+//!     assert_eq!(reader.read_ascii_u32().unwrap(), 3);
+//!     assert_eq!(reader.read_u8().unwrap(), b' ');
+//!     assert_eq!(reader.read_u16().unwrap(), 300);
+//! }
+//! ```
+use bytes::Buf;
+use std::io::{Error, ErrorKind};
+
+use super::{BinaryError, ByteReader, ByteWriter, ERR_EOB, ERR_INVALID_UTF8};
+
+const ERR_NOT_A_NUMBER: &str = "Expected ASCII decimal digits";
+const ERR_NUMBER_OVERFLOW: &str = "ASCII number does not fit within a u32";
+
+impl ByteReader {
+    /// Reads a line of UTF-8 text, up to and including the next `\n`.
+    /// The line is returned without its line ending, which may be `\n` or `\r\n`.
+    ///
+    /// If the buffer does not contain a complete line, an `UnexpectedEof` error is returned
+    /// and nothing is consumed, so the line can be read again once more data is available.
+    pub fn read_line(&mut self) -> Result<String, Error> {
+        let Some(end) = self.as_slice().iter().position(|b| *b == b'\n') else {
+            return Err(BinaryError::new(ErrorKind::UnexpectedEof, ERR_EOB)
+                .at_offset(self.offset())
+                .into());
+        };
+        let offset = self.offset();
+        let line = self.read_bytes(end + 1)?;
+        let line = line.strip_suffix(b"\n").unwrap_or(&line);
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        String::from_utf8(line.to_vec()).map_err(|e| {
+            BinaryError::new(ErrorKind::InvalidData, ERR_INVALID_UTF8)
+                .at_offset(offset)
+                .caused_by(e.utf8_error())
+                .into()
+        })
+    }
+
+    /// Returns an iterator over the remaining lines of the buffer, see `read_line`.
+    /// Unlike `read_line`, the last line does not need to end with a line ending.
+    ///
+    /// ```rust
+    /// use binary_util::io::ByteReader;
+    ///
+    /// fn main() {
+    ///     let mut reader = ByteReader::from(&b"hostname\r\nmap\nplayers"[..]);
+    ///     let lines = reader.lines().collect::<Result<Vec<String>, _>>().unwrap();
+    ///     assert_eq!(lines, vec!["hostname", "map", "players"]);
+    /// }
+    /// ```
+    pub fn lines(&mut self) -> Lines<'_> {
+        Lines { reader: self }
+    }
+
+    /// Reads an unsigned decimal number written in ASCII, IE: `b"1024"`.
+    ///
+    /// Reading stops at the first byte that is not a digit, which is not consumed.
+    /// An `InvalidData` error is returned if there are no digits, or if the number does not fit within a `u32`.
+    pub fn read_ascii_u32(&mut self) -> Result<u32, Error> {
+        let digits = self
+            .as_slice()
+            .iter()
+            .take_while(|b| b.is_ascii_digit())
+            .count();
+        if digits == 0 {
+            return Err(self.error_at_offset(ErrorKind::InvalidData, ERR_NOT_A_NUMBER));
+        }
+        let value = self.as_slice()[..digits]
+            .iter()
+            .try_fold(0u32, |value, b| {
+                value.checked_mul(10)?.checked_add((b - b'0') as u32)
+            })
+            .ok_or_else(|| -> Error {
+                BinaryError::new(ErrorKind::InvalidData, ERR_NUMBER_OVERFLOW)
+                    .at_offset(self.offset())
+                    .overflowing()
+                    .into()
+            })?;
+        self.buf.advance(digits);
+        track!(self, read, digits, false);
+        Ok(value)
+    }
+}
+
+/// An iterator over the lines of a `ByteReader`, created with `ByteReader::lines`.
+pub struct Lines<'a> {
+    reader: &'a mut ByteReader,
+}
+
+impl Iterator for Lines<'_> {
+    type Item = Result<String, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let remaining = self.reader.as_slice();
+        if remaining.is_empty() {
+            return None;
+        }
+        if remaining.contains(&b'\n') {
+            return Some(self.reader.read_line());
+        }
+        // the last line, without a line ending.
+        let offset = self.reader.offset();
+        let line = self.reader.read_bytes(remaining.len()).ok()?;
+        Some(String::from_utf8(line.to_vec()).map_err(|e| {
+            BinaryError::new(ErrorKind::InvalidData, ERR_INVALID_UTF8)
+                .at_offset(offset)
+                .caused_by(e.utf8_error())
+                .into()
+        }))
+    }
+}
+
+impl ByteWriter {
+    /// Writes `line` followed by a `\n`.
+    pub fn write_line(&mut self, line: &str) -> Result<(), Error> {
+        self.write(line.as_bytes())?;
+        self.write_u8(b'\n')
+    }
+
+    /// Writes `num` as an unsigned decimal number in ASCII, IE: `b"1024"`.
+    pub fn write_ascii_u32(&mut self, num: u32) -> Result<(), Error> {
+        self.write(num.to_string().as_bytes())
+    }
+}
@@ -0,0 +1,1402 @@
+use bytes::{buf::UninitSlice, Buf, BufMut, Bytes, BytesMut};
+use std::{
+    collections::VecDeque,
+    io::{Error, IoSlice},
+    ops::{Deref, DerefMut},
+};
+
+use super::{
+    BinaryError, ByteReader, ERR_EOM, ERR_SLICE_TOO_LONG, ERR_STRING_TOO_LONG, ERR_VARINT_SLOT,
+    ERR_WRITE_CAP, MAX_VAR_U32_LEN,
+};
+use crate::interfaces::Writer;
+use crate::pool::DecayPolicy;
+#[cfg(feature = "stats")]
+use crate::stats::Stats;
+use crate::varint::{self, zigzag_encode, MAX_VAR_U64_LEN};
+
+/// Panic-free numeric write helpers for any type implementing `bytes::BufMut`.
+///
+/// This trait is implemented for every `BufMut`, so third-party buffer types can use the
+/// same helpers as `ByteWriter` by importing it.
+///
+/// ```rust
+/// use binary_util::io::WriteExt;
+///
+/// fn main() {
+///     let mut buf: Vec<u8> = Vec::new();
+///     buf.write_u32(300).unwrap();
+///     buf.write_var_u32(300).unwrap();
+///     assert_eq!(buf, vec![0, 0, 1, 44, 172, 2]);
+/// }
+/// ```
+pub trait WriteExt: BufMut {
+    write_fn!(write_u8, u8, put_u8, 1);
+    write_fn!(write_i8, i8, put_i8, 1);
+    write_fn!(write_u16, u16, put_u16, 2);
+    write_fn!(write_u16_le, u16, put_u16_le, 2);
+    write_fn!(write_i16, i16, put_i16, 2);
+    write_fn!(write_i16_le, i16, put_i16_le, 2);
+
+    fn write_u24<I: Into<u32>>(&mut self, num: I) -> Result<(), std::io::Error> {
+        self.write_uint(num.into().into(), 3)
+    }
+
+    fn write_u24_le<I: Into<u32>>(&mut self, num: I) -> Result<(), std::io::Error> {
+        self.write_uint_le(num.into().into(), 3)
+    }
+
+    fn write_i24<I: Into<i32>>(&mut self, num: I) -> Result<(), std::io::Error> {
+        self.write_int(num.into().into(), 3)
+    }
+
+    fn write_i24_le<I: Into<i32>>(&mut self, num: I) -> Result<(), std::io::Error> {
+        self.write_int_le(num.into().into(), 3)
+    }
+
+    write_fn!(write_u32, u32, put_u32, 4);
+    write_fn!(write_u32_le, u32, put_u32_le, 4);
+    write_fn!(write_i32, i32, put_i32, 4);
+    write_fn!(write_i32_le, i32, put_i32_le, 4);
+    write_fn!(write_f32, f32, put_f32, 4);
+    write_fn!(write_f32_le, f32, put_f32_le, 4);
+
+    /// Writes a var-int 32-bit unsigned integer to the stream.
+    /// The var-int is written at once, so nothing is written if the buffer is too small to hold it.
+    fn write_var_u32(&mut self, num: u32) -> Result<(), std::io::Error> {
+        self.write_var_u64(num as u64)
+    }
+
+    /// Writes a var-int 32-bit signed integer to the stream, see `write_var_u32`.
+    fn write_var_i32(&mut self, num: i32) -> Result<(), std::io::Error> {
+        self.write_var_u32(zigzag_encode(num as i64) as u32)
+    }
+
+    write_fn!(write_u64, u64, put_u64, 8);
+    write_fn!(write_u64_le, u64, put_u64_le, 8);
+    write_fn!(write_i64, i64, put_i64, 8);
+    write_fn!(write_i64_le, i64, put_i64_le, 8);
+    write_fn!(write_f64, f64, put_f64, 8);
+    write_fn!(write_f64_le, f64, put_f64_le, 8);
+
+    /// Writes a var-int 64-bit unsigned integer to the stream.
+    /// The var-int is written at once, so nothing is written if the buffer is too small to hold it.
+    fn write_var_u64(&mut self, num: u64) -> Result<(), std::io::Error> {
+        let mut out = [0; MAX_VAR_U64_LEN];
+        let encoded = varint::encode(num, &mut out);
+        if can_write!(self, encoded.len()) {
+            self.put_slice(encoded);
+            Ok(())
+        } else {
+            Err(BinaryError::new(std::io::ErrorKind::OutOfMemory, ERR_EOM)
+                .needing(encoded.len() - self.remaining_mut())
+                .overflowing()
+                .into())
+        }
+    }
+
+    /// Writes a var-int 64-bit signed integer to the stream, see `write_var_u64`.
+    fn write_var_i64(&mut self, num: i64) -> Result<(), std::io::Error> {
+        self.write_var_u64(zigzag_encode(num))
+    }
+
+    /// Writes a var-int 32-bit unsigned integer with the most significant group first, IE: `BE<varu32>`.
+    fn write_var_u32_be(&mut self, num: u32) -> Result<(), std::io::Error> {
+        self.write_var_u64_be(num as u64)
+    }
+
+    /// Writes a zigzag encoded var-int 32-bit signed integer with the most significant group first.
+    fn write_var_i32_be(&mut self, num: i32) -> Result<(), std::io::Error> {
+        self.write_var_u32_be(zigzag_encode(num as i64) as u32)
+    }
+
+    /// Writes a var-int 64-bit unsigned integer with the most significant group first, IE: `BE<varu64>`.
+    /// The var-int is written at once, so nothing is written if the buffer is too small to hold it.
+    fn write_var_u64_be(&mut self, num: u64) -> Result<(), std::io::Error> {
+        let mut out = [0; MAX_VAR_U64_LEN];
+        let encoded = varint::encode_be(num, &mut out);
+        if can_write!(self, encoded.len()) {
+            self.put_slice(encoded);
+            Ok(())
+        } else {
+            Err(BinaryError::new(std::io::ErrorKind::OutOfMemory, ERR_EOM)
+                .needing(encoded.len() - self.remaining_mut())
+                .overflowing()
+                .into())
+        }
+    }
+
+    /// Writes a zigzag encoded var-int 64-bit signed integer with the most significant group first.
+    fn write_var_i64_be(&mut self, num: i64) -> Result<(), std::io::Error> {
+        self.write_var_u64_be(zigzag_encode(num))
+    }
+
+    write_fn!(write_u128, u128, put_u128, 16);
+    write_fn!(write_u128_le, u128, put_u128_le, 16);
+    write_fn!(write_i128, i128, put_i128, 16);
+    write_fn!(write_i128_le, i128, put_i128_le, 16);
+
+    fn write_uint(&mut self, num: u64, size: usize) -> Result<(), std::io::Error> {
+        if can_write!(self, size) {
+            self.put_uint(num, size);
+            Ok(())
+        } else {
+            Err(BinaryError::new(std::io::ErrorKind::OutOfMemory, ERR_EOM)
+                .needing(size - self.remaining_mut())
+                .overflowing()
+                .into())
+        }
+    }
+
+    fn write_uint_le(&mut self, num: u64, size: usize) -> Result<(), std::io::Error> {
+        if can_write!(self, size) {
+            self.put_uint_le(num, size);
+            Ok(())
+        } else {
+            Err(BinaryError::new(std::io::ErrorKind::OutOfMemory, ERR_EOM)
+                .needing(size - self.remaining_mut())
+                .overflowing()
+                .into())
+        }
+    }
+
+    fn write_int(&mut self, num: i64, size: usize) -> Result<(), std::io::Error> {
+        if can_write!(self, size) {
+            self.put_int(num, size);
+            Ok(())
+        } else {
+            Err(BinaryError::new(std::io::ErrorKind::OutOfMemory, ERR_EOM)
+                .needing(size - self.remaining_mut())
+                .overflowing()
+                .into())
+        }
+    }
+
+    fn write_int_le(&mut self, num: i64, size: usize) -> Result<(), std::io::Error> {
+        if can_write!(self, size) {
+            self.put_int_le(num, size);
+            Ok(())
+        } else {
+            Err(BinaryError::new(std::io::ErrorKind::OutOfMemory, ERR_EOM)
+                .needing(size - self.remaining_mut())
+                .overflowing()
+                .into())
+        }
+    }
+
+    /// Writes a char as its code point in a big endian `u32`, IE: `U32Char`.
+    #[deprecated(
+        note = "No common protocol encodes chars as a u32, use `write_char_utf8` or `write_char_utf16` instead"
+    )]
+    fn write_char(&mut self, c: char) -> Result<(), std::io::Error> {
+        self.write_u32(c as u32)
+    }
+
+    /// Writes a char as UTF-8, which takes 1 to 4 bytes, IE: `Utf8Char`.
+    fn write_char_utf8(&mut self, c: char) -> Result<(), std::io::Error> {
+        let mut bytes = [0; 4];
+        let encoded = c.encode_utf8(&mut bytes);
+        if can_write!(self, encoded.len()) {
+            self.put_slice(encoded.as_bytes());
+            Ok(())
+        } else {
+            Err(BinaryError::new(std::io::ErrorKind::OutOfMemory, ERR_EOM)
+                .needing(encoded.len() - self.remaining_mut())
+                .overflowing()
+                .into())
+        }
+    }
+
+    /// Writes a char as big endian UTF-16, which takes 2 bytes, or 4 bytes for a surrogate pair, IE: `Utf16Char`.
+    fn write_char_utf16(&mut self, c: char) -> Result<(), std::io::Error> {
+        let mut units = [0; 2];
+        let encoded = c.encode_utf16(&mut units);
+        if can_write!(self, encoded.len() * 2) {
+            encoded.iter().for_each(|unit| self.put_u16(*unit));
+            Ok(())
+        } else {
+            Err(BinaryError::new(std::io::ErrorKind::OutOfMemory, ERR_EOM)
+                .needing(encoded.len() * 2 - self.remaining_mut())
+                .overflowing()
+                .into())
+        }
+    }
+
+    /// Writes a char as little endian UTF-16, IE: `LE<Utf16Char>`.
+    /// This is the same as `write_char_utf16` but in little endian.
+    fn write_char_utf16_le(&mut self, c: char) -> Result<(), std::io::Error> {
+        let mut units = [0; 2];
+        let encoded = c.encode_utf16(&mut units);
+        if can_write!(self, encoded.len() * 2) {
+            encoded.iter().for_each(|unit| self.put_u16_le(*unit));
+            Ok(())
+        } else {
+            Err(BinaryError::new(std::io::ErrorKind::OutOfMemory, ERR_EOM)
+                .needing(encoded.len() * 2 - self.remaining_mut())
+                .overflowing()
+                .into())
+        }
+    }
+
+    fn write_bool(&mut self, b: bool) -> Result<(), std::io::Error> {
+        self.write_u8(b as u8)
+    }
+}
+
+impl<B: BufMut + ?Sized> WriteExt for B {}
+
+/// ByteWriter is a panic-free way to write bytes to a `BufMut` trait.
+///
+/// ## Example
+/// A generic example of how to use the `ByteWriter` struct.
+/// ```rust
+/// use binary_util::io::ByteWriter;
+/// use binary_util::io::ByteReader;
+///
+/// fn main() {
+///    let mut writer = ByteWriter::new();
+///    writer.write_string("Hello World!").unwrap();
+///    writer.write_var_u32(65536).unwrap();
+///    writer.write_u8(0).unwrap();
+///
+///    println!("Bytes: {:?}", writer.as_slice());
+/// }
+/// ```
+///
+/// `ByteWriter` also implements the `Into` trait to convert the `ByteWriter` into a `BytesMut` or `Bytes` structs.
+/// ```rust
+/// use binary_util::io::ByteWriter;
+/// use binary_util::io::ByteReader;
+///
+/// fn main() {
+///     let mut writer = ByteWriter::new();
+///     writer.write_u8(1);
+///     writer.write_u8(2);
+///     writer.write_u8(3);
+///
+///     let mut reader: ByteReader = writer.into();
+///     assert_eq!(reader.read_u8().unwrap(), 1);
+///     assert_eq!(reader.read_u8().unwrap(), 2);
+///     assert_eq!(reader.read_u8().unwrap(), 3);
+/// }
+/// ```
+///
+/// With the `inline` feature, small packets can also be written without allocating, see `ByteWriter::inline`.
+#[derive(Debug, Clone)]
+pub struct ByteWriter {
+    pub(crate) buf: WriteBuf,
+    /// The policy releasing spare capacity when the writer is cleared, see `with_decay_policy`.
+    pub(crate) decay: Option<DecayPolicy>,
+    /// The amount of times in a row the capacity exceeded the decay policy.
+    pub(crate) decay_strikes: u32,
+    #[cfg(feature = "stats")]
+    pub(crate) stats: Stats,
+}
+
+impl Into<BytesMut> for ByteWriter {
+    fn into(self) -> BytesMut {
+        self.buf.into_bytes_mut()
+    }
+}
+
+impl Into<Bytes> for ByteWriter {
+    fn into(self) -> Bytes {
+        self.buf.freeze()
+    }
+}
+
+impl Into<Vec<u8>> for ByteWriter {
+    fn into(self) -> Vec<u8> {
+        self.buf.to_vec()
+    }
+}
+
+impl Into<VecDeque<u8>> for ByteWriter {
+    fn into(self) -> VecDeque<u8> {
+        self.buf.to_vec().into()
+    }
+}
+
+impl From<IoSlice<'_>> for ByteWriter {
+    fn from(slice: IoSlice) -> Self {
+        let mut buf = BytesMut::with_capacity(slice.len());
+        buf.put_slice(&slice);
+        return Self::from(buf);
+    }
+}
+
+impl From<&[u8]> for ByteWriter {
+    fn from(slice: &[u8]) -> Self {
+        let mut buf = BytesMut::with_capacity(slice.len());
+        buf.put_slice(slice);
+        return Self::from(buf);
+    }
+}
+
+impl From<ByteReader> for ByteWriter {
+    fn from(reader: ByteReader) -> Self {
+        Self::from(BytesMut::from(reader.buf.chunk()))
+    }
+}
+
+impl From<BytesMut> for ByteWriter {
+    fn from(buf: BytesMut) -> Self {
+        Self {
+            buf: WriteBuf::Heap(buf),
+            decay: None,
+            decay_strikes: 0,
+            #[cfg(feature = "stats")]
+            stats: Stats::default(),
+        }
+    }
+}
+
+impl ByteWriter {
+    pub fn new() -> Self {
+        return Self::from(BytesMut::new());
+    }
+
+    /// Creates a writer which builds packets of up to `INLINE_CAPACITY` bytes inline, without allocating.
+    ///
+    /// Most control packets are only a few bytes long, where allocating a `BytesMut` for every packet dominates
+    /// the cost of writing it. An inline writer builds the packet within an array, and only allocates once the
+    /// packet grows past its threshold. Taking the bytes of a writer which is still inline, IE: with `take_bytes`
+    /// or by converting it into `Bytes`, copies them into a new allocation, so inline writers pay off when the
+    /// packet is written out with `as_slice` instead.
+    ///
+    /// The array makes every `ByteWriter` `INLINE_CAPACITY` bytes larger, which is why inline writers
+    /// are only available with the `inline` feature.
+    /// ```rust
+    /// use binary_util::io::ByteWriter;
+    ///
+    /// fn main() {
+    ///     let mut writer = ByteWriter::inline();
+    ///     writer.write_u32(300).unwrap();
+    ///     assert!(writer.is_inline());
+    ///
+    ///     writer.write_string(&"a".repeat(100)).unwrap();
+    ///     assert!(!writer.is_inline());
+    /// }
+    /// ```
+    #[cfg(feature = "inline")]
+    pub fn inline() -> Self {
+        Self::with_inline_threshold(INLINE_CAPACITY)
+    }
+
+    /// Creates a writer which builds packets inline until they exceed `threshold` bytes,
+    /// at which point they are moved to a `BytesMut`.
+    ///
+    /// The threshold can not be larger than `INLINE_CAPACITY`, and is clamped to it,
+    /// or panics in instrumented builds.
+    #[cfg(feature = "inline")]
+    pub fn with_inline_threshold(threshold: usize) -> Self {
+        misuse!(
+            threshold > INLINE_CAPACITY,
+            "inline threshold {} exceeds INLINE_CAPACITY ({})",
+            threshold,
+            INLINE_CAPACITY
+        );
+        Self {
+            buf: WriteBuf::Inline {
+                data: [0; INLINE_CAPACITY],
+                len: 0,
+                limit: threshold.min(INLINE_CAPACITY),
+            },
+            decay: None,
+            decay_strikes: 0,
+            #[cfg(feature = "stats")]
+            stats: Stats::default(),
+        }
+    }
+
+    /// Returns whether the written bytes are still stored inline, IE: the writer has not allocated.
+    #[cfg(feature = "inline")]
+    pub fn is_inline(&self) -> bool {
+        matches!(self.buf, WriteBuf::Inline { .. })
+    }
+
+    /// Returns the amount of bytes the writer can hold without allocating, including the written bytes.
+    /// For inline writers, this is the threshold they allocate at.
+    pub fn capacity(&self) -> usize {
+        match &self.buf {
+            #[cfg(feature = "inline")]
+            WriteBuf::Inline { limit, .. } => *limit,
+            WriteBuf::Heap(heap) => heap.capacity(),
+        }
+    }
+
+    /// Releases the spare capacity of the writer, IE: after a connection sent a huge packet.
+    /// The written bytes are kept, and copied to a buffer of their exact size.
+    pub fn shrink_to_fit(&mut self) {
+        match &mut self.buf {
+            WriteBuf::Heap(heap) if heap.capacity() > heap.len() => {
+                *heap = BytesMut::from(&heap[..]);
+            }
+            _ => {}
+        }
+    }
+
+    /// Releases the spare capacity of the writer with `shrink_to_fit` whenever it is cleared,
+    /// IE: with `clear` or `take_bytes`, while its capacity exceeds `policy`. See `DecayPolicy`.
+    pub fn with_decay_policy(mut self, policy: DecayPolicy) -> Self {
+        self.decay = Some(policy);
+        self
+    }
+
+    /// Applies the decay policy of the writer, if any.
+    fn decay(&mut self) {
+        if let Some(policy) = self.decay {
+            if policy.exceeded(self.capacity(), &mut self.decay_strikes) {
+                self.shrink_to_fit();
+            }
+        }
+    }
+
+    /// Returns the serialization counters of this writer.
+    /// See [`Stats`] for more information.
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+
+    delegate_write_fn! {
+        write_u8, u8;
+        write_i8, i8;
+        write_u16, u16;
+        write_u16_le, u16;
+        write_i16, i16;
+        write_i16_le, i16;
+        write_u32, u32;
+        write_u32_le, u32;
+        write_i32, i32;
+        write_i32_le, i32;
+        write_f32, f32;
+        write_f32_le, f32;
+        write_var_u32, u32;
+        write_var_i32, i32;
+        write_u64, u64;
+        write_u64_le, u64;
+        write_i64, i64;
+        write_i64_le, i64;
+        write_f64, f64;
+        write_f64_le, f64;
+        write_var_u64, u64;
+        write_var_i64, i64;
+        write_var_u32_be, u32;
+        write_var_i32_be, i32;
+        write_var_u64_be, u64;
+        write_var_i64_be, i64;
+        write_u128, u128;
+        write_u128_le, u128;
+        write_i128, i128;
+        write_i128_le, i128;
+        #[deprecated(
+            note = "No common protocol encodes chars as a u32, use `write_char_utf8` or `write_char_utf16` instead"
+        )]
+        write_char, char;
+        write_char_utf8, char;
+        write_char_utf16, char;
+        write_char_utf16_le, char;
+        write_bool, bool;
+    }
+
+    write_slice_fn! {
+        write_u16_slice, u16, to_be_bytes, "big endian";
+        write_u16_le_slice, u16, to_le_bytes, "little endian";
+        write_i16_slice, i16, to_be_bytes, "big endian";
+        write_i16_le_slice, i16, to_le_bytes, "little endian";
+        write_u32_slice, u32, to_be_bytes, "big endian";
+        write_u32_le_slice, u32, to_le_bytes, "little endian";
+        write_i32_slice, i32, to_be_bytes, "big endian";
+        write_i32_le_slice, i32, to_le_bytes, "little endian";
+        write_f32_slice, f32, to_be_bytes, "big endian";
+        write_f32_le_slice, f32, to_le_bytes, "little endian";
+        write_u64_slice, u64, to_be_bytes, "big endian";
+        write_u64_le_slice, u64, to_le_bytes, "little endian";
+        write_i64_slice, i64, to_be_bytes, "big endian";
+        write_i64_le_slice, i64, to_le_bytes, "little endian";
+        write_f64_slice, f64, to_be_bytes, "big endian";
+        write_f64_le_slice, f64, to_le_bytes, "little endian";
+    }
+
+    pub fn write_u24<I: Into<u32>>(&mut self, num: I) -> Result<(), std::io::Error> {
+        WriteExt::write_u24(&mut self.buf, num)?;
+        track!(self, written, 3, false);
+        Ok(())
+    }
+
+    pub fn write_u24_le<I: Into<u32>>(&mut self, num: I) -> Result<(), std::io::Error> {
+        WriteExt::write_u24_le(&mut self.buf, num)?;
+        track!(self, written, 3, false);
+        Ok(())
+    }
+
+    pub fn write_i24<I: Into<i32>>(&mut self, num: I) -> Result<(), std::io::Error> {
+        WriteExt::write_i24(&mut self.buf, num)?;
+        track!(self, written, 3, false);
+        Ok(())
+    }
+
+    pub fn write_i24_le<I: Into<i32>>(&mut self, num: I) -> Result<(), std::io::Error> {
+        WriteExt::write_i24_le(&mut self.buf, num)?;
+        track!(self, written, 3, false);
+        Ok(())
+    }
+
+    pub fn write_uint(&mut self, num: u64, size: usize) -> Result<(), std::io::Error> {
+        WriteExt::write_uint(&mut self.buf, num, size)?;
+        track!(self, written, size, false);
+        Ok(())
+    }
+
+    pub fn write_uint_le(&mut self, num: u64, size: usize) -> Result<(), std::io::Error> {
+        WriteExt::write_uint_le(&mut self.buf, num, size)?;
+        track!(self, written, size, false);
+        Ok(())
+    }
+
+    pub fn write_int(&mut self, num: i64, size: usize) -> Result<(), std::io::Error> {
+        WriteExt::write_int(&mut self.buf, num, size)?;
+        track!(self, written, size, false);
+        Ok(())
+    }
+
+    pub fn write_int_le(&mut self, num: i64, size: usize) -> Result<(), std::io::Error> {
+        WriteExt::write_int_le(&mut self.buf, num, size)?;
+        track!(self, written, size, false);
+        Ok(())
+    }
+
+    /// Returns the amount of bytes `num` will take up when written with `write_var_u32`.
+    ///
+    /// ```rust
+    /// use binary_util::io::ByteWriter;
+    ///
+    /// fn main() {
+    ///     assert_eq!(ByteWriter::var_u32_len(127), 1);
+    ///     assert_eq!(ByteWriter::var_u32_len(255), 2);
+    ///     assert_eq!(ByteWriter::var_u32_len(u32::MAX), 5);
+    /// }
+    /// ```
+    pub fn var_u32_len(num: u32) -> usize {
+        varint::encoded_len(num as u64)
+    }
+
+    /// Overwrites the bytes at `pos..pos + 5` with `num` encoded as a var-int.
+    /// If `num` is shorter than 5 bytes, it is padded with empty continuation bytes
+    /// so it will always occupy the full slot.
+    ///
+    /// This allows you to back-patch a length prefix after the body has been written,
+    /// without re-serializing the body. The padded var-int is decoded by `read_var_u32`, however it is
+    /// not the shortest encoding of `num`, so readers with canonical var-ints, IE: `ByteReader::hardened`,
+    /// reject it. Use `insert_var_u32_at` for prefixes read by those.
+    ///
+    /// ```rust
+    /// use binary_util::io::{ByteReader, ByteWriter};
+    ///
+    /// fn main() {
+    ///     let mut writer = ByteWriter::new();
+    ///     // reserve the length prefix
+    ///     writer.write(&[0; 5]).unwrap();
+    ///     writer.write_string("Hello world!").unwrap();
+    ///
+    ///     let body_len = writer.as_slice().len() - 5;
+    ///     writer.write_var_u32_at(0, body_len as u32).unwrap();
+    ///
+    ///     let mut reader = ByteReader::from(writer.as_slice());
+    ///     assert_eq!(reader.read_var_u32().unwrap(), 13);
+    ///     assert_eq!(reader.read_string().unwrap(), "Hello world!");
+    /// }
+    /// ```
+    pub fn write_var_u32_at(&mut self, pos: usize, num: u32) -> Result<(), std::io::Error> {
+        if pos.saturating_add(MAX_VAR_U32_LEN) > self.buf.len() {
+            return Err(
+                BinaryError::new(std::io::ErrorKind::InvalidInput, ERR_VARINT_SLOT)
+                    .at_offset(pos)
+                    .into(),
+            );
+        }
+
+        let mut slot = [0; MAX_VAR_U32_LEN];
+        varint::encode_padded(num, &mut slot);
+        self.buf[pos..pos + MAX_VAR_U32_LEN].copy_from_slice(&slot);
+        Ok(())
+    }
+
+    /// Inserts `num` encoded as the shortest var-int at `pos`, moving the bytes written after `pos` forward.
+    ///
+    /// Unlike `write_var_u32_at`, no slot has to be reserved, and the prefix is accepted by readers with
+    /// canonical var-ints, at the cost of moving the body within the buffer.
+    ///
+    /// ```rust
+    /// use binary_util::io::{ByteReader, ByteWriter};
+    ///
+    /// fn main() {
+    ///     let mut writer = ByteWriter::new();
+    ///     writer.write_string("Hello world!").unwrap();
+    ///
+    ///     let body_len = writer.len();
+    ///     writer.insert_var_u32_at(0, body_len as u32).unwrap();
+    ///
+    ///     let mut reader = ByteReader::hardened(writer.as_slice());
+    ///     assert_eq!(reader.read_var_u32().unwrap(), 13);
+    ///     assert_eq!(reader.read_string().unwrap(), "Hello world!");
+    /// }
+    /// ```
+    pub fn insert_var_u32_at(&mut self, pos: usize, num: u32) -> Result<(), std::io::Error> {
+        let len = self.buf.len();
+        if pos > len {
+            return Err(
+                BinaryError::new(std::io::ErrorKind::InvalidInput, ERR_VARINT_SLOT)
+                    .at_offset(pos)
+                    .into(),
+            );
+        }
+
+        let mut prefix = [0; MAX_VAR_U64_LEN];
+        let prefix = varint::encode(num as u64, &mut prefix);
+        if !can_write!(self.buf, prefix.len()) {
+            return Err(BinaryError::new(std::io::ErrorKind::OutOfMemory, ERR_EOM)
+                .needing(prefix.len() - self.buf.remaining_mut())
+                .overflowing()
+                .into());
+        }
+        self.buf.put_slice(prefix);
+        self.buf.copy_within(pos..len, pos + prefix.len());
+        self.buf[pos..pos + prefix.len()].copy_from_slice(prefix);
+        track!(self, written, prefix.len(), true);
+        Ok(())
+    }
+
+    /// Write a string to the buffer
+    /// The string is written as a var_u32 length followed by the bytes of the string.
+    /// Uses <https://protobuf.dev/programming-guides/encoding/#length-types> for length encoding
+    ///
+    /// The length and the string are written at once, so nothing is written if the buffer can not hold both.
+    pub fn write_string(&mut self, string: &str) -> Result<(), std::io::Error> {
+        self.write_string_varu32(string)
+    }
+
+    /// Writes a string prefixed with its length as a `var_u32`, which is the canonical encoding of `write_string`.
+    pub fn write_string_varu32(&mut self, string: &str) -> Result<(), std::io::Error> {
+        // https://protobuf.dev/programming-guides/encoding/#length-types
+        let len = u32::try_from(string.len()).map_err(|_| string_too_long())?;
+        let mut prefix = [0; MAX_VAR_U64_LEN];
+        self.write_prefixed(
+            varint::encode(len as u64, &mut prefix),
+            true,
+            string.as_bytes(),
+        )
+    }
+
+    /// Writes a string prefixed with its length as a big endian `u16`.
+    pub fn write_string_u16be(&mut self, string: &str) -> Result<(), std::io::Error> {
+        let len = u16::try_from(string.len()).map_err(|_| string_too_long())?;
+        self.write_prefixed(&len.to_be_bytes(), false, string.as_bytes())
+    }
+
+    /// Writes a string prefixed with its length as a little endian `u32`.
+    pub fn write_string_u32le(&mut self, string: &str) -> Result<(), std::io::Error> {
+        let len = u32::try_from(string.len()).map_err(|_| string_too_long())?;
+        self.write_prefixed(&len.to_le_bytes(), false, string.as_bytes())
+    }
+
+    /// Writes the encoded length `prefix`, followed by `bytes`, only if both fit within the buffer,
+    /// so a failed write never leaves a dangling length prefix behind. `varint` is whether the prefix is a var-int.
+    #[cfg_attr(not(feature = "stats"), allow(unused_variables))]
+    fn write_prefixed(
+        &mut self,
+        prefix: &[u8],
+        varint: bool,
+        bytes: &[u8],
+    ) -> Result<(), std::io::Error> {
+        let size = prefix.len().saturating_add(bytes.len());
+        if !can_write!(self.buf, size) {
+            return Err(BinaryError::new(std::io::ErrorKind::OutOfMemory, ERR_EOM)
+                .needing(size - self.buf.remaining_mut())
+                .overflowing()
+                .into());
+        }
+        self.buf.put_slice(prefix);
+        self.buf.put_slice(bytes);
+        track!(self, written, prefix.len(), varint);
+        track!(self, written, bytes.len(), false);
+        Ok(())
+    }
+
+    /// Writes an `Option` to the buffer. The option must implement the `Writer` trait.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use binary_util::io::ByteWriter;
+    /// use binary_util::interfaces::Writer;
+    ///
+    /// pub struct HelloWorld {
+    ///     pub magic: u32
+    /// }
+    ///
+    /// impl Writer for HelloWorld {
+    ///     fn write(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+    ///         buf.write_u32(self.magic)?;
+    ///         return Ok(());
+    ///     }
+    /// }
+    ///
+    /// fn main() {
+    ///     let hello = HelloWorld { magic: 0xCAFEBABE };
+    ///     let mut buf = hello.write_to_bytes().unwrap();
+    ///
+    ///     println!("Hello World: {:?}", buf);
+    /// }
+    /// ```
+    pub fn write_option(&mut self, option: &Option<impl Writer>) -> Result<(), std::io::Error> {
+        if let Some(option) = option {
+            self.write_bool(true)?;
+            option.write(self)?;
+        } else {
+            self.write_bool(false)?;
+        }
+        return Ok(());
+    }
+
+    /// Writes an `Option` to the buffer, using `f` to write the value if it is present.
+    /// This is useful for types that do not implement the `Writer` trait, such as foreign types.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use binary_util::io::ByteWriter;
+    /// use std::time::Duration;
+    ///
+    /// fn main() {
+    ///     let mut buf = ByteWriter::new();
+    ///     buf.write_option_with(&Some(Duration::from_secs(1)), |w, timeout| {
+    ///         w.write_u32(timeout.as_millis() as u32)
+    ///     })
+    ///     .unwrap();
+    ///     assert_eq!(buf.as_slice(), &[0x01, 0x00, 0x00, 0x03, 0xe8]);
+    /// }
+    /// ```
+    pub fn write_option_with<T>(
+        &mut self,
+        option: &Option<T>,
+        f: impl FnOnce(&mut ByteWriter, &T) -> Result<(), std::io::Error>,
+    ) -> Result<(), std::io::Error> {
+        if let Some(value) = option {
+            self.write_bool(true)?;
+            f(self, value)
+        } else {
+            self.write_bool(false)
+        }
+    }
+
+    /// Writes a size-prefixed slice of bytes to the buffer. The slice is prefixed with a var_u32 length.
+    /// Like `write_string`, nothing is written if the buffer can not hold both the prefix and the slice.
+    pub fn write_slice(&mut self, slice: &[u8]) -> Result<(), std::io::Error> {
+        let len = slice_len(slice.len())?;
+        let mut prefix = [0; MAX_VAR_U64_LEN];
+        self.write_prefixed(varint::encode(len as u64, &mut prefix), true, slice)
+    }
+
+    /// Writes a slice of bytes to the buffer
+    /// This is not the same as a size-prefixed slice, this is just a raw slice of bytes.
+    ///
+    /// For automatically size-prefixed slices, use `write_slice`.
+    pub fn write(&mut self, buf: &[u8]) -> Result<(), std::io::Error> {
+        if can_write!(self.buf, buf.len()) {
+            self.buf.put_slice(buf);
+            track!(self, written, buf.len(), false);
+            return Ok(());
+        } else {
+            return Err(BinaryError::new(std::io::ErrorKind::OutOfMemory, ERR_EOM)
+                .needing(buf.len() - self.buf.remaining_mut())
+                .overflowing()
+                .into());
+        }
+    }
+
+    /// Writes `T` to the buffer. `T` must implement the `Writer` trait.
+    /// This is the same as calling `T.write(self)`.
+    /// ```rust
+    /// use binary_util::interfaces::{Reader, Writer};
+    /// use binary_util::io::{ByteReader, ByteWriter};
+    ///
+    /// pub struct HelloPacket {
+    ///     pub name: String,
+    ///     pub age: u8,
+    ///     pub is_cool: bool,
+    ///     pub friends: Vec<String>,
+    /// }
+    ///
+    /// impl Reader<HelloPacket> for HelloPacket {
+    ///     fn read(buf: &mut ByteReader) -> std::io::Result<Self> {
+    ///         Ok(Self {
+    ///             name: buf.read_string()?,
+    ///             age: buf.read_u8()?,
+    ///             is_cool: buf.read_bool()?,
+    ///             friends: Vec::<String>::read(buf)?
+    ///         })
+    ///     }
+    /// }
+    ///
+    /// impl Writer for HelloPacket {
+    ///     fn write(&self, buf: &mut ByteWriter) -> std::io::Result<()> {
+    ///         buf.write_string(&self.name);
+    ///         buf.write_u8(self.age);
+    ///         buf.write_bool(self.is_cool);
+    ///         self.friends.write(buf)?;
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// fn main() {
+    ///     let mut buf = ByteWriter::new();
+    ///     let packet = HelloPacket {
+    ///         name: "John".to_string(),
+    ///         age: 18,
+    ///         is_cool: true,
+    ///         friends: vec!["Bob".to_string(), "Joe".to_string()]
+    ///     };
+    ///     buf.write_type(&packet).unwrap();
+    /// }
+    /// ```
+    pub fn write_type<T: Writer>(&mut self, t: &T) -> Result<(), std::io::Error> {
+        #[cfg(feature = "stats")]
+        let len = self.buf.len();
+        t.write(self)?;
+        track!(self, frame, self.buf.len() - len);
+        Ok(())
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        &self.buf
+    }
+
+    /// Returns the written bytes as an `IoSlice`, for use with `write_vectored` or `send_vectored`.
+    /// To send multiple segments at once, use `ChainedWriter`.
+    pub fn as_io_slices(&self) -> [IoSlice<'_>; 1] {
+        [IoSlice::new(self.as_slice())]
+    }
+
+    pub fn clear(&mut self) {
+        self.buf.clear();
+        self.decay();
+    }
+
+    /// Returns the amount of bytes written.
+    pub fn len(&self) -> usize {
+        self.buf.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buf.len() == 0
+    }
+
+    /// Discards every byte written after the first `len`, IE: to roll back an optional section
+    /// which turned out not to be needed. This has no effect if fewer than `len` bytes are written.
+    ///
+    /// The capacity of the writer is kept, and its `stats` still count the discarded bytes.
+    ///
+    /// ```rust
+    /// use binary_util::io::ByteWriter;
+    ///
+    /// fn main() {
+    ///     let mut writer = ByteWriter::new();
+    ///     writer.write_u8(1).unwrap();
+    ///
+    ///     let start = writer.len();
+    ///     writer.write_string("optional").unwrap();
+    ///     writer.truncate(start);
+    ///     assert_eq!(writer.as_slice(), &[1]);
+    /// }
+    /// ```
+    pub fn truncate(&mut self, len: usize) {
+        self.buf.truncate(len);
+    }
+
+    /// Returns the written bytes, leaving the writer empty so it can be reused for the next packet.
+    ///
+    /// Unlike converting the writer into `Bytes`, the writer is kept, along with its spare capacity.
+    /// Once every `Bytes` taken from the writer has been dropped, the writer reclaims their capacity
+    /// when it next grows, so a "serialize, send, reuse" loop does not allocate for every packet.
+    /// Writers which are still inline copy their bytes instead, see `ByteWriter::inline`.
+    /// Marks taken before this call should not be used afterwards.
+    ///
+    /// ```rust
+    /// use binary_util::io::ByteWriter;
+    ///
+    /// fn main() {
+    ///     let mut writer = ByteWriter::new();
+    ///     writer.write_u16(300).unwrap();
+    ///     let packet = writer.take_bytes();
+    ///     assert_eq!(&packet[..], &[1, 44]);
+    ///
+    ///     writer.write_u8(7).unwrap();
+    ///     assert_eq!(writer.as_slice(), &[7]);
+    /// }
+    /// ```
+    pub fn take_bytes(&mut self) -> Bytes {
+        let bytes = self.buf.take();
+        self.decay();
+        bytes
+    }
+
+    /// Returns a mark of the current write position, which can later be passed
+    /// to `bytes_written_since` to compute the size of a section.
+    pub fn mark(&self) -> WriteMark {
+        WriteMark(self.buf.len())
+    }
+
+    /// Returns the amount of bytes written since `mark` was taken.
+    /// If the writer was cleared after the mark was taken, this returns `0`,
+    /// or panics in instrumented builds.
+    ///
+    /// ```rust
+    /// use binary_util::io::ByteWriter;
+    ///
+    /// fn main() {
+    ///     let mut writer = ByteWriter::new();
+    ///     writer.write_u8(0).unwrap();
+    ///
+    ///     let mark = writer.mark();
+    ///     writer.write_string("Hello").unwrap();
+    ///     assert_eq!(writer.bytes_written_since(mark), 6);
+    /// }
+    /// ```
+    pub fn bytes_written_since(&self, mark: WriteMark) -> usize {
+        self.check_mark(mark);
+        self.buf.len().saturating_sub(mark.0)
+    }
+
+    /// Returns the bytes written between the `start` and `end` marks, IE: the bytes of a single field.
+    /// If the writer was cleared after the marks were taken, this returns an empty slice,
+    /// or panics in instrumented builds.
+    ///
+    /// ```rust
+    /// use binary_util::io::ByteWriter;
+    ///
+    /// fn main() {
+    ///     let mut writer = ByteWriter::new();
+    ///     writer.write_u8(0).unwrap();
+    ///
+    ///     let start = writer.mark();
+    ///     writer.write_u16(300).unwrap();
+    ///     let end = writer.mark();
+    ///     writer.write_u8(0).unwrap();
+    ///     assert_eq!(writer.written_between(start, end), &[1, 44]);
+    /// }
+    /// ```
+    pub fn written_between(&self, start: WriteMark, end: WriteMark) -> &[u8] {
+        self.check_mark(start);
+        self.check_mark(end);
+        misuse!(
+            start.0 > end.0,
+            "start mark {} is after end mark {}",
+            start.0,
+            end.0
+        );
+        self.buf.get(start.0..end.0).unwrap_or(&[])
+    }
+
+    /// Panics in instrumented builds if `mark` is past the end of the writer,
+    /// IE: it was taken before the writer was cleared.
+    #[inline]
+    fn check_mark(&self, _mark: WriteMark) {
+        misuse!(
+            _mark.0 > self.buf.len(),
+            "mark at {} was taken before the writer was cleared, only {} bytes are written",
+            _mark.0,
+            self.buf.len()
+        );
+    }
+
+    /// Calls `f` with this writer, returning the amount of bytes it wrote.
+    ///
+    /// ```rust
+    /// use binary_util::io::ByteWriter;
+    ///
+    /// fn main() {
+    ///     let mut writer = ByteWriter::new();
+    ///     assert_eq!(writer.measure(|w| w.write_var_u32(300)).unwrap(), 2);
+    ///     assert_eq!(writer.measure(|w| w.write_u64(0)).unwrap(), 8);
+    /// }
+    /// ```
+    pub fn measure(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<(), std::io::Error>,
+    ) -> Result<usize, std::io::Error> {
+        let mark = self.mark();
+        f(self)?;
+        Ok(self.bytes_written_since(mark))
+    }
+
+    /// Calls `f` with this writer, failing if it writes more than `max` bytes.
+    /// If the limit is exceeded, everything `f` wrote is discarded, leaving the writer as it was before the call.
+    ///
+    /// ```rust
+    /// use binary_util::io::ByteWriter;
+    ///
+    /// fn main() {
+    ///     let mut writer = ByteWriter::new();
+    ///     assert!(writer.write_capped(4, |w| w.write_string("Hi")).is_ok());
+    ///     assert!(writer.write_capped(4, |w| w.write_string("Hello")).is_err());
+    ///     assert_eq!(writer.as_slice(), &[2, b'H', b'i']);
+    /// }
+    /// ```
+    pub fn write_capped(
+        &mut self,
+        max: usize,
+        f: impl FnOnce(&mut Self) -> Result<(), std::io::Error>,
+    ) -> Result<usize, std::io::Error> {
+        let mark = self.mark();
+        let written = self.measure(f)?;
+        if written > max {
+            self.buf.truncate(mark.0);
+            return Err(Error::new(std::io::ErrorKind::InvalidData, ERR_WRITE_CAP));
+        }
+        Ok(written)
+    }
+
+    /// Writes every slice in `bufs` to the buffer, IE: the segments of a `ChainedWriter`.
+    /// Nothing is written if the buffer can not hold every slice.
+    ///
+    /// ```rust
+    /// use binary_util::io::ByteWriter;
+    /// use std::io::IoSlice;
+    ///
+    /// fn main() {
+    ///     let mut writer = ByteWriter::new();
+    ///     writer
+    ///         .write_all_vectored(&[IoSlice::new(&[1, 2]), IoSlice::new(&[3])])
+    ///         .unwrap();
+    ///     assert_eq!(writer.as_slice(), &[1, 2, 3]);
+    /// }
+    /// ```
+    pub fn write_all_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<(), std::io::Error> {
+        let len = bufs
+            .iter()
+            .fold(0usize, |len, buf| len.saturating_add(buf.len()));
+        if !can_write!(self.buf, len) {
+            return Err(BinaryError::new(std::io::ErrorKind::OutOfMemory, ERR_EOM)
+                .needing(len - self.buf.remaining_mut())
+                .overflowing()
+                .into());
+        }
+        self.buf.reserve(len);
+        for buf in bufs {
+            self.buf.put_slice(buf);
+        }
+        track!(self, written, len, false);
+        Ok(())
+    }
+}
+
+/// `ByteWriter` implements `BufMut`, so encoders of other crates, IE: `prost` or `flate2`, can write
+/// directly into the writer, rather than into an intermediate `Vec` which is then copied.
+///
+/// ```rust
+/// use binary_util::io::ByteWriter;
+/// use bytes::BufMut;
+/// use std::io::Write;
+///
+/// fn main() {
+///     let mut writer = ByteWriter::new();
+///     writer.write_u8(2).unwrap();
+///     (&mut writer).writer().write_all(b"hi").unwrap();
+///     assert_eq!(writer.as_slice(), &[2, b'h', b'i']);
+/// }
+/// ```
+unsafe impl BufMut for ByteWriter {
+    fn remaining_mut(&self) -> usize {
+        self.buf.remaining_mut()
+    }
+
+    unsafe fn advance_mut(&mut self, cnt: usize) {
+        // SAFETY: upheld by the caller.
+        unsafe { self.buf.advance_mut(cnt) };
+        track!(self, written, cnt, false);
+    }
+
+    fn chunk_mut(&mut self) -> &mut UninitSlice {
+        self.buf.chunk_mut()
+    }
+
+    fn put_slice(&mut self, src: &[u8]) {
+        self.buf.put_slice(src);
+        track!(self, written, src.len(), false);
+    }
+}
+
+/// The largest threshold of an inline `ByteWriter`, see `ByteWriter::inline`.
+#[cfg(feature = "inline")]
+pub const INLINE_CAPACITY: usize = 64;
+
+/// The storage of a `ByteWriter`.
+///
+/// Inline storage holds the bytes in a fixed array, and moves them to a `BytesMut` once
+/// a write would exceed `limit`. Once moved, the storage does not return to being inline.
+#[derive(Debug, Clone)]
+pub(crate) enum WriteBuf {
+    #[cfg(feature = "inline")]
+    Inline {
+        data: [u8; INLINE_CAPACITY],
+        len: usize,
+        limit: usize,
+    },
+    Heap(BytesMut),
+}
+
+impl WriteBuf {
+    /// Moves inline bytes to a `BytesMut`, with room for at least `additional` more bytes.
+    #[cfg(feature = "inline")]
+    fn spill(&mut self, additional: usize) -> &mut BytesMut {
+        if let WriteBuf::Inline { data, len, limit } = self {
+            let mut heap = BytesMut::with_capacity((*len + additional).max(*limit * 2));
+            heap.extend_from_slice(&data[..*len]);
+            *self = WriteBuf::Heap(heap);
+        }
+        match self {
+            WriteBuf::Heap(heap) => heap,
+            WriteBuf::Inline { .. } => unreachable!(),
+        }
+    }
+
+    pub(crate) fn reserve(&mut self, additional: usize) {
+        match self {
+            #[cfg(feature = "inline")]
+            WriteBuf::Inline { len, limit, .. } if *len + additional <= *limit => {}
+            #[cfg(feature = "inline")]
+            WriteBuf::Inline { .. } => {
+                self.spill(additional);
+            }
+            WriteBuf::Heap(heap) => heap.reserve(additional),
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    pub(crate) fn truncate(&mut self, new_len: usize) {
+        match self {
+            #[cfg(feature = "inline")]
+            WriteBuf::Inline { len, .. } => *len = new_len.min(*len),
+            WriteBuf::Heap(heap) => heap.truncate(new_len),
+        }
+    }
+
+    pub(crate) fn freeze(self) -> Bytes {
+        match self {
+            #[cfg(feature = "inline")]
+            WriteBuf::Inline { data, len, .. } => Bytes::copy_from_slice(&data[..len]),
+            WriteBuf::Heap(heap) => heap.freeze(),
+        }
+    }
+
+    pub(crate) fn into_bytes_mut(self) -> BytesMut {
+        match self {
+            #[cfg(feature = "inline")]
+            WriteBuf::Inline { data, len, .. } => BytesMut::from(&data[..len]),
+            WriteBuf::Heap(heap) => heap,
+        }
+    }
+
+    /// Discards the first `cnt` written bytes, IE: the unused part of a slot reserved for a length prefix.
+    pub(crate) fn advance(&mut self, cnt: usize) {
+        match self {
+            #[cfg(feature = "inline")]
+            WriteBuf::Inline { data, len, .. } => {
+                data.copy_within(cnt..*len, 0);
+                *len -= cnt;
+            }
+            WriteBuf::Heap(heap) => heap.advance(cnt),
+        }
+    }
+
+    /// Returns the written bytes, leaving the storage empty.
+    pub(crate) fn take(&mut self) -> Bytes {
+        match self {
+            #[cfg(feature = "inline")]
+            WriteBuf::Inline { data, len, .. } => {
+                let bytes = Bytes::copy_from_slice(&data[..*len]);
+                *len = 0;
+                bytes
+            }
+            WriteBuf::Heap(heap) => heap.split().freeze(),
+        }
+    }
+}
+
+impl Deref for WriteBuf {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            #[cfg(feature = "inline")]
+            WriteBuf::Inline { data, len, .. } => &data[..*len],
+            WriteBuf::Heap(heap) => heap,
+        }
+    }
+}
+
+impl DerefMut for WriteBuf {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        match self {
+            #[cfg(feature = "inline")]
+            WriteBuf::Inline { data, len, .. } => &mut data[..*len],
+            WriteBuf::Heap(heap) => heap,
+        }
+    }
+}
+
+// SAFETY: `chunk_mut` only returns memory past the written bytes, and `advance_mut` only
+// marks bytes within the last returned chunk as written, as required by `BufMut`.
+unsafe impl BufMut for WriteBuf {
+    fn remaining_mut(&self) -> usize {
+        match self {
+            #[cfg(feature = "inline")]
+            WriteBuf::Inline { len, .. } => usize::MAX - len,
+            WriteBuf::Heap(heap) => heap.remaining_mut(),
+        }
+    }
+
+    unsafe fn advance_mut(&mut self, cnt: usize) {
+        match self {
+            #[cfg(feature = "inline")]
+            WriteBuf::Inline { len, limit, .. } => {
+                assert!(
+                    *len + cnt <= *limit,
+                    "advance_mut past the end of the chunk"
+                );
+                *len += cnt;
+            }
+            // SAFETY: upheld by the caller.
+            WriteBuf::Heap(heap) => unsafe { heap.advance_mut(cnt) },
+        }
+    }
+
+    fn chunk_mut(&mut self) -> &mut UninitSlice {
+        match self {
+            #[cfg(feature = "inline")]
+            WriteBuf::Inline { len, limit, .. } if len == limit => self.spill(1).chunk_mut(),
+            #[cfg(feature = "inline")]
+            WriteBuf::Inline {
+                data, len, limit, ..
+            } => UninitSlice::new(&mut data[*len..*limit]),
+            WriteBuf::Heap(heap) => heap.chunk_mut(),
+        }
+    }
+
+    fn put_slice(&mut self, src: &[u8]) {
+        self.reserve(src.len());
+        match self {
+            #[cfg(feature = "inline")]
+            WriteBuf::Inline { data, len, .. } => {
+                data[*len..*len + src.len()].copy_from_slice(src);
+                *len += src.len();
+            }
+            WriteBuf::Heap(heap) => heap.put_slice(src),
+        }
+    }
+}
+
+/// A position within a `ByteWriter`, created with `ByteWriter::mark`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WriteMark(usize);
+
+impl WriteMark {
+    /// Returns the position of the mark within the writer.
+    pub fn position(&self) -> usize {
+        self.0
+    }
+}
+
+/// ChainedWriter holds multiple independently encoded segments, such as a header and a body,
+/// which can be sent with a single vectored write without concatenating them first.
+///
+/// ## Example
+/// ```rust
+/// use binary_util::io::{ByteWriter, ChainedWriter};
+/// use std::io::Write;
+///
+/// fn main() {
+///     let mut body = ByteWriter::new();
+///     body.write_string("Hello!").unwrap();
+///
+///     let mut header = ByteWriter::new();
+///     header.write_u16(body.as_slice().len() as u16).unwrap();
+///
+///     let mut chain = ChainedWriter::new();
+///     chain.push(header);
+///     chain.push(body);
+///     assert_eq!(chain.len(), 9);
+///
+///     let mut out: Vec<u8> = Vec::new();
+///     out.write_vectored(&chain.as_io_slices()).unwrap();
+///     assert_eq!(out, b"\x00\x07\x06Hello!");
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ChainedWriter {
+    segments: Vec<Bytes>,
+}
+
+impl ChainedWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a segment to the end of the chain.
+    pub fn push(&mut self, segment: impl Into<Bytes>) {
+        self.segments.push(segment.into());
+    }
+
+    /// Encodes `T` and appends it to the end of the chain as a new segment.
+    pub fn push_type<T: Writer>(&mut self, t: &T) -> Result<(), std::io::Error> {
+        self.push(t.write_to_bytes()?);
+        Ok(())
+    }
+
+    /// Returns the segments of the chain, in order.
+    pub fn segments(&self) -> &[Bytes] {
+        &self.segments
+    }
+
+    /// Returns the total amount of bytes across all segments.
+    pub fn len(&self) -> usize {
+        self.segments.iter().map(|s| s.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns every segment as an `IoSlice`, for use with `write_vectored` or `send_vectored`.
+    pub fn as_io_slices(&self) -> Vec<IoSlice<'_>> {
+        self.segments.iter().map(|s| IoSlice::new(s)).collect()
+    }
+
+    /// Concatenates every segment into a single buffer.
+    pub fn to_bytes(&self) -> Bytes {
+        let mut buf = BytesMut::with_capacity(self.len());
+        for segment in self.segments.iter() {
+            buf.put_slice(segment);
+        }
+        buf.freeze()
+    }
+}
+
+/// Returns the `var_u32` length prefix of a slice of `len` bytes, failing if the prefix can not store it.
+pub(crate) fn slice_len(len: usize) -> Result<u32, std::io::Error> {
+    u32::try_from(len).map_err(|_| {
+        BinaryError::new(std::io::ErrorKind::InvalidInput, ERR_SLICE_TOO_LONG)
+            .overflowing()
+            .into()
+    })
+}
+
+/// The error returned when a string is longer than its length prefix can store.
+fn string_too_long() -> std::io::Error {
+    BinaryError::new(std::io::ErrorKind::InvalidInput, ERR_STRING_TOO_LONG)
+        .overflowing()
+        .into()
+}
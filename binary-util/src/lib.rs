@@ -55,6 +55,20 @@
 //! binary_util = { version = "0.3.4", default-features = false }
 //! ```
 //!
+//! # Instrumented Builds
+//! Enabling the `instrumented` feature makes misuse of the io types panic with a description of
+//! the mistake in debug builds, rather than silently returning an empty or clamped result:
+//! * Using a `WriteMark` after the writer was cleared or taken from, or passing marks out of order.
+//! * Requesting an inline threshold larger than `INLINE_CAPACITY`, with the `inline` feature.
+//! * Restoring, or reading since, a `ReadCheckpoint` taken from a different reader.
+//! * Restoring a `ReadCheckpoint` taken before `ByteReader::split_at_current`.
+//!
+//! Release builds are unaffected and remain panic-free, so the feature can be left enabled.
+//! ```toml
+//! [dev-dependencies]
+//! binary_util = { version = "0.3.4", features = ["instrumented"] }
+//! ```
+//!
 //! # Binary IO
 //! The [`io`] module provides a way to contingiously write and read binary data with the garauntees of being panic-free.
 //! This module provides two structs, [`ByteReader`] and [`ByteWriter`], which are both wrappers
@@ -330,6 +344,19 @@
 //! [`Reader`]: crate::interfaces::Reader
 //! [`Writer`]: crate::interfaces::Writer
 //!
+/// Provides an iterator over the UDP and TCP payloads of a pcap file, enabled with the `pcap` feature.
+/// See [`capture::Capture`].
+#[cfg(feature = "pcap")]
+pub mod capture;
+/// Provides the checksum algorithms used by the `#[crc_of]` attribute.
+/// See [`checksum::crc32`].
+pub mod checksum;
+/// Provides utilities for comparing encoded buffers, optionally labelling mismatches with field names.
+/// See [`diff::compare`] and [`diff::compare_type`].
+pub mod diff;
+/// Provides a minimal sans-io packet loop, which decodes packets from bytes fed to it and encodes packets to transmit.
+/// See [`driver::Driver`].
+pub mod driver;
 /// Provides a panic-free way to read and write binary data.
 /// All of the methods within this module follow the protobuf specification at <https://protobuf.dev/programming-guides/encoding/>.
 ///
@@ -361,11 +388,32 @@ pub use binary_util_derive::*;
 /// }
 /// ```
 pub mod io;
+/// Provides pools of reusable write buffers and decoded values, and the policy releasing the memory retained by buffers.
+/// See [`pool::BytePool`], [`pool::ObjectPool`] and [`pool::DecayPolicy`].
 pub mod pool;
+/// Re-exports the traits, derive macros, reader, writer and wrapper types used by most packet modules,
+/// so they can be imported with a single `use binary_util::prelude::*;`.
+pub mod prelude;
+/// Provides a description of the wire layout of derived types, enabled with `#[binary_io(schema)]`.
+/// See [`schema::Schema`].
+pub mod schema;
+/// Provides counters of the bytes and var-ints read and written, enabled with the `stats` feature.
+/// See [`stats::Stats`].
+#[cfg(feature = "stats")]
+pub mod stats;
+/// Provides a way to build randomized, but wire-valid instances of types for tests and fuzzers.
+/// See [`testing::Mock`] and the `MockBinary` derive macro.
+pub mod testing;
 /// This module contains all of the types that are used within the `binary_util` crate.
 /// For example, Sometimes you may need to use a `u24` or `varu32` type, on structs,
 /// and this module provides those types.
 pub mod types;
+/// Provides helpers for handling sensitive data read from or written to the wire.
+/// See [`util::ct_eq`] and [`util::Secret`].
+pub mod util;
+/// Provides the var-int encoding used by `ByteReader` and `ByteWriter`, including a
+/// decoder which can be fed one byte at a time. See [`varint::Decoder`].
+pub mod varint;
 /// This is a legacy module that will be removed in the future.
 /// This module has been replaced in favor of `std::io::Error`.
 ///
@@ -1,7 +1,360 @@
-/// Byte pools a specialized structure that allows you to reuse byte slices
-/// instead of allocating new ones.
+use bytes::BytesMut;
+
+use crate::interfaces::Reader;
+use crate::io::{ByteReader, ByteWriter};
+
+/// A pool of the buffers `ByteWriter`s write to, so packets can be written without allocating a new buffer each time.
 ///
-/// When an byteslice is returned to the pool, it is immediately reused.
-/// Do not use this if you are using a `BinaryStream` in multiple threads.
-/// This will cause latency issues.
-pub struct BytePool {}
+/// Writers are taken from the pool with `get`, and their buffer is returned with `put`, to be reused by the next `get`.
+/// The pool is borrowed mutably to do so, so each thread, or connection, keeps its own pool.
+///
+/// Buffers keep the capacity they grew to when returned to the pool, so a pool which once held a huge packet
+/// would otherwise keep that memory forever. `total_reserved_bytes` reports how much memory the pool retains,
+/// which `shrink_to_fit` releases, or a `DecayPolicy` releases automatically.
+///
+/// ```rust
+/// use binary_util::pool::{BytePool, DecayPolicy};
+///
+/// fn main() {
+///     let mut pool = BytePool::new(1024).with_decay_policy(DecayPolicy::new(4096, 0));
+///     let mut writer = pool.get();
+///     writer.write(&[0; 10_000]).unwrap();
+///     pool.put(writer);
+///
+///     // the huge buffer is released, rather than being kept by the pool.
+///     assert!(pool.total_reserved_bytes() <= 4096);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct BytePool {
+    buffers: Vec<BytesMut>,
+    buffer_capacity: usize,
+    decay: Option<DecayPolicy>,
+    strikes: u32,
+}
+
+impl BytePool {
+    /// Creates an empty pool, which allocates buffers of `buffer_capacity` bytes.
+    pub fn new(buffer_capacity: usize) -> Self {
+        Self {
+            buffers: Vec::new(),
+            buffer_capacity,
+            decay: None,
+            strikes: 0,
+        }
+    }
+
+    /// Shrinks the pool with `shrink_to_fit` whenever `policy` is exceeded, see `DecayPolicy`.
+    /// If the pool still retains more than the policy allows afterwards, buffers are released until it does not.
+    pub fn with_decay_policy(mut self, policy: DecayPolicy) -> Self {
+        self.decay = Some(policy);
+        self
+    }
+
+    /// Returns an empty writer, reusing a buffer from the pool if there is one.
+    pub fn get(&mut self) -> ByteWriter {
+        match self.buffers.pop() {
+            Some(buf) => ByteWriter::from(buf),
+            None => {
+                #[cfg_attr(not(feature = "stats"), allow(unused_mut))]
+                let mut writer = ByteWriter::from(BytesMut::with_capacity(self.buffer_capacity));
+                #[cfg(feature = "stats")]
+                writer.stats.pool_allocation();
+                writer
+            }
+        }
+    }
+
+    /// Returns the buffer of `writer` to the pool, discarding its contents.
+    /// Writers which are still inline have no buffer to return.
+    pub fn put(&mut self, writer: ByteWriter) {
+        #[cfg(feature = "inline")]
+        let owns_buffer = !writer.is_inline();
+        #[cfg(not(feature = "inline"))]
+        let owns_buffer = true;
+        if owns_buffer {
+            let mut buf: BytesMut = writer.into();
+            buf.clear();
+            self.buffers.push(buf);
+        }
+        if let Some(policy) = self.decay {
+            if policy.exceeded(self.total_reserved_bytes(), &mut self.strikes) {
+                self.shrink_to_fit();
+                let mut retained = self.total_reserved_bytes();
+                while retained > policy.max_retained {
+                    let Some(buf) = self.buffers.pop() else {
+                        break;
+                    };
+                    retained -= buf.capacity();
+                }
+            }
+        }
+    }
+
+    /// Returns the amount of buffers in the pool.
+    pub fn len(&self) -> usize {
+        self.buffers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buffers.is_empty()
+    }
+
+    /// Returns the amount of bytes reserved by the buffers in the pool.
+    pub fn total_reserved_bytes(&self) -> usize {
+        self.buffers.iter().map(|buf| buf.capacity()).sum()
+    }
+
+    /// Releases every buffer in the pool which grew past the capacity the pool allocates buffers with.
+    pub fn shrink_to_fit(&mut self) {
+        let capacity = self.buffer_capacity;
+        self.buffers.retain(|buf| buf.capacity() <= capacity);
+    }
+}
+
+/// A writer taken from a `BytePool`, which is returned to the pool when dropped, see `Writer::write_pooled`.
+///
+/// The handle borrows the pool, so it is meant to be dropped once its bytes have been sent.
+/// Use `detach` to keep the writer instead.
+#[derive(Debug)]
+pub struct PooledBytes<'a> {
+    writer: Option<ByteWriter>,
+    pool: &'a mut BytePool,
+}
+
+impl<'a> PooledBytes<'a> {
+    pub(crate) fn new(writer: ByteWriter, pool: &'a mut BytePool) -> Self {
+        Self {
+            writer: Some(writer),
+            pool,
+        }
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        self.writer().as_slice()
+    }
+
+    /// Returns the writer, which is not returned to the pool.
+    pub fn detach(mut self) -> ByteWriter {
+        self.writer.take().expect("writer is only taken once")
+    }
+
+    fn writer(&self) -> &ByteWriter {
+        self.writer.as_ref().expect("writer is only taken once")
+    }
+}
+
+impl std::ops::Deref for PooledBytes<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl AsRef<[u8]> for PooledBytes<'_> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl Drop for PooledBytes<'_> {
+    fn drop(&mut self) {
+        if let Some(writer) = self.writer.take() {
+            self.pool.put(writer);
+        }
+    }
+}
+
+/// A pool of reusable values, IE: decoded packets, so their allocations are kept from one packet to the next.
+///
+/// Values are returned to the pool when their `Pooled` handle is dropped, and keep their contents.
+/// `read` overwrites them with `Reader::read_into`, which reuses the capacity of their `Vec`s,
+/// while `with_reset` sets a function that clears values as they are returned.
+///
+/// ```rust
+/// use binary_util::interfaces::{Reader, Writer};
+/// use binary_util::io::ByteReader;
+/// use binary_util::pool::ObjectPool;
+/// use binary_util::BinaryIo;
+///
+/// #[derive(BinaryIo, Default)]
+/// #[binary_io(in_place)]
+/// struct Chunk {
+///     x: i32,
+///     blocks: Vec<u16>,
+/// }
+///
+/// fn main() {
+///     let mut pool = ObjectPool::<Chunk>::new();
+///     let packet = Chunk { x: 1, blocks: vec![7; 256] }.write_to_bytes().unwrap();
+///
+///     for _ in 0..3 {
+///         let chunk = pool.read(&mut ByteReader::from(packet.as_slice())).unwrap();
+///         assert_eq!(chunk.blocks.len(), 256);
+///     }
+///     // a single chunk was allocated, and reused for every read.
+///     assert_eq!(pool.len(), 1);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ObjectPool<T> {
+    objects: Vec<T>,
+    reset: Option<fn(&mut T)>,
+}
+
+impl<T: Default> ObjectPool<T> {
+    pub fn new() -> Self {
+        Self {
+            objects: Vec::new(),
+            reset: None,
+        }
+    }
+
+    /// Calls `reset` on every value returned to the pool, IE: to clear it.
+    pub fn with_reset(mut self, reset: fn(&mut T)) -> Self {
+        self.reset = Some(reset);
+        self
+    }
+
+    /// Returns a value from the pool, or `T::default()` if the pool is empty.
+    /// The value is returned to the pool when the handle is dropped.
+    pub fn acquire(&mut self) -> Pooled<'_, T> {
+        let value = self.objects.pop().unwrap_or_default();
+        Pooled {
+            value: Some(value),
+            pool: self,
+        }
+    }
+
+    /// Reads a value with `Reader::read_into`, reusing a value from the pool if there is one.
+    /// If reading fails, the value is returned to the pool.
+    pub fn read(&mut self, buf: &mut ByteReader) -> Result<Pooled<'_, T>, std::io::Error>
+    where
+        T: Reader<T>,
+    {
+        let mut pooled = self.acquire();
+        T::read_into(&mut pooled, buf)?;
+        Ok(pooled)
+    }
+
+    /// Returns `value` to the pool.
+    pub fn put(&mut self, mut value: T) {
+        if let Some(reset) = self.reset {
+            reset(&mut value);
+        }
+        self.objects.push(value);
+    }
+}
+
+impl<T> ObjectPool<T> {
+    /// Returns the amount of values in the pool.
+    pub fn len(&self) -> usize {
+        self.objects.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+
+    /// Drops every value in the pool.
+    pub fn clear(&mut self) {
+        self.objects.clear();
+    }
+}
+
+impl<T: Default> Default for ObjectPool<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A value taken from an `ObjectPool`, which is returned to the pool when dropped.
+///
+/// Use `detach` to keep the value instead.
+#[derive(Debug)]
+pub struct Pooled<'a, T: Default> {
+    value: Option<T>,
+    pool: &'a mut ObjectPool<T>,
+}
+
+impl<T: Default> Pooled<'_, T> {
+    /// Returns the value, which is not returned to the pool.
+    pub fn detach(mut self) -> T {
+        self.value.take().expect("value is only taken once")
+    }
+}
+
+impl<T: Default> std::ops::Deref for Pooled<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.value.as_ref().expect("value is only taken once")
+    }
+}
+
+impl<T: Default> std::ops::DerefMut for Pooled<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.value.as_mut().expect("value is only taken once")
+    }
+}
+
+impl<T: Default> Drop for Pooled<'_, T> {
+    fn drop(&mut self) {
+        if let Some(value) = self.value.take() {
+            self.pool.put(value);
+        }
+    }
+}
+
+/// Decides when a `ByteWriter` or `BytePool` releases the memory it retains between packets.
+///
+/// Once the retained memory exceeds `max_retained` bytes more than `grace` times in a row,
+/// it is released with `shrink_to_fit`. The grace period avoids reallocating for every packet
+/// while a burst of large packets is being sent.
+///
+/// ```rust
+/// use binary_util::io::ByteWriter;
+/// use binary_util::pool::DecayPolicy;
+///
+/// fn main() {
+///     let mut writer = ByteWriter::new().with_decay_policy(DecayPolicy::new(1024, 1));
+///     writer.write(&[0; 4096]).unwrap();
+///
+///     writer.clear();
+///     assert!(writer.capacity() >= 4096);
+///     // the capacity exceeded the policy twice in a row.
+///     writer.clear();
+///     assert_eq!(writer.capacity(), 0);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecayPolicy {
+    /// The amount of bytes which may be retained without being released.
+    pub max_retained: usize,
+    /// The amount of times in a row `max_retained` may be exceeded before the memory is released.
+    pub grace: u32,
+}
+
+impl DecayPolicy {
+    pub const fn new(max_retained: usize, grace: u32) -> Self {
+        Self {
+            max_retained,
+            grace,
+        }
+    }
+
+    /// Returns whether `retained` bytes should be released, counting the times in a row it was exceeded in `strikes`.
+    pub(crate) fn exceeded(&self, retained: usize, strikes: &mut u32) -> bool {
+        if retained <= self.max_retained {
+            *strikes = 0;
+            return false;
+        }
+        *strikes += 1;
+        if *strikes > self.grace {
+            *strikes = 0;
+            return true;
+        }
+        false
+    }
+}
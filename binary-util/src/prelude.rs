@@ -0,0 +1,41 @@
+//! The items needed by most modules defining packets.
+//!
+//! ```rust
+//! use binary_util::prelude::*;
+//!
+//! #[derive(BinaryIo, Debug, PartialEq)]
+//! struct Move {
+//!     entity: varu64,
+//!     yaw: Angle8,
+//!     x: LE<f32>,
+//! }
+//!
+//! fn main() {
+//!     let packet = Move { entity: varu64(7), yaw: Angle8::new(64), x: LE(1.5) };
+//!     let bytes = packet.write_to_bytes().unwrap();
+//!     let mut buf = ByteReader::from(bytes.as_slice());
+//!     assert_eq!(Move::read(&mut buf).unwrap(), packet);
+//! }
+//! ```
+pub use crate::interfaces::{
+    BinaryDiscriminant, Counted, LengthPrefix, ReadInPlace, ReadPartial, Reader, WireDebug,
+    WireDisplay, Writer,
+};
+pub use crate::io::{ByteReader, ByteWriter};
+pub use crate::schema::Schema;
+#[cfg(feature = "tagged")]
+pub use crate::types::tagged::{Compound, Nbt, Tag};
+#[cfg(feature = "bitflags")]
+pub use crate::types::BitFlags;
+#[cfg(feature = "json")]
+pub use crate::types::Json;
+#[cfg(feature = "msgpack")]
+pub use crate::types::MsgPack;
+#[cfg(feature = "prost")]
+pub use crate::types::Proto;
+pub use crate::types::{
+    i24, i40, i48, i56, u24, u40, u48, u56, vari32, vari64, varu32, varu64, Angle16, Angle8,
+    ByteArray, Ordered, OrderedMap, Prefixed, Raw, RawVec, Reserved, Sequenced, U32Char, UnitVec24,
+    Utf16Char, Utf8Char, WriterFn, BE, LE,
+};
+pub use crate::{BinaryIo, MockBinary};
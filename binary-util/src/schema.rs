@@ -0,0 +1,99 @@
+/// A description of the wire layout of a type, implemented with `#[binary_io(schema)]`.
+///
+/// Doc comments on the type, its fields and its variants are carried into the schema,
+/// so tools like packet inspectors can show human readable descriptions.
+///
+/// ```rust
+/// use binary_util::interfaces::{Reader, Writer};
+/// use binary_util::schema::{Schema, SchemaKind};
+/// use binary_util::BinaryIo;
+///
+/// /// Sent by the client to start a session.
+/// #[derive(BinaryIo)]
+/// #[binary_io(schema)]
+/// struct Handshake {
+///     /// The protocol version of the client.
+///     protocol: u16,
+///     name: String,
+/// }
+///
+/// fn main() {
+///     let schema = Handshake::schema();
+///     assert_eq!(schema.doc, Some("Sent by the client to start a session."));
+///
+///     let SchemaKind::Struct(fields) = schema.kind else { unreachable!() };
+///     assert_eq!(fields[0].name, "protocol");
+///     assert_eq!(fields[0].ty, "u16");
+///     assert_eq!(fields[0].doc, Some("The protocol version of the client."));
+///     assert_eq!(fields[1].doc, None);
+/// }
+/// ```
+pub trait Schema {
+    fn schema() -> TypeSchema;
+}
+
+/// The schema of a struct or enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TypeSchema {
+    pub name: &'static str,
+    pub doc: Option<&'static str>,
+    pub kind: SchemaKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaKind {
+    /// The fields of the struct, in the order they are encoded. Skipped fields are not included,
+    /// however fields removed with `#[removed_in]` are, see `FieldHistory`.
+    Struct(&'static [FieldSchema]),
+    /// The variants of the enum, in the order they are declared.
+    Enum(&'static [VariantSchema]),
+}
+
+/// A single field of a struct or enum variant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldSchema {
+    /// The name of the field, or its index if the field is unnamed.
+    pub name: &'static str,
+    /// The type of the field, as it was written in the source.
+    pub ty: &'static str,
+    pub doc: Option<&'static str>,
+    pub history: FieldHistory,
+}
+
+/// The changes made to a field across protocol versions,
+/// set with `#[renamed_from]`, `#[added_in]` and `#[removed_in]`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FieldHistory {
+    /// The names the field previously had, oldest first.
+    pub renamed_from: &'static [&'static str],
+    /// The first version the field is encoded in, `None` if it has always been encoded.
+    pub added_in: Option<u32>,
+    /// The first version the field is no longer encoded in, `None` if it is still encoded.
+    pub removed_in: Option<u32>,
+}
+
+impl FieldHistory {
+    /// A field that has never changed.
+    pub const NONE: FieldHistory = FieldHistory {
+        renamed_from: &[],
+        added_in: None,
+        removed_in: None,
+    };
+
+    /// Returns whether the field is encoded in `version`.
+    pub fn is_present_in(&self, version: u32) -> bool {
+        let added = self.added_in.unwrap_or(u32::MIN);
+        match self.removed_in {
+            Some(removed) => (added..removed).contains(&version),
+            None => version >= added,
+        }
+    }
+}
+
+/// A single variant of an enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VariantSchema {
+    pub name: &'static str,
+    pub doc: Option<&'static str>,
+    pub fields: &'static [FieldSchema],
+}
@@ -0,0 +1,106 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static BYTES_READ: AtomicU64 = AtomicU64::new(0);
+static BYTES_WRITTEN: AtomicU64 = AtomicU64::new(0);
+static VARINTS_READ: AtomicU64 = AtomicU64::new(0);
+static VARINTS_WRITTEN: AtomicU64 = AtomicU64::new(0);
+static POOL_ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static MAX_FRAME_SIZE: AtomicU64 = AtomicU64::new(0);
+
+/// A snapshot of serialization counters.
+///
+/// Every `ByteReader` and `ByteWriter` keeps its own counters, which are retrieved with
+/// `ByteReader::stats` and `ByteWriter::stats`. The same counters are also summed up
+/// across the whole process, which can be retrieved with [`global`].
+///
+/// A frame is a single type read with `read_type` or written with `write_type`,
+/// including the types read and written by `Reader` and `Writer` helpers and `ByteStream`.
+///
+/// ```rust
+/// use binary_util::io::ByteWriter;
+///
+/// fn main() {
+///     let mut writer = ByteWriter::new();
+///     writer.write_var_u32(300).unwrap();
+///     writer.write_type(&String::from("Hello")).unwrap();
+///
+///     let stats = writer.stats();
+///     assert_eq!(stats.bytes_written, 8);
+///     assert_eq!(stats.varints_written, 2);
+///     assert_eq!(stats.max_frame_size, 6);
+///     assert!(binary_util::stats::global().bytes_written >= 8);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub bytes_read: u64,
+    pub bytes_written: u64,
+    pub varints_read: u64,
+    pub varints_written: u64,
+    /// The amount of buffers allocated by `BytePool`.
+    /// A writer returned by `BytePool::get` counts the buffer the pool allocated for it, if any.
+    pub pool_allocations: u64,
+    /// The size of the largest frame read or written, in bytes.
+    pub max_frame_size: u64,
+}
+
+impl Stats {
+    pub(crate) fn read(&mut self, bytes: usize, varint: bool) {
+        self.bytes_read += bytes as u64;
+        BYTES_READ.fetch_add(bytes as u64, Ordering::Relaxed);
+        if varint {
+            self.varints_read += 1;
+            VARINTS_READ.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub(crate) fn written(&mut self, bytes: usize, varint: bool) {
+        self.bytes_written += bytes as u64;
+        BYTES_WRITTEN.fetch_add(bytes as u64, Ordering::Relaxed);
+        if varint {
+            self.varints_written += 1;
+            VARINTS_WRITTEN.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub(crate) fn frame(&mut self, size: usize) {
+        self.max_frame_size = self.max_frame_size.max(size as u64);
+        record_frame(size);
+    }
+
+    pub(crate) fn pool_allocation(&mut self) {
+        self.pool_allocations += 1;
+        POOL_ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Records a frame that was not read or written through a `ByteReader` or `ByteWriter` directly.
+pub(crate) fn record_frame(size: usize) {
+    MAX_FRAME_SIZE.fetch_max(size as u64, Ordering::Relaxed);
+}
+
+/// Returns the counters summed up across every reader and writer in the process.
+pub fn global() -> Stats {
+    Stats {
+        bytes_read: BYTES_READ.load(Ordering::Relaxed),
+        bytes_written: BYTES_WRITTEN.load(Ordering::Relaxed),
+        varints_read: VARINTS_READ.load(Ordering::Relaxed),
+        varints_written: VARINTS_WRITTEN.load(Ordering::Relaxed),
+        pool_allocations: POOL_ALLOCATIONS.load(Ordering::Relaxed),
+        max_frame_size: MAX_FRAME_SIZE.load(Ordering::Relaxed),
+    }
+}
+
+/// Resets the global counters to zero.
+pub fn reset_global() {
+    for counter in [
+        &BYTES_READ,
+        &BYTES_WRITTEN,
+        &VARINTS_READ,
+        &VARINTS_WRITTEN,
+        &POOL_ALLOCATIONS,
+        &MAX_FRAME_SIZE,
+    ] {
+        counter.store(0, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,567 @@
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+
+use bytes::{Bytes, BytesMut};
+
+use crate::interfaces::{Reader, WireDebug};
+use crate::io::ByteWriter;
+use crate::schema::{Schema, SchemaKind};
+
+use crate::types::{
+    i24, i40, i48, i56, u24, u40, u48, u56, vari32, vari64, varu32, varu64, Angle16, Angle8,
+    ByteArray, Ordered, OrderedMap, Prefixed, Raw, Reserved, Sequenced, U32Char, UnitVec24,
+    Utf16Char, Utf8Char, BE, LE,
+};
+
+/// A small, deterministic pseudo-random number generator (xorshift64*) used to build mock values.
+///
+/// This is **not** cryptographically secure, and is only intended to be used within tests and fuzzers.
+#[derive(Debug, Clone)]
+pub struct MockRng(u64);
+
+impl MockRng {
+    /// Creates a new generator from the given seed.
+    /// The same seed will always produce the same sequence of values.
+    pub fn new(seed: u64) -> Self {
+        // xorshift can not recover from a zero state.
+        Self(seed ^ 0x9E37_79B9_7F4A_7C15)
+    }
+
+    /// Creates a new generator seeded from the current time.
+    pub fn from_time() -> Self {
+        let seed = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0);
+        Self::new(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        self.0 = x;
+        x.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    pub fn next_bool(&mut self) -> bool {
+        self.next_u64() & 1 == 1
+    }
+
+    /// Returns a value within `0..max`, or `0` if `max` is `0`.
+    pub fn below(&mut self, max: usize) -> usize {
+        if max == 0 {
+            0
+        } else {
+            (self.next_u64() % max as u64) as usize
+        }
+    }
+}
+
+/// Allows a type to construct a randomized, but wire-valid instance of itself.
+///
+/// For automatic implementations, use the `#[derive(MockBinary)]` macro, which respects
+/// the `#[skip]`, `#[satisfy]`, `#[require]` and `#[if_present]` attributes of `BinaryIo`.
+///
+/// ```rust
+/// use binary_util::interfaces::{Reader, Writer};
+/// use binary_util::testing::random_with_seed;
+/// use binary_util::{BinaryIo, MockBinary};
+///
+/// #[derive(BinaryIo, MockBinary, Debug, PartialEq)]
+/// struct Login {
+///     name: String,
+///     has_token: bool,
+///     #[satisfy(self.has_token == true)]
+///     token: Option<u64>,
+/// }
+///
+/// fn main() {
+///     for seed in 0..32 {
+///         let login: Login = random_with_seed(seed);
+///         let buf = login.write_to_bytes().unwrap();
+///         assert_eq!(Login::read_from_slice(buf.as_slice()).unwrap(), login);
+///     }
+/// }
+/// ```
+pub trait Mock: Sized {
+    /// Builds a random instance of `Self` from the given generator.
+    fn mock(rng: &mut MockRng) -> Self;
+}
+
+/// Allows a collection to construct a random instance of itself, holding at most `max` items (or bytes).
+///
+/// `#[derive(MockBinary)]` uses this for collections whose length is bounded, IE: by `#[max_len]`
+/// or the type of the field storing their length with `#[len_of]`.
+pub trait MockLen: Mock {
+    /// Builds a random instance of `Self` holding at most `max` items, where `Counted::count` is at most `max`.
+    fn mock_with_max_len(rng: &mut MockRng, max: usize) -> Self;
+}
+
+/// Returns the largest count which fits within `L`, the type of a `#[len_of]` field.
+///
+/// ```rust
+/// use binary_util::testing::max_count;
+///
+/// fn main() {
+///     assert_eq!(max_count::<u8>(), 255);
+///     assert_eq!(max_count::<u64>(), usize::MAX);
+/// }
+/// ```
+pub fn max_count<L: TryFrom<usize>>() -> usize {
+    if L::try_from(0).is_err() {
+        return 0;
+    }
+    // the counts which fit are assumed to be a range starting at 0, as they are for integers.
+    let (mut low, mut high) = (0, usize::MAX);
+    while low < high {
+        let mid = low + (high - low).div_ceil(2);
+        match L::try_from(mid) {
+            Ok(_) => low = mid,
+            Err(_) => high = mid - 1,
+        }
+    }
+    low
+}
+
+/// Builds a random instance of `T`, seeded from the current time.
+pub fn random<T: Mock>() -> T {
+    T::mock(&mut MockRng::from_time())
+}
+
+/// Builds a random instance of `T` from the given seed.
+pub fn random_with_seed<T: Mock>(seed: u64) -> T {
+    T::mock(&mut MockRng::new(seed))
+}
+
+/// The most numeric fields `assert_byte_swap_matrix` permutes, which bounds it to 1024 permutations per value.
+const MAX_MATRIX_FIELDS: usize = 10;
+
+/// Asserts that `T` reads and re-writes its encoding unchanged when any subset of its numeric fields is byte swapped,
+/// for `seeds` mock values of `T`.
+///
+/// Every numeric field, IE: `u32`, `LE<u16>` or `BE<f64>`, is found with the schema of `T`, and located within
+/// the encoded value with `WireDebug`, so `T` must be derived with `#[binary_io(schema, debug_wire)]` and `MockBinary`.
+/// For every subset of those fields, their bytes are reversed, as if they were declared in the opposite byte order,
+/// and the result must read back as a `T` which writes the exact same bytes, with every field at the same offset.
+/// This catches readers and writers which disagree on the byte order of a field, or on where it starts.
+///
+/// This does not check the byte order a field is encoded in: a field which is read and written in the same,
+/// wrong byte order, IE: a `LE<u16>` encoded as big endian on both sides, still passes.
+/// Compare the encoding of a known value against the expected bytes to check that.
+///
+/// Only the first 10 numeric fields are permuted. Fields which other fields depend on, such as `#[len_of]` fields,
+/// or the fields of a `#[crc_of]` checksum, can not be permuted freely, so types with them are not supported.
+///
+/// ```rust
+/// use binary_util::interfaces::{Reader, Writer};
+/// use binary_util::testing::assert_byte_swap_matrix;
+/// use binary_util::types::{BE, LE};
+/// use binary_util::{BinaryIo, MockBinary};
+///
+/// #[derive(BinaryIo, MockBinary, Debug)]
+/// #[binary_io(schema, debug_wire)]
+/// struct Header {
+///     magic: LE<u16>,
+///     length: BE<u32>,
+///     flags: u8,
+///     checksum: u64,
+/// }
+///
+/// fn main() {
+///     assert_byte_swap_matrix::<Header>(16);
+/// }
+/// ```
+pub fn assert_byte_swap_matrix<T>(seeds: u64)
+where
+    T: Schema + WireDebug + Mock + Reader<T> + std::fmt::Debug,
+{
+    let schema = T::schema();
+    let SchemaKind::Struct(schema_fields) = schema.kind else {
+        panic!(
+            "{} is not a struct, only the fields of structs can be permuted",
+            schema.name
+        );
+    };
+
+    for seed in 0..seeds {
+        let value: T = random_with_seed(seed);
+        let mut buf = ByteWriter::new();
+        let fields = value
+            .write_fields(&mut buf)
+            .expect("mock value failed to write");
+        // fields which were not written, IE: a `None` option, have no bytes to permute.
+        let numeric = fields
+            .iter()
+            .filter(|field| {
+                schema_fields.iter().any(|schema| {
+                    schema.name == field.name && numeric_width(schema.ty) == Some(field.range.len())
+                })
+            })
+            .take(MAX_MATRIX_FIELDS)
+            .collect::<Vec<_>>();
+
+        for mask in 0u32..1 << numeric.len() {
+            let mut permuted = buf.as_slice().to_vec();
+            for (i, field) in numeric.iter().enumerate() {
+                if mask & (1 << i) != 0 {
+                    permuted[field.range.clone()].reverse();
+                }
+            }
+            let swapped = numeric
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| mask & (1 << i) != 0)
+                .map(|(_, field)| field.name)
+                .collect::<Vec<_>>();
+
+            let read = T::read_from_slice(&permuted).unwrap_or_else(|e| {
+                panic!(
+                    "{} failed to read with {:?} byte swapped (seed {}): {}",
+                    schema.name, swapped, seed, e
+                )
+            });
+            let mut rewritten = ByteWriter::new();
+            let rewritten_fields = read
+                .write_fields(&mut rewritten)
+                .expect("read value failed to write");
+            assert_eq!(
+                (rewritten.as_slice(), &rewritten_fields),
+                (&permuted[..], &fields),
+                "{} does not round trip with {:?} byte swapped (seed {}): {:?}",
+                schema.name,
+                swapped,
+                seed,
+                read
+            );
+        }
+    }
+}
+
+/// Returns the encoded width of a multi-byte numeric type, IE: `u32` or `LE<f64>`, from its name in a schema.
+fn numeric_width(ty: &str) -> Option<usize> {
+    // `LE<T>` and `BE<T>` are encoded like `T`, in either byte order.
+    let ty = match ty.strip_suffix('>').and_then(|ty| ty.split_once('<')) {
+        Some((wrapper, inner)) if matches!(wrapper.rsplit("::").next(), Some("LE" | "BE")) => inner,
+        Some(_) => return None,
+        None => ty,
+    };
+    match ty.rsplit("::").next()? {
+        "u16" | "i16" => Some(2),
+        "u24" | "i24" => Some(3),
+        "u32" | "i32" | "f32" => Some(4),
+        "u40" | "i40" => Some(5),
+        "u48" | "i48" => Some(6),
+        "u56" | "i56" => Some(7),
+        "u64" | "i64" | "f64" => Some(8),
+        "u128" | "i128" => Some(16),
+        _ => None,
+    }
+}
+
+macro_rules! impl_mock {
+    ($($t:ty),*) => {
+        $(
+            impl Mock for $t {
+                fn mock(rng: &mut MockRng) -> Self {
+                    ((rng.next_u64() as u128) << 64 | rng.next_u64() as u128) as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_mock!(u8, i8, u16, i16, u32, i32, u64, i64, u128, i128);
+
+impl Mock for f32 {
+    fn mock(rng: &mut MockRng) -> Self {
+        // NaN can not be compared, so we stick to finite values.
+        (rng.next_u32() as f32 / u32::MAX as f32) * 2048.0 - 1024.0
+    }
+}
+
+impl Mock for f64 {
+    fn mock(rng: &mut MockRng) -> Self {
+        (rng.next_u64() as f64 / u64::MAX as f64) * 2048.0 - 1024.0
+    }
+}
+
+impl Mock for bool {
+    fn mock(rng: &mut MockRng) -> Self {
+        rng.next_bool()
+    }
+}
+
+impl Mock for char {
+    fn mock(rng: &mut MockRng) -> Self {
+        char::from_u32(rng.next_u32() % 0x11_0000).unwrap_or('?')
+    }
+}
+
+impl Mock for Utf8Char {
+    fn mock(rng: &mut MockRng) -> Self {
+        Utf8Char(char::mock(rng))
+    }
+}
+
+impl Mock for Utf16Char {
+    fn mock(rng: &mut MockRng) -> Self {
+        Utf16Char(char::mock(rng))
+    }
+}
+
+impl Mock for U32Char {
+    fn mock(rng: &mut MockRng) -> Self {
+        U32Char(char::mock(rng))
+    }
+}
+
+impl Mock for String {
+    fn mock(rng: &mut MockRng) -> Self {
+        Self::mock_with_max_len(rng, 15)
+    }
+}
+
+impl MockLen for String {
+    fn mock_with_max_len(rng: &mut MockRng, max: usize) -> Self {
+        const CHARSET: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+        (0..rng.below(max.min(15) + 1))
+            .map(|_| CHARSET[rng.below(CHARSET.len())] as char)
+            .collect()
+    }
+}
+
+impl Mock for () {
+    fn mock(_: &mut MockRng) -> Self {}
+}
+
+impl<T: Mock> Mock for Vec<T> {
+    fn mock(rng: &mut MockRng) -> Self {
+        Self::mock_with_max_len(rng, 7)
+    }
+}
+
+impl<T: Mock> MockLen for Vec<T> {
+    fn mock_with_max_len(rng: &mut MockRng, max: usize) -> Self {
+        (0..rng.below(max.min(7) + 1))
+            .map(|_| T::mock(rng))
+            .collect()
+    }
+}
+
+impl<K: Mock + Eq + std::hash::Hash, V: Mock> Mock for OrderedMap<K, V> {
+    fn mock(rng: &mut MockRng) -> Self {
+        Self::mock_with_max_len(rng, 7)
+    }
+}
+
+impl<K: Mock + Eq + std::hash::Hash, V: Mock> MockLen for OrderedMap<K, V> {
+    fn mock_with_max_len(rng: &mut MockRng, max: usize) -> Self {
+        // duplicate keys are merged, so the map may hold fewer entries.
+        (0..rng.below(max.min(7) + 1))
+            .map(|_| (K::mock(rng), V::mock(rng)))
+            .collect()
+    }
+}
+
+impl Mock for Bytes {
+    fn mock(rng: &mut MockRng) -> Self {
+        Vec::<u8>::mock(rng).into()
+    }
+}
+
+impl MockLen for Bytes {
+    fn mock_with_max_len(rng: &mut MockRng, max: usize) -> Self {
+        Vec::<u8>::mock_with_max_len(rng, max).into()
+    }
+}
+
+impl Mock for BytesMut {
+    fn mock(rng: &mut MockRng) -> Self {
+        BytesMut::from(&Vec::<u8>::mock(rng)[..])
+    }
+}
+
+impl MockLen for BytesMut {
+    fn mock_with_max_len(rng: &mut MockRng, max: usize) -> Self {
+        BytesMut::from(&Vec::<u8>::mock_with_max_len(rng, max)[..])
+    }
+}
+
+impl<T: Mock> Mock for Option<T> {
+    fn mock(rng: &mut MockRng) -> Self {
+        if rng.next_bool() {
+            Some(T::mock(rng))
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: Mock, E: Mock> Mock for Result<T, E> {
+    fn mock(rng: &mut MockRng) -> Self {
+        if rng.next_bool() {
+            Ok(T::mock(rng))
+        } else {
+            Err(E::mock(rng))
+        }
+    }
+}
+
+impl<T: Mock> Mock for LE<T> {
+    fn mock(rng: &mut MockRng) -> Self {
+        LE(T::mock(rng))
+    }
+}
+
+impl<T: Mock> Mock for BE<T> {
+    fn mock(rng: &mut MockRng) -> Self {
+        BE(T::mock(rng))
+    }
+}
+
+impl Mock for u24 {
+    fn mock(rng: &mut MockRng) -> Self {
+        u24(rng.next_u32() & 0xFFFFFF)
+    }
+}
+
+impl<T: Mock> Mock for Sequenced<T> {
+    fn mock(rng: &mut MockRng) -> Self {
+        Sequenced::new(u24::mock(rng), T::mock(rng))
+    }
+}
+
+impl<T: Mock> Mock for Ordered<T> {
+    fn mock(rng: &mut MockRng) -> Self {
+        Ordered::new(u24::mock(rng), u8::mock(rng), T::mock(rng))
+    }
+}
+
+impl<L, T: Mock> Mock for Prefixed<L, T> {
+    fn mock(rng: &mut MockRng) -> Self {
+        Prefixed::new(T::mock(rng))
+    }
+}
+
+impl<L, T: MockLen> MockLen for Prefixed<L, T> {
+    fn mock_with_max_len(rng: &mut MockRng, max: usize) -> Self {
+        Prefixed::new(T::mock_with_max_len(rng, max))
+    }
+}
+
+impl<const N: usize> Mock for ByteArray<N> {
+    fn mock(rng: &mut MockRng) -> Self {
+        ByteArray(core::array::from_fn(|_| u8::mock(rng)))
+    }
+}
+
+impl<const N: usize, const STRICT: bool> Mock for Reserved<N, STRICT> {
+    fn mock(_: &mut MockRng) -> Self {
+        Reserved
+    }
+}
+
+impl<T: Mock> Mock for Raw<T> {
+    fn mock(rng: &mut MockRng) -> Self {
+        Raw(T::mock(rng))
+    }
+}
+
+impl<T: MockLen> MockLen for Raw<T> {
+    fn mock_with_max_len(rng: &mut MockRng, max: usize) -> Self {
+        Raw(T::mock_with_max_len(rng, max))
+    }
+}
+
+impl Mock for i24 {
+    fn mock(rng: &mut MockRng) -> Self {
+        i24((rng.next_u32() & 0xFFFFFF) as i32 - 0x800000)
+    }
+}
+
+macro_rules! impl_wide_int_mock {
+    ($($name: ident, $offset: expr);* $(;)?) => {
+        $(
+            impl Mock for $name {
+                fn mock(rng: &mut MockRng) -> Self {
+                    let bits = (rng.next_u64() >> (64 - 8 * $name::BYTES)) as i128;
+                    $name((bits - $offset) as _)
+                }
+            }
+        )*
+    };
+}
+
+impl_wide_int_mock! {
+    u40, 0;
+    u48, 0;
+    u56, 0;
+    i40, 1 << 39;
+    i48, 1 << 47;
+    i56, 1 << 55;
+}
+
+impl Mock for Angle8 {
+    fn mock(rng: &mut MockRng) -> Self {
+        Angle8(rng.next_u32() as u8)
+    }
+}
+
+impl Mock for Angle16 {
+    fn mock(rng: &mut MockRng) -> Self {
+        Angle16(rng.next_u32() as u16)
+    }
+}
+
+impl Mock for UnitVec24 {
+    fn mock(rng: &mut MockRng) -> Self {
+        UnitVec24(rng.next_u32() & 0xFFFFFF)
+    }
+}
+
+impl Mock for varu32 {
+    fn mock(rng: &mut MockRng) -> Self {
+        varu32(rng.next_u32())
+    }
+}
+
+impl Mock for vari32 {
+    fn mock(rng: &mut MockRng) -> Self {
+        vari32(rng.next_u32() as i32)
+    }
+}
+
+impl Mock for varu64 {
+    fn mock(rng: &mut MockRng) -> Self {
+        varu64(rng.next_u64())
+    }
+}
+
+impl Mock for vari64 {
+    fn mock(rng: &mut MockRng) -> Self {
+        vari64(rng.next_u64() as i64)
+    }
+}
+
+impl Mock for SocketAddr {
+    fn mock(rng: &mut MockRng) -> Self {
+        if rng.next_bool() {
+            SocketAddr::V4(SocketAddrV4::new(
+                Ipv4Addr::from(rng.next_u32()),
+                u16::mock(rng),
+            ))
+        } else {
+            SocketAddr::V6(SocketAddrV6::new(
+                Ipv6Addr::from(u128::mock(rng)),
+                u16::mock(rng),
+                rng.next_u32(),
+                rng.next_u32(),
+            ))
+        }
+    }
+}
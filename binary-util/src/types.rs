@@ -32,6 +32,56 @@ macro_rules! impl_type {
     };
 }
 
+/// Implements `new`, `into_inner`, `From<$inner>`, `Deref` and `DerefMut` for a type wrapping `$inner` in `$field`,
+/// where `$wrap` builds the type from `$val`. With `no_new`, the type declares its own `new`.
+macro_rules! impl_wrapper {
+    (
+        $(#[$meta: meta])* [$($generics: tt)*] $ty: ty => $inner: ty, $field: tt, |$val: ident| $wrap: expr
+    ) => {
+        $(#[$meta])*
+        impl<$($generics)*> $ty {
+            pub fn new($val: $inner) -> Self {
+                $wrap
+            }
+        }
+
+        impl_wrapper!($(#[$meta])* [$($generics)*] $ty => $inner, $field, |$val| $wrap, no_new);
+    };
+    (
+        $(#[$meta: meta])* [$($generics: tt)*] $ty: ty => $inner: ty, $field: tt, |$val: ident| $wrap: expr, no_new
+    ) => {
+        $(#[$meta])*
+        impl<$($generics)*> $ty {
+            pub fn into_inner(self) -> $inner {
+                self.$field
+            }
+        }
+
+        $(#[$meta])*
+        impl<$($generics)*> From<$inner> for $ty {
+            fn from($val: $inner) -> Self {
+                $wrap
+            }
+        }
+
+        $(#[$meta])*
+        impl<$($generics)*> std::ops::Deref for $ty {
+            type Target = $inner;
+
+            fn deref(&self) -> &Self::Target {
+                &self.$field
+            }
+        }
+
+        $(#[$meta])*
+        impl<$($generics)*> std::ops::DerefMut for $ty {
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                &mut self.$field
+            }
+        }
+    };
+}
+
 /// Little Endian (LE) wrapper type
 /// This type is used to indicate that the value is in little endian format
 /// It's primary use is in deriving from `BinaryIo` trait
@@ -122,6 +172,9 @@ impl_type!(BE<f64>, f64);
 pub struct u24(pub u32);
 
 impl u24 {
+    pub const MIN: u32 = 0;
+    pub const MAX: u32 = 0xFFFFFF;
+
     pub fn new(val: u32) -> Self {
         if val <= 0xFFFFFF {
             Self(val)
@@ -129,6 +182,40 @@ impl u24 {
             panic!("u24: value out of range")
         }
     }
+
+    /// Adds `rhs`, wrapping around to `0` after `u24::MAX`, IE: to get the next sequence number.
+    pub fn wrapping_add(self, rhs: u32) -> Self {
+        Self(self.0.wrapping_add(rhs) & Self::MAX)
+    }
+
+    /// Returns how far `self` is ahead of `other`, treating both as 24 bit sequence numbers which wrap around.
+    ///
+    /// This is serial number arithmetic (RFC 1982), so `u24(1)` is 2 ahead of `u24(u24::MAX)`.
+    /// Numbers which are exactly half of the sequence space apart are ambiguous, and are always reported
+    /// as `self` being behind.
+    ///
+    /// ```rust
+    /// use binary_util::types::u24;
+    ///
+    /// fn main() {
+    ///     assert_eq!(u24(5).serial_diff(u24(3)), 2);
+    ///     assert_eq!(u24(3).serial_diff(u24(5)), -2);
+    ///     assert_eq!(u24(1).serial_diff(u24(u24::MAX)), 2);
+    /// }
+    /// ```
+    pub fn serial_diff(self, other: u24) -> i32 {
+        let diff = self.0.wrapping_sub(other.0) & Self::MAX;
+        if diff >= 0x800000 {
+            diff as i32 - 0x1000000
+        } else {
+            diff as i32
+        }
+    }
+
+    /// Returns whether `self` is a newer sequence number than `other`, see `serial_diff`.
+    pub fn is_newer_than(self, other: u24) -> bool {
+        self.serial_diff(other) > 0
+    }
 }
 
 impl fmt::Display for u24 {
@@ -175,12 +262,75 @@ impl fmt::Display for i24 {
 
 impl_type!(i24, i32);
 
+/// Declares a fixed width integer type, backed by a larger primitive, which is encoded in `$bytes` bytes.
+macro_rules! impl_wide_int {
+    ($($name: ident, $inner: ty, $bits: literal, $bytes: literal, $min: expr, $max: expr);* $(;)?) => {
+        $(
+            #[doc = concat!(stringify!($bits), " bit integer explicit type, encoded in ", stringify!($bytes), " bytes.")]
+            /// You should really only use this when you need to derive the `BinaryIo` trait
+            /// as it is a helper type. Wrap it in `LE` to encode it in little endian.
+            ///
+            /// # Example
+            /// ```rust ignore
+            #[doc = concat!("use binary_util::types::", stringify!($name), ";")]
+            /// use binary_util::BinaryIo;
+            ///
+            /// #[derive(BinaryIo)]
+            /// struct MyStruct {
+            #[doc = concat!("    test: ", stringify!($name), ",")]
+            /// }
+            /// ```
+            #[allow(non_camel_case_types)]
+            #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+            pub struct $name(pub $inner);
+
+            impl $name {
+                /// The amount of bytes this type takes up on the wire.
+                pub const BYTES: usize = $bytes;
+                pub const MIN: $inner = $min;
+                pub const MAX: $inner = $max;
+
+                pub fn new(val: $inner) -> Self {
+                    if (Self::MIN..=Self::MAX).contains(&val) {
+                        Self(val)
+                    } else {
+                        panic!(concat!(stringify!($name), ": value out of range"))
+                    }
+                }
+            }
+
+            impl fmt::Display for $name {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    write!(f, "{}", self.0)
+                }
+            }
+
+            impl_type!($name, $inner);
+            impl_type!(LE<$name>, $name);
+            impl_type!(BE<$name>, $name);
+        )*
+    };
+}
+
+impl_wide_int! {
+    u40, u64, 40, 5, 0, (1 << 40) - 1;
+    u48, u64, 48, 6, 0, (1 << 48) - 1;
+    u56, u64, 56, 7, 0, (1 << 56) - 1;
+    i40, i64, 40, 5, -(1 << 39), (1 << 39) - 1;
+    i48, i64, 48, 6, -(1 << 47), (1 << 47) - 1;
+    i56, i64, 56, 7, -(1 << 55), (1 << 55) - 1;
+}
+
 /// A variable length integer type that can be up to 32 bits.
 /// This is a helper type for deriving the `BinaryIo` trait.
 ///
 /// You should not use this type directly, if you are reading or writing
 /// a variable length integer, use the `ByteWriter` or `ByteReader` and use
 /// the corresponding `read_var_u32` or `write_var_u32` methods.
+///
+/// Var-ints are LEB128 encoded, IE: the least significant group of 7 bits comes first, which `LE<varu32>` spells out.
+/// `BE<varu32>` encodes the most significant group first instead, as some protocols do.
+/// The same applies to `vari32`, `varu64` and `vari64`, whose signed forms are always zigzag encoded.
 #[allow(non_camel_case_types)]
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct varu32(pub u32);
@@ -244,3 +394,935 @@ impl vari64 {
 }
 
 impl_type!(vari64, i64);
+
+/// A char encoded as UTF-8, which takes 1 to 4 bytes.
+///
+/// # Example
+/// ```rust
+/// use binary_util::interfaces::{Reader, Writer};
+/// use binary_util::types::{Utf16Char, Utf8Char, LE};
+///
+/// fn main() {
+///     assert_eq!(Utf8Char('é').write_to_bytes().unwrap().as_slice(), &[0xc3, 0xa9]);
+///     assert_eq!(Utf16Char('é').write_to_bytes().unwrap().as_slice(), &[0x00, 0xe9]);
+///     assert_eq!(LE(Utf16Char('é')).write_to_bytes().unwrap().as_slice(), &[0xe9, 0x00]);
+///
+///     // chars beyond U+FFFF are encoded as a surrogate pair in UTF-16.
+///     assert_eq!(Utf16Char::read_from_slice(&[0xd8, 0x3d, 0xde, 0x00]).unwrap().0, '😀');
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Utf8Char(pub char);
+
+impl Utf8Char {
+    pub fn new(val: char) -> Self {
+        Self(val)
+    }
+}
+
+impl_type!(Utf8Char, char);
+
+/// A char encoded as UTF-16, which takes 2 bytes, or 4 bytes for a surrogate pair.
+/// The code units are big endian, use `LE<Utf16Char>` for little endian code units.
+///
+/// See `Utf8Char` for an example.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Utf16Char(pub char);
+
+impl Utf16Char {
+    pub fn new(val: char) -> Self {
+        Self(val)
+    }
+}
+
+impl_type!(Utf16Char, char);
+
+/// A char encoded as its code point in a big endian `u32`, which is how `char` itself is encoded.
+///
+/// Encoding `char` directly is deprecated, as no common protocol encodes chars this way.
+/// This type keeps the encoding available for compatibility, use `Utf8Char` or `Utf16Char` otherwise.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct U32Char(pub char);
+
+impl U32Char {
+    pub fn new(val: char) -> Self {
+        Self(val)
+    }
+}
+
+impl_type!(U32Char, char);
+
+#[cfg(feature = "bitflags")]
+pub use bitflags::Flags;
+
+/// A wrapper for types generated by the `bitflags!` macro, which encodes the bits of the flags as `W`.
+/// When `W` is not specified, the bits are encoded as the underlying type of the flags.
+///
+/// Unknown bits are kept when reading, so flags added by a newer peer are not lost when re-encoded.
+/// To implement `Reader` and `Writer` on the flags type directly, use `impl_bitflags_io!`.
+///
+/// # Example
+/// ```rust ignore
+/// use binary_util::types::BitFlags;
+/// use binary_util::BinaryIo;
+///
+/// bitflags::bitflags! {
+///     #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+///     pub struct Permissions: u32 {
+///         const BUILD = 1;
+///         const FLY = 1 << 1;
+///     }
+/// }
+///
+/// #[derive(BinaryIo)]
+/// struct Abilities {
+///     // encoded as a single byte instead of a u32.
+///     permissions: BitFlags<Permissions, u8>,
+/// }
+/// ```
+#[cfg(feature = "bitflags")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BitFlags<F: Flags, W = <F as Flags>::Bits>(pub F, core::marker::PhantomData<W>);
+
+impl_wrapper!(
+    #[cfg(feature = "bitflags")]
+    [F: Flags, W] BitFlags<F, W> => F, 0, |val| Self(val, core::marker::PhantomData)
+);
+
+/// A wrapper for protobuf messages generated by `prost`, which are embedded in a packet
+/// as a var-int length prefixed blob.
+///
+/// This allows protocols with custom framing and protobuf bodies to be expressed in a single derive.
+///
+/// # Example
+/// ```rust ignore
+/// use binary_util::types::Proto;
+/// use binary_util::BinaryIo;
+///
+/// #[derive(Clone, PartialEq, prost::Message)]
+/// pub struct Chat {
+///     #[prost(string, tag = "1")]
+///     pub message: String,
+/// }
+///
+/// #[derive(BinaryIo)]
+/// struct ChatPacket {
+///     channel: u8,
+///     body: Proto<Chat>,
+/// }
+/// ```
+#[cfg(feature = "prost")]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Proto<T: prost::Message>(pub T);
+
+impl_wrapper!(
+    #[cfg(feature = "prost")]
+    [T: prost::Message] Proto<T> => T, 0, |val| Self(val)
+);
+
+/// A wrapper for types implementing `serde::Serialize` and `serde::Deserialize`, which are embedded
+/// in a packet as a JSON string, prefixed with its length as a var-int.
+///
+/// Many game protocols tunnel JSON within binary packets, IE: status responses and chat components.
+///
+/// # Example
+/// ```rust ignore
+/// use binary_util::types::Json;
+/// use binary_util::BinaryIo;
+///
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Status {
+///     motd: String,
+///     players: u32,
+/// }
+///
+/// #[derive(BinaryIo)]
+/// struct StatusResponse {
+///     status: Json<Status>,
+/// }
+/// ```
+#[cfg(feature = "json")]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Json<T>(pub T);
+
+impl_wrapper!(
+    #[cfg(feature = "json")]
+    [T] Json<T> => T, 0, |val| Self(val)
+);
+
+/// A wrapper for types implementing `serde::Serialize` and `serde::Deserialize`, which are embedded
+/// in a packet as MessagePack, prefixed with its length as a var-int.
+///
+/// Structs are encoded as maps, keyed by the name of each field, so fields may be added without breaking older peers.
+///
+/// # Example
+/// ```rust ignore
+/// use binary_util::types::MsgPack;
+/// use binary_util::BinaryIo;
+///
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Metadata {
+///     tags: Vec<String>,
+/// }
+///
+/// #[derive(BinaryIo)]
+/// struct Entity {
+///     id: u64,
+///     metadata: MsgPack<Metadata>,
+/// }
+/// ```
+#[cfg(feature = "msgpack")]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct MsgPack<T>(pub T);
+
+impl_wrapper!(
+    #[cfg(feature = "msgpack")]
+    [T] MsgPack<T> => T, 0, |val| Self(val)
+);
+
+/// A value carrying a 24 bit sequence index, used by reliability layers (IE: RakNet) to discard
+/// values which arrive after a newer value was already received.
+///
+/// The index is written in little endian, followed by the value.
+/// Indices wrap around after `u24::MAX`, so they should be compared with `is_newer_than`, rather than `>`.
+///
+/// # Example
+/// ```rust
+/// use binary_util::types::{u24, Sequenced};
+///
+/// fn main() {
+///     let last = Sequenced::new(u24(u24::MAX), "old");
+///     let next = Sequenced::new(last.index.wrapping_add(1), "new");
+///     assert_eq!(next.index, u24(0));
+///     assert!(next.is_newer_than(&last));
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Sequenced<T> {
+    pub index: u24,
+    pub value: T,
+}
+
+impl<T> Sequenced<T> {
+    pub fn new(index: u24, value: T) -> Self {
+        Self { index, value }
+    }
+
+    /// Returns whether this value was sent after `other`, accounting for the index wrapping around.
+    pub fn is_newer_than<U>(&self, other: &Sequenced<U>) -> bool {
+        self.index.is_newer_than(other.index)
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+/// A value carrying a 24 bit ordering index and an ordering channel, used by reliability layers (IE: RakNet)
+/// to deliver the values of each channel in the order they were sent.
+///
+/// The index is written in little endian, followed by the channel and the value.
+/// Indices wrap around after `u24::MAX`, so they should be compared with `serial_diff`, rather than `-`.
+///
+/// # Example
+/// ```rust
+/// use binary_util::types::{u24, Ordered};
+///
+/// fn main() {
+///     let expected = u24(u24::MAX);
+///     let received = Ordered::new(u24(1), 0, "early");
+///     // the value is 2 ahead of the next expected index, so it must be buffered until the gap is filled.
+///     assert_eq!(received.distance_from(expected), 2);
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Ordered<T> {
+    pub index: u24,
+    pub channel: u8,
+    pub value: T,
+}
+
+impl<T> Ordered<T> {
+    pub fn new(index: u24, channel: u8, value: T) -> Self {
+        Self {
+            index,
+            channel,
+            value,
+        }
+    }
+
+    /// Returns how far the index of this value is ahead of `expected`, IE: the next index to deliver.
+    /// This is negative if the value is older than `expected`, meaning it was already delivered.
+    pub fn distance_from(&self, expected: u24) -> i32 {
+        self.index.serial_diff(expected)
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+/// Bytes written verbatim, without a length prefix.
+///
+/// When read, `Raw` consumes every remaining byte of the buffer, so it is meant to be the last field of a packet,
+/// IE: a body forwarded between connections without being decoded. When the length is stored in another field,
+/// `#[len_of]` reads only that many bytes instead. Otherwise, deriving `BinaryIo` on a type with a field after a `Raw`
+/// field is a compile error, since that field could never be read:
+/// ```compile_fail
+/// use binary_util::types::Raw;
+/// use binary_util::BinaryIo;
+///
+/// #[derive(BinaryIo)]
+/// struct Forward {
+///     body: Raw,
+///     connection: u8,
+/// }
+/// ```
+///
+/// The derive recognizes `Raw` by the name of the field's type, as it can not resolve types. A type alias of `Raw`
+/// is therefore not checked, and any type named `Raw` or `RawVec` is treated as greedy.
+///
+/// `T` may be `Bytes`, `BytesMut` or `Vec<u8>`, see `RawVec`. `Bytes` is read without copying the buffer.
+///
+/// # Example
+/// ```rust
+/// use binary_util::interfaces::{Reader, Writer};
+/// use binary_util::types::Raw;
+/// use binary_util::BinaryIo;
+///
+/// #[derive(BinaryIo)]
+/// struct Forward {
+///     connection: u8,
+///     body: Raw,
+/// }
+///
+/// fn main() {
+///     let buf = Forward::read_from_slice(&[1, 0xde, 0xad]).unwrap();
+///     assert_eq!(&buf.body[..], &[0xde, 0xad]);
+///     assert_eq!(buf.write_to_bytes().unwrap().as_slice(), &[1, 0xde, 0xad]);
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Raw<T = bytes::Bytes>(pub T);
+
+/// Raw bytes read into a `Vec<u8>`, see `Raw`.
+pub type RawVec = Raw<Vec<u8>>;
+
+impl_wrapper!([T] Raw<T> => T, 0, |val| Self(val));
+
+/// A `Writer` which writes by calling a closure, so ad-hoc payloads can be passed anywhere a `Writer` is expected
+/// without declaring a one-off struct, IE: queued alongside packets as a `Box<dyn Writer>`.
+///
+/// # Example
+/// ```rust
+/// use binary_util::interfaces::Writer;
+/// use binary_util::types::WriterFn;
+///
+/// fn main() {
+///     let count = 3u8;
+///     let payload = WriterFn::new(move |buf| {
+///         for i in 0..count {
+///             buf.write_u8(i)?;
+///         }
+///         Ok(())
+///     });
+///
+///     let queue: Vec<Box<dyn Writer>> = vec![1u16.boxed(), payload.boxed()];
+///     assert_eq!(queue.write_to_bytes().unwrap().as_slice(), &[2, 0, 1, 0, 1, 2]);
+/// }
+/// ```
+#[derive(Clone, Copy)]
+pub struct WriterFn<F>(pub F);
+
+impl<F> WriterFn<F>
+where
+    F: Fn(&mut crate::io::ByteWriter) -> Result<(), std::io::Error>,
+{
+    pub fn new(f: F) -> Self {
+        Self(f)
+    }
+}
+
+impl<F> WriterFn<F> {
+    pub fn into_inner(self) -> F {
+        self.0
+    }
+}
+
+impl<F> std::fmt::Debug for WriterFn<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("WriterFn")
+    }
+}
+
+/// A collection prefixed with its length, encoded as `L`, rather than the `varu32` prefix of `Vec<T>` and `String`.
+///
+/// The prefix is part of the type, so it can be reused outside of the derive, where `#[len_of]` is not available.
+/// `L` may be any `LengthPrefix`, IE: `u8`, `BE<u16>`, `LE<u32>` or `varu64`, and `T` any `Counted` collection,
+/// IE: `Vec<T>`, `String` or `Bytes`. Writing a collection longer than `L` can store is an error.
+///
+/// # Example
+/// ```rust
+/// use binary_util::interfaces::{Reader, Writer};
+/// use binary_util::types::{varu32, Prefixed, BE};
+/// use binary_util::BinaryIo;
+///
+/// #[derive(BinaryIo)]
+/// struct Inventory {
+///     items: Prefixed<BE<u16>, Vec<u8>>,
+///     owner: Prefixed<varu32, String>,
+/// }
+///
+/// fn main() {
+///     let inventory = Inventory {
+///         items: Prefixed::new(vec![4, 5]),
+///         owner: Prefixed::new("me".into()),
+///     };
+///     let buf = inventory.write_to_bytes().unwrap();
+///     assert_eq!(buf.as_slice(), &[0, 2, 4, 5, 2, b'm', b'e']);
+///     assert_eq!(*Inventory::read_from_slice(buf.as_slice()).unwrap().owner, "me");
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Prefixed<L, T> {
+    value: T,
+    prefix: core::marker::PhantomData<fn() -> L>,
+}
+
+impl_wrapper!(
+    [L, T] Prefixed<L, T> => T, value, |value| Self {
+        value,
+        prefix: core::marker::PhantomData,
+    }
+);
+
+impl<L, T: Default> Default for Prefixed<L, T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+/// `N` reserved bytes, which are written as zeros and skipped when read, IE: the reserved region of a header.
+///
+/// When `STRICT` is `true`, reading fails if any of the bytes is not zero.
+///
+/// # Example
+/// ```rust
+/// use binary_util::interfaces::{Reader, Writer};
+/// use binary_util::types::Reserved;
+/// use binary_util::BinaryIo;
+///
+/// #[derive(BinaryIo)]
+/// struct Header {
+///     version: u8,
+///     reserved: Reserved<3>,
+///     flags: Reserved<2, true>,
+/// }
+///
+/// fn main() {
+///     let header = Header { version: 1, reserved: Reserved, flags: Reserved };
+///     assert_eq!(header.write_to_bytes().unwrap().as_slice(), &[1, 0, 0, 0, 0, 0]);
+///     assert!(Header::read_from_slice(&[1, 9, 9, 9, 0, 0]).is_ok());
+///     assert!(Header::read_from_slice(&[1, 0, 0, 0, 0, 9]).is_err());
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Reserved<const N: usize, const STRICT: bool = false>;
+
+/// `N` bytes written as is, without a length prefix, IE: a token, a MAC or a GUID.
+///
+/// Unlike `[u8; N]`, this is formatted as hex by `Debug`, and converts to and from `Bytes`.
+///
+/// # Example
+/// ```rust
+/// use binary_util::interfaces::{Reader, Writer};
+/// use binary_util::types::ByteArray;
+/// use binary_util::BinaryIo;
+///
+/// #[derive(BinaryIo, Debug)]
+/// struct Session {
+///     id: u8,
+///     token: ByteArray<4>,
+/// }
+///
+/// fn main() {
+///     let session = Session::read_from_slice(&[1, 0xde, 0xad, 0xbe, 0xef]).unwrap();
+///     assert_eq!(session.token, [0xde, 0xad, 0xbe, 0xef]);
+///     assert_eq!(format!("{:?}", session.token), "ByteArray<4>(deadbeef)");
+///     assert_eq!(session.write_to_bytes().unwrap().as_slice(), &[1, 0xde, 0xad, 0xbe, 0xef]);
+/// }
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ByteArray<const N: usize>(pub [u8; N]);
+
+impl_wrapper!([const N: usize] ByteArray<N> => [u8; N], 0, |val| Self(val));
+
+impl<const N: usize> ByteArray<N> {
+    pub fn as_slice(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl<const N: usize> Default for ByteArray<N> {
+    fn default() -> Self {
+        Self([0; N])
+    }
+}
+
+impl<const N: usize> fmt::Debug for ByteArray<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ByteArray<{}>(", N)?;
+        for b in &self.0 {
+            write!(f, "{:02x}", b)?;
+        }
+        f.write_str(")")
+    }
+}
+
+impl<const N: usize> From<ByteArray<N>> for [u8; N] {
+    fn from(val: ByteArray<N>) -> Self {
+        val.0
+    }
+}
+
+impl<const N: usize> From<ByteArray<N>> for bytes::Bytes {
+    fn from(val: ByteArray<N>) -> Self {
+        bytes::Bytes::copy_from_slice(&val.0)
+    }
+}
+
+impl<const N: usize> TryFrom<&[u8]> for ByteArray<N> {
+    type Error = core::array::TryFromSliceError;
+
+    fn try_from(val: &[u8]) -> Result<Self, Self::Error> {
+        <[u8; N]>::try_from(val).map(Self)
+    }
+}
+
+impl<const N: usize> TryFrom<bytes::Bytes> for ByteArray<N> {
+    type Error = core::array::TryFromSliceError;
+
+    fn try_from(val: bytes::Bytes) -> Result<Self, Self::Error> {
+        Self::try_from(&val[..])
+    }
+}
+
+impl<const N: usize> PartialEq<[u8; N]> for ByteArray<N> {
+    fn eq(&self, other: &[u8; N]) -> bool {
+        self.0 == *other
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for ByteArray<N> {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// A `HashMap` which is written with its entries sorted by key, so equal maps always produce the same bytes,
+/// IE: when a signature or hash is computed over an encoded packet.
+///
+/// The map is prefixed with its length as a `varu32`, like `Vec`, followed by each key and its value.
+/// Reading fails if the keys are not in strictly ascending order, so every map has exactly one valid encoding.
+///
+/// # Example
+/// ```rust
+/// use binary_util::interfaces::{Reader, Writer};
+/// use binary_util::types::OrderedMap;
+///
+/// fn main() {
+///     let mut scores = OrderedMap::new();
+///     scores.insert(3u8, 30u8);
+///     scores.insert(1u8, 10u8);
+///     assert_eq!(scores.write_to_bytes().unwrap().as_slice(), &[2, 1, 10, 3, 30]);
+///
+///     assert!(OrderedMap::<u8, u8>::read_from_slice(&[2, 1, 10, 3, 30]).is_ok());
+///     assert!(OrderedMap::<u8, u8>::read_from_slice(&[2, 3, 30, 1, 10]).is_err());
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct OrderedMap<K, V>(pub std::collections::HashMap<K, V>);
+
+impl_wrapper!(
+    [K, V] OrderedMap<K, V> => std::collections::HashMap<K, V>, 0, |val| Self(val), no_new
+);
+
+impl<K, V> OrderedMap<K, V> {
+    pub fn new() -> Self {
+        Self(std::collections::HashMap::new())
+    }
+
+    /// Returns the entries of the map, sorted by key, in the order they are written.
+    pub fn sorted(&self) -> Vec<(&K, &V)>
+    where
+        K: Ord,
+    {
+        let mut entries = self.0.iter().collect::<Vec<_>>();
+        entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+        entries
+    }
+}
+
+impl<K: Eq + std::hash::Hash, V: PartialEq> PartialEq for OrderedMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<K: Eq + std::hash::Hash, V: Eq> Eq for OrderedMap<K, V> {}
+
+impl<K, V> Default for OrderedMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> From<OrderedMap<K, V>> for std::collections::HashMap<K, V> {
+    fn from(val: OrderedMap<K, V>) -> Self {
+        val.0
+    }
+}
+
+impl<K: Eq + std::hash::Hash, V> FromIterator<(K, V)> for OrderedMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+/// Declares an angle quantized to `$inner`, where the full turn is split into `$steps` equal steps.
+macro_rules! impl_angle {
+    ($($name: ident, $inner: ty, $steps: literal, $precision: literal);* $(;)?) => {
+        $(
+            #[doc = concat!("An angle quantized to a `", stringify!($inner), "`, IE: the yaw or pitch of an entity.")]
+            ///
+            #[doc = concat!("The full turn is split into ", stringify!($steps), " steps, so angles are rounded to the nearest ", $precision, " degrees.")]
+            /// Angles outside of `0..360` degrees are wrapped around into it.
+            ///
+            /// # Example
+            /// ```rust
+            #[doc = concat!("use binary_util::types::", stringify!($name), ";")]
+            ///
+            /// fn main() {
+            #[doc = concat!("    let yaw = ", stringify!($name), "::from_degrees(-90.0);")]
+            ///     assert_eq!(yaw.to_degrees(), 270.0);
+            /// }
+            /// ```
+            #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+            pub struct $name(pub $inner);
+
+            impl $name {
+                /// The amount of steps the full turn is split into.
+                pub const STEPS: u32 = $steps;
+
+                pub fn new(val: $inner) -> Self {
+                    Self(val)
+                }
+
+                /// Quantizes `degrees` to the nearest step.
+                pub fn from_degrees(degrees: f32) -> Self {
+                    let steps = (degrees.rem_euclid(360.0) / 360.0 * Self::STEPS as f32).round() as u32;
+                    // an angle just below 360 degrees rounds up to the full turn, which is 0.
+                    Self((steps % Self::STEPS) as $inner)
+                }
+
+                /// Quantizes `radians` to the nearest step.
+                pub fn from_radians(radians: f32) -> Self {
+                    Self::from_degrees(radians.to_degrees())
+                }
+
+                /// Returns the angle in degrees, within `0..360`.
+                pub fn to_degrees(self) -> f32 {
+                    self.0 as f32 * 360.0 / Self::STEPS as f32
+                }
+
+                /// Returns the angle in radians, within `0..2π`.
+                pub fn to_radians(self) -> f32 {
+                    self.to_degrees().to_radians()
+                }
+            }
+
+            impl fmt::Display for $name {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    write!(f, "{}°", self.to_degrees())
+                }
+            }
+
+            impl_type!($name, $inner);
+        )*
+    };
+}
+
+impl_angle! {
+    Angle8, u8, 256, "1.4";
+    Angle16, u16, 65536, "0.0055";
+}
+
+/// A unit vector quantized to 24 bits, IE: a surface normal or the direction an entity is facing.
+///
+/// The vector is octahedron encoded, with 12 bits for each of the two coordinates,
+/// which keeps every component within about 0.001 of the original vector.
+/// The vector is written as a big endian `u24`.
+///
+/// # Example
+/// ```rust
+/// use binary_util::types::UnitVec24;
+///
+/// fn main() {
+///     let facing = UnitVec24::from_vector([0.6, 0.0, -0.8]);
+///     let [x, y, z] = facing.to_vector();
+///     assert!((x - 0.6).abs() < 0.001 && y.abs() < 0.001 && (z + 0.8).abs() < 0.001);
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct UnitVec24(pub u32);
+
+impl UnitVec24 {
+    /// The amount of bytes this type takes up on the wire.
+    pub const BYTES: usize = 3;
+    /// The largest value of each 12 bit coordinate.
+    const MAX_COORD: f32 = 4095.0;
+
+    pub fn new(val: u32) -> Self {
+        if val <= u24::MAX {
+            Self(val)
+        } else {
+            panic!("UnitVec24: value out of range")
+        }
+    }
+
+    /// Quantizes `vector`, which is normalized first. A zero vector is encoded as `[0.0, 0.0, 1.0]`.
+    pub fn from_vector([x, y, z]: [f32; 3]) -> Self {
+        let norm = x.abs() + y.abs() + z.abs();
+        if norm == 0.0 || !norm.is_finite() {
+            return Self::from_vector([0.0, 0.0, 1.0]);
+        }
+        let (x, y, z) = (x / norm, y / norm, z / norm);
+        // the lower half of the octahedron is folded over the upper half.
+        let (u, v) = match z < 0.0 {
+            true => ((1.0 - y.abs()) * x.signum(), (1.0 - x.abs()) * y.signum()),
+            false => (x, y),
+        };
+        let quantize = |c: f32| ((c * 0.5 + 0.5) * Self::MAX_COORD).round() as u32;
+        Self(quantize(u) << 12 | quantize(v))
+    }
+
+    /// Returns the normalized vector.
+    pub fn to_vector(self) -> [f32; 3] {
+        let dequantize = |q: u32| (q & 0xfff) as f32 / Self::MAX_COORD * 2.0 - 1.0;
+        let (u, v) = (dequantize(self.0 >> 12), dequantize(self.0));
+        let z = 1.0 - u.abs() - v.abs();
+        let (x, y) = match z < 0.0 {
+            true => ((1.0 - v.abs()) * u.signum(), (1.0 - u.abs()) * v.signum()),
+            false => (u, v),
+        };
+        let len = (x * x + y * y + z * z).sqrt();
+        [x / len, y / len, z / len]
+    }
+}
+
+impl fmt::Display for UnitVec24 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [x, y, z] = self.to_vector();
+        write!(f, "({}, {}, {})", x, y, z)
+    }
+}
+
+impl_type!(UnitVec24, u32);
+
+/// Generates a packet id newtype, with a named constant for every id, so raw numbers do not have to be passed around.
+///
+/// The id is encoded as one of `u8`, `u16`, `u32` or `varu32`. Ids that have no constant can still be read,
+/// which `is_known` can be used to check.
+///
+/// When followed by `for Enum`, where `Enum` is derived with `BinaryIo`, a compile time check ensures every variant
+/// of the enum has an id, and every id is the discriminant of a variant.
+/// Ids must be unique in either case.
+///
+/// Followed by `, impl Mock`, the newtype also implements `testing::Mock` by picking one of its constants.
+/// This is opt-in so the impl is not part of the public API of crates that only use the ids,
+/// those that only need it in their own tests can implement `Mock` under `#[cfg(test)]` instead.
+/// ```compile_fail
+/// use binary_util::testing::{Mock, MockRng};
+///
+/// binary_util::packet_id! {
+///     struct ChannelId: u8 {
+///         CHAT = 1,
+///     }
+/// }
+///
+/// fn main() {
+///     ChannelId::mock(&mut MockRng::new(7));
+/// }
+/// ```
+///
+/// # Example
+/// ```rust
+/// use binary_util::interfaces::{Reader, Writer};
+/// use binary_util::BinaryIo;
+///
+/// #[derive(BinaryIo)]
+/// #[repr(u8)]
+/// enum Packet {
+///     Login(String) = 0x01,
+///     Disconnect = 0x05,
+/// }
+///
+/// binary_util::packet_id! {
+///     /// The id of every packet of the protocol.
+///     pub struct PacketId: varu32 for Packet {
+///         LOGIN = 0x01,
+///         DISCONNECT = 0x05,
+///     }
+/// }
+///
+/// fn main() {
+///     let buf = PacketId::DISCONNECT.write_to_bytes().unwrap();
+///     let id = PacketId::read_from_slice(buf.as_slice()).unwrap();
+///     assert_eq!(id, PacketId::DISCONNECT);
+///     assert_eq!(id.name(), Some("DISCONNECT"));
+///     assert!(!PacketId::new(0x7f).is_known());
+/// }
+/// ```
+#[macro_export]
+macro_rules! packet_id {
+    (
+        $(#[$meta: meta])*
+        $vis: vis struct $name: ident: $wire: ident $(for $enum: ty)? $(, impl $mock: ident)? {
+            $( $(#[$id_meta: meta])* $id: ident = $value: expr ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        $vis struct $name($crate::packet_id!(@repr $wire));
+
+        impl $name {
+            $(
+                $(#[$id_meta])*
+                pub const $id: Self = Self($value);
+            )*
+
+            /// Every id with a constant, and the name of the constant.
+            pub const ALL: &'static [(Self, &'static str)] = &[$((Self::$id, stringify!($id))),*];
+
+            pub const fn new(id: $crate::packet_id!(@repr $wire)) -> Self {
+                Self(id)
+            }
+
+            /// Returns the raw id.
+            pub const fn get(self) -> $crate::packet_id!(@repr $wire) {
+                self.0
+            }
+
+            /// Returns the name of the constant of this id, if any.
+            pub fn name(self) -> ::std::option::Option<&'static str> {
+                Self::ALL
+                    .iter()
+                    .find(|(id, _)| *id == self)
+                    .map(|(_, name)| *name)
+            }
+
+            /// Returns whether this id has a constant.
+            pub fn is_known(self) -> bool {
+                self.name().is_some()
+            }
+        }
+
+        const _: () = {
+            let ids = $name::ALL;
+            let mut i = 0;
+            while i < ids.len() {
+                let mut j = i + 1;
+                while j < ids.len() {
+                    if ids[i].0 .0 == ids[j].0 .0 {
+                        panic!(concat!("Two ids of `", stringify!($name), "` have the same value."));
+                    }
+                    j += 1;
+                }
+                i += 1;
+            }
+        };
+
+        $(
+            const _: () = {
+                let ids = $name::ALL;
+                let variants = <$enum as $crate::interfaces::BinaryDiscriminant>::DISCRIMINANTS;
+                if ids.len() != variants.len() {
+                    panic!(concat!("`", stringify!($name), "` must have exactly one id for every variant of `", stringify!($enum), "`."));
+                }
+                let mut i = 0;
+                while i < variants.len() {
+                    let mut j = 0;
+                    while j < ids.len() && ids[j].0 .0 as i128 != variants[i].0 as i128 {
+                        j += 1;
+                    }
+                    if j == ids.len() {
+                        panic!(concat!("A variant of `", stringify!($enum), "` has no id in `", stringify!($name), "`."));
+                    }
+                    i += 1;
+                }
+            };
+        )?
+
+        impl ::std::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                match self.name() {
+                    Some(name) => write!(f, "{} ({:#x})", name, self.0),
+                    None => write!(f, "{:#x}", self.0),
+                }
+            }
+        }
+
+        impl ::std::convert::From<$name> for $crate::packet_id!(@repr $wire) {
+            fn from(id: $name) -> Self {
+                id.0
+            }
+        }
+
+        impl $crate::interfaces::Reader<$name> for $name {
+            fn read(buf: &mut $crate::io::ByteReader) -> ::std::result::Result<$name, ::std::io::Error> {
+                Ok($name($crate::packet_id!(@read $wire, buf)))
+            }
+        }
+
+        impl $crate::interfaces::Writer for $name {
+            fn write(&self, buf: &mut $crate::io::ByteWriter) -> ::std::result::Result<(), ::std::io::Error> {
+                $crate::packet_id!(@write $wire, buf, self.0)
+            }
+        }
+
+        $($crate::packet_id!(@impl $mock, $name);)?
+    };
+    (@impl Mock, $name: ident) => {
+        impl $crate::testing::Mock for $name {
+            fn mock(rng: &mut $crate::testing::MockRng) -> Self {
+                match Self::ALL.len() {
+                    0 => Self($crate::testing::Mock::mock(rng)),
+                    len => Self::ALL[rng.below(len)].0,
+                }
+            }
+        }
+    };
+    (@impl $other: ident, $name: ident) => {
+        compile_error!(concat!("Packet ids can not implement `", stringify!($other), "`, only `Mock` is supported"));
+    };
+    (@repr u8) => { u8 };
+    (@repr u16) => { u16 };
+    (@repr u32) => { u32 };
+    (@repr varu32) => { u32 };
+    (@repr $other: ident) => {
+        compile_error!(concat!("Packet ids can not be encoded as `", stringify!($other), "`, use one of: u8, u16, u32, varu32"))
+    };
+    (@read u8, $buf: ident) => { $buf.read_u8()? };
+    (@read u16, $buf: ident) => { $buf.read_u16()? };
+    (@read u32, $buf: ident) => { $buf.read_u32()? };
+    (@read varu32, $buf: ident) => { $buf.read_var_u32()? };
+    (@write u8, $buf: ident, $id: expr) => { $buf.write_u8($id) };
+    (@write u16, $buf: ident, $id: expr) => { $buf.write_u16($id) };
+    (@write u32, $buf: ident, $id: expr) => { $buf.write_u32($id) };
+    (@write varu32, $buf: ident, $id: expr) => { $buf.write_var_u32($id) };
+}
+
+/// A generic tagged tree value, in the format used by Minecraft's NBT, enabled with the `tagged` feature.
+/// See [`tagged::Tag`] and [`tagged::Nbt`].
+#[cfg(feature = "tagged")]
+pub mod tagged;
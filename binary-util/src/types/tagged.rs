@@ -0,0 +1,462 @@
+//! A generic tagged tree value, in the format used by Minecraft's NBT.
+//!
+//! Every value is prefixed with the id of its tag, and compounds hold a list of named tags
+//! terminated by [`TAG_END`]. All numbers and lengths are big endian, and strings are prefixed
+//! with their length as a `u16`.
+//!
+//! Strings are read and written as standard UTF-8, whereas Java writes them as modified UTF-8, which only
+//! differs for `\0` and chars beyond U+FFFF. Java-produced strings containing those are rejected when read,
+//! and strings containing them are written in a form Java does not read back.
+//!
+//! [`Tag`] reads and writes a single unnamed tag, as sent over the network by newer versions
+//! of the protocol, while [`Nbt`] reads and writes a named root compound, as stored in files.
+//!
+//! This module is enabled with the `tagged` feature.
+//!
+//! # Example
+//! ```rust
+//! use binary_util::interfaces::{Reader, Writer};
+//! use binary_util::types::tagged::{Compound, Nbt, Tag};
+//!
+//! fn main() {
+//!     let player = Compound::new()
+//!         .with("name", "Steve")
+//!         .with("health", 20.0f32)
+//!         .with_compound("pos", |pos| pos.with("x", 1i32).with("y", 64i32).with("z", -3i32))
+//!         .with_list("inventory", [Tag::from(1i16), Tag::from(2i16)]);
+//!
+//!     let nbt = Nbt::new("player", player);
+//!     let bytes = nbt.write_to_bytes().unwrap();
+//!     let read = Nbt::read_from_slice(bytes.as_slice()).unwrap();
+//!
+//!     assert_eq!(read, nbt);
+//!     assert_eq!(read.root.get("name").and_then(Tag::as_str), Some("Steve"));
+//! }
+//! ```
+use bytes::Buf;
+use std::collections::HashSet;
+
+use crate::interfaces::{Reader, Writer};
+use crate::io::{ByteReader, ByteWriter};
+
+pub const TAG_END: u8 = 0;
+pub const TAG_BYTE: u8 = 1;
+pub const TAG_SHORT: u8 = 2;
+pub const TAG_INT: u8 = 3;
+pub const TAG_LONG: u8 = 4;
+pub const TAG_FLOAT: u8 = 5;
+pub const TAG_DOUBLE: u8 = 6;
+pub const TAG_BYTE_ARRAY: u8 = 7;
+pub const TAG_STRING: u8 = 8;
+pub const TAG_LIST: u8 = 9;
+pub const TAG_COMPOUND: u8 = 10;
+pub const TAG_INT_ARRAY: u8 = 11;
+pub const TAG_LONG_ARRAY: u8 = 12;
+
+const ERR_UNKNOWN_TAG: &str = "Unknown tag id";
+const ERR_UNEXPECTED_END: &str = "End tag found where a value was expected";
+const ERR_NEGATIVE_LENGTH: &str = "Tag length is negative";
+const ERR_LENGTH_OVERFLOW: &str = "Tag length does not fit in an i32";
+const ERR_MIXED_LIST: &str = "List contains tags of different types";
+const ERR_ROOT_NOT_COMPOUND: &str = "Root tag is not a compound";
+const ERR_DUPLICATE_NAME: &str = "Compound contains the same name twice";
+
+/// A single value within a tagged tree.
+///
+/// Lists hold tags of a single type, which is checked when written. An empty list is always
+/// written with the element type of [`TAG_END`], so the element type of an empty list
+/// is not kept when it is read back.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Tag {
+    Byte(i8),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    ByteArray(Vec<i8>),
+    String(String),
+    List(Vec<Tag>),
+    Compound(Compound),
+    IntArray(Vec<i32>),
+    LongArray(Vec<i64>),
+}
+
+impl Tag {
+    /// Returns the id this tag is written with.
+    pub fn id(&self) -> u8 {
+        match self {
+            Tag::Byte(_) => TAG_BYTE,
+            Tag::Short(_) => TAG_SHORT,
+            Tag::Int(_) => TAG_INT,
+            Tag::Long(_) => TAG_LONG,
+            Tag::Float(_) => TAG_FLOAT,
+            Tag::Double(_) => TAG_DOUBLE,
+            Tag::ByteArray(_) => TAG_BYTE_ARRAY,
+            Tag::String(_) => TAG_STRING,
+            Tag::List(_) => TAG_LIST,
+            Tag::Compound(_) => TAG_COMPOUND,
+            Tag::IntArray(_) => TAG_INT_ARRAY,
+            Tag::LongArray(_) => TAG_LONG_ARRAY,
+        }
+    }
+
+    /// Returns the value of any integer tag, widened to an `i64`.
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            Tag::Byte(v) => Some(v as i64),
+            Tag::Short(v) => Some(v as i64),
+            Tag::Int(v) => Some(v as i64),
+            Tag::Long(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of any floating point tag, widened to an `f64`.
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            Tag::Float(v) => Some(v as f64),
+            Tag::Double(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Tag::String(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_list(&self) -> Option<&[Tag]> {
+        match self {
+            Tag::List(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_compound(&self) -> Option<&Compound> {
+        match self {
+            Tag::Compound(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_compound_mut(&mut self) -> Option<&mut Compound> {
+        match self {
+            Tag::Compound(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Reads the payload of a tag with the given id, without the id itself.
+    pub fn read_payload(buf: &mut ByteReader, id: u8) -> Result<Tag, std::io::Error> {
+        Ok(match id {
+            TAG_BYTE => Tag::Byte(buf.read_i8()?),
+            TAG_SHORT => Tag::Short(buf.read_i16()?),
+            TAG_INT => Tag::Int(buf.read_i32()?),
+            TAG_LONG => Tag::Long(buf.read_i64()?),
+            TAG_FLOAT => Tag::Float(buf.read_f32()?),
+            TAG_DOUBLE => Tag::Double(buf.read_f64()?),
+            TAG_BYTE_ARRAY => Tag::ByteArray(read_array(buf, 1, ByteReader::read_i8)?),
+            TAG_STRING => Tag::String(buf.read_string_u16be()?),
+            TAG_LIST => buf.nested(|buf| {
+                let kind = buf.read_u8()?;
+                let len = read_len(buf)?;
+                if kind == TAG_END && len > 0 {
+                    return Err(
+                        buf.error_at_offset(std::io::ErrorKind::InvalidData, ERR_UNEXPECTED_END)
+                    );
+                }
+                let mut items = Vec::with_capacity(len.min(buf.remaining()));
+                for _ in 0..len {
+                    items.push(Tag::read_payload(buf, kind)?);
+                }
+                Ok(Tag::List(items))
+            })?,
+            TAG_COMPOUND => Tag::Compound(buf.nested(Compound::read_payload)?),
+            TAG_INT_ARRAY => Tag::IntArray(read_array(buf, 4, ByteReader::read_i32)?),
+            TAG_LONG_ARRAY => Tag::LongArray(read_array(buf, 8, ByteReader::read_i64)?),
+            TAG_END => {
+                return Err(buf.error_at_offset(std::io::ErrorKind::InvalidData, ERR_UNEXPECTED_END))
+            }
+            _ => return Err(buf.error_at_offset(std::io::ErrorKind::InvalidData, ERR_UNKNOWN_TAG)),
+        })
+    }
+
+    /// Writes the payload of this tag, without its id.
+    pub fn write_payload(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+        match self {
+            Tag::Byte(v) => buf.write_i8(*v),
+            Tag::Short(v) => buf.write_i16(*v),
+            Tag::Int(v) => buf.write_i32(*v),
+            Tag::Long(v) => buf.write_i64(*v),
+            Tag::Float(v) => buf.write_f32(*v),
+            Tag::Double(v) => buf.write_f64(*v),
+            Tag::ByteArray(v) => write_array(buf, v, |buf, v| buf.write_i8(*v)),
+            Tag::String(v) => buf.write_string_u16be(v),
+            Tag::List(items) => {
+                let kind = items.first().map_or(TAG_END, Tag::id);
+                if items.iter().any(|item| item.id() != kind) {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        ERR_MIXED_LIST,
+                    ));
+                }
+                buf.write_u8(kind)?;
+                write_len(buf, items.len())?;
+                items.iter().try_for_each(|item| item.write_payload(buf))
+            }
+            Tag::Compound(v) => v.write_payload(buf),
+            Tag::IntArray(v) => write_array(buf, v, |buf, v| buf.write_i32(*v)),
+            Tag::LongArray(v) => write_array(buf, v, |buf, v| buf.write_i64(*v)),
+        }
+    }
+}
+
+impl Reader<Tag> for Tag {
+    fn read(buf: &mut ByteReader) -> Result<Tag, std::io::Error> {
+        let id = buf.read_u8()?;
+        Tag::read_payload(buf, id)
+    }
+}
+
+impl Writer for Tag {
+    fn write(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+        buf.write_u8(self.id())?;
+        self.write_payload(buf)
+    }
+}
+
+macro_rules! impl_tag_from {
+    ($($ty: ty => $variant: ident),*) => {
+        $(
+            impl From<$ty> for Tag {
+                fn from(val: $ty) -> Self {
+                    Tag::$variant(val)
+                }
+            }
+        )*
+    };
+}
+
+impl_tag_from!(
+    i8 => Byte,
+    i16 => Short,
+    i32 => Int,
+    i64 => Long,
+    f32 => Float,
+    f64 => Double,
+    Vec<i8> => ByteArray,
+    String => String,
+    Vec<Tag> => List,
+    Compound => Compound,
+    Vec<i32> => IntArray,
+    Vec<i64> => LongArray
+);
+
+impl From<&str> for Tag {
+    fn from(val: &str) -> Self {
+        Tag::String(val.to_string())
+    }
+}
+
+impl From<bool> for Tag {
+    fn from(val: bool) -> Self {
+        Tag::Byte(val as i8)
+    }
+}
+
+/// A list of named tags, kept in the order they were inserted, so a compound
+/// that is read and written again produces the same bytes.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Compound(Vec<(String, Tag)>);
+
+impl Compound {
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Adds the given tag, returning `self`, which allows compounds to be built in a single expression.
+    pub fn with(mut self, name: impl Into<String>, tag: impl Into<Tag>) -> Self {
+        self.insert(name, tag);
+        self
+    }
+
+    /// Adds a compound built by `f`, returning `self`.
+    pub fn with_compound(
+        self,
+        name: impl Into<String>,
+        f: impl FnOnce(Compound) -> Compound,
+    ) -> Self {
+        self.with(name, f(Compound::new()))
+    }
+
+    /// Adds a list of the given tags, returning `self`.
+    pub fn with_list(
+        self,
+        name: impl Into<String>,
+        items: impl IntoIterator<Item = impl Into<Tag>>,
+    ) -> Self {
+        self.with(name, Tag::List(items.into_iter().map(Into::into).collect()))
+    }
+
+    /// Inserts the given tag, returning the tag it replaced, if any.
+    /// A replaced tag keeps its position within the compound.
+    pub fn insert(&mut self, name: impl Into<String>, tag: impl Into<Tag>) -> Option<Tag> {
+        let name = name.into();
+        let tag = tag.into();
+        match self.0.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, old)) => Some(std::mem::replace(old, tag)),
+            None => {
+                self.0.push((name, tag));
+                None
+            }
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Tag> {
+        self.0.iter().find(|(n, _)| n == name).map(|(_, tag)| tag)
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Tag> {
+        self.0
+            .iter_mut()
+            .find(|(n, _)| n == name)
+            .map(|(_, tag)| tag)
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<Tag> {
+        let pos = self.0.iter().position(|(n, _)| n == name)?;
+        Some(self.0.remove(pos).1)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Tag)> {
+        self.0.iter().map(|(n, tag)| (n.as_str(), tag))
+    }
+
+    /// Reads named tags until an end tag is found.
+    ///
+    /// A name appearing twice is rejected, rather than the later tag replacing the earlier one.
+    pub fn read_payload(buf: &mut ByteReader) -> Result<Compound, std::io::Error> {
+        let mut compound = Compound::new();
+        // `insert` scans every tag for the name, so names are looked up here instead.
+        let mut names = HashSet::new();
+        loop {
+            let id = buf.read_u8()?;
+            if id == TAG_END {
+                return Ok(compound);
+            }
+            let name = buf.read_string_u16be()?;
+            if !names.insert(name.clone()) {
+                return Err(
+                    buf.error_at_offset(std::io::ErrorKind::InvalidData, ERR_DUPLICATE_NAME)
+                );
+            }
+            let tag = Tag::read_payload(buf, id)?;
+            compound.0.push((name, tag));
+        }
+    }
+
+    /// Writes every named tag, followed by an end tag.
+    pub fn write_payload(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+        for (name, tag) in &self.0 {
+            buf.write_u8(tag.id())?;
+            buf.write_string_u16be(name)?;
+            tag.write_payload(buf)?;
+        }
+        buf.write_u8(TAG_END)
+    }
+}
+
+impl<N: Into<String>, T: Into<Tag>> FromIterator<(N, T)> for Compound {
+    fn from_iter<I: IntoIterator<Item = (N, T)>>(iter: I) -> Self {
+        let mut compound = Compound::new();
+        for (name, tag) in iter {
+            compound.insert(name, tag);
+        }
+        compound
+    }
+}
+
+/// A named root compound, as found at the start of an NBT file.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Nbt {
+    pub name: String,
+    pub root: Compound,
+}
+
+impl Nbt {
+    pub fn new(name: impl Into<String>, root: Compound) -> Self {
+        Self {
+            name: name.into(),
+            root,
+        }
+    }
+}
+
+impl Reader<Nbt> for Nbt {
+    fn read(buf: &mut ByteReader) -> Result<Nbt, std::io::Error> {
+        if buf.read_u8()? != TAG_COMPOUND {
+            return Err(buf.error_at_offset(std::io::ErrorKind::InvalidData, ERR_ROOT_NOT_COMPOUND));
+        }
+        let name = buf.read_string_u16be()?;
+        let root = buf.nested(Compound::read_payload)?;
+        Ok(Nbt { name, root })
+    }
+}
+
+impl Writer for Nbt {
+    fn write(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+        buf.write_u8(TAG_COMPOUND)?;
+        buf.write_string_u16be(&self.name)?;
+        self.root.write_payload(buf)
+    }
+}
+
+fn read_len(buf: &mut ByteReader) -> Result<usize, std::io::Error> {
+    let len = buf.read_i32()?;
+    let len = usize::try_from(len)
+        .map_err(|_| buf.error_at_offset(std::io::ErrorKind::InvalidData, ERR_NEGATIVE_LENGTH))?;
+    buf.check_alloc(len)
+}
+
+fn write_len(buf: &mut ByteWriter, len: usize) -> Result<(), std::io::Error> {
+    let len = i32::try_from(len)
+        .map_err(|_| std::io::Error::new(std::io::ErrorKind::InvalidInput, ERR_LENGTH_OVERFLOW))?;
+    buf.write_i32(len)
+}
+
+fn read_array<T>(
+    buf: &mut ByteReader,
+    width: usize,
+    read: impl Fn(&mut ByteReader) -> Result<T, std::io::Error>,
+) -> Result<Vec<T>, std::io::Error> {
+    let len = read_len(buf)?;
+    let mut items = Vec::with_capacity(len.min(buf.remaining() / width));
+    for _ in 0..len {
+        items.push(read(buf)?);
+    }
+    Ok(items)
+}
+
+fn write_array<T>(
+    buf: &mut ByteWriter,
+    items: &[T],
+    write: impl Fn(&mut ByteWriter, &T) -> Result<(), std::io::Error>,
+) -> Result<(), std::io::Error> {
+    write_len(buf, items.len())?;
+    items.iter().try_for_each(|item| write(buf, item))
+}
@@ -0,0 +1,90 @@
+use std::fmt;
+
+/// Compares two byte slices in constant time, relative to their length.
+///
+/// Every byte of both slices is compared, regardless of where the first mismatch is,
+/// so the time taken does not reveal how much of a secret was guessed correctly.
+/// Slices of different lengths return `false` immediately, as the length of a secret is not
+/// considered secret.
+///
+/// ```rust
+/// use binary_util::util::ct_eq;
+///
+/// fn main() {
+///     assert!(ct_eq(b"token", b"token"));
+///     assert!(!ct_eq(b"token", b"tokem"));
+///     assert!(!ct_eq(b"token", b"tokens"));
+/// }
+/// ```
+pub fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let diff = a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y));
+    // prevents the compiler from short-circuiting the fold once a mismatch is found.
+    std::hint::black_box(diff) == 0
+}
+
+/// A wrapper for sensitive values, such as auth tokens, read from or written to the wire.
+///
+/// `Secret` is encoded exactly like `T`, but its `Debug` implementation never prints the
+/// value, and comparing two secrets is done in constant time with [`ct_eq`].
+///
+/// ```rust
+/// use binary_util::interfaces::{Reader, Writer};
+/// use binary_util::util::Secret;
+/// use binary_util::BinaryIo;
+///
+/// #[derive(BinaryIo, Debug)]
+/// struct Login {
+///     username: String,
+///     token: Secret<String>,
+/// }
+///
+/// fn main() {
+///     let login = Login { username: "john".into(), token: Secret::new("hunter2".into()) };
+///     assert_eq!(format!("{:?}", login), "Login { username: \"john\", token: Secret([REDACTED]) }");
+///
+///     let read = Login::read_from_slice(login.write_to_bytes().unwrap().as_slice()).unwrap();
+///     assert_eq!(read.token, login.token);
+///     assert_eq!(read.token.expose(), "hunter2");
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct Secret<T>(T);
+
+impl<T> Secret<T> {
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// Returns a reference to the secret value.
+    pub fn expose(&self) -> &T {
+        &self.0
+    }
+
+    /// Consumes the wrapper, returning the secret value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for Secret<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> fmt::Debug for Secret<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Secret([REDACTED])")
+    }
+}
+
+impl<T: AsRef<[u8]>> PartialEq for Secret<T> {
+    fn eq(&self, other: &Self) -> bool {
+        ct_eq(self.0.as_ref(), other.0.as_ref())
+    }
+}
+
+impl<T: AsRef<[u8]>> Eq for Secret<T> {}
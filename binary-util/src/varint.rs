@@ -0,0 +1,234 @@
+use std::io::ErrorKind;
+
+use crate::io::{BinaryError, MAX_VAR_U32_LEN};
+
+/// The maximum amount of bytes a var-int 64-bit integer can take up on the wire.
+pub const MAX_VAR_U64_LEN: usize = 10;
+
+const ERR_OVERFLOW_32: &str = "Varint overflow's 32-bit integer";
+const ERR_OVERFLOW_64: &str = "Varint overflow's 64-bit integer";
+
+/// The progress of a `Decoder` after being fed a byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decode<T> {
+    /// The var-int is complete, and the decoder has been reset for the next one.
+    Complete(T),
+    /// The var-int continues in the next byte.
+    NeedMore,
+}
+
+/// A var-int decoder which is fed one byte at a time, keeping its progress between calls.
+///
+/// Var-ints are LEB128 encoded, IE: the least significant group of 7 bits comes first.
+/// The `_be` decoders read the alternate order used by some protocols, where the most significant group comes first.
+///
+/// This allows a var-int split across several reads, IE: from a non-blocking socket polled
+/// in an async loop, to be decoded without buffering it first.
+/// `ReadExt::read_var_u32` and `ReadExt::read_var_u64` are implemented with this decoder.
+///
+/// ```rust
+/// use binary_util::varint::{Decode, Decoder};
+///
+/// fn main() {
+///     let mut decoder = Decoder::u32();
+///     assert_eq!(decoder.push(172).unwrap(), Decode::NeedMore);
+///     assert_eq!(decoder.push(2).unwrap(), Decode::Complete(300));
+///
+///     // the decoder is reset once a var-int completes
+///     assert_eq!(decoder.push(1).unwrap(), Decode::Complete(1));
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Decoder {
+    value: u64,
+    len: usize,
+    max_len: usize,
+    overflow: &'static str,
+    big_endian: bool,
+}
+
+impl Decoder {
+    /// Creates a decoder for a var-int of up to 32 bits, IE: `varu32`.
+    pub const fn u32() -> Self {
+        Self::with_max_len(MAX_VAR_U32_LEN, ERR_OVERFLOW_32)
+    }
+
+    /// Creates a decoder for a var-int of up to 64 bits, IE: `varu64`.
+    pub const fn u64() -> Self {
+        Self::with_max_len(MAX_VAR_U64_LEN, ERR_OVERFLOW_64)
+    }
+
+    /// Creates a decoder for a var-int of up to 32 bits, with the most significant group first, IE: `BE<varu32>`.
+    pub const fn u32_be() -> Self {
+        Self::u32().big_endian()
+    }
+
+    /// Creates a decoder for a var-int of up to 64 bits, with the most significant group first, IE: `BE<varu64>`.
+    pub const fn u64_be() -> Self {
+        Self::u64().big_endian()
+    }
+
+    const fn with_max_len(max_len: usize, overflow: &'static str) -> Self {
+        Self {
+            value: 0,
+            len: 0,
+            max_len,
+            overflow,
+            big_endian: false,
+        }
+    }
+
+    const fn big_endian(mut self) -> Self {
+        self.big_endian = true;
+        self
+    }
+
+    /// Feeds the next byte of the var-int to the decoder.
+    ///
+    /// If the var-int does not end within the maximum length of the decoder, an error is returned
+    /// and the decoder is reset.
+    #[inline]
+    pub fn push(&mut self, byte: u8) -> Result<Decode<u64>, std::io::Error> {
+        self.push_raw(byte).map_err(Into::into)
+    }
+
+    /// Feeds the next byte of the var-int to the decoder like `push`, returning the `BinaryError` itself,
+    /// so readers without detailed errors do not allocate on failure.
+    #[inline]
+    pub(crate) fn push_raw(&mut self, byte: u8) -> Result<Decode<u64>, BinaryError> {
+        if self.big_endian {
+            self.value = (self.value << 7) | (byte & 0x7F) as u64;
+        } else {
+            self.value |= ((byte & 0x7F) as u64) << (self.len * 7);
+        }
+        self.len += 1;
+
+        if byte & 0x80 == 0 {
+            let value = self.value;
+            self.reset();
+            return Ok(Decode::Complete(value));
+        }
+        if self.len >= self.max_len {
+            self.reset();
+            return Err(BinaryError::new(ErrorKind::Other, self.overflow).overflowing());
+        }
+        Ok(Decode::NeedMore)
+    }
+
+    /// Returns the amount of bytes fed to the decoder for the current var-int.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether no bytes have been fed to the decoder for the current var-int.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Discards the progress of the current var-int.
+    pub fn reset(&mut self) {
+        self.value = 0;
+        self.len = 0;
+    }
+}
+
+/// Encodes `num` as a var-int into `out`, returning the encoded bytes.
+///
+/// ```rust
+/// use binary_util::varint::{encode, MAX_VAR_U64_LEN};
+///
+/// fn main() {
+///     let mut out = [0; MAX_VAR_U64_LEN];
+///     assert_eq!(encode(300, &mut out), &[172, 2]);
+/// }
+/// ```
+#[inline]
+pub fn encode(num: u64, out: &mut [u8; MAX_VAR_U64_LEN]) -> &[u8] {
+    let mut x = num;
+    let mut len = 0;
+    while x >= 0x80 {
+        out[len] = (x as u8) | 0x80;
+        x >>= 7;
+        len += 1;
+    }
+    out[len] = x as u8;
+    &out[..=len]
+}
+
+/// Encodes `num` as a var-int padded to `MAX_VAR_U32_LEN` bytes with empty continuation bytes into `out`,
+/// IE: a length prefix back-patched into a reserved slot with `ByteWriter::write_var_u32_at`.
+///
+/// The padded encoding is not the shortest form of `num`, so readers with canonical var-ints reject it.
+///
+/// ```rust
+/// use binary_util::io::MAX_VAR_U32_LEN;
+/// use binary_util::varint::encode_padded;
+///
+/// fn main() {
+///     let mut out = [0; MAX_VAR_U32_LEN];
+///     encode_padded(255, &mut out);
+///     assert_eq!(out, [255, 129, 128, 128, 0]);
+/// }
+/// ```
+#[inline]
+pub fn encode_padded(num: u32, out: &mut [u8; MAX_VAR_U32_LEN]) {
+    let mut x = num;
+    for (i, byte) in out.iter_mut().enumerate() {
+        *byte = (x & 0x7F) as u8 | if i + 1 < MAX_VAR_U32_LEN { 0x80 } else { 0 };
+        x >>= 7;
+    }
+}
+
+/// Encodes `num` as a var-int with the most significant group first into `out`, returning the encoded bytes.
+///
+/// ```rust
+/// use binary_util::varint::{encode_be, MAX_VAR_U64_LEN};
+///
+/// fn main() {
+///     let mut out = [0; MAX_VAR_U64_LEN];
+///     assert_eq!(encode_be(300, &mut out), &[130, 44]);
+/// }
+/// ```
+#[inline]
+pub fn encode_be(num: u64, out: &mut [u8; MAX_VAR_U64_LEN]) -> &[u8] {
+    let len = encoded_len(num);
+    for (i, byte) in out[..len].iter_mut().enumerate() {
+        let shift = (len - 1 - i) * 7;
+        *byte = ((num >> shift) as u8 & 0x7F) | if i + 1 < len { 0x80 } else { 0 };
+    }
+    &out[..len]
+}
+
+/// Returns the amount of bytes `num` will take up when encoded as a var-int, in either order.
+#[inline]
+pub const fn encoded_len(num: u64) -> usize {
+    let mut x = num;
+    let mut len = 1;
+    while x >= 0x80 {
+        x >>= 7;
+        len += 1;
+    }
+    len
+}
+
+/// Maps a signed integer to an unsigned one, so small negative numbers encode as short var-ints.
+///
+/// ```rust
+/// use binary_util::varint::{zigzag_decode, zigzag_encode};
+///
+/// fn main() {
+///     assert_eq!(zigzag_encode(-1), 1);
+///     assert_eq!(zigzag_encode(1), 2);
+///     assert_eq!(zigzag_decode(zigzag_encode(-300)), -300);
+/// }
+/// ```
+#[inline]
+pub const fn zigzag_encode(num: i64) -> u64 {
+    ((num << 1) ^ (num >> 63)) as u64
+}
+
+/// Reverses `zigzag_encode`.
+#[inline]
+pub const fn zigzag_decode(num: u64) -> i64 {
+    (num >> 1) as i64 ^ -((num & 1) as i64)
+}
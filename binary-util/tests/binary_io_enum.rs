@@ -1,5 +1,5 @@
-use binary_util::interfaces::{Reader, Writer};
-use binary_util::io::ByteReader;
+use binary_util::interfaces::{BinaryDiscriminant, Reader, Writer};
+use binary_util::io::{ByteReader, ByteWriter};
 use binary_util::BinaryIo;
 
 #[derive(BinaryIo, Debug)]
@@ -30,3 +30,261 @@ fn decode_test() {
         _ => panic!("Wrong packet type"),
     }
 }
+
+#[derive(BinaryIo, Debug, PartialEq)]
+#[repr(u8)]
+pub enum Bounds {
+    Low = 254,
+    High,
+}
+
+#[test]
+fn discriminant_at_repr_bound() {
+    assert_eq!(Bounds::High.write_to_bytes().unwrap().as_slice(), &[255]);
+    assert_eq!(Bounds::read_from_slice(&[255]).unwrap(), Bounds::High);
+    assert_eq!(Bounds::read_from_slice(&[254]).unwrap(), Bounds::Low);
+}
+
+#[derive(BinaryIo, Debug, PartialEq)]
+#[repr(i8)]
+pub enum Signed {
+    Neg = -2,
+    NegOne,
+    Zero,
+    Pos(u8) = 5,
+}
+
+#[test]
+fn negative_discriminants() {
+    assert_eq!(Signed::Neg.write_to_bytes().unwrap().as_slice(), &[0xFE]);
+    assert_eq!(Signed::NegOne.write_to_bytes().unwrap().as_slice(), &[0xFF]);
+    assert_eq!(Signed::Zero.write_to_bytes().unwrap().as_slice(), &[0]);
+    assert_eq!(Signed::Pos(1).write_to_bytes().unwrap().as_slice(), &[5, 1]);
+    assert_eq!(Signed::read_from_slice(&[0xFF]).unwrap(), Signed::NegOne);
+    assert_eq!(Signed::read_from_slice(&[5, 9]).unwrap(), Signed::Pos(9));
+}
+
+const PACKET_BASE: u16 = 0x100;
+
+#[derive(BinaryIo, Debug, PartialEq)]
+#[repr(u16)]
+pub enum ConstDiscriminant {
+    Login = PACKET_BASE,
+    Logout,
+    Kick(u8) = PACKET_BASE * 2 + 1,
+    Other = 3,
+}
+
+#[test]
+fn const_discriminants() {
+    assert_eq!(
+        ConstDiscriminant::Login
+            .write_to_bytes()
+            .unwrap()
+            .as_slice(),
+        &[1, 0]
+    );
+    assert_eq!(
+        ConstDiscriminant::Logout
+            .write_to_bytes()
+            .unwrap()
+            .as_slice(),
+        &[1, 1]
+    );
+    assert_eq!(
+        ConstDiscriminant::Kick(7)
+            .write_to_bytes()
+            .unwrap()
+            .as_slice(),
+        &[2, 1, 7]
+    );
+    assert_eq!(
+        ConstDiscriminant::Other
+            .write_to_bytes()
+            .unwrap()
+            .as_slice(),
+        &[0, 3]
+    );
+    assert_eq!(
+        ConstDiscriminant::read_from_slice(&[1, 1]).unwrap(),
+        ConstDiscriminant::Logout
+    );
+    assert_eq!(
+        ConstDiscriminant::read_from_slice(&[2, 1, 4]).unwrap(),
+        ConstDiscriminant::Kick(4)
+    );
+}
+
+#[derive(BinaryIo, Debug, PartialEq)]
+#[binary_io(untagged)]
+pub enum Untagged {
+    Long(u64),
+    Pair(u8, u8),
+    Empty,
+}
+
+#[test]
+fn untagged_enum() {
+    assert_eq!(
+        Untagged::Pair(1, 2).write_to_bytes().unwrap().as_slice(),
+        &[1, 2]
+    );
+    assert!(Untagged::Empty
+        .write_to_bytes()
+        .unwrap()
+        .as_slice()
+        .is_empty());
+
+    let mut reader = ByteReader::from(&[0, 0, 0, 0, 0, 0, 0, 9][..]);
+    assert_eq!(Untagged::read(&mut reader).unwrap(), Untagged::Long(9));
+
+    // `Long` fails to read, so the reader is rolled back before reading `Pair`.
+    let mut reader = ByteReader::from(&[3, 4, 5][..]);
+    assert_eq!(Untagged::read(&mut reader).unwrap(), Untagged::Pair(3, 4));
+    assert_eq!(reader.offset(), 2);
+
+    assert_eq!(Untagged::read_from_slice(&[]).unwrap(), Untagged::Empty);
+}
+
+#[derive(BinaryIo, Debug, PartialEq)]
+#[binary_io(external_tag)]
+#[repr(u8)]
+pub enum Payload {
+    Ping(u64) = 1,
+    Disconnect(String),
+    Empty,
+}
+
+#[test]
+fn external_tag() {
+    let payload = Payload::Disconnect("Bye".to_string());
+    assert_eq!(payload.discriminant(), 2);
+    assert_eq!(Payload::Empty.discriminant(), 3);
+
+    let mut writer = ByteWriter::new();
+    writer.write_u8(payload.discriminant()).unwrap();
+    writer.write_u16(0xFFFF).unwrap();
+    payload.write_payload(&mut writer).unwrap();
+    assert_eq!(writer.as_slice(), &[2, 0xFF, 0xFF, 3, b'B', b'y', b'e']);
+
+    let mut reader = ByteReader::from(writer.as_slice());
+    let tag = reader.read_u8().unwrap();
+    reader.read_u16().unwrap();
+    assert_eq!(Payload::read_variant(&mut reader, tag).unwrap(), payload);
+    assert!(Payload::read_variant(&mut reader, 9).is_err());
+
+    // the enum can still be written with its tag.
+    assert_eq!(Payload::Ping(1).write_to_bytes().unwrap().as_slice()[0], 1);
+}
+
+#[derive(BinaryIo, Debug, PartialEq)]
+#[repr(u8)]
+pub enum Reply<T> {
+    Ok(T),
+    Err(String),
+}
+
+#[test]
+fn generic_enum() {
+    let reply: Reply<u16> = Reply::Ok(300);
+    let buf = reply.write_to_bytes().unwrap();
+    assert_eq!(buf.as_slice(), &[0, 1, 44]);
+    assert_eq!(
+        Reply::<u16>::read_from_slice(buf.as_slice()).unwrap(),
+        reply
+    );
+
+    let reply: Reply<u16> = Reply::Err("no".into());
+    let buf = reply.write_to_bytes().unwrap();
+    assert_eq!(
+        Reply::<u16>::read_from_slice(buf.as_slice()).unwrap(),
+        reply
+    );
+}
+
+const BASE: i16 = 100;
+
+#[derive(BinaryIo, Debug)]
+#[repr(i16)]
+pub enum Registry {
+    Handshake = -1,
+    Ping(u64),
+    Pong(u64),
+    Custom(String) = BASE,
+    Next,
+}
+
+#[test]
+fn discriminant_table() {
+    assert_eq!(
+        Registry::DISCRIMINANTS,
+        &[
+            (-1, "Handshake"),
+            (0, "Ping"),
+            (1, "Pong"),
+            (100, "Custom"),
+            (101, "Next")
+        ]
+    );
+    assert_eq!(Registry::Handshake.discriminant(), -1);
+    assert_eq!(Registry::Pong(5).discriminant(), 1);
+    assert_eq!(Registry::Next.discriminant(), 101);
+
+    let name = |id: i16| {
+        Registry::DISCRIMINANTS
+            .iter()
+            .find(|(discriminant, _)| *discriminant == id)
+            .map(|(_, name)| *name)
+    };
+    assert_eq!(
+        name(Registry::Custom("a".into()).discriminant()),
+        Some("Custom")
+    );
+    assert_eq!(Reply::<u8>::DISCRIMINANTS, &[(0, "Ok"), (1, "Err")]);
+}
+
+#[derive(BinaryIo, Debug, PartialEq)]
+#[binary_io(deny_trailing)]
+#[repr(u8)]
+pub enum Command {
+    Stop,
+    Say(String),
+}
+
+#[test]
+fn enum_deny_trailing() {
+    assert_eq!(Command::read_from_slice(&[0]).unwrap(), Command::Stop);
+    assert!(Command::read_from_slice(&[0, 0]).is_err());
+    assert_eq!(
+        Command::read_from_slice(&[1, 1, b'a']).unwrap(),
+        Command::Say("a".into())
+    );
+    assert!(Command::read_from_slice(&[1, 1, b'a', b'b']).is_err());
+}
+
+#[derive(BinaryIo, Debug, PartialEq)]
+#[binary_io(skip_reader)]
+#[repr(u8)]
+pub enum Gamemode {
+    Survival,
+    Creative,
+}
+
+/// Unknown gamemodes are read as survival, rather than rejected.
+impl Reader<Gamemode> for Gamemode {
+    fn read(buf: &mut ByteReader) -> Result<Gamemode, std::io::Error> {
+        match buf.read_u8()? {
+            1 => Ok(Gamemode::Creative),
+            _ => Ok(Gamemode::Survival),
+        }
+    }
+}
+
+#[test]
+fn enum_skip_reader() {
+    assert_eq!(
+        Gamemode::Creative.write_to_bytes().unwrap().as_slice(),
+        &[1]
+    );
+    assert_eq!(Gamemode::read_from_slice(&[7]).unwrap(), Gamemode::Survival);
+}
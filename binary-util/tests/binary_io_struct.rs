@@ -1,6 +1,6 @@
 use binary_util::interfaces::{Reader, Writer};
-use binary_util::io::ByteReader;
-use binary_util::types::{BE, LE};
+use binary_util::io::{ByteReader, ByteWriter};
+use binary_util::types::{Prefixed, Raw, BE, LE};
 use binary_util::BinaryIo;
 
 #[derive(BinaryIo, Debug)]
@@ -124,3 +124,860 @@ fn mixed_endian_struct_write() {
         &[255, 255, 0, 1, 15, 44, 100, 0, 0, 0, 0, 0, 0, 0, 0, 5]
     );
 }
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct BoolWidthStruct {
+    #[bool_width(bit)]
+    a: bool,
+    #[bool_width(bit)]
+    b: bool,
+    #[bool_width(bit)]
+    c: bool,
+    d: u8,
+    #[bool_width(u32)]
+    e: bool,
+    #[bool_width(bit)]
+    f: bool,
+}
+
+#[test]
+fn bool_width_struct() {
+    let packet = BoolWidthStruct {
+        a: true,
+        b: false,
+        c: true,
+        d: 7,
+        e: true,
+        f: true,
+    };
+    let buf = packet.write_to_bytes().unwrap();
+    assert_eq!(buf.as_slice(), &[0b101, 7, 0, 0, 0, 1, 1]);
+
+    let mut reader = ByteReader::from(buf.as_slice());
+    assert_eq!(BoolWidthStruct::read(&mut reader).unwrap(), packet);
+
+    // a u32 width bool of 2 is only rejected by strict readers.
+    let corrupt = [0b101, 7, 0, 0, 0, 2, 1];
+    assert!(BoolWidthStruct::read_from_slice(&corrupt).unwrap().e);
+    let mut reader = ByteReader::from(&corrupt[..]).with_strict_bools(true);
+    assert_eq!(
+        BoolWidthStruct::read(&mut reader).unwrap_err().kind(),
+        std::io::ErrorKind::InvalidData
+    );
+}
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct PackedFlags(
+    #[bool_width(bit)] bool,
+    #[bool_width(bit)] bool,
+    #[bool_width(bit)] bool,
+    #[bool_width(bit)] bool,
+    #[bool_width(bit)] bool,
+    #[bool_width(bit)] bool,
+    #[bool_width(bit)] bool,
+    #[bool_width(bit)] bool,
+    #[bool_width(bit)] bool,
+);
+
+#[test]
+fn bool_width_overflows_byte() {
+    let flags = PackedFlags(true, false, false, false, false, false, false, true, true);
+    let buf = flags.write_to_bytes().unwrap();
+    assert_eq!(buf.as_slice(), &[0b1000_0001, 1]);
+    assert_eq!(PackedFlags::read_from_slice(buf.as_slice()).unwrap(), flags);
+}
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct Handshake {
+    #[skip]
+    is_server: bool,
+    id: u8,
+    #[write_if(self.is_server)]
+    token: u16,
+    #[skip_if(self.id == 0)]
+    extra: u8,
+}
+
+#[test]
+fn write_side_conditions() {
+    let server = Handshake {
+        is_server: true,
+        id: 1,
+        token: 3,
+        extra: 4,
+    };
+    let buf = server.write_to_bytes().unwrap();
+    assert_eq!(buf.as_slice(), &[1, 0, 3, 4]);
+
+    // fields are always read, regardless of the write condition.
+    let read = Handshake::read_from_slice(buf.as_slice()).unwrap();
+    assert_eq!((read.id, read.token, read.extra), (1, 3, 4));
+
+    let client = Handshake {
+        is_server: false,
+        id: 0,
+        token: 3,
+        extra: 4,
+    };
+    assert_eq!(client.write_to_bytes().unwrap().as_slice(), &[0]);
+}
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct UnnamedSkipIf(u8, #[skip_if(self.0 > 1)] u8);
+
+#[test]
+fn skip_if_unnamed() {
+    assert_eq!(
+        UnnamedSkipIf(1, 2).write_to_bytes().unwrap().as_slice(),
+        &[1, 2]
+    );
+    assert_eq!(
+        UnnamedSkipIf(2, 2).write_to_bytes().unwrap().as_slice(),
+        &[2]
+    );
+}
+
+#[derive(BinaryIo, Debug)]
+#[binary_io(debug_wire)]
+struct Login {
+    id: u8,
+    #[satisfy(self.id == 1)]
+    token: Option<u32>,
+    #[bool_width(bit)]
+    admin: bool,
+    #[bool_width(bit)]
+    muted: bool,
+    #[skip]
+    cached: u8,
+}
+
+#[derive(BinaryIo, Debug)]
+#[binary_io(debug_wire)]
+struct LoginPair(u16, #[skip_if(self.0 == 0)] String);
+
+#[test]
+fn debug_wire_ranges() {
+    use binary_util::interfaces::{WireDebug, WireField};
+
+    let login = Login {
+        id: 1,
+        token: Some(7),
+        admin: false,
+        muted: true,
+        cached: 0,
+    };
+    let mut writer = binary_util::io::ByteWriter::new();
+    let fields = login.write_fields(&mut writer).unwrap();
+    assert_eq!(
+        writer.as_slice(),
+        login.write_to_bytes().unwrap().as_slice()
+    );
+    assert_eq!(
+        fields,
+        vec![
+            WireField {
+                name: "id",
+                range: 0..1
+            },
+            WireField {
+                name: "token",
+                range: 1..5
+            },
+            WireField {
+                name: "admin, muted",
+                range: 5..6
+            },
+            WireField {
+                name: "cached",
+                range: 6..6
+            },
+        ]
+    );
+    let read = Login::read_from_slice(writer.as_slice()).unwrap();
+    assert_eq!((read.token, read.muted, read.cached), (Some(7), true, 0));
+
+    let pair = LoginPair(0, "x".into());
+    assert_eq!(pair.wire_debug().unwrap(), "0 [0..2] 00 00\n1 [2..2]\n");
+}
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct Ordered {
+    #[order(2)]
+    #[if_present(token)]
+    expiry: Option<u32>,
+    #[skip]
+    cached: u8,
+    #[order(0)]
+    id: u8,
+    #[order(1)]
+    token: Option<u16>,
+}
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct OrderedUnnamed(#[order(1)] u8, #[order(0)] u16);
+
+#[test]
+fn explicit_field_order() {
+    let ordered = Ordered {
+        expiry: Some(5),
+        cached: 0,
+        id: 1,
+        token: Some(2),
+    };
+    let buf = ordered.write_to_bytes().unwrap();
+    assert_eq!(buf.as_slice(), &[1, 1, 0, 2, 0, 0, 0, 5]);
+    assert_eq!(Ordered::read_from_slice(buf.as_slice()).unwrap(), ordered);
+
+    let unnamed = OrderedUnnamed(1, 2);
+    let buf = unnamed.write_to_bytes().unwrap();
+    assert_eq!(buf.as_slice(), &[0, 2, 1]);
+    assert_eq!(
+        OrderedUnnamed::read_from_slice(buf.as_slice()).unwrap(),
+        unnamed
+    );
+}
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct LengthPrefixed {
+    #[len_of(entries)]
+    count: u8,
+    name_len: u16,
+    entries: Vec<u16>,
+}
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct Oversized {
+    #[len_of(data)]
+    len: u8,
+    data: Vec<u8>,
+}
+
+#[test]
+fn len_of_field() {
+    let packet = LengthPrefixed {
+        count: 0,
+        name_len: 9,
+        entries: vec![1, 2],
+    };
+    let buf = packet.write_to_bytes().unwrap();
+    assert_eq!(buf.as_slice(), &[2, 0, 9, 0, 1, 0, 2]);
+
+    let read = LengthPrefixed::read_from_slice(buf.as_slice()).unwrap();
+    assert_eq!(read.count, 2);
+    assert_eq!(read.entries, vec![1, 2]);
+
+    // the length field drives how many entries are read.
+    assert!(LengthPrefixed::read_from_slice(&[3, 0, 9, 0, 1, 0, 2]).is_err());
+
+    let oversized = Oversized {
+        len: 0,
+        data: vec![0; 256],
+    };
+    let err = oversized.write_to_bytes().unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct Checksummed {
+    id: u8,
+    payload: String,
+    flags: u8,
+    #[crc_of(payload, crc32)]
+    crc: u32,
+}
+
+#[test]
+fn crc_of_field() {
+    let packet = Checksummed {
+        id: 1,
+        payload: "123456789".into(),
+        flags: 2,
+        crc: 0,
+    };
+    let buf = packet.write_to_bytes().unwrap();
+    // the checksum covers the length prefix of the string as well.
+    let crc = binary_util::checksum::crc32(&buf.as_slice()[1..11]);
+    assert_eq!(&buf.as_slice()[12..], &crc.to_be_bytes());
+
+    let read = Checksummed::read_from_slice(buf.as_slice()).unwrap();
+    assert_eq!(read.crc, crc);
+    assert_eq!(read.payload, "123456789");
+
+    let mut corrupt = buf.as_slice().to_vec();
+    corrupt[3] ^= 1;
+    let err = Checksummed::read_from_slice(&corrupt).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[derive(BinaryIo, Debug)]
+#[binary_io(display_wire)]
+struct Summary {
+    #[len_of(entries)]
+    count: u8,
+    entries: Vec<u16>,
+    #[crc_of(entries, crc32)]
+    crc: u32,
+}
+
+#[test]
+fn display_wire_computed_fields() {
+    use binary_util::interfaces::WireDisplay;
+
+    // the stored count and checksum are ignored, like they are when writing.
+    let summary = Summary {
+        count: 9,
+        entries: vec![1, 2],
+        crc: 0,
+    };
+    let buf = summary.write_to_bytes().unwrap();
+    let crc = binary_util::checksum::crc32(&buf.as_slice()[1..5]);
+    assert_eq!(
+        summary.display_wire().to_string(),
+        format!("Summary count=2 entries=[1, 2] crc={}", crc)
+    );
+
+    let read = Summary::read_from_slice(buf.as_slice()).unwrap();
+    assert_eq!((read.count, read.crc), (2, crc));
+}
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct VersionedLogin {
+    #[renamed_from(username)]
+    name: String,
+    #[removed_in(3)]
+    legacy_id: u32,
+    #[added_in(2)]
+    skin: Vec<u8>,
+}
+
+#[test]
+fn versioned_fields() {
+    // version 1 only has `name` and `legacy_id`.
+    let v1 = [1, b'a', 0, 0, 0, 7];
+    let mut reader = ByteReader::from(&v1[..]).with_version(1);
+    let login = VersionedLogin::read(&mut reader).unwrap();
+    assert_eq!(login.legacy_id, 7);
+    assert!(login.skin.is_empty());
+
+    // version 2 has every field.
+    let v2 = [1, b'a', 0, 0, 0, 7, 2, 1, 2];
+    let mut reader = ByteReader::from(&v2[..]).with_version(2);
+    let login = VersionedLogin::read(&mut reader).unwrap();
+    assert_eq!((login.legacy_id, login.skin), (7, vec![1, 2]));
+
+    // version 3, and the latest, no longer have `legacy_id`.
+    let v3 = [1, b'a', 2, 1, 2];
+    let mut reader = ByteReader::from(&v3[..]).with_version(3);
+    assert_eq!(VersionedLogin::read(&mut reader).unwrap().legacy_id, 0);
+    let latest = VersionedLogin::read_from_slice(&v3).unwrap();
+    assert_eq!(latest.skin, vec![1, 2]);
+
+    // the latest layout is always written.
+    let old = VersionedLogin {
+        name: "a".into(),
+        legacy_id: 7,
+        skin: vec![1, 2],
+    };
+    assert_eq!(old.write_to_bytes().unwrap().as_slice(), &v3);
+}
+
+/// Stands in for a crate that does not depend on `binary_util`.
+mod foreign {
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Point {
+        pub x: i16,
+        pub y: i16,
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Span(pub u8, pub u8);
+}
+
+#[derive(BinaryIo)]
+#[binary_io(remote = "foreign::Point")]
+struct PointDef {
+    y: i16,
+    x: i16,
+}
+
+#[derive(BinaryIo)]
+#[binary_io(remote = "foreign::Span")]
+struct SpanDef(u8, u8);
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct Selection {
+    #[with(PointDef)]
+    origin: foreign::Point,
+    #[with(SpanDef)]
+    span: foreign::Span,
+}
+
+#[test]
+fn remote_fields() {
+    let selection = Selection {
+        origin: foreign::Point { x: 1, y: 2 },
+        span: foreign::Span(3, 4),
+    };
+    let buf = selection.write_to_bytes().unwrap();
+    // the mirror decides the layout, so `y` is written before `x`.
+    assert_eq!(buf.as_slice(), &[0, 2, 0, 1, 3, 4]);
+    assert_eq!(
+        Selection::read_from_slice(buf.as_slice()).unwrap(),
+        selection
+    );
+
+    let point: foreign::Point = PointDef::read_from_slice(&[0, 2, 0, 1]).unwrap().into();
+    assert_eq!(point, foreign::Point { x: 1, y: 2 });
+}
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct Envelope<T> {
+    id: u16,
+    body: T,
+}
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct Pair<A, B>(A, B);
+
+#[derive(BinaryIo, Debug, PartialEq)]
+#[binary_io(bound = "")]
+struct Handle<T> {
+    id: u8,
+    #[skip]
+    kind: std::marker::PhantomData<T>,
+}
+
+/// Not encodable, only used as a marker.
+#[derive(Debug, PartialEq)]
+struct Opaque;
+
+#[test]
+fn generic_fields() {
+    let envelope = Envelope {
+        id: 1,
+        body: Pair(2u8, String::from("a")),
+    };
+    let buf = envelope.write_to_bytes().unwrap();
+    assert_eq!(buf.as_slice(), &[0, 1, 2, 1, b'a']);
+    assert_eq!(
+        Envelope::<Pair<u8, String>>::read_from_slice(buf.as_slice()).unwrap(),
+        envelope
+    );
+}
+
+#[test]
+fn generic_bound_override() {
+    let handle: Handle<Opaque> = Handle {
+        id: 3,
+        kind: std::marker::PhantomData,
+    };
+    let buf = handle.write_to_bytes().unwrap();
+    assert_eq!(buf.as_slice(), &[3]);
+    assert_eq!(Handle::<Opaque>::read_from_slice(&[3]).unwrap(), handle);
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+struct ConnectionId(u64);
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct Chat {
+    message: String,
+    #[from_ctx]
+    sender: ConnectionId,
+    #[from_ctx]
+    peer: Option<std::net::SocketAddr>,
+}
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct Ack(u8, #[from_ctx] ConnectionId);
+
+#[test]
+fn fields_from_context() {
+    let peer: std::net::SocketAddr = "127.0.0.1:19132".parse().unwrap();
+    let chat = Chat {
+        message: "hi".into(),
+        sender: ConnectionId(7),
+        peer: Some(peer),
+    };
+    // context fields are not written.
+    let buf = chat.write_to_bytes().unwrap();
+    assert_eq!(buf.as_slice(), &[2, b'h', b'i']);
+
+    let mut reader = ByteReader::from(buf.as_slice())
+        .with_context(ConnectionId(7))
+        .with_context(Some(peer));
+    assert_eq!(Chat::read(&mut reader).unwrap(), chat);
+
+    // without a context, the fields are their default.
+    let chat = Chat::read_from_slice(buf.as_slice()).unwrap();
+    assert_eq!(chat.sender, ConnectionId(0));
+    assert_eq!(chat.peer, None);
+
+    let mut reader = ByteReader::from(&[3][..]).with_context(ConnectionId(9));
+    assert_eq!(Ack::read(&mut reader).unwrap(), Ack(3, ConnectionId(9)));
+}
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct LookupResponse {
+    id: u16,
+    result: Result<u32, String>,
+}
+
+#[test]
+fn result_fields() {
+    let ok = LookupResponse {
+        id: 1,
+        result: Ok(300),
+    };
+    let buf = ok.write_to_bytes().unwrap();
+    assert_eq!(buf.as_slice(), &[0, 1, 0, 0, 0, 1, 44]);
+    assert_eq!(LookupResponse::read_from_slice(buf.as_slice()).unwrap(), ok);
+
+    let err = LookupResponse {
+        id: 2,
+        result: Err("missing".into()),
+    };
+    let buf = err.write_to_bytes().unwrap();
+    assert_eq!(&buf.as_slice()[..4], &[0, 2, 1, 7]);
+    assert_eq!(
+        LookupResponse::read_from_slice(buf.as_slice()).unwrap(),
+        err
+    );
+
+    // only 0 (Ok) and 1 (Err) are valid tags.
+    let err = LookupResponse::read_from_slice(&[0, 3, 2]).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[derive(BinaryIo, Debug)]
+#[binary_io(display_wire)]
+struct Join {
+    id: LE<u16>,
+    name: String,
+    #[bool_width(bit)]
+    admin: bool,
+    #[skip]
+    _cached: u8,
+    aliases: Vec<String>,
+    password: binary_util::util::Secret<String>,
+    result: Result<u8, String>,
+}
+
+#[derive(BinaryIo, Debug)]
+#[binary_io(debug_wire, display_wire)]
+struct JoinAck<T>(T, Option<u8>);
+
+#[test]
+fn display_wire_single_line() {
+    use binary_util::interfaces::WireDisplay;
+
+    let join = Join {
+        id: LE(3),
+        name: "a\"b".into(),
+        admin: true,
+        _cached: 9,
+        aliases: vec!["x".into(), "y".into()],
+        password: String::from("hunter2").into(),
+        result: Err("no".into()),
+    };
+    assert_eq!(
+        join.display_wire().to_string(),
+        r#"Join id=3 name="a\"b" admin=true aliases=["x", "y"] password=[REDACTED] result=Err("no")"#
+    );
+
+    // written straight into an existing buffer.
+    let mut line = String::from("> ");
+    JoinAck(7u32, None).fmt_wire(&mut line).unwrap();
+    assert_eq!(line, "> JoinAck 0=7 1=None");
+}
+
+#[derive(BinaryIo, Debug, PartialEq)]
+#[binary_io(deny_trailing)]
+struct Pong {
+    time: u16,
+}
+
+#[derive(BinaryIo, Debug, PartialEq)]
+#[binary_io(deny_trailing)]
+struct PongBatch(#[allow_trailing] Pong, Vec<u8>);
+
+#[test]
+fn deny_trailing_bytes() {
+    assert_eq!(Pong::read_from_slice(&[0, 1]).unwrap(), Pong { time: 1 });
+    let err = Pong::read_from_slice(&[0, 1, 2]).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+    // the nested packet is followed by the rest of the batch.
+    let batch = PongBatch(Pong { time: 1 }, vec![2, 3]);
+    let buf = batch.write_to_bytes().unwrap();
+    assert_eq!(PongBatch::read_from_slice(buf.as_slice()).unwrap(), batch);
+
+    let mut trailing = buf.as_slice().to_vec();
+    trailing.push(0);
+    assert!(PongBatch::read_from_slice(&trailing).is_err());
+
+    // a batch nested within an `allow_trailing` scope is not checked either.
+    let mut reader = ByteReader::from(trailing);
+    let read = reader.allow_trailing(PongBatch::read).unwrap();
+    assert_eq!(read, batch);
+    assert!(!reader.trailing_allowed());
+    assert_eq!(reader.as_slice(), &[0]);
+}
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct AckOf<T = ()> {
+    sequence: u16,
+    payload: T,
+}
+
+#[test]
+fn unit_payloads() {
+    let ack: AckOf = AckOf {
+        sequence: 3,
+        payload: (),
+    };
+    let buf = ack.write_to_bytes().unwrap();
+    assert_eq!(buf.as_slice(), &[0, 3]);
+    assert_eq!(AckOf::<()>::read_from_slice(buf.as_slice()).unwrap(), ack);
+
+    let ack = AckOf {
+        sequence: 3,
+        payload: 7u8,
+    };
+    assert_eq!(ack.write_to_bytes().unwrap().as_slice(), &[0, 3, 7]);
+
+    // an infallible result always writes its `Ok` variant.
+    let ok: Result<u8, std::convert::Infallible> = Ok(1);
+    assert_eq!(ok.write_to_bytes().unwrap().as_slice(), &[0, 1]);
+}
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct LoginStart {
+    protocol: u8,
+    #[cfg(feature = "json")]
+    compression_threshold: u16,
+    #[cfg_attr(not(feature = "json"), skip)]
+    key: Vec<u8>,
+}
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct Version(u8, #[cfg(feature = "json")] u8);
+
+#[test]
+fn cfg_gated_fields() {
+    #[cfg(feature = "json")]
+    let (login, wire) = (
+        LoginStart {
+            protocol: 1,
+            compression_threshold: 256,
+            key: vec![9],
+        },
+        vec![1, 1, 0, 1, 9],
+    );
+    #[cfg(not(feature = "json"))]
+    let (login, wire) = (
+        LoginStart {
+            protocol: 1,
+            key: Vec::new(),
+        },
+        vec![1],
+    );
+    assert_eq!(login.write_to_bytes().unwrap().as_slice(), &wire[..]);
+    assert_eq!(LoginStart::read_from_slice(&wire).unwrap(), login);
+
+    #[cfg(feature = "json")]
+    let (version, wire) = (Version(1, 2), vec![1, 2]);
+    #[cfg(not(feature = "json"))]
+    let (version, wire) = (Version(1), vec![1]);
+    assert_eq!(version.write_to_bytes().unwrap().as_slice(), &wire[..]);
+    assert_eq!(Version::read_from_slice(&wire).unwrap(), version);
+}
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct Forward {
+    connection: u8,
+    body: bytes::Bytes,
+    scratch: bytes::BytesMut,
+    #[len_of(trailer)]
+    trailer_len: u8,
+    trailer: bytes::Bytes,
+}
+
+#[test]
+fn byte_handles() {
+    let forward = Forward {
+        connection: 1,
+        body: bytes::Bytes::from_static(b"hey"),
+        scratch: bytes::BytesMut::from(&[7u8][..]),
+        trailer_len: 2,
+        trailer: bytes::Bytes::from_static(&[8, 9]),
+    };
+    let wire = [1, 3, b'h', b'e', b'y', 1, 7, 2, 8, 9];
+    assert_eq!(forward.write_to_bytes().unwrap().as_slice(), &wire);
+
+    // the body is a handle to the buffer it was read from, rather than a copy.
+    let source = bytes::Bytes::copy_from_slice(&wire);
+    let read = Forward::read(&mut ByteReader::from(source.clone())).unwrap();
+    assert_eq!(read, forward);
+    assert_eq!(read.body.as_ptr(), source[2..].as_ptr());
+
+    // borrowed bytes are encoded like a `Vec<u8>`.
+    let borrowed: &[u8] = b"hey";
+    let buf = borrowed.write_to_bytes().unwrap();
+    assert_eq!(buf.as_slice(), &wire[1..5]);
+    assert_eq!(Vec::<u8>::read_from_slice(buf.as_slice()).unwrap(), b"hey");
+}
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct Region {
+    name_offset: u8,
+    chunk_offset: u8,
+    #[offset_from(chunk_offset)]
+    chunk: Vec<u8>,
+    #[offset_from(name_offset)]
+    name: String,
+}
+
+#[test]
+fn offset_from_fields() {
+    let region = Region {
+        name_offset: 8,
+        chunk_offset: 4,
+        chunk: vec![1, 2],
+        name: "r".into(),
+    };
+    // the chunk is padded to its offset, and the name follows the chunk at offset 7, which is padded again.
+    let wire = [8, 4, 0, 0, 2, 1, 2, 0, 1, b'r'];
+    assert_eq!(region.write_to_bytes().unwrap().as_slice(), &wire);
+
+    let mut reader = ByteReader::from(&wire[..]);
+    assert_eq!(Region::read(&mut reader).unwrap(), region);
+    assert!(reader.as_slice().is_empty());
+
+    // sections may be stored in any order, and before the current position.
+    let swapped = [6, 2, 2, 1, 2, 0, 1, b'r'];
+    let read = Region::read_from_slice(&swapped).unwrap();
+    assert_eq!((read.chunk, read.name), (vec![1, 2], "r".to_string()));
+    let overlapping = [2, 2, 1, 7];
+    let read = Region::read_from_slice(&overlapping).unwrap();
+    assert_eq!((read.chunk, read.name), (vec![7], "\u{7}".to_string()));
+
+    assert!(Region::read_from_slice(&[20, 2, 0]).is_err());
+    let overlapping = Region {
+        chunk_offset: 1,
+        ..region
+    };
+    assert_eq!(
+        overlapping.write_to_bytes().unwrap_err().kind(),
+        std::io::ErrorKind::InvalidData
+    );
+}
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct BoundedChat {
+    #[max_len(4)]
+    message: String,
+    #[max_len(2)]
+    mentions: Vec<u16>,
+}
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct BoundedTag(#[max_len(1)] Vec<u8>);
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct BoundedFrame {
+    #[max_len(2)]
+    header: Prefixed<BE<u16>, Vec<u8>>,
+    #[max_len(3)]
+    body: Raw<Vec<u8>>,
+}
+
+#[test]
+fn max_len_reads_the_declared_prefix() {
+    let frame = BoundedFrame {
+        header: Prefixed::new(vec![1, 2]),
+        body: Raw(vec![3, 4, 5]),
+    };
+    let buf = frame.write_to_bytes().unwrap();
+    assert_eq!(buf.as_slice(), &[0, 2, 1, 2, 3, 4, 5]);
+    assert_eq!(
+        BoundedFrame::read_from_slice(buf.as_slice()).unwrap(),
+        frame
+    );
+
+    // the `u16` prefix is over the bound, rather than being read as a var-int of 0.
+    let err = BoundedFrame::read_from_slice(&[0, 3, 1, 2, 3]).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    // `Raw` has no prefix, so every remaining byte counts towards the bound.
+    assert!(BoundedFrame::read_from_slice(&[0, 0, 1, 2, 3, 4]).is_err());
+}
+
+#[test]
+fn max_len_fields() {
+    let chat = BoundedChat {
+        message: "hey".into(),
+        mentions: vec![1, 2],
+    };
+    let buf = chat.write_to_bytes().unwrap();
+    assert_eq!(BoundedChat::read_from_slice(buf.as_slice()).unwrap(), chat);
+
+    let long = BoundedChat {
+        message: "hello".into(),
+        mentions: vec![],
+    };
+    assert_eq!(
+        long.write_to_bytes().unwrap_err().kind(),
+        std::io::ErrorKind::InvalidInput
+    );
+
+    // a huge length prefix is rejected before it is read.
+    let mut buf = ByteReader::from(&[0, 0xff, 0xff, 0xff, 0xff, 0x0f][..]);
+    let err = BoundedChat::read(&mut buf).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+    assert!(BoundedTag::read_from_slice(&[1, 7]).is_ok());
+    assert!(BoundedTag::read_from_slice(&[2, 7, 7]).is_err());
+    assert!(BoundedTag(vec![1, 2]).write_to_bytes().is_err());
+}
+
+#[derive(BinaryIo, Debug, PartialEq)]
+#[binary_io(skip_reader)]
+struct Resize {
+    width: u16,
+    height: u16,
+}
+
+impl Reader<Resize> for Resize {
+    fn read(buf: &mut ByteReader) -> Result<Resize, std::io::Error> {
+        let (width, height) = (buf.read_u16()?, buf.read_u16()?);
+        if width == 0 || height == 0 {
+            return Err(buf.error_at_offset(std::io::ErrorKind::InvalidData, "Empty window"));
+        }
+        Ok(Resize { width, height })
+    }
+}
+
+#[derive(BinaryIo, Debug, PartialEq)]
+#[binary_io(skip_writer)]
+struct Volume(u8);
+
+impl Writer for Volume {
+    fn write(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+        buf.write_u8(self.0.min(100))
+    }
+}
+
+#[test]
+fn skipped_traits_are_hand_written() {
+    let resize = Resize {
+        width: 640,
+        height: 480,
+    };
+    let buf = resize.write_to_bytes().unwrap();
+    assert_eq!(buf.as_slice(), &[2, 128, 1, 224]);
+    assert_eq!(Resize::read_from_slice(buf.as_slice()).unwrap(), resize);
+    assert!(Resize::read_from_slice(&[0, 0, 1, 224]).is_err());
+
+    assert_eq!(Volume(250).write_to_bytes().unwrap().as_slice(), &[100]);
+    assert_eq!(Volume::read_from_slice(&[250]).unwrap(), Volume(250));
+}
@@ -0,0 +1,80 @@
+#![cfg(feature = "bitflags")]
+use binary_util::interfaces::{Reader, Writer};
+use binary_util::io::{ByteReader, ByteWriter};
+use binary_util::types::BitFlags;
+use binary_util::BinaryIo;
+
+bitflags::bitflags! {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub struct Permissions: u32 {
+        const BUILD = 1;
+        const MINE = 1 << 1;
+        const FLY = 1 << 2;
+    }
+}
+
+bitflags::bitflags! {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct Capabilities: u64 {
+        const COMPRESSION = 1;
+        const ENCRYPTION = 1 << 1;
+    }
+}
+
+binary_util::impl_bitflags_io!(Permissions as u8);
+binary_util::impl_bitflags_io!(Capabilities);
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct Abilities {
+    permissions: Permissions,
+    capabilities: Capabilities,
+    wide: BitFlags<Permissions>,
+}
+
+#[test]
+fn read_write_wrapper() {
+    let flags: BitFlags<Permissions, u16> = (Permissions::BUILD | Permissions::FLY).into();
+    let buf = flags.write_to_bytes().unwrap();
+    assert_eq!(buf.as_slice(), &[0, 5]);
+
+    let read = BitFlags::<Permissions, u16>::read_from_slice(buf.as_slice()).unwrap();
+    assert_eq!(*read, Permissions::BUILD | Permissions::FLY);
+}
+
+#[test]
+fn unknown_bits_are_retained() {
+    let read = BitFlags::<Permissions, u8>::read_from_slice(&[0b1001_0001]).unwrap();
+    assert!(read.contains(Permissions::BUILD));
+    assert_eq!(read.bits(), 0b1001_0001);
+    assert_eq!(read.write_to_bytes().unwrap().as_slice(), &[0b1001_0001]);
+}
+
+#[test]
+fn write_too_wide() {
+    let flags = BitFlags::<Permissions, u8>::new(Permissions::from_bits_retain(0x100));
+    let err = flags.write_to_bytes().unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn read_too_wide() {
+    let mut buf = ByteReader::from(&[0, 0, 0, 1, 0, 0, 0, 0][..]);
+    let err = BitFlags::<Permissions, u64>::read(&mut buf).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn derive_with_flags() {
+    let abilities = Abilities {
+        permissions: Permissions::MINE,
+        capabilities: Capabilities::all(),
+        wide: Permissions::FLY.into(),
+    };
+    let mut buf = ByteWriter::new();
+    abilities.write(&mut buf).unwrap();
+    assert_eq!(buf.as_slice(), &[2, 0, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 4]);
+    assert_eq!(
+        Abilities::read_from_slice(buf.as_slice()).unwrap(),
+        abilities
+    );
+}
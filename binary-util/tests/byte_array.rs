@@ -0,0 +1,46 @@
+use binary_util::interfaces::{Reader, WireDisplay, Writer};
+use binary_util::types::ByteArray;
+use binary_util::BinaryIo;
+use bytes::Bytes;
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct Handshake {
+    magic: ByteArray<4>,
+    guid: ByteArray<16>,
+    port: u16,
+}
+
+#[test]
+fn read_write_without_prefix() {
+    let handshake = Handshake {
+        magic: ByteArray([0x00, 0xff, 0xff, 0x00]),
+        guid: ByteArray([7; 16]),
+        port: 19132,
+    };
+    let bytes = handshake.write_to_bytes().unwrap();
+    assert_eq!(bytes.as_slice().len(), 4 + 16 + 2);
+    assert_eq!(&bytes.as_slice()[..4], &[0x00, 0xff, 0xff, 0x00]);
+    assert_eq!(
+        Handshake::read_from_slice(bytes.as_slice()).unwrap(),
+        handshake
+    );
+
+    // too short for the guid.
+    assert!(Handshake::read_from_slice(&bytes.as_slice()[..10]).is_err());
+}
+
+#[test]
+fn formatting_and_conversions() {
+    let mac = ByteArray::new([0x00, 0x1a, 0x2b, 0x3c, 0x4d, 0x5e]);
+    assert_eq!(format!("{:?}", mac), "ByteArray<6>(001a2b3c4d5e)");
+    assert_eq!(mac.display_wire().to_string(), "001a2b3c4d5e");
+
+    let bytes: Bytes = mac.into();
+    assert_eq!(&bytes[..], mac.as_slice());
+    assert_eq!(ByteArray::<6>::try_from(bytes.clone()).unwrap(), mac);
+    assert!(ByteArray::<4>::try_from(bytes).is_err());
+
+    let raw: [u8; 6] = mac.into();
+    assert_eq!(ByteArray::from(raw), mac);
+    assert_eq!(ByteArray::<3>::default(), [0, 0, 0]);
+}
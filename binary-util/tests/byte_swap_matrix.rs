@@ -0,0 +1,47 @@
+use binary_util::interfaces::{Reader, Writer};
+use binary_util::io::ByteReader;
+use binary_util::testing::assert_byte_swap_matrix;
+use binary_util::types::{u24, BE, LE};
+use binary_util::{BinaryIo, MockBinary};
+
+#[derive(BinaryIo, MockBinary, Debug)]
+#[binary_io(schema, debug_wire)]
+struct MixedEndian {
+    a: LE<u16>,
+    b: BE<u32>,
+    flag: bool,
+    c: LE<u64>,
+    d: u24,
+    ratio: f32,
+    #[satisfy(self.flag)]
+    extra: Option<i64>,
+    name: String,
+}
+
+#[test]
+fn mixed_endian_permutations() {
+    assert_byte_swap_matrix::<MixedEndian>(8);
+}
+
+/// A reader which reads `length` in the wrong byte order.
+#[derive(BinaryIo, MockBinary, Debug)]
+#[binary_io(schema, debug_wire, skip_reader)]
+struct Mismatched {
+    id: u8,
+    length: BE<u32>,
+}
+
+impl Reader<Mismatched> for Mismatched {
+    fn read(buf: &mut ByteReader) -> Result<Mismatched, std::io::Error> {
+        Ok(Mismatched {
+            id: buf.read_u8()?,
+            length: BE(buf.read_u32_le()?),
+        })
+    }
+}
+
+#[test]
+#[should_panic(expected = "Mismatched does not round trip")]
+fn mismatched_byte_order_is_caught() {
+    assert_byte_swap_matrix::<Mismatched>(4);
+}
@@ -0,0 +1,228 @@
+use binary_util::interfaces::Writer;
+use binary_util::io::{ByteReader, ByteWriter, ChainedWriter};
+
+#[test]
+fn bytes_written_since_mark() {
+    let mut writer = ByteWriter::new();
+    writer.write_u32(0).unwrap();
+
+    let mark = writer.mark();
+    assert_eq!(mark.position(), 4);
+    assert_eq!(writer.bytes_written_since(mark), 0);
+
+    writer.write_var_u32(u32::MAX).unwrap();
+    writer.write_bool(true).unwrap();
+    assert_eq!(writer.bytes_written_since(mark), 6);
+
+    // stale marks panic in instrumented builds, see tests/instrumented.rs
+    #[cfg(not(all(feature = "instrumented", debug_assertions)))]
+    {
+        writer.clear();
+        assert_eq!(writer.bytes_written_since(mark), 0);
+    }
+}
+
+#[test]
+fn measure_section() {
+    let mut writer = ByteWriter::new();
+    let written = writer
+        .measure(|w| {
+            w.write_string("abc")?;
+            vec![1u16, 2, 3].write(w)
+        })
+        .unwrap();
+    assert_eq!(written, 4 + 1 + 6);
+    assert_eq!(writer.as_slice().len(), written);
+}
+
+#[test]
+fn write_capped_discards_oversized_field() {
+    let mut writer = ByteWriter::new();
+    writer.write_u8(9).unwrap();
+
+    assert_eq!(writer.write_capped(2, |w| w.write_u16(1)).unwrap(), 2);
+    let err = writer.write_capped(2, |w| w.write_u32(1)).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    assert_eq!(writer.as_slice(), &[9, 0, 1]);
+}
+
+#[test]
+fn io_slices() {
+    let mut writer = ByteWriter::new();
+    writer.write_u16(5).unwrap();
+    let slices = writer.as_io_slices();
+    assert_eq!(&*slices[0], &[0, 5]);
+}
+
+#[test]
+fn chained_writer_segments() {
+    let mut chain = ChainedWriter::new();
+    assert!(chain.is_empty());
+
+    chain.push(&b"\x01\x02"[..]);
+    chain.push_type(&String::from("abc")).unwrap();
+    chain.push(ByteWriter::new());
+
+    assert_eq!(chain.segments().len(), 3);
+    assert_eq!(chain.len(), 6);
+
+    let slices = chain.as_io_slices();
+    assert_eq!(slices.len(), 3);
+    assert_eq!(&*slices[1], b"\x03abc");
+    assert_eq!(&chain.to_bytes()[..], b"\x01\x02\x03abc");
+}
+
+#[test]
+fn write_option_with() {
+    let mut buf = ByteWriter::new();
+    let write_char = |w: &mut ByteWriter, c: &char| w.write_u16(*c as u16);
+    buf.write_option_with(&Some('"'), write_char).unwrap();
+    buf.write_option_with(&None, write_char).unwrap();
+    assert_eq!(buf.as_slice(), &[0x01, 0x00, 0x22, 0x00]);
+}
+
+#[test]
+fn numeric_slices() {
+    let mut buf = ByteWriter::new();
+    buf.write_u16_slice(&[1, 0x0203]).unwrap();
+    buf.write_i32_le_slice(&[-2]).unwrap();
+    buf.write_f32_slice(&[1.5]).unwrap();
+    assert_eq!(
+        buf.as_slice(),
+        &[0, 1, 2, 3, 0xfe, 0xff, 0xff, 0xff, 0x3f, 0xc0, 0, 0]
+    );
+
+    let mut reader = binary_util::io::ByteReader::from(buf);
+    assert_eq!(reader.read_u16_slice(2).unwrap(), vec![1, 0x0203]);
+    assert_eq!(reader.read_i32_le_slice(1).unwrap(), vec![-2]);
+    assert!(reader.read_f32_slice(2).is_err());
+    assert_eq!(reader.read_f32_slice(1).unwrap(), vec![1.5]);
+    assert!(reader.read_u64_slice(0).unwrap().is_empty());
+}
+
+#[test]
+fn take_bytes_reuses_writer() {
+    let mut writer = ByteWriter::new();
+    let mut sent = Vec::new();
+    for i in 0..3u8 {
+        writer.write_u8(i).unwrap();
+        writer.write_string("abc").unwrap();
+        sent.push(writer.take_bytes());
+        assert!(writer.as_slice().is_empty());
+    }
+    assert_eq!(&sent[2][..], &[2, 3, b'a', b'b', b'c']);
+
+    // once the taken bytes are dropped, the writer reuses their allocation.
+    drop(sent);
+    writer.write_u64(1).unwrap();
+    let ptr = writer.as_slice().as_ptr();
+    let packet = writer.take_bytes();
+    assert_eq!(packet.as_ptr(), ptr);
+    drop(packet);
+    writer.write_u64(2).unwrap();
+    assert_eq!(writer.as_slice().as_ptr(), ptr);
+}
+
+#[test]
+#[cfg(feature = "inline")]
+fn inline_writer_spills_past_threshold() {
+    let mut writer = ByteWriter::with_inline_threshold(8);
+    writer.write_u32(1).unwrap();
+    writer.write_u16(2).unwrap();
+    assert!(writer.is_inline());
+    assert_eq!(writer.take_bytes().as_ref(), &[0, 0, 0, 1, 0, 2]);
+    assert!(writer.is_inline());
+
+    writer.write_u64(3).unwrap();
+    assert!(writer.is_inline());
+    writer.write_u8(4).unwrap();
+    assert!(!writer.is_inline());
+    assert_eq!(writer.as_slice(), &[0, 0, 0, 0, 0, 0, 0, 3, 4]);
+
+    // every other method behaves the same as a heap writer.
+    let mut inline = ByteWriter::inline();
+    let mut heap = ByteWriter::new();
+    for writer in [&mut inline, &mut heap] {
+        writer.write_string("Hello").unwrap();
+        writer.write_var_u32(300).unwrap();
+        assert!(writer.write_capped(2, |w| w.write_u32(0)).is_err());
+        writer.write_f32_le_slice(&[1.0, 2.0]).unwrap();
+    }
+    assert!(inline.is_inline());
+    assert_eq!(inline.as_slice(), heap.as_slice());
+    let bytes: Vec<u8> = inline.into();
+    assert_eq!(bytes, heap.as_slice());
+}
+
+/// Returns an inline writer if the `inline` feature is enabled, so the tests using it also cover spilling it.
+fn small_writer() -> ByteWriter {
+    #[cfg(feature = "inline")]
+    return ByteWriter::inline();
+    #[cfg(not(feature = "inline"))]
+    return ByteWriter::new();
+}
+
+#[test]
+fn buf_mut_passthrough() {
+    use bytes::BufMut;
+
+    let mut writer = small_writer();
+    writer.write_u8(1).unwrap();
+    writer.put_u16(300);
+    writer.put_bytes(7, 100);
+    #[cfg(feature = "inline")]
+    assert!(!writer.is_inline());
+
+    let mut reader = ByteReader::from(writer.as_slice());
+    assert_eq!(reader.read_u8().unwrap(), 1);
+    assert_eq!(reader.read_u16().unwrap(), 300);
+    assert_eq!(&reader.read_bytes(100).unwrap()[..], &[7; 100][..]);
+}
+
+#[test]
+fn write_all_vectored() {
+    let mut chained = ChainedWriter::new();
+    chained.push_type(&1u16).unwrap();
+    chained.push_type(&String::from("ab")).unwrap();
+
+    let mut writer = ByteWriter::new();
+    writer.write_u8(9).unwrap();
+    writer.write_all_vectored(&chained.as_io_slices()).unwrap();
+    assert_eq!(writer.as_slice(), &[9, 0, 1, 2, b'a', b'b']);
+}
+
+#[test]
+fn length_prefixed_writes() {
+    let mut writer = small_writer();
+    writer.write_slice(&[1; 200]).unwrap();
+    writer.write_string_u16be("ab").unwrap();
+    writer.write_string_u32le("c").unwrap();
+    let buf = writer.as_slice();
+    assert_eq!(&buf[..2], &[200, 1]);
+    assert_eq!(&buf[202..], &[0, 2, b'a', b'b', 1, 0, 0, 0, b'c']);
+}
+
+#[test]
+fn truncate_rolls_back_sections() {
+    let writers = [
+        ByteWriter::new(),
+        #[cfg(feature = "inline")]
+        ByteWriter::inline(),
+    ];
+    for mut writer in writers {
+        assert!(writer.is_empty());
+        writer.write_u16(7).unwrap();
+        let start = writer.len();
+        writer.write_string("optional section").unwrap();
+        assert_eq!(writer.len(), 2 + 17);
+        writer.truncate(start);
+        assert_eq!(writer.as_slice(), &[0, 7]);
+
+        // truncating past the written bytes does nothing, and writing resumes after the kept bytes.
+        writer.truncate(100);
+        writer.write_u8(1).unwrap();
+        assert_eq!(writer.as_slice(), &[0, 7, 1]);
+        writer.truncate(0);
+        assert!(writer.is_empty());
+    }
+}
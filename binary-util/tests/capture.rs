@@ -0,0 +1,124 @@
+#![cfg(feature = "pcap")]
+use std::time::Duration;
+
+use binary_util::capture::{link_type, Capture, Transport};
+use binary_util::interfaces::{Reader, Writer};
+use binary_util::io::ByteWriter;
+use binary_util::BinaryIo;
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct Ping {
+    time: u64,
+    motd: String,
+}
+
+fn pcap(link_type: u32, records: &[(u32, &[u8])]) -> Vec<u8> {
+    let mut writer = ByteWriter::new();
+    writer.write_u32_le(0xa1b2c3d4).unwrap();
+    writer.write_u16_le(2).unwrap();
+    writer.write_u16_le(4).unwrap();
+    writer.write(&[0; 8]).unwrap();
+    writer.write_u32_le(65535).unwrap();
+    writer.write_u32_le(link_type).unwrap();
+    for (micros, frame) in records {
+        writer.write_u32_le(1_700_000_000).unwrap();
+        writer.write_u32_le(*micros).unwrap();
+        writer.write_u32_le(frame.len() as u32).unwrap();
+        writer.write_u32_le(frame.len() as u32).unwrap();
+        writer.write(frame).unwrap();
+    }
+    writer.as_slice().to_vec()
+}
+
+fn ethernet(ether_type: u16, payload: &[u8]) -> Vec<u8> {
+    let mut frame = vec![0xff; 12];
+    frame.extend_from_slice(&ether_type.to_be_bytes());
+    frame.extend_from_slice(payload);
+    frame
+}
+
+fn ipv4_udp(payload: &[u8]) -> Vec<u8> {
+    let total = (20 + 8 + payload.len()) as u16;
+    let mut packet = vec![0x45, 0];
+    packet.extend_from_slice(&total.to_be_bytes());
+    packet.extend_from_slice(&[0, 0, 0, 0, 64, 17, 0, 0]);
+    packet.extend_from_slice(&[10, 0, 0, 1, 10, 0, 0, 2]);
+    packet.extend_from_slice(&50000u16.to_be_bytes());
+    packet.extend_from_slice(&19132u16.to_be_bytes());
+    packet.extend_from_slice(&((8 + payload.len()) as u16).to_be_bytes());
+    packet.extend_from_slice(&[0, 0]);
+    packet.extend_from_slice(payload);
+    packet
+}
+
+fn ipv6_tcp(payload: &[u8]) -> Vec<u8> {
+    let mut packet = vec![0x60, 0, 0, 0];
+    packet.extend_from_slice(&((20 + payload.len()) as u16).to_be_bytes());
+    packet.extend_from_slice(&[6, 64]);
+    packet.extend_from_slice(&std::net::Ipv6Addr::LOCALHOST.octets());
+    packet.extend_from_slice(&std::net::Ipv6Addr::LOCALHOST.octets());
+    packet.extend_from_slice(&25565u16.to_be_bytes());
+    packet.extend_from_slice(&40000u16.to_be_bytes());
+    packet.extend_from_slice(&[0; 8]);
+    packet.extend_from_slice(&[0x50, 0x18, 0, 0, 0, 0, 0, 0]);
+    packet.extend_from_slice(payload);
+    packet
+}
+
+#[test]
+fn replay_ethernet_capture() {
+    let ping = Ping {
+        time: 99,
+        motd: "MCPE;Server".into(),
+    };
+    let mut udp = ethernet(0x0800, &ipv4_udp(ping.write_to_bytes().unwrap().as_slice()));
+    // ethernet pads short frames, which is not part of the payload.
+    udp.extend_from_slice(&[0; 4]);
+    let arp = ethernet(0x0806, &[0; 28]);
+    let tcp = ethernet(0x86dd, &ipv6_tcp(b"hello"));
+    let data = pcap(link_type::ETHERNET, &[(1, &udp), (2, &arp), (3, &tcp)]);
+
+    let mut capture = Capture::from_bytes(data).unwrap();
+    assert_eq!(capture.link_type(), link_type::ETHERNET);
+
+    let mut packet = capture.next().unwrap().unwrap();
+    assert_eq!(packet.timestamp, Duration::new(1_700_000_000, 1_000));
+    assert_eq!(packet.src, "10.0.0.1:50000".parse().unwrap());
+    assert_eq!(packet.dst, "10.0.0.2:19132".parse().unwrap());
+    assert_eq!(packet.transport, Transport::Udp);
+    assert_eq!(Ping::read(&mut packet.payload).unwrap(), ping);
+    assert!(packet.payload.as_slice().is_empty());
+
+    // the arp frame is skipped.
+    let packet = capture.next().unwrap().unwrap();
+    assert_eq!(packet.transport, Transport::Tcp);
+    assert_eq!(packet.src, "[::1]:25565".parse().unwrap());
+    assert_eq!(packet.payload.as_slice(), b"hello");
+    assert!(capture.next().is_none());
+}
+
+#[test]
+fn raw_ip_capture() {
+    let data = pcap(link_type::RAW, &[(0, &ipv4_udp(&[1, 2, 3]))]);
+    let packets = Capture::from_bytes(data)
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(packets.len(), 1);
+    assert_eq!(packets[0].payload.as_slice(), &[1, 2, 3]);
+}
+
+#[test]
+fn truncated_and_invalid_captures() {
+    let mut data = pcap(link_type::RAW, &[(0, &ipv4_udp(&[1, 2, 3]))]);
+    data.truncate(data.len() - 2);
+    let mut capture = Capture::from_bytes(data).unwrap();
+    assert!(capture.next().unwrap().is_err());
+    assert!(capture.next().is_none());
+
+    assert_eq!(
+        Capture::from_bytes(vec![0; 24]).unwrap_err().kind(),
+        std::io::ErrorKind::InvalidData
+    );
+    assert!(Capture::from_bytes(vec![0x0a, 0x0d, 0x0d, 0x0a]).is_err());
+}
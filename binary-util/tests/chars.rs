@@ -0,0 +1,91 @@
+use binary_util::interfaces::{Reader, Writer};
+use binary_util::io::{BinaryErrorExt, ByteReader, ByteWriter};
+use binary_util::types::{U32Char, Utf16Char, Utf8Char, LE};
+use binary_util::BinaryIo;
+
+#[test]
+fn utf8_chars() {
+    for (c, bytes) in [
+        ('a', &[0x61][..]),
+        ('é', &[0xc3, 0xa9]),
+        ('€', &[0xe2, 0x82, 0xac]),
+        ('😀', &[0xf0, 0x9f, 0x98, 0x80]),
+    ] {
+        let mut buf = ByteWriter::new();
+        buf.write_char_utf8(c).unwrap();
+        assert_eq!(buf.as_slice(), bytes);
+        assert_eq!(ByteReader::from(bytes).read_char_utf8().unwrap(), c);
+    }
+}
+
+#[test]
+fn utf16_chars() {
+    for (c, bytes) in [
+        ('a', &[0x00, 0x61][..]),
+        ('€', &[0x20, 0xac]),
+        ('😀', &[0xd8, 0x3d, 0xde, 0x00]),
+    ] {
+        assert_eq!(Utf16Char(c).write_to_bytes().unwrap().as_slice(), bytes);
+        assert_eq!(Utf16Char::read_from_slice(bytes).unwrap().0, c);
+    }
+    let le = LE(Utf16Char('😀')).write_to_bytes().unwrap();
+    assert_eq!(le.as_slice(), &[0x3d, 0xd8, 0x00, 0xde]);
+    let LE(Utf16Char(c)) = LE::<Utf16Char>::read_from_slice(le.as_slice()).unwrap();
+    assert_eq!(c, '😀');
+}
+
+#[test]
+fn invalid_chars() {
+    // a continuation byte can not lead a char, and nothing is consumed.
+    let mut buf = ByteReader::from(&[0x80][..]);
+    assert_eq!(
+        buf.read_char_utf8().unwrap_err().kind(),
+        std::io::ErrorKind::InvalidData
+    );
+    assert_eq!(buf.as_slice(), &[0x80]);
+
+    // a truncated char may be read once the rest of it arrives.
+    let mut buf = ByteReader::from(&[0xe2, 0x82][..]);
+    let err = buf.read_char_utf8().unwrap_err();
+    assert!(err.is_eof());
+    assert_eq!(err.needed_bytes(), Some(1));
+    assert_eq!(buf.as_slice().len(), 2);
+
+    assert!(Utf8Char::read_from_slice(&[0xe2, 0x28, 0xa1]).is_err());
+    // an unpaired surrogate.
+    assert!(Utf16Char::read_from_slice(&[0xde, 0x00]).is_err());
+    assert!(Utf16Char::read_from_slice(&[0xd8, 0x3d, 0x00, 0x61]).is_err());
+
+    // a surrogate pair cut after its leading unit is not consumed either.
+    let mut buf = ByteReader::from(&[0xd8, 0x3d, 0xde][..]);
+    let err = buf.read_char_utf16().unwrap_err();
+    assert!(err.is_eof());
+    assert_eq!(err.needed_bytes(), Some(1));
+    assert_eq!(buf.as_slice().len(), 3);
+    let mut buf = ByteReader::from(&[0x3d, 0xd8][..]);
+    assert!(buf.read_char_utf16_le().unwrap_err().is_eof());
+    assert_eq!(buf.as_slice().len(), 2);
+    assert!(U32Char::read_from_slice(&[0x00, 0x00, 0xd8, 0x00]).is_err());
+}
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct KeyPress {
+    key: Utf8Char,
+    legacy: U32Char,
+}
+
+#[test]
+fn char_fields() {
+    let press = KeyPress {
+        key: Utf8Char('ß'),
+        legacy: U32Char('ß'),
+    };
+    let buf = press.write_to_bytes().unwrap();
+    assert_eq!(buf.as_slice(), &[0xc3, 0x9f, 0x00, 0x00, 0x00, 0xdf]);
+    assert_eq!(KeyPress::read_from_slice(buf.as_slice()).unwrap(), press);
+    // `char` is still encoded like `U32Char`.
+    assert_eq!(
+        'ß'.write_to_bytes().unwrap().as_slice(),
+        &buf.as_slice()[2..]
+    );
+}
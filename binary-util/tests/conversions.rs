@@ -0,0 +1,67 @@
+use binary_util::interfaces::{Reader, Writer};
+use binary_util::io::{ByteReader, ByteWriter};
+use binary_util::BinaryIo;
+
+#[derive(BinaryIo, Debug, PartialEq)]
+#[binary_io(conversions)]
+struct Record {
+    id: u32,
+    name: String,
+}
+
+#[derive(BinaryIo, Debug, PartialEq)]
+#[binary_io(conversions)]
+#[repr(u8)]
+enum Entry {
+    Empty,
+    Value(u16),
+}
+
+#[derive(BinaryIo, Debug, PartialEq)]
+#[binary_io(conversions)]
+struct Pair<T>(T, T);
+
+#[derive(BinaryIo, Debug, PartialEq)]
+#[binary_io(conversions, skip_writer)]
+struct Manual(u8);
+
+impl Writer for Manual {
+    fn write(&self, buf: &mut ByteWriter) -> Result<(), std::io::Error> {
+        buf.write_u8(self.0 + 1)
+    }
+}
+
+fn load<T: for<'a> TryFrom<&'a [u8], Error = std::io::Error>>(bytes: &[u8]) -> T {
+    T::try_from(bytes).unwrap()
+}
+
+#[test]
+fn conversions_round_trip() {
+    let record = Record {
+        id: 7,
+        name: "seven".to_string(),
+    };
+    let bytes = Vec::<u8>::try_from(&record).unwrap();
+    assert_eq!(bytes, record.write_to_bytes().unwrap().as_slice());
+    assert_eq!(load::<Record>(&bytes), record);
+    assert!(Record::try_from(&bytes[..3]).is_err());
+
+    let entry = Entry::Value(300);
+    let bytes = Vec::<u8>::try_from(&entry).unwrap();
+    assert_eq!(Entry::try_from(bytes.as_slice()).unwrap(), entry);
+
+    let pair = Pair(1u16, 2u16);
+    let bytes = Vec::<u8>::try_from(&pair).unwrap();
+    assert_eq!(bytes, [0, 1, 0, 2]);
+    assert_eq!(Pair::<u16>::try_from(&bytes[..]).unwrap(), pair);
+}
+
+#[test]
+fn conversions_use_hand_written_impls() {
+    assert_eq!(Vec::<u8>::try_from(&Manual(1)).unwrap(), [2]);
+    assert_eq!(Manual::try_from(&[5][..]).unwrap(), Manual(5));
+    assert_eq!(
+        Manual::read(&mut ByteReader::from(&[5][..])).unwrap(),
+        Manual(5)
+    );
+}
@@ -0,0 +1,58 @@
+// `binary_util` is only reachable under another name, as if it was renamed in `Cargo.toml`.
+pub extern crate binary_util as renamed;
+
+use renamed::interfaces::{Reader, Writer};
+use renamed::testing::random;
+use renamed::types::{varu32, LE};
+use renamed::{BinaryIo, MockBinary};
+
+/// A facade crate re-exporting `binary_util`.
+mod facade {
+    pub use renamed as binary_util;
+}
+
+#[derive(BinaryIo, MockBinary, Debug, PartialEq)]
+#[binary_io(crate = "renamed")]
+struct Login {
+    protocol: LE<u16>,
+    #[len_of(name)]
+    len: u8,
+    name: String,
+    #[satisfy(self.protocol.0 > 10)]
+    session: Option<varu32>,
+}
+
+#[derive(BinaryIo, Debug, PartialEq)]
+#[binary_io(crate = "facade::binary_util")]
+#[repr(u8)]
+enum Packet {
+    Login(Login) = 1,
+    Kick(String) = 2,
+}
+
+#[test]
+fn renamed_crate_path() {
+    let login = Login {
+        protocol: LE(47),
+        len: 0,
+        name: "abc".into(),
+        session: Some(varu32(9)),
+    };
+    let buf = login.write_to_bytes().unwrap();
+    assert_eq!(buf.as_slice(), &[47, 0, 3, b'a', b'b', b'c', 9]);
+    let packet = Packet::read_from_slice(&[1, 47, 0, 3, b'a', b'b', b'c', 9]).unwrap();
+    assert_eq!(packet, Packet::Login(Login { len: 3, ..login }));
+
+    let kick = Packet::Kick("bye".into());
+    assert_eq!(
+        Packet::read_from_slice(kick.write_to_bytes().unwrap().as_slice()).unwrap(),
+        kick
+    );
+    let mocked: Login = random();
+    assert_eq!(
+        Login::read_from_slice(mocked.write_to_bytes().unwrap().as_slice())
+            .unwrap()
+            .name,
+        mocked.name
+    );
+}
@@ -0,0 +1,83 @@
+use binary_util::interfaces::{Reader, Writer};
+use binary_util::io::ByteReader;
+use binary_util::BinaryIo;
+use std::time::Duration;
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct Node {
+    value: u8,
+    children: Vec<Node>,
+}
+
+#[derive(BinaryIo, Debug, PartialEq)]
+#[repr(u8)]
+enum Tree {
+    Leaf,
+    Branch(Vec<Tree>),
+}
+
+/// Builds a chain of nodes `depth` levels deep.
+fn chain(depth: usize) -> Vec<u8> {
+    let mut node = Node {
+        value: 0,
+        children: Vec::new(),
+    };
+    for i in 1..depth {
+        node = Node {
+            value: i as u8,
+            children: vec![node],
+        };
+    }
+    node.write_to_bytes().unwrap().as_slice().to_vec()
+}
+
+#[test]
+fn read_within_depth() {
+    let buf = chain(8);
+    let mut reader = ByteReader::from(buf).with_max_depth(8);
+    assert_eq!(Node::read(&mut reader).unwrap().value, 7);
+}
+
+#[test]
+fn read_exceeds_depth() {
+    let buf = chain(9);
+    let mut reader = ByteReader::from(buf).with_max_depth(8);
+    assert!(Node::read(&mut reader).is_err());
+}
+
+#[test]
+fn read_hostile_enum_nesting() {
+    // thousands of nested branches, each containing a single element.
+    let mut buf = Vec::new();
+    for _ in 0..100_000 {
+        buf.extend_from_slice(&[1, 1]);
+    }
+    buf.push(0);
+
+    let mut reader = ByteReader::from(buf).with_max_depth(64);
+    assert!(Tree::read(&mut reader).is_err());
+}
+
+#[test]
+fn depth_is_restored() {
+    let tree = Tree::Branch(vec![Tree::Leaf, Tree::Branch(vec![Tree::Leaf]), Tree::Leaf]);
+    let buf = tree.write_to_bytes().unwrap();
+    let mut reader = ByteReader::from(buf.as_slice()).with_max_depth(3);
+    assert_eq!(Tree::read(&mut reader).unwrap(), tree);
+}
+
+#[test]
+fn read_after_deadline() {
+    let buf = chain(4);
+    let mut reader = ByteReader::from(buf).with_deadline(Duration::ZERO);
+    let err = Node::read(&mut reader).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::TimedOut);
+}
+
+#[test]
+fn read_within_deadline() {
+    let buf = chain(4);
+    let mut reader = ByteReader::from(buf).with_deadline(Duration::from_secs(60));
+    assert!(reader.deadline().is_some());
+    assert_eq!(Node::read(&mut reader).unwrap().value, 3);
+}
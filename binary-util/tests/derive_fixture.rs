@@ -0,0 +1,62 @@
+//! A fixture of 300 derived types, used to keep an eye on the compile time of the derive macros.
+//! Time it with `cargo bench --bench derive`.
+use binary_util::interfaces::{Reader, Writer};
+
+macro_rules! fixture {
+    ($($module: ident)*) => {
+        $(
+            #[allow(dead_code)]
+            mod $module {
+                use binary_util::BinaryIo;
+
+                #[derive(BinaryIo, Debug, PartialEq)]
+                pub struct Packet {
+                    pub id: u8,
+                    #[satisfy(self.id == 1)]
+                    pub name: Option<String>,
+                    #[skip_if(self.id == 0)]
+                    pub flags: u16,
+                    #[bool_width(bit)]
+                    pub a: bool,
+                    #[bool_width(bit)]
+                    pub b: bool,
+                    pub body: Vec<u32>,
+                }
+
+                #[derive(BinaryIo, Debug, PartialEq)]
+                #[repr(u8)]
+                pub enum Kind {
+                    A = 1,
+                    B(u16, String),
+                    C(i32, i32),
+                }
+
+                #[derive(BinaryIo, Debug, PartialEq)]
+                pub struct Pair(pub u32, #[skip] pub u8, pub Kind);
+            }
+        )*
+    };
+}
+
+fixture!(m0 m1 m2 m3 m4 m5 m6 m7 m8 m9 m10 m11 m12 m13 m14 m15 m16 m17 m18 m19 m20 m21 m22 m23 m24 m25 m26 m27 m28 m29 m30 m31 m32 m33 m34 m35 m36 m37 m38 m39 m40 m41 m42 m43 m44 m45 m46 m47 m48 m49 m50 m51 m52 m53 m54 m55 m56 m57 m58 m59 m60 m61 m62 m63 m64 m65 m66 m67 m68 m69 m70 m71 m72 m73 m74 m75 m76 m77 m78 m79 m80 m81 m82 m83 m84 m85 m86 m87 m88 m89 m90 m91 m92 m93 m94 m95 m96 m97 m98 m99);
+
+#[test]
+fn fixture_round_trip() {
+    let packet = m42::Packet {
+        id: 1,
+        name: Some("fixture".into()),
+        flags: 3,
+        a: true,
+        b: false,
+        body: vec![1, 2],
+    };
+    let bytes = packet.write_to_bytes().unwrap();
+    assert_eq!(
+        m42::Packet::read_from_slice(bytes.as_slice()).unwrap(),
+        packet
+    );
+
+    let pair = m99::Pair(7, 0, m99::Kind::C(-1, 2));
+    let bytes = pair.write_to_bytes().unwrap();
+    assert_eq!(m99::Pair::read_from_slice(bytes.as_slice()).unwrap(), pair);
+}
@@ -0,0 +1,55 @@
+use binary_util::diff::{compare, compare_fields, compare_type};
+use binary_util::interfaces::{Reader, WireField, Writer};
+use binary_util::BinaryIo;
+
+#[test]
+fn identical_buffers() {
+    let report = compare(&[1, 2, 3], &[1, 2, 3]);
+    assert!(report.is_empty());
+    assert_eq!(report.to_string(), "");
+}
+
+#[test]
+fn ranges_split_at_fields() {
+    let fields = [
+        WireField {
+            name: "a",
+            range: 0..2,
+        },
+        WireField {
+            name: "b",
+            range: 2..4,
+        },
+    ];
+    let report = compare_fields(&[0, 1, 2, 3], &[0, 9, 9, 3], &fields);
+    assert_eq!(report.ranges.len(), 2);
+    assert_eq!(report.ranges[0].field, Some("a"));
+    assert_eq!(report.ranges[0].range, 1..2);
+    assert_eq!(report.ranges[1].field, Some("b"));
+    assert_eq!(report.ranges[1].right, vec![9]);
+}
+
+#[derive(BinaryIo, Debug)]
+#[binary_io(debug_wire)]
+struct Status {
+    online: u32,
+    motd: String,
+}
+
+#[test]
+fn schema_aware_trailing_bytes() {
+    let expected = Status {
+        online: 3,
+        motd: "hi".into(),
+    };
+    let mut actual = expected.write_to_bytes().unwrap().as_slice().to_vec();
+    actual[3] = 4;
+    actual.push(0);
+
+    let report = compare_type(&expected, &actual).unwrap();
+    assert_eq!(
+        report.to_string(),
+        "length 7 != 8\n[3..4] online: 03 != 04\n[7..8]: (none) != 00\n"
+    );
+    assert!(Status::read_from_slice(&actual).is_ok());
+}
@@ -0,0 +1,128 @@
+use std::io::ErrorKind;
+
+use binary_util::driver::{Driver, Framing};
+use binary_util::pool::BytePool;
+use binary_util::BinaryIo;
+
+#[derive(BinaryIo, Debug, PartialEq)]
+#[repr(u8)]
+enum Packet {
+    Ping(u64),
+    Chat(String),
+    Kick,
+}
+
+fn wire(framing: Framing, packets: &[Packet]) -> Vec<u8> {
+    let mut driver = Driver::new().with_framing(framing);
+    for packet in packets {
+        driver.send(packet).unwrap();
+    }
+    assert_eq!(driver.queued(), packets.len());
+    let mut wire = Vec::new();
+    driver.flush_to(&mut wire).unwrap();
+    assert_eq!(driver.queued(), 0);
+    wire
+}
+
+#[test]
+fn decodes_bytes_fed_one_at_a_time() {
+    for framing in [Framing::Delimited, Framing::VarU32] {
+        let packets = [Packet::Chat("hi".into()), Packet::Kick, Packet::Ping(300)];
+        let wire = wire(framing, &packets);
+
+        let mut driver = Driver::<Packet>::new().with_framing(framing);
+        let mut decoded = Vec::new();
+        for byte in &wire {
+            driver.feed(&[*byte]);
+            while let Some(packet) = driver.poll_packet().unwrap() {
+                decoded.push(packet);
+            }
+        }
+        assert_eq!(decoded, packets);
+        assert_eq!(driver.buffered(), 0);
+    }
+}
+
+#[test]
+fn length_prefixed_frames() {
+    let wire = wire(Framing::VarU32, &[Packet::Ping(1)]);
+    assert_eq!(wire, [9, 0, 0, 0, 0, 0, 0, 0, 0, 1]);
+
+    // a frame with an unknown packet id is skipped, and a frame too short for its packet is rejected.
+    let mut driver = Driver::<Packet>::new().with_framing(Framing::VarU32);
+    driver.feed(&[1, 9, 2, 0, 0]);
+    driver.feed(&wire);
+    assert_eq!(
+        driver.poll_packet().unwrap_err().kind(),
+        ErrorKind::InvalidData
+    );
+    assert_eq!(
+        driver.poll_packet().unwrap_err().kind(),
+        ErrorKind::InvalidData
+    );
+    assert_eq!(driver.poll_packet().unwrap(), Some(Packet::Ping(1)));
+    assert_eq!(driver.poll_packet().unwrap(), None);
+}
+
+#[test]
+fn delimited_errors_discard_the_stream() {
+    let mut driver = Driver::<Packet>::new();
+    driver.feed(&wire(Framing::Delimited, &[Packet::Kick]));
+    driver.feed(&[9, 2, 2]);
+    // the packet decoded before the error is returned first.
+    assert_eq!(driver.poll_packet().unwrap(), Some(Packet::Kick));
+    assert!(driver.poll_packet().is_err());
+    assert_eq!(driver.buffered(), 0);
+    assert_eq!(driver.poll_packet().unwrap(), None);
+}
+
+#[test]
+fn transmitted_buffers_are_recycled() {
+    let mut driver = Driver::<Packet>::new().with_pool(BytePool::new(64));
+    driver.send(&Packet::Ping(5)).unwrap();
+    let buf = driver.poll_transmit().unwrap();
+    assert_eq!(buf.as_slice(), &[0, 0, 0, 0, 0, 0, 0, 0, 5]);
+    assert!(driver.poll_transmit().is_none());
+    driver.recycle(buf);
+
+    driver.send(&Packet::Kick).unwrap();
+    assert_eq!(driver.poll_transmit().unwrap().as_slice(), &[2]);
+}
+
+#[test]
+fn oversized_frames_are_skipped_without_buffering() {
+    let mut driver = Driver::<Packet>::new()
+        .with_framing(Framing::VarU32)
+        .with_max_frame_len(16);
+    // a frame of 300 bytes, of which only the first few have arrived.
+    driver.feed(&[0xac, 0x02, 1, 2, 3]);
+    assert_eq!(
+        driver.poll_packet().unwrap_err().kind(),
+        ErrorKind::InvalidData
+    );
+    assert_eq!(driver.buffered(), 0);
+
+    // the rest of the frame is discarded as it arrives, and decoding continues after it.
+    driver.feed(&[0; 200]);
+    driver.feed(&[0; 97]);
+    assert_eq!(driver.buffered(), 0);
+    driver.feed(&wire(Framing::VarU32, &[Packet::Kick]));
+    assert_eq!(driver.poll_packet().unwrap(), Some(Packet::Kick));
+    assert_eq!(driver.poll_packet().unwrap(), None);
+}
+
+#[test]
+fn long_frames_are_prefixed_with_their_shortest_length() {
+    let message = "a".repeat(200);
+    let wire = wire(Framing::VarU32, &[Packet::Chat(message.clone())]);
+    // the frame holds the packet id, the var-int length of the string, and the string.
+    assert_eq!(&wire[..2], &[0xcb, 0x01]);
+    assert_eq!(wire.len(), 2 + 203);
+
+    let mut driver = Driver::<Packet>::new().with_framing(Framing::VarU32);
+    for chunk in wire.chunks(7) {
+        driver.feed(chunk);
+    }
+    assert_eq!(driver.poll_packet().unwrap(), Some(Packet::Chat(message)));
+    assert_eq!(driver.buffered(), 0);
+}
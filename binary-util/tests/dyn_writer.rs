@@ -0,0 +1,47 @@
+use binary_util::interfaces::Writer;
+use binary_util::io::ByteWriter;
+use binary_util::BinaryIo;
+
+#[derive(BinaryIo, Clone)]
+struct Login {
+    protocol: u16,
+}
+
+#[derive(BinaryIo)]
+struct Chat(String);
+
+/// Writes every packet of a queue, without knowing their types.
+fn flush<T: Writer>(queue: &[T]) -> Vec<u8> {
+    let mut buf = ByteWriter::new();
+    for packet in queue {
+        packet.write(&mut buf).unwrap();
+    }
+    buf.as_slice().to_vec()
+}
+
+#[test]
+fn heterogeneous_queues() {
+    let login = Login { protocol: 47 };
+    let queue: Vec<Box<dyn Writer>> = vec![
+        login.boxed_clone(),
+        Chat("hi".into()).boxed(),
+        login.boxed(),
+    ];
+    assert_eq!(flush(&queue), [0, 47, 2, b'h', b'i', 0, 47]);
+    assert_eq!(
+        queue[1].write_to_bytes().unwrap().as_slice(),
+        &[2, b'h', b'i']
+    );
+}
+
+#[test]
+fn references_are_written_like_values() {
+    let chat = Chat("ok".into());
+    let queue = [&chat, &chat];
+    assert_eq!(flush(&queue), [2, b'o', b'k', 2, b'o', b'k']);
+
+    let borrowed: &dyn Writer = &chat;
+    assert_eq!(flush(&[borrowed]), [2, b'o', b'k']);
+    // borrowed bytes keep their length prefix.
+    assert_eq!(flush(&[&[1u8, 2][..]]), [2, 1, 2]);
+}
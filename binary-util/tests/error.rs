@@ -0,0 +1,152 @@
+use binary_util::interfaces::{Reader, Writer};
+use binary_util::io::{BinaryErrorExt, ByteReader, ByteStream, WriteExt};
+use binary_util::BinaryIo;
+
+#[test]
+fn eof_is_retryable() {
+    let mut buf = ByteReader::from(&[0, 0, 0, 1, 0][..]);
+    buf.read_u32().unwrap();
+    let err = buf.read_u16().unwrap_err();
+    assert!(err.is_eof());
+    assert!(!err.is_overflow());
+    // one of the two bytes is available.
+    assert_eq!(err.needed_bytes(), Some(1));
+    assert_eq!(err.offset(), Some(4));
+}
+
+#[test]
+fn var_int_overflow() {
+    let mut buf = ByteReader::from(&[255, 255, 255, 255, 255, 255][..]);
+    let err = buf.read_var_u32().unwrap_err();
+    assert!(err.is_overflow());
+    assert!(!err.is_eof());
+    assert_eq!(err.offset(), Some(0));
+}
+
+#[test]
+fn slice_needs_length() {
+    let mut buf = ByteReader::from(&[10, 1, 2][..]);
+    let err = buf.read_sized_slice().unwrap_err();
+    assert!(err.is_eof());
+    assert_eq!(err.needed_bytes(), Some(8));
+}
+
+#[derive(BinaryIo, Debug)]
+struct Login {
+    protocol: u16,
+    name: String,
+}
+
+#[test]
+fn derived_errors_keep_payload() {
+    let err = Login::read_from_slice(&[0, 1, 5, b'a']).unwrap_err();
+    assert!(err.is_eof());
+    assert_eq!(err.needed_bytes(), Some(4));
+    assert_eq!(err.offset(), Some(3));
+}
+
+#[test]
+fn stream_eof_needs_remaining() {
+    let mut stream = ByteStream::new(&[1, 2][..]);
+    let err = stream.read_frame(5).unwrap_err();
+    assert!(err.is_eof());
+    assert_eq!(err.needed_bytes(), Some(3));
+}
+
+#[test]
+fn needed_bytes_are_the_bytes_missing() {
+    let needed = |err: std::io::Error| err.needed_bytes();
+
+    assert_eq!(
+        needed(ByteReader::from(&[0; 5][..]).read_u64().unwrap_err()),
+        Some(3)
+    );
+    assert_eq!(
+        needed(ByteReader::from(&[0; 2][..]).read_uint(3).unwrap_err()),
+        Some(1)
+    );
+    assert_eq!(
+        needed(ByteReader::from(&[0; 3][..]).read_bytes(7).unwrap_err()),
+        Some(4)
+    );
+    assert_eq!(
+        needed(
+            ByteReader::from(&[0xf0, 0x9f][..])
+                .read_char_utf8()
+                .unwrap_err()
+        ),
+        Some(2)
+    );
+    // the length of a truncated var-int is unknown, so at least one more byte is needed.
+    assert_eq!(
+        needed(
+            ByteReader::from(&[0x80, 0x80][..])
+                .read_var_u32()
+                .unwrap_err()
+        ),
+        Some(1)
+    );
+    assert_eq!(
+        needed(String::read_from_slice(&[4, b'a']).unwrap_err()),
+        Some(3)
+    );
+    assert_eq!(
+        needed(ByteReader::from(&[0; 2][..]).window(1, 4).unwrap_err()),
+        Some(3)
+    );
+
+    let mut out = [0u8; 3];
+    let err = WriteExt::write_u32(&mut &mut out[..], 1).unwrap_err();
+    assert_eq!(needed(err), Some(1));
+    let err = WriteExt::write_var_u32(&mut &mut out[..1], 300).unwrap_err();
+    assert_eq!(needed(err), Some(1));
+}
+
+#[test]
+fn foreign_errors_have_no_payload() {
+    let err = std::io::Error::other("oops");
+    assert!(err.binary_error().is_none());
+    assert_eq!(err.needed_bytes(), None);
+    assert!(!err.is_overflow());
+}
+
+#[test]
+fn wrapped_errors_keep_source() {
+    use std::error::Error;
+
+    let err = String::read_from_slice(&[2, 0xff, 0xfe]).unwrap_err();
+    let payload = err.binary_error().unwrap();
+    assert_eq!(payload.offset(), Some(1));
+    let source = payload.source().unwrap();
+    assert_eq!(
+        source
+            .downcast_ref::<std::str::Utf8Error>()
+            .unwrap()
+            .valid_up_to(),
+        0
+    );
+}
+
+#[derive(BinaryIo, Debug)]
+struct Chunk {
+    #[len_of(data)]
+    len: u8,
+    data: Vec<u8>,
+}
+
+#[test]
+fn derived_errors_keep_source() {
+    use std::error::Error;
+
+    let err = Chunk {
+        len: 0,
+        data: vec![0; 300],
+    }
+    .write_to_bytes()
+    .unwrap_err();
+    let source = err.binary_error().unwrap().source().unwrap();
+    assert!(source.is::<std::num::TryFromIntError>());
+
+    let chunk = Chunk::read_from_slice(&[1, 7]).unwrap();
+    assert_eq!((chunk.len, chunk.data), (1, vec![7]));
+}
@@ -0,0 +1,81 @@
+use binary_util::interfaces::Writer;
+use binary_util::io::prelude::*;
+use bytes::Buf;
+
+#[test]
+fn read_ext_slice() {
+    let mut buf = &[0, 1, 0, 0, 1, 44, 255, 255, 127, 1][..];
+    assert_eq!(buf.read_u16().unwrap(), 1);
+    assert_eq!(buf.read_u32().unwrap(), 300);
+    assert_eq!(buf.read_var_u32().unwrap(), 2097151);
+    assert!(buf.read_bool().unwrap());
+    assert!(buf.read_u8().is_err());
+}
+
+#[test]
+fn read_ext_var_int_is_recoverable() {
+    let mut buf = &[255, 255][..];
+    assert!(buf.read_var_u32().is_err());
+    assert_eq!(buf.len(), 2);
+}
+
+#[test]
+fn read_ext_var_int_across_chunks() {
+    let mut buf = (&[172][..]).chain(&[2][..]);
+    assert_eq!(buf.read_var_u32().unwrap(), 300);
+    assert_eq!(buf.remaining(), 0);
+}
+
+#[test]
+fn write_ext_vec() {
+    let mut buf: Vec<u8> = Vec::new();
+    buf.write_u16(1).unwrap();
+    buf.write_u24(300u32).unwrap();
+    buf.write_var_i32(-12).unwrap();
+    buf.write_bool(true).unwrap();
+    assert_eq!(buf, vec![0, 1, 0, 1, 44, 23, 1]);
+}
+
+#[test]
+fn write_ext_matches_byte_writer() {
+    let mut writer = ByteWriter::new();
+    writer.write_var_u64(u64::MAX).unwrap();
+    writer.write_i128_le(-5).unwrap();
+
+    let mut buf: Vec<u8> = Vec::new();
+    buf.write_var_u64(u64::MAX).unwrap();
+    buf.write_i128_le(-5).unwrap();
+
+    assert_eq!(writer.as_slice(), &buf[..]);
+}
+
+#[test]
+fn stream_write_type() {
+    let mut stream = ByteStream::new(Vec::<u8>::new());
+    stream.write_type(&String::from("Hi")).unwrap();
+    stream.write_type(&Some(1u8)).unwrap();
+
+    let expected = String::from("Hi").write_to_bytes().unwrap();
+    let out = stream.into_inner();
+    assert_eq!(&out[..3], expected.as_slice());
+    assert_eq!(&out[3..], &[1, 1]);
+}
+
+#[test]
+fn write_ext_var_ints_are_atomic() {
+    let mut out = [0xaa; 3];
+    let mut buf = &mut out[..];
+    buf.write_u8(1).unwrap();
+    // 300 takes 2 bytes, and u32::MAX takes 5, so only the first fits.
+    buf.write_var_u32(300).unwrap();
+    assert!(buf.write_var_u32(u32::MAX).is_err());
+    assert!(buf.write_var_i64_be(i64::MIN).is_err());
+    assert_eq!(buf.len(), 0);
+    assert_eq!(out, [1, 172, 2]);
+
+    let mut out = [0xaa; 4];
+    let mut buf = &mut out[..];
+    assert!(buf.write_var_u64(u64::MAX).is_err());
+    assert_eq!(buf.len(), 4);
+    assert_eq!(out, [0xaa; 4]);
+}
@@ -0,0 +1,27 @@
+#![cfg(feature = "glam")]
+
+use binary_util::interfaces::{Reader, Writer};
+use binary_util::types::LE;
+use binary_util::BinaryIo;
+use glam::{Quat, Vec2, Vec3};
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct MoveEntity {
+    position: Vec3,
+    rotation: LE<Quat>,
+    velocity: Vec2,
+}
+
+#[test]
+fn glam_roundtrip() {
+    let packet = MoveEntity {
+        position: Vec3::new(1.0, 2.0, 3.0),
+        rotation: LE(Quat::from_xyzw(0.0, 0.0, 0.0, 1.0)),
+        velocity: Vec2::new(-1.0, 0.5),
+    };
+    let buf = packet.write_to_bytes().unwrap();
+    assert_eq!(buf.as_slice().len(), 12 + 16 + 8);
+    assert_eq!(&buf.as_slice()[..4], &1.0f32.to_be_bytes());
+    assert_eq!(&buf.as_slice()[24..28], &1.0f32.to_le_bytes());
+    assert_eq!(MoveEntity::read_from_slice(buf.as_slice()).unwrap(), packet);
+}
@@ -0,0 +1,125 @@
+use binary_util::interfaces::{Reader, Writer};
+use binary_util::io::{ByteReader, ByteWriter, HARDENED_MAX_ALLOC, HARDENED_MAX_DEPTH};
+use binary_util::types::{varu32, Prefixed};
+use binary_util::BinaryIo;
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct Inventory {
+    #[len_of(items)]
+    count: u8,
+    items: Vec<u8>,
+}
+
+#[test]
+fn hardened_enables_every_setting() {
+    let buf = ByteReader::hardened(&[][..]);
+    assert!(buf.canonical_varints());
+    assert!(buf.strict_bools());
+    assert_eq!(buf.max_alloc(), Some(HARDENED_MAX_ALLOC));
+    assert_eq!(buf.max_depth(), Some(HARDENED_MAX_DEPTH));
+
+    let buf = ByteReader::from(&[][..]);
+    assert!(!buf.canonical_varints());
+    assert_eq!(buf.max_alloc(), None);
+
+    // settings can be relaxed afterwards.
+    let buf = ByteReader::hardened(&[][..]).with_canonical_varints(false);
+    assert!(!buf.canonical_varints());
+}
+
+#[test]
+fn canonical_varints() {
+    let read = |bytes: &[u8]| {
+        ByteReader::from(bytes)
+            .with_canonical_varints(true)
+            .read_var_u32()
+    };
+    assert_eq!(read(&[0]).unwrap(), 0);
+    assert_eq!(read(&[172, 2]).unwrap(), 300);
+    assert_eq!(read(&[0xff, 0xff, 0xff, 0xff, 0x0f]).unwrap(), u32::MAX);
+    // overlong encodings.
+    assert!(read(&[0x80, 0x00]).is_err());
+    assert!(read(&[0xac, 0x82, 0x00]).is_err());
+    // bits beyond 32 bits.
+    assert!(read(&[0xff, 0xff, 0xff, 0xff, 0x1f]).is_err());
+
+    let mut buf = ByteReader::from(&[0x81, 0x80, 0x00][..]).with_canonical_varints(true);
+    assert!(buf.read_var_i64().is_err());
+    assert_eq!(buf.offset(), 0);
+
+    // most significant group first.
+    let mut buf = ByteReader::from(&[0x80, 0x01][..]).with_canonical_varints(true);
+    assert!(buf.read_var_u32_be().is_err());
+    let mut buf = ByteReader::from(&[130, 44][..]).with_canonical_varints(true);
+    assert_eq!(buf.read_var_u32_be().unwrap(), 300);
+
+    // every written var-int is canonical.
+    for num in [0i32, 1, -1, 63, -64, 300, i32::MIN, i32::MAX] {
+        let bytes = binary_util::types::vari32(num).write_to_bytes().unwrap();
+        let mut buf = ByteReader::hardened(bytes.as_slice());
+        assert_eq!(buf.read_var_i32().unwrap(), num);
+    }
+}
+
+#[test]
+fn max_alloc_limits_length_prefixes() {
+    let bytes = vec![7u8; 4].write_to_bytes().unwrap();
+    let reader = |max| ByteReader::from(bytes.as_slice()).with_max_alloc(max);
+    assert_eq!(Vec::<u8>::read(&mut reader(4)).unwrap(), vec![7; 4]);
+    assert!(Vec::<u8>::read(&mut reader(3)).is_err());
+    assert!(String::read(&mut reader(3)).is_err());
+    assert!(reader(3).read_sized_slice().is_err());
+    assert!(Prefixed::<varu32, Vec<u8>>::read(&mut reader(3)).is_err());
+
+    let mut string = String::new();
+    assert!(reader(3).read_string_into(&mut string).is_err());
+
+    let inventory = Inventory {
+        count: 2,
+        items: vec![1, 2],
+    };
+    let bytes = inventory.write_to_bytes().unwrap();
+    assert!(Inventory::read(&mut ByteReader::from(bytes.as_slice()).with_max_alloc(1)).is_err());
+    assert_eq!(
+        Inventory::read(&mut ByteReader::hardened(bytes.as_slice())).unwrap(),
+        inventory
+    );
+
+    // a forged length is rejected before anything is read.
+    let mut buf = ByteReader::hardened(&[0xff, 0xff, 0xff, 0xff, 0x0f][..]);
+    let err = Vec::<u64>::read(&mut buf).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn forged_length_does_not_reserve_past_the_buffer() {
+    // without a limit, a length of `u32::MAX` would reserve 32 GiB for the items up front.
+    let mut buf = ByteReader::from(&[0xff, 0xff, 0xff, 0xff, 0x0f, 1][..]);
+    let err = Vec::<u64>::read(&mut buf).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn back_patched_prefixes_are_not_canonical() {
+    let mut writer = ByteWriter::new();
+    writer.write(&[0; 5]).unwrap();
+    writer.write_string("body").unwrap();
+    writer.write_var_u32_at(0, 5).unwrap();
+
+    // the padded prefix is only accepted by readers without canonical var-ints.
+    assert_eq!(
+        ByteReader::from(writer.as_slice()).read_var_u32().unwrap(),
+        5
+    );
+    let mut buf = ByteReader::hardened(writer.as_slice());
+    let err = buf.read_var_u32().unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    assert_eq!(buf.offset(), 0);
+
+    let mut writer = ByteWriter::new();
+    writer.write_string("body").unwrap();
+    writer.insert_var_u32_at(0, 5).unwrap();
+    let mut buf = ByteReader::hardened(writer.as_slice());
+    assert_eq!(buf.read_var_u32().unwrap(), 5);
+    assert_eq!(buf.read_string().unwrap(), "body");
+}
@@ -0,0 +1,72 @@
+#![cfg(all(feature = "instrumented", debug_assertions))]
+#[cfg(feature = "inline")]
+use binary_util::io::INLINE_CAPACITY;
+use binary_util::io::{ByteReader, ByteWriter};
+
+#[test]
+#[should_panic(expected = "taken before the writer was cleared")]
+fn stale_mark() {
+    let mut writer = ByteWriter::new();
+    writer.write_u32(0).unwrap();
+    let mark = writer.mark();
+    writer.clear();
+    writer.bytes_written_since(mark);
+}
+
+#[test]
+#[should_panic(expected = "is after end mark")]
+fn marks_out_of_order() {
+    let mut writer = ByteWriter::new();
+    let start = writer.mark();
+    writer.write_u32(0).unwrap();
+    let end = writer.mark();
+    writer.written_between(end, start);
+}
+
+#[test]
+#[cfg(feature = "inline")]
+#[should_panic(expected = "exceeds INLINE_CAPACITY")]
+fn inline_threshold_too_large() {
+    ByteWriter::with_inline_threshold(INLINE_CAPACITY + 1);
+}
+
+#[test]
+#[should_panic(expected = "checkpoint was taken from a different reader")]
+fn foreign_checkpoint() {
+    let first = ByteReader::from(&[0, 1, 2, 3][..]);
+    let mut second = ByteReader::from(&[0, 1, 2, 3][..]);
+    second.restore(first.checkpoint());
+}
+
+#[test]
+#[should_panic(expected = "checkpoint was taken before the reader was split")]
+fn checkpoint_before_split() {
+    let mut reader = ByteReader::from(&[0, 1, 2, 3][..]);
+    let start = reader.checkpoint();
+    reader.read_u16().unwrap();
+    reader.split_at_current();
+    reader.restore(start);
+}
+
+#[test]
+fn valid_use_does_not_panic() {
+    #[cfg(feature = "inline")]
+    let mut writer = ByteWriter::with_inline_threshold(INLINE_CAPACITY);
+    #[cfg(not(feature = "inline"))]
+    let mut writer = ByteWriter::new();
+    writer.write(&[0; 5]).unwrap();
+    let start = writer.mark();
+    writer.write_string("Hello").unwrap();
+    let end = writer.mark();
+    writer.write_var_u32_at(0, 6).unwrap();
+    assert_eq!(writer.written_between(start, end).len(), 6);
+
+    let mut reader = ByteReader::from(writer.as_slice());
+    let clone = reader.clone();
+    reader.read_u8().unwrap();
+    let checkpoint = reader.checkpoint();
+    reader.read_u16().unwrap();
+    assert_eq!(reader.read_since(&checkpoint).len(), 2);
+    reader.restore(clone.checkpoint());
+    assert_eq!(reader.offset(), 0);
+}
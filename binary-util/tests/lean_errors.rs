@@ -0,0 +1,99 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use binary_util::interfaces::Reader;
+use binary_util::io::{BinaryErrorExt, ByteReader};
+use binary_util::BinaryIo;
+
+/// Counts the allocations made by each thread, so tests running in parallel do not see each other's.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+// SAFETY: every call is forwarded to the system allocator.
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Returns the amount of allocations `f` makes.
+fn allocations(f: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    f();
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+#[derive(BinaryIo, Debug)]
+struct Header {
+    id: u8,
+    length: u32,
+}
+
+#[test]
+fn lean_errors() {
+    let mut reader = ByteReader::from(&[0, 1][..]).with_detailed_errors(false);
+    assert!(!reader.detailed_errors());
+    let err = reader.read_u32().unwrap_err();
+    assert!(err.is_eof());
+    assert!(err.binary_error().is_none());
+    assert_eq!(err.offset(), None);
+
+    let mut reader =
+        ByteReader::from(&[255, 255, 255, 255, 255, 255][..]).with_detailed_errors(false);
+    assert!(reader.read_var_u32().unwrap_err().binary_error().is_none());
+}
+
+#[test]
+fn lean_errors_from_nested_reads() {
+    let mut reader = ByteReader::from(&[1, 0][..]).with_detailed_errors(false);
+    let err = Header::read(&mut reader).unwrap_err();
+    assert!(err.is_eof());
+    assert!(err.binary_error().is_none());
+}
+
+#[test]
+fn detailed_errors_are_per_reader() {
+    let _lean = ByteReader::from(&[0][..]).with_detailed_errors(false);
+    let mut reader = ByteReader::from(&[0][..]);
+    assert!(reader.detailed_errors());
+    let err = reader.read_u16().unwrap_err();
+    assert_eq!(err.needed_bytes(), Some(1));
+}
+
+#[test]
+fn lean_errors_do_not_allocate() {
+    let mut short = ByteReader::from(&[1][..]).with_detailed_errors(false);
+    let mut long_var = ByteReader::from(&[255; 6][..]).with_detailed_errors(false);
+    let mut bad_bool = ByteReader::from(&[2][..]).with_detailed_errors(false);
+    let mut string = ByteReader::from(&[5, b'a'][..]).with_detailed_errors(false);
+
+    assert_eq!(
+        allocations(|| {
+            assert!(short.read_u32().is_err());
+            assert!(short.read_u24_le().is_err());
+            assert!(short.read_uint(3).is_err());
+            assert!(short.read_char_utf16().is_err());
+            assert!(Header::read(&mut short).is_err());
+            assert!(long_var.read_var_u32().is_err());
+            assert!(long_var.read_var_i64().is_err());
+            assert!(bad_bool.read_bool_strict().is_err());
+            assert!(string.read_string().is_err());
+        }),
+        0
+    );
+
+    // detailed errors carry a payload, which is allocated.
+    let mut detailed = ByteReader::from(&[1, 0][..]);
+    assert!(allocations(|| assert!(detailed.read_u32().is_err())) > 0);
+}
@@ -0,0 +1,29 @@
+#![cfg(feature = "mmap")]
+use binary_util::io::ByteReader;
+
+#[test]
+fn read_records_from_mapped_file() {
+    let path = std::env::temp_dir().join(format!("binary_util_mmap_{}.bin", std::process::id()));
+    let mut data = Vec::new();
+    for i in 0..1000u32 {
+        data.extend_from_slice(&4u32.to_be_bytes());
+        data.extend_from_slice(&i.to_le_bytes());
+    }
+    std::fs::write(&path, &data).unwrap();
+
+    let mut buf = ByteReader::from_mmap(&path).unwrap();
+    let mut records = Vec::new();
+    while let Ok(len) = buf.read_u32() {
+        records.push(buf.read_bytes(len as usize).unwrap());
+    }
+    drop(buf);
+
+    assert_eq!(records.len(), 1000);
+    assert_eq!(&records[999][..], &999u32.to_le_bytes());
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn missing_file() {
+    assert!(ByteReader::from_mmap("/this/file/does/not/exist").is_err());
+}
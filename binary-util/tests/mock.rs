@@ -0,0 +1,216 @@
+use binary_util::interfaces::{Reader, Writer};
+use binary_util::testing::{random, random_with_seed};
+use binary_util::types::{u24, varu32, Prefixed, BE, LE};
+use binary_util::{BinaryIo, MockBinary};
+
+#[derive(BinaryIo, MockBinary, Debug, PartialEq)]
+struct Handshake {
+    protocol: varu32,
+    name: String,
+    port: LE<u16>,
+    sequence: u24,
+    #[skip]
+    is_local: bool,
+    contains_content: bool,
+    #[satisfy(self.contains_content == true)]
+    content: Option<String>,
+    #[if_present(content)]
+    content_validated: Option<u32>,
+}
+
+#[derive(BinaryIo, MockBinary, Debug, PartialEq)]
+struct Required {
+    token: Option<u64>,
+    #[require(token)]
+    expiry: Option<u32>,
+}
+
+#[derive(BinaryIo, MockBinary, Debug, PartialEq)]
+struct Bounded {
+    #[max_len(1)]
+    tags: Vec<u8>,
+    #[max_len(0)]
+    motd: String,
+    #[max_len(2)]
+    header: Prefixed<BE<u16>, Vec<u8>>,
+}
+
+#[derive(BinaryIo, MockBinary, Debug, PartialEq)]
+#[repr(u8)]
+enum Packet {
+    Ping,
+    Handshake(Handshake),
+    Batch(Vec<u8>, bool),
+}
+
+#[test]
+fn mock_is_deterministic() {
+    assert_eq!(
+        random_with_seed::<Handshake>(42),
+        random_with_seed::<Handshake>(42)
+    );
+}
+
+#[test]
+fn mock_roundtrip() {
+    for seed in 0..256 {
+        let handshake: Handshake = random_with_seed(seed);
+        let buf = handshake.write_to_bytes().unwrap();
+        assert_eq!(
+            Handshake::read_from_slice(buf.as_slice()).unwrap(),
+            handshake
+        );
+
+        let required: Required = random_with_seed(seed);
+        assert!(required.token.is_some() && required.expiry.is_some());
+        let buf = required.write_to_bytes().unwrap();
+        assert_eq!(Required::read_from_slice(buf.as_slice()).unwrap(), required);
+
+        let packet: Packet = random_with_seed(seed);
+        let buf = packet.write_to_bytes().unwrap();
+        assert_eq!(Packet::read_from_slice(buf.as_slice()).unwrap(), packet);
+    }
+}
+
+#[test]
+fn mock_random() {
+    let packet: Packet = random();
+    assert!(packet.write_to_bytes().is_ok());
+}
+
+#[derive(BinaryIo, MockBinary, Debug, PartialEq)]
+struct OrderedMock {
+    #[order(1)]
+    #[satisfy(self.kind == 1)]
+    payload: Option<u32>,
+    #[order(0)]
+    kind: u8,
+}
+
+#[test]
+fn mock_follows_field_order() {
+    for seed in 0..32 {
+        let value: OrderedMock = random_with_seed(seed);
+        let buf = value.write_to_bytes().unwrap();
+        assert_eq!(OrderedMock::read_from_slice(buf.as_slice()).unwrap(), value);
+    }
+}
+
+#[derive(BinaryIo, MockBinary, Debug, PartialEq)]
+struct CountedMock {
+    #[len_of(name)]
+    len: u8,
+    name: String,
+}
+
+#[test]
+fn mock_populates_len_of() {
+    for seed in 0..32 {
+        let value: CountedMock = random_with_seed(seed);
+        assert_eq!(value.len as usize, value.name.len());
+        let buf = value.write_to_bytes().unwrap();
+        assert_eq!(CountedMock::read_from_slice(buf.as_slice()).unwrap(), value);
+    }
+}
+
+/// A length which only fits counts up to 3.
+#[derive(BinaryIo, Debug, Clone, Copy, PartialEq)]
+struct Nibble(u8);
+
+impl TryFrom<usize> for Nibble {
+    type Error = String;
+
+    fn try_from(count: usize) -> Result<Self, Self::Error> {
+        match count {
+            0..=3 => Ok(Nibble(count as u8)),
+            _ => Err(format!("{} does not fit within a nibble", count)),
+        }
+    }
+}
+
+impl From<Nibble> for usize {
+    fn from(nibble: Nibble) -> Self {
+        nibble.0 as usize
+    }
+}
+
+#[derive(BinaryIo, MockBinary, Debug, PartialEq)]
+struct NibbleCountedMock {
+    #[len_of(items)]
+    len: Nibble,
+    items: Vec<u8>,
+}
+
+#[test]
+fn mock_bounds_len_of_collections() {
+    assert_eq!(binary_util::testing::max_count::<Nibble>(), 3);
+    for seed in 0..64 {
+        let value: NibbleCountedMock = random_with_seed(seed);
+        assert!(value.items.len() <= 3);
+        let buf = value.write_to_bytes().unwrap();
+        assert_eq!(
+            NibbleCountedMock::read_from_slice(buf.as_slice()).unwrap(),
+            value
+        );
+    }
+}
+
+#[derive(BinaryIo, MockBinary, Debug, PartialEq)]
+struct ChecksumMock {
+    data: Vec<u16>,
+    #[crc_of(data, crc32)]
+    crc: u64,
+}
+
+#[test]
+fn mock_populates_crc_of() {
+    for seed in 0..32 {
+        let value: ChecksumMock = random_with_seed(seed);
+        let buf = value.write_to_bytes().unwrap();
+        assert_eq!(
+            ChecksumMock::read_from_slice(buf.as_slice()).unwrap(),
+            value
+        );
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+#[derive(BinaryIo, MockBinary)]
+#[binary_io(remote = "Color")]
+struct ColorDef {
+    r: u8,
+    g: u8,
+    b: u8,
+}
+
+#[derive(BinaryIo, MockBinary, Debug, PartialEq)]
+struct RemoteMock {
+    #[with(ColorDef)]
+    color: Color,
+    alpha: u8,
+}
+
+#[test]
+fn mock_remote_field() {
+    for seed in 0..8 {
+        let value: RemoteMock = random_with_seed(seed);
+        let buf = value.write_to_bytes().unwrap();
+        assert_eq!(RemoteMock::read_from_slice(buf.as_slice()).unwrap(), value);
+    }
+}
+
+#[test]
+fn mock_respects_max_len() {
+    for seed in 0..32 {
+        let bounded = random_with_seed::<Bounded>(seed);
+        assert!(bounded.tags.len() <= 1 && bounded.motd.is_empty() && bounded.header.len() <= 2);
+        let buf = bounded.write_to_bytes().unwrap();
+        assert_eq!(Bounded::read_from_slice(buf.as_slice()).unwrap(), bounded);
+    }
+}
@@ -0,0 +1,34 @@
+#![cfg(feature = "nalgebra")]
+
+use binary_util::interfaces::{Reader, Writer};
+use binary_util::types::LE;
+use binary_util::BinaryIo;
+use nalgebra::{Quaternion, UnitQuaternion, Vector3};
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct Transform {
+    translation: LE<Vector3<f64>>,
+    rotation: UnitQuaternion<f32>,
+}
+
+#[test]
+fn nalgebra_roundtrip() {
+    let packet = Transform {
+        translation: LE(Vector3::new(1.0, -2.0, 3.5)),
+        rotation: UnitQuaternion::from_quaternion(Quaternion::new(0.0, 1.0, 0.0, 0.0)),
+    };
+    let buf = packet.write_to_bytes().unwrap();
+    assert_eq!(&buf.as_slice()[..8], &1.0f64.to_le_bytes());
+    // encoded as `i, j, k, w`.
+    assert_eq!(&buf.as_slice()[24..28], &1.0f32.to_be_bytes());
+    assert_eq!(&buf.as_slice()[36..], &0.0f32.to_be_bytes());
+    assert_eq!(Transform::read_from_slice(buf.as_slice()).unwrap(), packet);
+}
+
+#[test]
+fn unit_quaternion_is_normalized() {
+    let raw = Quaternion::new(2.0f32, 0.0, 0.0, 0.0);
+    let buf = raw.write_to_bytes().unwrap();
+    let unit = UnitQuaternion::<f32>::read_from_slice(buf.as_slice()).unwrap();
+    assert_eq!(unit.w, 1.0);
+}
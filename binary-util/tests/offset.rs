@@ -0,0 +1,54 @@
+use binary_util::interfaces::Reader;
+use binary_util::io::ByteReader;
+use binary_util::BinaryIo;
+
+#[test]
+fn offset_advances() {
+    let mut buf = ByteReader::from(&[0, 1, 2, 172, 2, 0, 0, 0, 0][..]);
+    assert_eq!(buf.offset(), 0);
+    buf.read_u8().unwrap();
+    assert_eq!(buf.offset(), 1);
+    buf.read_u16().unwrap();
+    assert_eq!(buf.offset(), 3);
+    buf.read_var_u32().unwrap();
+    assert_eq!(buf.offset(), 5);
+    buf.read_u32().unwrap();
+    assert_eq!(buf.offset(), 9);
+}
+
+#[test]
+fn eof_error_has_offset() {
+    let mut buf = ByteReader::from(&[0, 0, 0, 1, 0, 0][..]);
+    buf.read_u32().unwrap();
+    let err = buf.read_u32().unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+    assert!(err
+        .to_string()
+        .starts_with("UnexpectedEof at offset 4 needing 2 more bytes"));
+    // a failed read does not consume
+    assert_eq!(buf.offset(), 4);
+}
+
+#[test]
+fn slice_error_has_offset() {
+    let mut buf = ByteReader::from(&[1, 10, 1, 2][..]);
+    buf.read_u8().unwrap();
+    let err = buf.read_sized_slice().unwrap_err();
+    assert!(err
+        .to_string()
+        .starts_with("UnexpectedEof at offset 2 needing 8 more bytes"));
+}
+
+#[derive(BinaryIo, Debug)]
+#[repr(u8)]
+enum Kind {
+    A = 1,
+    B = 2,
+}
+
+#[test]
+fn derived_error_has_offset() {
+    let err = Kind::read_from_slice(&[7]).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    assert!(err.to_string().starts_with("InvalidData at offset 1"));
+}
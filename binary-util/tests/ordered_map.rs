@@ -0,0 +1,77 @@
+use std::collections::HashMap;
+
+use binary_util::interfaces::{Reader, WireDisplay, Writer};
+use binary_util::types::{OrderedMap, Prefixed};
+use binary_util::BinaryIo;
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct Signed {
+    attributes: OrderedMap<String, u32>,
+    signature: u64,
+}
+
+fn attributes(keys: &[&str]) -> OrderedMap<String, u32> {
+    keys.iter()
+        .enumerate()
+        .map(|(i, key)| (key.to_string(), i as u32))
+        .collect()
+}
+
+#[test]
+fn encoding_is_independent_of_insertion_order() {
+    let forward = attributes(&["alpha", "beta", "gamma", "delta", "epsilon"]);
+    let mut backward = OrderedMap::new();
+    for (key, value) in forward.iter().collect::<Vec<_>>().into_iter().rev() {
+        backward.insert(key.clone(), *value);
+    }
+    // a map with a different capacity iterates in a different order, but encodes the same.
+    let mut resized: HashMap<String, u32> = HashMap::with_capacity(1024);
+    resized.extend(forward.0.clone());
+
+    let bytes = forward.write_to_bytes().unwrap();
+    assert_eq!(
+        backward.write_to_bytes().unwrap().as_slice(),
+        bytes.as_slice()
+    );
+    assert_eq!(
+        OrderedMap::from(resized)
+            .write_to_bytes()
+            .unwrap()
+            .as_slice(),
+        bytes.as_slice()
+    );
+
+    let packet = Signed {
+        attributes: forward,
+        signature: 7,
+    };
+    let bytes = packet.write_to_bytes().unwrap();
+    assert_eq!(Signed::read_from_slice(bytes.as_slice()).unwrap(), packet);
+}
+
+#[test]
+fn unsorted_and_duplicate_keys_are_rejected() {
+    assert_eq!(
+        OrderedMap::<u8, u8>::read_from_slice(&[2, 1, 9, 2, 8])
+            .unwrap()
+            .sorted(),
+        vec![(&1, &9), (&2, &8)]
+    );
+    assert!(OrderedMap::<u8, u8>::read_from_slice(&[2, 2, 9, 1, 8]).is_err());
+    assert!(OrderedMap::<u8, u8>::read_from_slice(&[2, 1, 9, 1, 8]).is_err());
+}
+
+#[test]
+fn length_prefixed_and_displayed_sorted() {
+    let map: OrderedMap<u8, u16> = [(2, 20), (1, 10)].into_iter().collect();
+    let prefixed = Prefixed::<u8, _>::new(map.clone());
+    let bytes = prefixed.write_to_bytes().unwrap();
+    assert_eq!(bytes.as_slice(), &[2, 1, 0, 10, 2, 0, 20]);
+    assert_eq!(
+        Prefixed::<u8, OrderedMap<u8, u16>>::read_from_slice(bytes.as_slice())
+            .unwrap()
+            .into_inner(),
+        map
+    );
+    assert_eq!(map.display_wire().to_string(), "{1: 10, 2: 20}");
+}
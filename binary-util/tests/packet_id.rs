@@ -0,0 +1,73 @@
+use binary_util::interfaces::{BinaryDiscriminant, Reader, Writer};
+use binary_util::testing::{Mock, MockRng};
+use binary_util::BinaryIo;
+
+#[derive(BinaryIo, Debug, PartialEq)]
+#[repr(u16)]
+enum Packet {
+    Ping(u64) = 0x00,
+    Pong(u64) = 0x01,
+    Login(String) = 0x8f,
+}
+
+binary_util::packet_id! {
+    pub struct PacketId: varu32 for Packet, impl Mock {
+        PING = 0x00,
+        PONG = 0x01,
+        /// Sent by the client once connected.
+        LOGIN = 0x8f,
+    }
+}
+
+binary_util::packet_id! {
+    struct ChannelId: u8 {
+        CHAT = 1,
+        SYSTEM = 2,
+    }
+}
+
+#[test]
+fn encoded_with_its_wire_type() {
+    let buf = PacketId::LOGIN.write_to_bytes().unwrap();
+    assert_eq!(buf.as_slice(), &[0x8f, 0x01]);
+    assert_eq!(
+        PacketId::read_from_slice(buf.as_slice()).unwrap(),
+        PacketId::LOGIN
+    );
+
+    let buf = ChannelId::SYSTEM.write_to_bytes().unwrap();
+    assert_eq!(buf.as_slice(), &[2]);
+    assert_eq!(ChannelId::read_from_slice(&[1]).unwrap(), ChannelId::CHAT);
+}
+
+#[test]
+fn names_and_unknown_ids() {
+    assert_eq!(PacketId::ALL.len(), 3);
+    assert_eq!(PacketId::PONG.name(), Some("PONG"));
+    assert_eq!(PacketId::PONG.get(), 1);
+    assert_eq!(u32::from(PacketId::LOGIN), 0x8f);
+    assert_eq!(PacketId::LOGIN.to_string(), "LOGIN (0x8f)");
+
+    // unknown ids are still read, so they can be skipped.
+    let id = PacketId::read_from_slice(&[0x7f]).unwrap();
+    assert!(!id.is_known());
+    assert_eq!(id.to_string(), "0x7f");
+}
+
+#[test]
+fn matches_the_enum() {
+    for (id, _) in PacketId::ALL {
+        assert!(Packet::DISCRIMINANTS
+            .iter()
+            .any(|(discriminant, _)| *discriminant as u32 == id.get()));
+    }
+    assert_eq!(
+        Packet::Login("a".into()).discriminant() as u32,
+        PacketId::LOGIN.get()
+    );
+
+    let mut rng = MockRng::new(7);
+    for _ in 0..16 {
+        assert!(PacketId::mock(&mut rng).is_known());
+    }
+}
@@ -0,0 +1,83 @@
+use binary_util::interfaces::{ReadPartial, Reader, Writer};
+use binary_util::io::ByteReader;
+use binary_util::BinaryIo;
+
+#[derive(BinaryIo, Debug, PartialEq)]
+#[binary_io(partial, deny_trailing)]
+struct Header {
+    id: u8,
+    #[bool_width(bit)]
+    reliable: bool,
+    #[bool_width(bit)]
+    split: bool,
+    #[skip]
+    received: u64,
+    length: u16,
+    payload: String,
+}
+
+#[derive(BinaryIo, Debug, PartialEq)]
+#[binary_io(partial)]
+struct Pair(u8, u32);
+
+fn header() -> Header {
+    Header {
+        id: 9,
+        reliable: true,
+        split: false,
+        received: 0,
+        length: 3,
+        payload: "abc".to_string(),
+    }
+}
+
+#[test]
+fn complete_read_returns_value() {
+    let bytes = header().write_to_bytes().unwrap();
+    let mut buf = ByteReader::from(bytes.as_slice());
+    assert_eq!(Header::read_partial(&mut buf).unwrap(), header());
+    assert_eq!(Header::read_from_slice(bytes.as_slice()).unwrap(), header());
+}
+
+#[test]
+fn truncated_read_returns_decoded_prefix() {
+    let bytes = header().write_to_bytes().unwrap();
+    // cut off within the payload.
+    let mut buf = ByteReader::from(&bytes.as_slice()[..5]);
+    let partial = Header::read_partial(&mut buf).unwrap_err();
+
+    let names = partial.fields.iter().map(|f| f.name).collect::<Vec<_>>();
+    assert_eq!(names, ["id", "reliable, split", "length"]);
+    assert_eq!(partial.fields[2].range, 2..4);
+    assert_eq!(partial.failed, Some("payload"));
+    assert_eq!(partial.error.kind(), std::io::ErrorKind::UnexpectedEof);
+    assert!(partial.to_string().contains("while reading `payload`"));
+
+    // the plain reader fails with the same error.
+    let error: std::io::Error = partial.into();
+    assert_eq!(
+        Header::read_from_slice(&bytes.as_slice()[..5])
+            .unwrap_err()
+            .kind(),
+        error.kind()
+    );
+}
+
+#[test]
+fn trailing_bytes_fail_after_every_field() {
+    let mut bytes = header().write_to_bytes().unwrap().as_slice().to_vec();
+    bytes.push(0);
+    let partial = Header::read_partial(&mut ByteReader::from(&bytes[..])).unwrap_err();
+    assert_eq!(partial.fields.len(), 4);
+    assert_eq!(partial.failed, None);
+}
+
+#[test]
+fn unnamed_fields_use_their_index() {
+    let mut buf = ByteReader::from(&[1, 0, 0][..]);
+    let partial = Pair::read_partial(&mut buf).unwrap_err();
+    assert_eq!(partial.fields.len(), 1);
+    assert_eq!(partial.fields[0].name, "0");
+    assert_eq!(partial.fields[0].range, 0..1);
+    assert_eq!(partial.failed, Some("1"));
+}
@@ -0,0 +1,221 @@
+use binary_util::interfaces::{Reader, Writer};
+use binary_util::io::{ByteReader, ByteWriter};
+use binary_util::pool::{BytePool, DecayPolicy, ObjectPool};
+use binary_util::BinaryIo;
+
+#[test]
+fn reuses_buffers() {
+    let mut pool = BytePool::new(256);
+    let mut writer = pool.get();
+    assert!(writer.capacity() >= 256);
+    writer.write_u32(7).unwrap();
+    pool.put(writer);
+    assert_eq!(pool.len(), 1);
+    assert!(pool.total_reserved_bytes() >= 256);
+
+    // the buffer is reused, without its contents.
+    let writer = pool.get();
+    assert!(writer.as_slice().is_empty());
+    assert!(pool.is_empty());
+
+    // inline writers have no buffer to return.
+    #[cfg(feature = "inline")]
+    {
+        pool.put(ByteWriter::inline());
+        assert!(pool.is_empty());
+    }
+}
+
+#[test]
+fn shrinks_oversized_buffers() {
+    let mut pool = BytePool::new(256);
+    let mut huge = pool.get();
+    huge.write(&[0; 100_000]).unwrap();
+    pool.put(huge);
+    pool.put(BytePool::new(256).get());
+    assert_eq!(pool.len(), 2);
+    assert!(pool.total_reserved_bytes() >= 100_000);
+
+    pool.shrink_to_fit();
+    assert_eq!(pool.len(), 1);
+    assert!(pool.total_reserved_bytes() < 100_000);
+}
+
+#[test]
+fn decays_after_grace() {
+    let mut pool = BytePool::new(64).with_decay_policy(DecayPolicy::new(1024, 1));
+    let mut huge = pool.get();
+    huge.write(&[0; 4096]).unwrap();
+    pool.put(huge);
+    // the first time the policy is exceeded is within the grace period.
+    assert!(pool.total_reserved_bytes() >= 4096);
+
+    let small = ByteWriter::new();
+    pool.put(small);
+    assert!(pool.total_reserved_bytes() <= 1024);
+
+    // buffers which did not grow still exceed the policy once there are enough of them.
+    let mut pool = BytePool::new(64).with_decay_policy(DecayPolicy::new(1024, 0));
+    let writers = (0..40).map(|_| pool.get()).collect::<Vec<_>>();
+    for writer in writers {
+        pool.put(writer);
+    }
+    assert!(pool.total_reserved_bytes() <= 1024);
+    assert!(!pool.is_empty());
+}
+
+#[test]
+fn writer_capacity() {
+    let mut writer = ByteWriter::new();
+    writer.write(&[1; 5000]).unwrap();
+    writer.clear();
+    writer.write_u8(2).unwrap();
+    assert!(writer.capacity() >= 5000);
+
+    writer.shrink_to_fit();
+    assert_eq!(writer.capacity(), 1);
+    assert_eq!(writer.as_slice(), &[2]);
+
+    #[cfg(feature = "inline")]
+    assert_eq!(
+        ByteWriter::inline().capacity(),
+        binary_util::io::INLINE_CAPACITY
+    );
+
+    // the spare capacity left after taking a packet decays too.
+    let mut writer = ByteWriter::new().with_decay_policy(DecayPolicy::new(128, 0));
+    writer.write(&[1; 5000]).unwrap();
+    let packet = writer.take_bytes();
+    assert_eq!(packet.len(), 5000);
+    assert_eq!(writer.capacity(), 0);
+}
+
+#[derive(binary_util::BinaryIo)]
+struct Chat {
+    sender: u32,
+    message: String,
+}
+
+#[test]
+fn write_pooled_returns_buffers_on_drop() {
+    let mut pool = BytePool::new(256);
+    let chat = Chat {
+        sender: 1,
+        message: "hi".into(),
+    };
+    let ptr = {
+        let packet = chat.write_pooled(&mut pool).unwrap();
+        assert_eq!(&packet[..], &[0, 0, 0, 1, 2, b'h', b'i']);
+        packet.as_ptr()
+    };
+    assert_eq!(pool.len(), 1);
+    // the next packet is written to the same buffer.
+    let packet = chat.write_pooled(&mut pool).unwrap();
+    assert_eq!(packet.as_ptr(), ptr);
+
+    // a detached writer is kept by the caller.
+    let writer = packet.detach();
+    assert_eq!(writer.as_slice(), &[0, 0, 0, 1, 2, b'h', b'i']);
+    assert_eq!(pool.len(), 0);
+}
+
+#[test]
+fn write_pooled_errors_return_buffers() {
+    let mut pool = BytePool::new(256);
+    let result: Result<u8, u8> = Ok(1);
+    let long = binary_util::types::Prefixed::<u8, Vec<u8>>::new(vec![0; 300]);
+    assert!(long.write_pooled(&mut pool).is_err());
+    assert_eq!(pool.len(), 1);
+    assert_eq!(&result.write_pooled(&mut pool).unwrap()[..], &[0, 1]);
+}
+
+#[derive(BinaryIo, Debug, Default, PartialEq)]
+#[binary_io(in_place)]
+struct Chunk {
+    x: i32,
+    #[skip]
+    cached: bool,
+    blocks: Vec<u16>,
+}
+
+#[derive(BinaryIo, Debug, Default, PartialEq)]
+#[binary_io(in_place)]
+struct Pair(u8, Vec<u8>);
+
+#[test]
+fn object_pool_reuses_values() {
+    let mut pool = ObjectPool::<Chunk>::new();
+    let big = Chunk {
+        x: 1,
+        cached: false,
+        blocks: vec![7; 256],
+    }
+    .write_to_bytes()
+    .unwrap();
+    let small = Chunk {
+        x: 2,
+        cached: false,
+        blocks: vec![9; 2],
+    }
+    .write_to_bytes()
+    .unwrap();
+
+    let mut chunk = pool.read(&mut ByteReader::from(big.as_slice())).unwrap();
+    let ptr = chunk.blocks.as_ptr();
+    chunk.cached = true;
+    drop(chunk);
+    assert_eq!(pool.len(), 1);
+
+    // the value is overwritten, keeping the allocation of its blocks.
+    let chunk = pool.read(&mut ByteReader::from(small.as_slice())).unwrap();
+    assert_eq!(chunk.x, 2);
+    assert!(!chunk.cached);
+    assert_eq!(chunk.blocks, vec![9; 2]);
+    assert_eq!(chunk.blocks.as_ptr(), ptr);
+    assert!(chunk.blocks.capacity() >= 256);
+}
+
+#[test]
+fn object_pool_reset_and_detach() {
+    let mut pool = ObjectPool::<Vec<u8>>::new().with_reset(Vec::clear);
+    pool.acquire().extend_from_slice(&[1, 2, 3]);
+    assert_eq!(pool.len(), 1);
+    assert!(pool.acquire().is_empty());
+
+    let mut detached = pool.acquire();
+    detached.push(4);
+    assert_eq!(detached.detach(), vec![4]);
+    assert!(pool.is_empty());
+
+    pool.put(vec![5]);
+    pool.clear();
+    assert!(pool.is_empty());
+}
+
+#[test]
+fn object_pool_keeps_values_on_error() {
+    let mut pool = ObjectPool::<Pair>::new();
+    let bytes = Pair(3, vec![1, 2]).write_to_bytes().unwrap();
+    assert_eq!(
+        *pool.read(&mut ByteReader::from(bytes.as_slice())).unwrap(),
+        Pair(3, vec![1, 2])
+    );
+    assert!(pool
+        .read(&mut ByteReader::from(&bytes.as_slice()[..2]))
+        .is_err());
+    assert_eq!(pool.len(), 1);
+}
+
+#[test]
+fn vec_reads_in_place() {
+    let mut values = vec![vec![0u8; 8], vec![0u8; 8], vec![0u8; 8]];
+    let bytes = vec![vec![1u8], vec![2u8, 3]].write_to_bytes().unwrap();
+    Vec::<Vec<u8>>::read_into(&mut values, &mut ByteReader::from(bytes.as_slice())).unwrap();
+    assert_eq!(values, vec![vec![1], vec![2, 3]]);
+    assert!(values[0].capacity() >= 8);
+
+    let bytes = vec![4u8, 5, 6, 7].write_to_bytes().unwrap();
+    let mut values = vec![1u8];
+    Vec::<u8>::read_into(&mut values, &mut ByteReader::from(bytes.as_slice())).unwrap();
+    assert_eq!(values, vec![4, 5, 6, 7]);
+}
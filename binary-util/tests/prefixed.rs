@@ -0,0 +1,65 @@
+use std::io::ErrorKind;
+
+use binary_util::interfaces::{Reader, Writer};
+use binary_util::io::ByteWriter;
+use binary_util::types::{varu32, varu64, Prefixed, BE, LE};
+use binary_util::BinaryIo;
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct Entity {
+    id: u32,
+    name: Prefixed<u8, String>,
+}
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct World {
+    entities: Prefixed<LE<u32>, Vec<Entity>>,
+    seed: Prefixed<varu64, bytes::Bytes>,
+}
+
+#[test]
+fn prefixes_are_encoded_as_l() {
+    let vec = Prefixed::<BE<u16>, Vec<u8>>::new(vec![7, 8]);
+    assert_eq!(vec.write_to_bytes().unwrap().as_slice(), &[0, 2, 7, 8]);
+    let string = Prefixed::<varu32, String>::new("é".into());
+    assert_eq!(
+        string.write_to_bytes().unwrap().as_slice(),
+        &[2, 0xc3, 0xa9]
+    );
+    assert_eq!(
+        Prefixed::<varu32, String>::read_from_slice(&[2, 0xc3, 0xa9]).unwrap(),
+        string
+    );
+}
+
+#[test]
+fn prefixed_fields() {
+    let world = World {
+        entities: Prefixed::new(vec![Entity {
+            id: 1,
+            name: Prefixed::new("pig".into()),
+        }]),
+        seed: Prefixed::new(bytes::Bytes::from_static(&[9; 3])),
+    };
+    let buf = world.write_to_bytes().unwrap();
+    assert_eq!(
+        buf.as_slice(),
+        &[1, 0, 0, 0, 0, 0, 0, 1, 3, b'p', b'i', b'g', 3, 9, 9, 9]
+    );
+    let read = World::read_from_slice(buf.as_slice()).unwrap();
+    assert_eq!(read.entities[0].name.as_str(), "pig");
+    assert_eq!(read, world);
+}
+
+#[test]
+fn lengths_must_fit_the_prefix() {
+    let long = Prefixed::<u8, Vec<u8>>::new(vec![0; 256]);
+    let err = long.write(&mut ByteWriter::new()).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    assert!(Prefixed::<u8, Vec<u8>>::new(vec![0; 255])
+        .write_to_bytes()
+        .is_ok());
+
+    // the prefix claims more items than the buffer holds.
+    assert!(Prefixed::<BE<u16>, Vec<u8>>::read_from_slice(&[0xff, 0xff, 1]).is_err());
+}
@@ -0,0 +1,51 @@
+use binary_util::prelude::*;
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct Login {
+    protocol: BE<u32>,
+    name: String,
+    skin: Prefixed<u16, Vec<u8>>,
+    _reserved: Reserved<2>,
+}
+
+#[derive(binary_util::interfaces::BinaryIo, binary_util::interfaces::MockBinary, Debug)]
+struct Ping(varu32, u24);
+
+#[test]
+fn prelude_is_enough_for_packets() {
+    let login = Login {
+        protocol: BE(766),
+        name: "Steve".to_string(),
+        skin: Prefixed::new(vec![1, 2, 3]),
+        _reserved: Reserved,
+    };
+    let mut buf = ByteWriter::new();
+    buf.write_type(&login).unwrap();
+
+    let mut buf = ByteReader::from(buf.as_slice());
+    assert_eq!(Login::read(&mut buf).unwrap(), login);
+}
+
+#[test]
+fn derives_are_reexported_from_interfaces() {
+    let ping: Ping = binary_util::testing::random_with_seed(1);
+    let bytes = ping.write_to_bytes().unwrap();
+    let read = Ping::read_from_slice(bytes.as_slice()).unwrap();
+    assert_eq!((read.0, read.1), (ping.0, ping.1));
+}
+
+#[test]
+fn read_in_place_through_the_prelude() {
+    let mut name = String::with_capacity(32);
+    let bytes = "Alex".to_string().write_to_bytes().unwrap();
+    name.read_in_place(&mut ByteReader::from(bytes.as_slice()))
+        .unwrap();
+    assert_eq!(name, "Alex");
+    assert!(name.capacity() >= 32);
+
+    let mut skin: Vec<u8> = Vec::with_capacity(16);
+    let bytes = vec![4u8, 5].write_to_bytes().unwrap();
+    Vec::<u8>::read_into(&mut skin, &mut ByteReader::from(bytes.as_slice())).unwrap();
+    assert_eq!(skin, [4, 5]);
+    assert!(skin.capacity() >= 16);
+}
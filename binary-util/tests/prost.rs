@@ -0,0 +1,36 @@
+#![cfg(feature = "prost")]
+
+use binary_util::interfaces::{Reader, Writer};
+use binary_util::types::Proto;
+use binary_util::BinaryIo;
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct ChatPacket {
+    channel: u8,
+    // `prost` implements `Message` for the protobuf wrapper types, such as `StringValue`.
+    body: Proto<String>,
+    sender: Proto<u64>,
+}
+
+#[test]
+fn proto_roundtrip() {
+    let packet = ChatPacket {
+        channel: 3,
+        body: Proto::new("hi".to_string()),
+        sender: Proto::new(150),
+    };
+    let buf = packet.write_to_bytes().unwrap();
+    // the message is length prefixed, followed by the protobuf encoding of the wrapper.
+    assert_eq!(
+        buf.as_slice(),
+        &[3, 4, 0x0a, 2, b'h', b'i', 3, 0x08, 0x96, 0x01]
+    );
+    assert_eq!(ChatPacket::read_from_slice(buf.as_slice()).unwrap(), packet);
+}
+
+#[test]
+fn proto_invalid_message() {
+    // a length delimited field, which is longer than the message.
+    let err = Proto::<String>::read_from_slice(&[2, 0x0a, 5]).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
@@ -0,0 +1,63 @@
+use binary_util::interfaces::{Reader, WireDisplay, Writer};
+use binary_util::types::{Angle16, Angle8, UnitVec24};
+use binary_util::BinaryIo;
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct MoveEntity {
+    id: u32,
+    yaw: Angle8,
+    pitch: Angle16,
+    facing: UnitVec24,
+}
+
+#[test]
+fn angles() {
+    assert_eq!(Angle8::from_degrees(90.0), Angle8(64));
+    assert_eq!(Angle8::from_degrees(-90.0).to_degrees(), 270.0);
+    // angles round to the nearest step, and the full turn wraps around to 0.
+    assert_eq!(Angle8::from_degrees(359.9), Angle8(0));
+    assert_eq!(Angle8::from_degrees(720.0 + 1.5), Angle8(1));
+    assert_eq!(Angle16::from_degrees(180.0), Angle16(32768));
+    assert!((Angle16::from_radians(1.0).to_radians() - 1.0).abs() < 0.0001);
+    assert_eq!(Angle8(64).display_wire().to_string(), "90°");
+}
+
+#[test]
+fn unit_vectors() {
+    use std::f32::consts::{PI, TAU};
+
+    for i in 0..64 {
+        for j in 0..32 {
+            let (theta, phi) = (i as f32 / 64.0 * TAU, j as f32 / 31.0 * PI);
+            let v = [phi.sin() * theta.cos(), phi.sin() * theta.sin(), phi.cos()];
+            let quantized = UnitVec24::from_vector(v);
+            assert!(quantized.0 <= 0xFFFFFF);
+            for (read, original) in quantized.to_vector().iter().zip(v) {
+                assert!((read - original).abs() < 0.001, "{:?} -> {}", v, quantized);
+            }
+        }
+    }
+    // vectors are normalized first, and the zero vector points up.
+    assert_eq!(
+        UnitVec24::from_vector([0.0, 0.0, 5.0]),
+        UnitVec24::from_vector([0.0, 0.0, 1.0])
+    );
+    assert_eq!(
+        UnitVec24::from_vector([0.0; 3]),
+        UnitVec24::from_vector([0.0, 0.0, 1.0])
+    );
+}
+
+#[test]
+fn quantized_fields() {
+    let packet = MoveEntity {
+        id: 7,
+        yaw: Angle8::from_degrees(45.0),
+        pitch: Angle16::from_degrees(-10.0),
+        facing: UnitVec24::from_vector([1.0, 0.0, 0.0]),
+    };
+    let buf = packet.write_to_bytes().unwrap();
+    assert_eq!(buf.as_slice().len(), 4 + 1 + 2 + 3);
+    assert_eq!(buf.as_slice()[4], 32);
+    assert_eq!(MoveEntity::read_from_slice(buf.as_slice()).unwrap(), packet);
+}
@@ -0,0 +1,99 @@
+use binary_util::interfaces::{Reader, Writer};
+use binary_util::io::ByteReader;
+use binary_util::types::{Raw, RawVec};
+use binary_util::BinaryIo;
+use bytes::{Bytes, BytesMut};
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct Forward {
+    connection: u8,
+    body: Raw,
+}
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct Framed {
+    #[len_of(header)]
+    header_len: u8,
+    header: RawVec,
+    body: Raw<BytesMut>,
+}
+
+#[test]
+fn raw_consumes_the_rest() {
+    let forward = Forward {
+        connection: 2,
+        body: Raw(Bytes::from_static(&[1, 2, 3])),
+    };
+    assert_eq!(forward.write_to_bytes().unwrap().as_slice(), &[2, 1, 2, 3]);
+
+    let source = Bytes::from_static(&[2, 1, 2, 3]);
+    let mut reader = ByteReader::from(source.clone());
+    let read = Forward::read(&mut reader).unwrap();
+    assert_eq!(read, forward);
+    assert!(reader.as_slice().is_empty());
+    // the body shares the buffer it was read from.
+    assert_eq!(read.body.as_ptr(), source[1..].as_ptr());
+
+    // an empty body is valid.
+    assert!(Forward::read_from_slice(&[2]).unwrap().body.is_empty());
+}
+
+#[test]
+fn raw_with_len_of() {
+    let framed = Framed {
+        header_len: 2,
+        header: Raw(vec![7, 8]),
+        body: Raw(BytesMut::from(&[9u8][..])),
+    };
+    let buf = framed.write_to_bytes().unwrap();
+    assert_eq!(buf.as_slice(), &[2, 7, 8, 9]);
+    assert_eq!(Framed::read_from_slice(buf.as_slice()).unwrap(), framed);
+    assert!(Framed::read_from_slice(&[3, 7, 8]).is_err());
+}
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct Reordered {
+    #[order(1)]
+    body: RawVec,
+    #[skip]
+    cached: u8,
+    #[order(0)]
+    connection: u8,
+}
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct Tunnel(u16, Raw);
+
+#[derive(BinaryIo, Debug, PartialEq)]
+#[repr(u8)]
+enum Message {
+    Ping,
+    Payload(u8, RawVec),
+}
+
+#[test]
+fn raw_last_on_the_wire() {
+    // `body` is declared first, but is written after `connection`, and `cached` is never encoded.
+    let reordered = Reordered {
+        body: Raw(vec![1, 2]),
+        cached: 0,
+        connection: 9,
+    };
+    let buf = reordered.write_to_bytes().unwrap();
+    assert_eq!(buf.as_slice(), &[9, 1, 2]);
+    assert_eq!(
+        Reordered::read_from_slice(buf.as_slice()).unwrap(),
+        reordered
+    );
+
+    let tunnel = Tunnel(5, Raw(Bytes::from_static(&[3])));
+    let buf = tunnel.write_to_bytes().unwrap();
+    assert_eq!(buf.as_slice(), &[0, 5, 3]);
+    assert_eq!(Tunnel::read_from_slice(buf.as_slice()).unwrap(), tunnel);
+
+    let message = Message::Payload(4, Raw(vec![7, 7]));
+    let buf = message.write_to_bytes().unwrap();
+    assert_eq!(buf.as_slice(), &[1, 4, 7, 7]);
+    assert_eq!(Message::read_from_slice(buf.as_slice()).unwrap(), message);
+    assert_eq!(Message::read_from_slice(&[0]).unwrap(), Message::Ping);
+}
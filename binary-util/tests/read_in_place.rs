@@ -0,0 +1,75 @@
+use binary_util::interfaces::{ReadInPlace, Reader, Writer};
+use binary_util::io::ByteReader;
+use binary_util::BinaryIo;
+
+#[derive(BinaryIo, Debug, Default, PartialEq)]
+#[binary_io(in_place)]
+struct Chat {
+    id: u32,
+    message: String,
+    recipients: Vec<String>,
+}
+
+#[derive(BinaryIo, Debug, Default, PartialEq)]
+struct Ping {
+    time: u64,
+}
+
+#[test]
+fn primitives_read_in_place() {
+    let mut value = 0u32;
+    value
+        .read_in_place(&mut ByteReader::from(&[0, 0, 1, 0][..]))
+        .unwrap();
+    assert_eq!(value, 256);
+
+    let mut flag = false;
+    flag.read_in_place(&mut ByteReader::from(&[1][..])).unwrap();
+    assert!(flag);
+}
+
+#[test]
+fn string_reuses_capacity() {
+    let mut string = String::with_capacity(64);
+    let ptr = string.as_ptr();
+    string
+        .read_in_place(&mut ByteReader::from(&[3, b'a', b'b', b'c'][..]))
+        .unwrap();
+    assert_eq!(string, "abc");
+    assert_eq!(string.as_ptr(), ptr);
+
+    // invalid utf-8 leaves the string and the reader unchanged.
+    let mut buf = ByteReader::from(&[2, 0xff, 0xfe][..]);
+    assert!(string.read_in_place(&mut buf).is_err());
+    assert_eq!(string, "abc");
+    assert_eq!(buf.offset(), 0);
+}
+
+#[test]
+fn derived_types_read_in_place() {
+    let bytes = Chat {
+        id: 1,
+        message: "hello".into(),
+        recipients: vec!["a".into(), "b".into()],
+    }
+    .write_to_bytes()
+    .unwrap();
+
+    let mut chat = Chat {
+        id: 0,
+        message: String::with_capacity(32),
+        recipients: (0..4).map(|_| String::with_capacity(32)).collect(),
+    };
+    let (message, recipient) = (chat.message.as_ptr(), chat.recipients[0].as_ptr());
+    chat.read_in_place(&mut ByteReader::from(bytes.as_slice()))
+        .unwrap();
+    assert_eq!(chat, Chat::read_from_slice(bytes.as_slice()).unwrap());
+    assert_eq!(chat.message.as_ptr(), message);
+    assert_eq!(chat.recipients[0].as_ptr(), recipient);
+
+    // without `#[binary_io(in_place)]`, the value is replaced.
+    let mut ping = Ping::default();
+    ping.read_in_place(&mut ByteReader::from(&[0, 0, 0, 0, 0, 0, 0, 9][..]))
+        .unwrap();
+    assert_eq!(ping, Ping { time: 9 });
+}
@@ -1,6 +1,6 @@
 use binary_util::interfaces::Reader;
 use binary_util::interfaces::Writer;
-use binary_util::io::ByteReader;
+use binary_util::io::{BinaryErrorExt, ByteReader};
 use binary_util::BinaryIo;
 
 // A slice of bytes that is used to test the reader.
@@ -22,6 +22,17 @@ fn read_simple_test() {
     assert_eq!(buf.read_option::<u16>().unwrap(), Some(34));
 }
 
+#[test]
+fn read_option_with() {
+    let mut buf = ByteReader::from(&[0x01, 0x00, 0x22, 0x00][..]);
+    let read_char = |r: &mut ByteReader| {
+        char::from_u32(r.read_u16()? as u32)
+            .ok_or_else(|| std::io::Error::from(std::io::ErrorKind::InvalidData))
+    };
+    assert_eq!(buf.read_option_with(read_char).unwrap(), Some('"'));
+    assert_eq!(buf.read_option_with(read_char).unwrap(), None);
+}
+
 // A more complex test that tests the reader with a struct.
 #[derive(BinaryIo)]
 pub struct TestPacket {
@@ -37,3 +48,357 @@ fn construct_struct() {
     let pk = TestPacket::read_from_slice(&buf).unwrap();
     assert_eq!(buf, pk.write_to_bytes().unwrap().as_slice())
 }
+
+#[test]
+fn read_into_uninit() {
+    let mut buf = ByteReader::from(&[1, 2, 3, 4, 5][..]);
+    let mut out = [std::mem::MaybeUninit::<u8>::uninit(); 4];
+    assert_eq!(buf.read_into_uninit(&mut out).unwrap(), &[1, 2, 3, 4]);
+
+    let err = buf.read_into_uninit(&mut out).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+    assert_eq!(buf.as_slice(), &[5]);
+}
+
+#[test]
+fn read_string_invalid_utf8() {
+    let mut buf = ByteReader::from(&[0x02, 0xC3, 0x28][..]);
+    let err = buf.read_string().unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn expect_eof() {
+    let mut reader = ByteReader::from(&[0, 0, 0, 7, 9][..]);
+    assert_eq!(reader.read_u32().unwrap(), 7);
+    let err = reader.expect_eof().unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    assert!(err.to_string().contains("offset 4"));
+
+    reader.read_u8().unwrap();
+    assert!(reader.expect_eof().is_ok());
+}
+
+#[test]
+fn read_exact_type() {
+    let buf = String::from("abc").write_to_bytes().unwrap();
+    let mut reader = ByteReader::from(buf.as_slice());
+    assert_eq!(reader.read_exact_type::<String>().unwrap(), "abc");
+
+    let mut trailing = buf.as_slice().to_vec();
+    trailing.push(0);
+    let mut reader = ByteReader::from(trailing);
+    assert!(reader.read_exact_type::<String>().is_err());
+}
+
+#[test]
+fn reader_as_buf() {
+    use bytes::Buf;
+
+    fn sum(mut buf: impl Buf) -> u32 {
+        let mut sum = 0;
+        while buf.has_remaining() {
+            sum += buf.get_u8() as u32;
+        }
+        sum
+    }
+
+    let mut reader = ByteReader::from(&[9, 1, 2, 3][..]);
+    assert_eq!(reader.read_u8().unwrap(), 9);
+    assert_eq!(sum(&mut reader), 6);
+    assert_eq!(reader.offset(), 4);
+    assert!(reader.expect_eof().is_ok());
+}
+
+#[test]
+fn string_length_prefixes() {
+    let mut writer = binary_util::io::ByteWriter::new();
+    writer.write_string("ab").unwrap();
+    writer.write_string_varu32("cd").unwrap();
+    writer.write_string_u16be("ef").unwrap();
+    writer.write_string_u32le("gh").unwrap();
+    assert_eq!(
+        writer.as_slice(),
+        b"\x02ab\x02cd\x00\x02ef\x02\x00\x00\x00gh"
+    );
+
+    let mut buf = ByteReader::from(writer.as_slice());
+    assert_eq!(buf.read_string().unwrap(), "ab");
+    assert_eq!(buf.read_string_varu32().unwrap(), "cd");
+    assert_eq!(buf.read_string_u16be().unwrap(), "ef");
+    assert_eq!(buf.read_string_u32le().unwrap(), "gh");
+
+    // the string is longer than its prefix can store.
+    let long = "a".repeat(u16::MAX as usize + 1);
+    let err = writer.write_string_u16be(&long).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn read_string_is_reversable() {
+    // the length is read, but the string is cut short.
+    let mut buf = ByteReader::from(&[0, 5, b'a', b'b'][..]);
+    assert!(buf.read_string_u16be().is_err());
+    assert_eq!(buf.as_slice(), &[0, 5, b'a', b'b']);
+
+    // a var-int length that was previously read as a u64 is rejected.
+    let mut buf = ByteReader::from(&[0xff, 0xff, 0xff, 0xff, 0xff, 0x01][..]);
+    assert!(buf.read_string().is_err());
+    assert_eq!(buf.offset(), 0);
+}
+
+#[test]
+fn windows() {
+    let mut buf = ByteReader::from(&[0, 1, 2, 3, 4, 5][..]).with_version(3);
+    buf.read_u8().unwrap();
+
+    let mut window = buf.window(2, 3).unwrap();
+    assert_eq!(window.as_slice(), &[3, 4, 5]);
+    assert_eq!(window.version(), Some(3));
+    assert_eq!(window.read_u16().unwrap(), 0x0304);
+    // errors within the window are at the offset of the parent, and it does not read past its end.
+    let err = window.read_u16().unwrap_err();
+    assert!(err.to_string().contains("offset 5"));
+    assert_eq!(window.read_u8().unwrap(), 5);
+
+    // the parent is not consumed.
+    assert_eq!(buf.offset(), 1);
+    assert_eq!(buf.read_u8().unwrap(), 1);
+
+    assert!(buf.window(0, 4).is_ok());
+    assert!(buf.window(1, 4).is_err());
+    assert!(buf.window(usize::MAX, 2).is_err());
+    assert!(buf.window(4, 0).is_ok());
+}
+
+#[test]
+fn seeking() {
+    // a header with the offsets of two strings, which are stored out of order.
+    let mut buf = ByteReader::from(&[7, 3, 0, 3, b'b', b'y', b'e', 2, b'h', b'i'][..]);
+    let (hi, bye) = (buf.read_u8().unwrap(), buf.read_u8().unwrap());
+    buf.seek_to(hi as usize).unwrap();
+    assert_eq!(buf.read_string().unwrap(), "hi");
+    assert!(buf.as_slice().is_empty());
+    buf.seek_to(bye as usize).unwrap();
+    assert_eq!(buf.read_string().unwrap(), "bye");
+    assert_eq!(buf.offset(), 7);
+
+    let err = buf.seek_to(11).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    assert_eq!(buf.offset(), 7);
+    buf.seek_to(10).unwrap();
+    assert!(buf.as_slice().is_empty());
+
+    buf.rewind();
+    assert_eq!(buf.read_u8().unwrap(), 7);
+
+    // a window can only seek within itself, and keeps the offsets of its parent.
+    let mut window = buf.window(2, 5).unwrap();
+    window.seek_to(5).unwrap();
+    assert_eq!(window.read_u16().unwrap(), u16::from_be_bytes([b'y', b'e']));
+    assert!(window.seek_to(2).is_err());
+    window.rewind();
+    assert_eq!(window.offset(), 3);
+}
+
+#[test]
+fn sections() {
+    let mut buf = ByteReader::from(&[0, 0, 1, 2, 3][..]);
+    buf.read_u16().unwrap();
+    let window = buf.window(1, 2).unwrap();
+    assert_eq!(window.as_slice(), &[2, 3]);
+
+    // sections are absolute, and may be before the current position.
+    let mut section = buf.section(1, 3).unwrap();
+    assert_eq!(section.read_u16().unwrap(), 1);
+    assert_eq!(section.offset(), 3);
+    assert_eq!(buf.offset(), 2);
+
+    let err = window.section(0, 1).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    assert_eq!(
+        buf.section(3, 3).unwrap_err().kind(),
+        std::io::ErrorKind::UnexpectedEof
+    );
+    assert!(buf.section(5, 0).unwrap().as_slice().is_empty());
+}
+
+#[test]
+fn strict_bools() {
+    let mut buf = ByteReader::from(&[0, 1, 2, 1, 7][..]).with_strict_bools(true);
+    assert!(buf.strict_bools());
+    assert!(!buf.read_bool().unwrap());
+    assert!(buf.read_bool().unwrap());
+    let err = buf.read_bool().unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    assert_eq!(err.offset(), Some(2));
+
+    // types read from the reader, such as the tag of an `Option`, are strict as well.
+    assert_eq!(buf.read_type::<Option<u8>>().unwrap(), Some(7));
+    let mut buf = ByteReader::from(&[3, 7][..]).with_strict_bools(true);
+    assert!(buf.read_type::<Option<u8>>().is_err());
+
+    let mut buf = ByteReader::from(&[2][..]);
+    assert!(buf.read_bool().unwrap());
+    assert!(ByteReader::from(&[2][..]).read_bool_strict().is_err());
+}
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct LogRecord {
+    timestamp: u32,
+    level: u8,
+}
+
+#[test]
+fn read_to_end_vec() {
+    let mut file = Vec::new();
+    for timestamp in 0..3 {
+        let record = LogRecord {
+            timestamp,
+            level: 2,
+        };
+        file.extend_from_slice(record.write_to_bytes().unwrap().as_slice());
+    }
+    let records = ByteReader::from(&file[..])
+        .read_to_end_vec::<LogRecord>(3)
+        .unwrap();
+    assert_eq!(records.len(), 3);
+    assert_eq!(records[2].timestamp, 2);
+    assert_eq!(records[2].level, 2);
+    assert!(ByteReader::from(&[][..])
+        .read_to_end_vec::<LogRecord>(0)
+        .unwrap()
+        .is_empty());
+
+    // more records than the cap, a truncated record, and items which consume nothing are rejected.
+    let err = ByteReader::from(&file[..])
+        .read_to_end_vec::<LogRecord>(2)
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    assert_eq!(err.offset(), Some(10));
+    let err = ByteReader::from(&file[..14])
+        .read_to_end_vec::<LogRecord>(3)
+        .unwrap_err();
+    assert!(err.is_eof());
+    assert!(ByteReader::from(&[1][..])
+        .read_to_end_vec::<()>(usize::MAX)
+        .is_err());
+}
+
+#[derive(Debug, PartialEq)]
+enum Difficulty {
+    Peaceful,
+    Hard,
+}
+
+impl TryFrom<u8> for Difficulty {
+    type Error = String;
+
+    fn try_from(id: u8) -> Result<Self, Self::Error> {
+        match id {
+            0 => Ok(Difficulty::Peaceful),
+            3 => Ok(Difficulty::Hard),
+            _ => Err(format!("unknown difficulty {}", id)),
+        }
+    }
+}
+
+/// A hand-written reader, for a packet whose view distance is clamped, and whose difficulty was added later.
+#[derive(Debug, PartialEq)]
+struct Settings {
+    view_distance: u8,
+    difficulty: Option<u8>,
+}
+
+impl Reader<Settings> for Settings {
+    fn read(buf: &mut ByteReader) -> Result<Settings, std::io::Error> {
+        Ok(Settings {
+            view_distance: buf.read_validate::<u8>(|distance| (2..=32).contains(distance))?,
+            difficulty: buf.read_or_default::<Option<u8>>()?,
+        })
+    }
+}
+
+#[test]
+fn combinators() {
+    let mut buf = ByteReader::from(&[3, 0, 1][..]);
+    assert_eq!(
+        buf.read_map::<u8, _, _>(Difficulty::try_from).unwrap(),
+        Difficulty::Hard
+    );
+    assert_eq!(
+        buf.read_map::<u8, _, _>(Difficulty::try_from).unwrap(),
+        Difficulty::Peaceful
+    );
+    let err = buf.read_map::<u8, _, _>(Difficulty::try_from).unwrap_err();
+    assert_eq!(err.offset(), Some(2));
+    assert_eq!(
+        std::error::Error::source(&err).unwrap().to_string(),
+        "unknown difficulty 1"
+    );
+
+    assert_eq!(
+        Settings::read_from_slice(&[8]).unwrap(),
+        Settings {
+            view_distance: 8,
+            difficulty: None
+        }
+    );
+    assert_eq!(
+        Settings::read_from_slice(&[8, 1, 2]).unwrap().difficulty,
+        Some(2)
+    );
+    let err = Settings::read_from_slice(&[64]).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    assert_eq!(err.offset(), Some(0));
+    // a partially sent trailing field is still an error.
+    assert!(Settings::read_from_slice(&[8, 1]).is_err());
+}
+
+#[test]
+fn split_header_and_payload() {
+    let datagram = [0x05, 0x00, 0x02, 0xaa, 0xbb, 0xcc];
+    let mut buf = ByteReader::from(&datagram[..]).with_max_depth(3);
+    assert_eq!(buf.read_u8().unwrap(), 5);
+    assert_eq!(buf.read_u16().unwrap(), 2);
+
+    let header = buf.split_at_current();
+    assert_eq!(&header[..], &[0x05, 0x00, 0x02]);
+    assert_eq!(buf.max_depth(), Some(3));
+    assert_eq!(buf.offset(), 3);
+    assert_eq!(buf.read_u8().unwrap(), 0xaa);
+
+    // the header can no longer be reached by the payload handler.
+    assert!(buf.seek_to(0).is_err());
+    buf.rewind();
+    assert_eq!(buf.offset(), 3);
+
+    // splitting again only returns the bytes read since the last split.
+    buf.read_u8().unwrap();
+    assert_eq!(&buf.split_at_current()[..], &[0xaa]);
+
+    let (consumed, remaining) = buf.into_parts();
+    assert!(consumed.is_empty());
+    assert_eq!(&remaining[..], &[0xbb, 0xcc]);
+
+    let mut buf = ByteReader::from(&datagram[..]);
+    buf.seek_to(4).unwrap();
+    let (consumed, remaining) = buf.into_parts();
+    assert_eq!(&consumed[..], &datagram[..4]);
+    assert_eq!(&remaining[..], &datagram[4..]);
+
+    #[cfg(not(all(feature = "instrumented", debug_assertions)))]
+    {
+        // a checkpoint taken before a split can not move the reader before it.
+        let mut buf = ByteReader::from(&datagram[..]);
+        let start = buf.checkpoint();
+        buf.read_u16().unwrap();
+        assert_eq!(&buf.split_at_current()[..], &[0x05, 0x00]);
+        buf.restore(start);
+        assert_eq!(buf.offset(), 2);
+        assert!(buf.split_at_current().is_empty());
+        let (consumed, remaining) = buf.into_parts();
+        assert!(consumed.is_empty());
+        assert_eq!(&remaining[..], &datagram[2..]);
+    }
+}
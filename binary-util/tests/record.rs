@@ -0,0 +1,60 @@
+use binary_util::io::record::Direction;
+use binary_util::io::{read_capture, ByteStream, ReplayStream};
+use std::io::{Cursor, ErrorKind, Write};
+
+fn capture_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("binary_util_{}_{}.cap", name, std::process::id()))
+}
+
+#[test]
+fn records_both_directions() {
+    let path = capture_path("both");
+    let mut stream = ByteStream::new(Cursor::new(vec![0, 7, 1, 2]))
+        .with_recorder(&path)
+        .unwrap();
+    assert_eq!(stream.read_type::<u16>().unwrap(), 7);
+    stream.write_type(&String::from("hi")).unwrap();
+    stream.flush().unwrap();
+    drop(stream);
+
+    let records = read_capture(&path).unwrap();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].direction, Direction::Read);
+    assert_eq!(&records[0].data[..], &[0, 7, 1, 2]);
+    assert_eq!(records[1].direction, Direction::Write);
+    assert_eq!(&records[1].data[..], b"\x02hi");
+    assert!(records[0].timestamp <= records[1].timestamp);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn replay_checks_writes() {
+    let path = capture_path("replay");
+    let mut stream = ByteStream::new(Cursor::new(vec![0, 1]))
+        .with_recorder(&path)
+        .unwrap();
+    stream.read_type::<u16>().unwrap();
+    stream.write_type(&5u16).unwrap();
+    drop(stream);
+
+    let mut replay = ByteStream::new(ReplayStream::open(&path).unwrap());
+    assert_eq!(replay.read_type::<u16>().unwrap(), 1);
+    replay.write_type(&5u16).unwrap();
+    assert!(replay.get_ref().is_finished());
+
+    let mut replay = ByteStream::new(ReplayStream::open(&path).unwrap());
+    let err = replay.write_type(&6u16).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn rejects_foreign_files() {
+    let path = capture_path("foreign");
+    std::fs::write(&path, b"not a capture").unwrap();
+    assert_eq!(
+        read_capture(&path).unwrap_err().kind(),
+        ErrorKind::InvalidData
+    );
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,73 @@
+use binary_util::interfaces::{BinaryDiscriminant, Reader, Writer};
+use binary_util::BinaryIo;
+
+#[derive(BinaryIo, Debug, PartialEq)]
+#[binary_io(repr_wire = "varu32")]
+#[repr(u32)]
+enum Packet {
+    Ping(u64) = 1,
+    Pong(u64),
+    Disconnect(String) = 300,
+}
+
+#[derive(BinaryIo, Debug, PartialEq)]
+#[binary_io(repr_wire = "u8")]
+#[repr(u32)]
+enum Small {
+    A,
+    B = 200,
+}
+
+#[derive(BinaryIo, Debug, PartialEq)]
+#[binary_io(repr_wire = "vari32")]
+#[repr(i64)]
+enum Signed {
+    Negative = -2,
+    Zero = 0,
+}
+
+#[derive(BinaryIo, Debug, PartialEq)]
+#[binary_io(repr_wire = "varu32", external_tag)]
+#[repr(u32)]
+enum Tagged {
+    Value(u8) = 128,
+}
+
+#[test]
+fn discriminant_is_encoded_as_wire_repr() {
+    let packet = Packet::Disconnect("bye".to_string());
+    let bytes = packet.write_to_bytes().unwrap();
+    // 300 as a var-int, followed by the string.
+    assert_eq!(&bytes.as_slice()[..2], &[0xac, 0x02]);
+    assert_eq!(Packet::read_from_slice(bytes.as_slice()).unwrap(), packet);
+    assert_eq!(packet.discriminant(), 300u32);
+
+    let bytes = Packet::Pong(9).write_to_bytes().unwrap();
+    assert_eq!(bytes.as_slice()[0], 2);
+    assert_eq!(bytes.as_slice().len(), 1 + 8);
+
+    assert_eq!(Small::B.write_to_bytes().unwrap().as_slice(), &[200]);
+    assert_eq!(Small::read_from_slice(&[0]).unwrap(), Small::A);
+    assert!(Small::read_from_slice(&[1]).is_err());
+
+    let bytes = Signed::Negative.write_to_bytes().unwrap();
+    assert_eq!(bytes.as_slice(), &[3]);
+    assert_eq!(Signed::read_from_slice(&[3]).unwrap(), Signed::Negative);
+}
+
+#[test]
+fn discriminant_out_of_repr_range_is_rejected() {
+    // u32::MAX + 1 as a var-u32 is too long, and unknown discriminants are rejected.
+    assert!(Packet::read_from_slice(&[0xff, 0xff, 0xff, 0xff, 0x7f]).is_err());
+    assert!(Packet::read_from_slice(&[0x05, 0, 0, 0, 0, 0, 0, 0, 0]).is_err());
+}
+
+#[test]
+fn external_tag_keeps_repr_type() {
+    let value = Tagged::Value(4);
+    assert_eq!(value.write_to_bytes().unwrap().as_slice(), &[0x80, 0x01, 4]);
+    assert_eq!(
+        Tagged::read_variant(&mut [4u8][..].into(), 128).unwrap(),
+        value
+    );
+}
@@ -0,0 +1,41 @@
+use binary_util::interfaces::{Reader, WireDisplay, Writer};
+use binary_util::io::{BinaryErrorExt, ByteReader};
+use binary_util::types::Reserved;
+use binary_util::BinaryIo;
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct ChunkHeader {
+    magic: u16,
+    padding: Reserved<2>,
+    length: u32,
+    checked: Reserved<4, true>,
+}
+
+#[test]
+fn reserved_bytes_are_zeroed_and_skipped() {
+    let header = ChunkHeader {
+        magic: 0xcafe,
+        padding: Reserved,
+        length: 5,
+        checked: Reserved,
+    };
+    let buf = header.write_to_bytes().unwrap();
+    assert_eq!(buf.as_slice(), &[0xca, 0xfe, 0, 0, 0, 0, 0, 5, 0, 0, 0, 0]);
+    assert_eq!(
+        ChunkHeader::read_from_slice(&[0xca, 0xfe, 1, 2, 0, 0, 0, 5, 0, 0, 0, 0]).unwrap(),
+        header
+    );
+    assert_eq!(Reserved::<3>.display_wire().to_string(), "[3 reserved]");
+}
+
+#[test]
+fn strict_reserved_bytes() {
+    let err =
+        ChunkHeader::read_from_slice(&[0xca, 0xfe, 0, 0, 0, 0, 0, 5, 0, 0, 1, 0]).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    assert_eq!(err.offset(), Some(8));
+
+    let mut buf = ByteReader::from(&[0, 0][..]);
+    assert!(buf.read_type::<Reserved<3, true>>().unwrap_err().is_eof());
+    assert!(buf.read_type::<Reserved<0, true>>().is_ok());
+}
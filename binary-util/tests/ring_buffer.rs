@@ -0,0 +1,122 @@
+use binary_util::interfaces::Writer;
+use binary_util::io::{ReadExt, RingBuffer, WriteExt};
+use binary_util::varint::{Decode, Decoder};
+use binary_util::BinaryIo;
+use bytes::{Buf, BufMut};
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct Move {
+    entity: u32,
+    x: f32,
+    on_ground: bool,
+}
+
+#[test]
+fn wraps_without_growing() {
+    let mut ring = RingBuffer::with_capacity(8);
+    ring.extend_from_slice(&[1, 2, 3, 4, 5, 6]);
+    ring.advance(4);
+    ring.extend_from_slice(&[7, 8, 9, 10]);
+    assert_eq!(ring.capacity(), 8);
+    assert_eq!(ring.as_slices(), (&[5, 6, 7, 8][..], &[9, 10][..]));
+    assert_eq!(ring.chunk(), &[5, 6, 7, 8]);
+
+    // reads span the wrap around.
+    assert_eq!(ring.read_u16().unwrap(), 0x0506);
+    assert_eq!(ring.read_u32().unwrap(), 0x0708090a);
+    assert!(ring.read_u8().is_err());
+    assert!(ring.is_empty());
+}
+
+#[test]
+fn grows_when_full() {
+    let mut ring = RingBuffer::with_capacity(4);
+    ring.extend_from_slice(&[1, 2, 3]);
+    ring.advance(2);
+    ring.extend_from_slice(&[4, 5, 6, 7, 8]);
+    assert!(ring.capacity() >= 6);
+    assert_eq!(ring.as_slices(), (&[3, 4, 5, 6, 7, 8][..], &[][..]));
+
+    let mut ring = RingBuffer::new();
+    for i in 0..1000u32 {
+        ring.write_var_u32(i).unwrap();
+    }
+    for i in 0..1000u32 {
+        assert_eq!(ring.read_var_u32().unwrap(), i);
+    }
+}
+
+#[test]
+fn reads_packets_from_partial_chunks() {
+    let moves = (0..20)
+        .map(|i| Move {
+            entity: i,
+            x: i as f32 * 0.5,
+            on_ground: i % 2 == 0,
+        })
+        .collect::<Vec<Move>>();
+    let mut wire = Vec::new();
+    for m in moves.iter() {
+        wire.extend_from_slice(m.write_to_bytes().unwrap().as_slice());
+    }
+
+    // the socket delivers 7 bytes at a time, which never line up with a packet.
+    let mut ring = RingBuffer::with_capacity(16);
+    let mut read = Vec::new();
+    for chunk in wire.chunks(7) {
+        let mut socket = chunk;
+        while ring.fill_from(&mut socket).unwrap() > 0 {}
+        loop {
+            match ring.read_type::<Move>() {
+                Ok(m) => read.push(m),
+                Err(e) => {
+                    assert_eq!(e.kind(), std::io::ErrorKind::UnexpectedEof);
+                    break;
+                }
+            }
+        }
+    }
+    assert_eq!(read, moves);
+    assert!(ring.is_empty());
+    assert_eq!(ring.capacity(), 16, "{:?}", ring);
+}
+
+#[test]
+fn feeds_the_varint_decoder() {
+    let mut ring = RingBuffer::with_capacity(4);
+    ring.write_type(&binary_util::types::varu32(300)).unwrap();
+    let mut decoder = Decoder::u32();
+    assert_eq!(decoder.push(ring.get_u8()).unwrap(), Decode::NeedMore);
+    assert_eq!(decoder.push(ring.get_u8()).unwrap(), Decode::Complete(300));
+
+    // the writer end is a `BufMut`.
+    ring.put_u16(7);
+    assert_eq!(ring.remaining(), 2);
+    assert_eq!(ring.read_type::<u16>().unwrap(), 7);
+}
+
+#[test]
+fn read_type_copies_only_across_the_wrap() {
+    let mut ring = RingBuffer::with_capacity(16);
+    ring.write_u32(1).unwrap();
+    ring.write_u32(2).unwrap();
+    let start = ring.as_slices().0.as_ptr();
+
+    // contiguous values are read from the buffer itself, which is kept.
+    assert_eq!(ring.read_type::<u32>().unwrap(), 1);
+    assert_eq!(ring.as_slices().0.as_ptr(), start.wrapping_add(4));
+
+    // a list wrapping around the end of the buffer, followed by the start of the next one.
+    ring.extend_from_slice(&[3, 0, 1, 0, 2]);
+    ring.read_type::<u32>().unwrap();
+    ring.extend_from_slice(&[0, 3, 2, 0, 4, 0]);
+    assert!(!ring.as_slices().1.is_empty());
+    assert_eq!(ring.read_type::<Vec<u16>>().unwrap(), vec![1, 2, 3]);
+    assert_eq!(ring.remaining(), 4);
+    assert_eq!(ring.as_slices().0.as_ptr(), start.wrapping_add(15));
+
+    // the second list is still incomplete, so nothing is consumed.
+    let err = ring.read_type::<Vec<u16>>().unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+    assert_eq!(ring.remaining(), 4);
+}
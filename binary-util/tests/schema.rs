@@ -0,0 +1,157 @@
+use binary_util::interfaces::{Reader, Writer};
+use binary_util::schema::{FieldHistory, FieldSchema, Schema, SchemaKind, VariantSchema};
+use binary_util::BinaryIo;
+
+/// A chunk of the world.
+///
+/// Sent when a player moves close enough.
+#[derive(BinaryIo)]
+#[binary_io(schema)]
+pub struct Chunk {
+    /// The x coordinate of the chunk.
+    pub x: i32,
+    pub z: i32,
+    #[skip]
+    pub cached: Option<u8>,
+    /// The raw block data.
+    pub data: Vec<u8>,
+}
+
+#[derive(BinaryIo)]
+#[binary_io(schema)]
+pub struct Profile {
+    #[renamed_from(nick)]
+    #[renamed_from(username)]
+    pub name: String,
+    #[added_in(2)]
+    #[removed_in(4)]
+    pub avatar: u32,
+}
+
+#[derive(BinaryIo)]
+#[binary_io(schema)]
+pub struct Position(
+    /// Blocks east of the origin.
+    pub f32,
+    pub std::primitive::f32,
+);
+
+#[derive(BinaryIo)]
+#[binary_io(schema)]
+#[repr(u8)]
+pub enum Status {
+    /// The server is accepting players.
+    Online,
+    Full(
+        /// The maximum amount of players.
+        u32,
+    ),
+}
+
+#[test]
+fn struct_schema() {
+    let schema = Chunk::schema();
+    assert_eq!(schema.name, "Chunk");
+    assert_eq!(
+        schema.doc,
+        Some("A chunk of the world.\n\nSent when a player moves close enough.")
+    );
+    assert_eq!(
+        schema.kind,
+        SchemaKind::Struct(&[
+            FieldSchema {
+                name: "x",
+                ty: "i32",
+                doc: Some("The x coordinate of the chunk."),
+                history: FieldHistory::NONE,
+            },
+            FieldSchema {
+                name: "z",
+                ty: "i32",
+                doc: None,
+                history: FieldHistory::NONE,
+            },
+            FieldSchema {
+                name: "data",
+                ty: "Vec<u8>",
+                doc: Some("The raw block data."),
+                history: FieldHistory::NONE,
+            },
+        ])
+    );
+}
+
+#[test]
+fn field_history_schema() {
+    let SchemaKind::Struct(fields) = Profile::schema().kind else {
+        unreachable!()
+    };
+    assert_eq!(
+        fields[0].history,
+        FieldHistory {
+            renamed_from: &["nick", "username"],
+            added_in: None,
+            removed_in: None,
+        }
+    );
+    let avatar = fields[1].history;
+    assert_eq!((avatar.added_in, avatar.removed_in), (Some(2), Some(4)));
+    assert!(!avatar.is_present_in(1));
+    assert!(avatar.is_present_in(3));
+    assert!(!avatar.is_present_in(4));
+}
+
+#[test]
+fn tuple_struct_schema() {
+    let schema = Position::schema();
+    assert_eq!(schema.doc, None);
+    assert_eq!(
+        schema.kind,
+        SchemaKind::Struct(&[
+            FieldSchema {
+                name: "0",
+                ty: "f32",
+                doc: Some("Blocks east of the origin."),
+                history: FieldHistory::NONE,
+            },
+            FieldSchema {
+                name: "1",
+                ty: "std::primitive::f32",
+                doc: None,
+                history: FieldHistory::NONE,
+            },
+        ])
+    );
+    // doc comments do not change the encoding.
+    let buf = Position(1.0, 2.0).write_to_bytes().unwrap();
+    assert_eq!(Position::read_from_slice(buf.as_slice()).unwrap().1, 2.0);
+}
+
+#[test]
+fn enum_schema() {
+    assert_eq!(
+        Status::schema().kind,
+        SchemaKind::Enum(&[
+            VariantSchema {
+                name: "Online",
+                doc: Some("The server is accepting players."),
+                fields: &[],
+            },
+            VariantSchema {
+                name: "Full",
+                doc: None,
+                fields: &[FieldSchema {
+                    name: "0",
+                    ty: "u32",
+                    doc: Some("The maximum amount of players."),
+                    history: FieldHistory::NONE,
+                }],
+            },
+        ])
+    );
+    let buf = Status::Full(20).write_to_bytes().unwrap();
+    assert!(matches!(
+        Status::read_from_slice(buf.as_slice()).unwrap(),
+        Status::Full(20)
+    ));
+}
@@ -0,0 +1,47 @@
+use binary_util::interfaces::{Reader, Writer};
+use binary_util::types::{u24, Ordered, Sequenced};
+
+#[test]
+fn serial_arithmetic() {
+    assert_eq!(u24(10).serial_diff(u24(10)), 0);
+    assert_eq!(u24(0).serial_diff(u24(u24::MAX)), 1);
+    assert_eq!(u24(u24::MAX).serial_diff(u24(0)), -1);
+    assert!(u24(0).is_newer_than(u24(u24::MAX)));
+    assert!(!u24(u24::MAX).is_newer_than(u24(0)));
+    assert!(!u24(7).is_newer_than(u24(7)));
+
+    // the largest distance in either direction.
+    assert_eq!(u24(0x7FFFFF).serial_diff(u24(0)), 0x7FFFFF);
+    assert_eq!(u24(0x800001).serial_diff(u24(0)), -0x7FFFFF);
+    // exactly half of the sequence space apart is ambiguous, and never newer.
+    assert!(!u24(0x800000).is_newer_than(u24(0)));
+    assert!(!u24(0).is_newer_than(u24(0x800000)));
+
+    assert_eq!(u24(u24::MAX).wrapping_add(1), u24(0));
+    assert_eq!(u24(u24::MAX).wrapping_add(5), u24(4));
+    assert_eq!(u24(3).wrapping_add(u32::MAX), u24(2));
+}
+
+#[test]
+fn sequenced_roundtrip() {
+    let value = Sequenced::new(u24(0x010203), 300u16);
+    let buf = value.write_to_bytes().unwrap();
+    assert_eq!(buf.as_slice(), &[3, 2, 1, 1, 44]);
+    assert_eq!(
+        Sequenced::<u16>::read_from_slice(buf.as_slice()).unwrap(),
+        value
+    );
+}
+
+#[test]
+fn ordered_roundtrip() {
+    let value = Ordered::new(u24(0x010203), 4, String::from("a"));
+    let buf = value.write_to_bytes().unwrap();
+    assert_eq!(buf.as_slice(), &[3, 2, 1, 4, 1, b'a']);
+    assert_eq!(
+        Ordered::<String>::read_from_slice(buf.as_slice()).unwrap(),
+        value
+    );
+    assert_eq!(value.distance_from(u24(0x010200)), 3);
+    assert_eq!(value.distance_from(u24(0x010204)), -1);
+}
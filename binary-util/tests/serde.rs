@@ -0,0 +1,47 @@
+#![cfg(all(feature = "json", feature = "msgpack"))]
+
+use std::collections::BTreeMap;
+
+use binary_util::interfaces::{Reader, Writer};
+use binary_util::io::BinaryErrorExt;
+use binary_util::types::{Json, MsgPack};
+use binary_util::BinaryIo;
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct StatusResponse {
+    id: u8,
+    status: Json<BTreeMap<String, u32>>,
+    tags: MsgPack<Vec<String>>,
+}
+
+#[test]
+fn serde_roundtrip() {
+    let packet = StatusResponse {
+        id: 1,
+        status: Json::new(BTreeMap::from([("players".to_string(), 20)])),
+        tags: MsgPack::new(vec!["pvp".to_string()]),
+    };
+    let buf = packet.write_to_bytes().unwrap();
+
+    let json = br#"{"players":20}"#;
+    assert_eq!(buf.as_slice()[1] as usize, json.len());
+    assert_eq!(&buf.as_slice()[2..2 + json.len()], json);
+    // a fixarray of one fixstr.
+    assert_eq!(
+        &buf.as_slice()[2 + json.len()..],
+        &[5, 0x91, 0xa3, b'p', b'v', b'p']
+    );
+
+    assert_eq!(
+        StatusResponse::read_from_slice(buf.as_slice()).unwrap(),
+        packet
+    );
+}
+
+#[test]
+fn invalid_json() {
+    let err = Json::<Vec<u8>>::read_from_slice(&[3, b'[', b'1', b',']).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    let source = std::error::Error::source(err.binary_error().unwrap()).unwrap();
+    assert!(source.downcast_ref::<serde_json::Error>().unwrap().is_eof());
+}
@@ -0,0 +1,69 @@
+#![cfg(feature = "stats")]
+use binary_util::interfaces::{Reader, Writer};
+use binary_util::io::{ByteReader, ByteWriter};
+use binary_util::pool::BytePool;
+use binary_util::BinaryIo;
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct Move {
+    entity: u64,
+    x: f32,
+    name: String,
+}
+
+#[test]
+fn writer_counts() {
+    let mut writer = ByteWriter::new();
+    writer.write_u16(1).unwrap();
+    writer.write_var_i64(-300).unwrap();
+    writer.write_u24(5u32).unwrap();
+    writer.write(&[1, 2, 3]).unwrap();
+
+    let stats = writer.stats();
+    assert_eq!(stats.bytes_written as usize, writer.as_slice().len());
+    assert_eq!(stats.varints_written, 1);
+    assert_eq!(stats.max_frame_size, 0);
+}
+
+#[test]
+fn reader_counts_frames() {
+    let packet = Move {
+        entity: 9,
+        x: 1.5,
+        name: "steve".into(),
+    };
+    let bytes = packet.write_to_bytes().unwrap();
+    assert_eq!(bytes.stats().max_frame_size, 18);
+
+    let mut reader = ByteReader::from(bytes.as_slice());
+    assert_eq!(reader.read_type::<Move>().unwrap(), packet);
+
+    let stats = reader.stats();
+    assert_eq!(stats.bytes_read, 18);
+    assert_eq!(stats.varints_read, 1);
+    assert_eq!(stats.max_frame_size, 18);
+}
+
+#[test]
+fn pool_allocations() {
+    let mut pool = BytePool::new(64);
+    let writer = pool.get();
+    assert_eq!(writer.stats().pool_allocations, 1);
+    pool.put(writer);
+
+    // the buffer is reused, so the pool allocates nothing for the second writer.
+    let writer = pool.get();
+    assert_eq!(writer.stats().pool_allocations, 0);
+    assert_eq!(ByteWriter::new().stats().pool_allocations, 0);
+    assert!(binary_util::stats::global().pool_allocations >= 1);
+}
+
+#[test]
+fn global_registry() {
+    let before = binary_util::stats::global();
+    u32::read_from_slice(&[0, 0, 0, 1]).unwrap();
+
+    let after = binary_util::stats::global();
+    assert!(after.bytes_read >= before.bytes_read + 4);
+    assert!(after.max_frame_size >= 4);
+}
@@ -0,0 +1,156 @@
+use binary_util::interfaces::Reader;
+use binary_util::io::{ByteReader, ByteStream};
+use binary_util::BinaryIo;
+use bytes::Bytes;
+use std::io::{ErrorKind, Read};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// A source that yields one byte per read, and blocks once before every byte.
+struct Trickle {
+    data: Vec<u8>,
+    pos: usize,
+    blocked: bool,
+}
+
+impl Trickle {
+    fn new(data: &[u8]) -> Self {
+        Self {
+            data: data.to_vec(),
+            pos: 0,
+            blocked: false,
+        }
+    }
+}
+
+impl Read for Trickle {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if !self.blocked {
+            self.blocked = true;
+            return Err(ErrorKind::WouldBlock.into());
+        }
+        self.blocked = false;
+        if self.pos == self.data.len() {
+            return Ok(0);
+        }
+        buf[0] = self.data[self.pos];
+        self.pos += 1;
+        Ok(1)
+    }
+}
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct Ping {
+    id: u8,
+    time: u32,
+}
+
+#[test]
+fn read_type_retries_would_block() {
+    let mut stream = ByteStream::new(Trickle::new(&[1, 0, 0, 0, 9, 2]));
+
+    let ping = loop {
+        match stream.read_type::<Ping>() {
+            Ok(ping) => break ping,
+            Err(e) if e.kind() == ErrorKind::WouldBlock => continue,
+            Err(e) => panic!("{}", e),
+        }
+    };
+    assert_eq!(ping, Ping { id: 1, time: 9 });
+    assert!(stream.buffered().is_empty());
+}
+
+#[test]
+fn read_type_keeps_trailing_bytes() {
+    let mut stream = ByteStream::new(&[7, 0, 0, 0, 1, 8, 0, 0][..]);
+    assert_eq!(stream.read_type::<Ping>().unwrap(), Ping { id: 7, time: 1 });
+    assert_eq!(stream.buffered(), &[8, 0, 0]);
+
+    let err = stream.read_type::<Ping>().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    assert_eq!(stream.buffered(), &[8, 0, 0]);
+}
+
+/// A payload which counts how many times it is parsed.
+#[derive(Debug)]
+struct Counted(Bytes);
+
+static PARSES: AtomicUsize = AtomicUsize::new(0);
+
+impl Reader<Counted> for Counted {
+    fn read(buf: &mut ByteReader) -> Result<Counted, std::io::Error> {
+        PARSES.fetch_add(1, Ordering::Relaxed);
+        let len = buf.read_u32()? as usize;
+        Ok(Counted(buf.read_bytes(len)?))
+    }
+}
+
+/// A source that yields one byte per read, without blocking.
+struct OneByte<'a>(&'a [u8]);
+
+impl Read for OneByte<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let len = buf.len().min(1);
+        self.0.read(&mut buf[..len])
+    }
+}
+
+#[test]
+fn read_type_fills_missing_bytes_at_once() {
+    let mut data = vec![0, 0, 4, 0];
+    data.extend((0..1024).map(|i| i as u8));
+    data.extend([0, 0, 0, 1, 9]);
+    let mut stream = ByteStream::new(OneByte(&data));
+
+    // once the length is known, the payload is filled in a single call rather than reparsed for every byte.
+    let payload = stream.read_type::<Counted>().unwrap();
+    assert_eq!(payload.0.len(), 1024);
+    assert_eq!(PARSES.load(Ordering::Relaxed), 3);
+
+    // the payload shares the buffer, which is kept as is.
+    assert_eq!(&stream.read_type::<Counted>().unwrap().0[..], &[9]);
+    assert!(stream.buffered().is_empty());
+}
+
+#[test]
+fn read_frame() {
+    let mut stream = ByteStream::new(&[1, 2, 3, 4, 5][..]);
+    stream.fill_at_least(2).unwrap();
+    assert_eq!(&stream.read_frame(3).unwrap()[..], &[1, 2, 3]);
+    assert!(stream.read_frame(3).is_err());
+
+    let mut rest = Vec::new();
+    stream.read_to_end(&mut rest).unwrap();
+    assert_eq!(rest, vec![4, 5]);
+}
+
+#[test]
+fn read_type_timeout() {
+    use std::io::Write;
+    use std::net::{TcpListener, TcpStream};
+    use std::time::Duration;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let mut peer = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let mut stream = ByteStream::new(listener.accept().unwrap().0);
+    stream
+        .set_read_timeout(Some(Duration::from_secs(30)))
+        .unwrap();
+
+    // half of a ping arrives, and the peer goes quiet.
+    peer.write_all(&[1, 0, 0]).unwrap();
+    let err = stream
+        .read_type_timeout::<Ping>(Duration::from_millis(50))
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::WouldBlock);
+    assert_eq!(stream.buffered(), &[1, 0, 0]);
+    assert_eq!(
+        stream.read_timeout().unwrap(),
+        Some(Duration::from_secs(30))
+    );
+
+    peer.write_all(&[0, 9]).unwrap();
+    let ping = stream
+        .read_type_timeout::<Ping>(Duration::from_secs(5))
+        .unwrap();
+    assert_eq!(ping, Ping { id: 1, time: 9 });
+}
@@ -36,3 +36,12 @@ fn write_read_buffer() -> Result<(), BinaryError> {
     );
     Ok(())
 }
+
+#[test]
+fn invalid_discriminant_is_an_error() {
+    use binary_util::interfaces::Reader;
+
+    assert!(Test::compose(&[7], &mut 0).is_err());
+    let err = Test::read_from_slice(&[7]).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
@@ -0,0 +1,122 @@
+#![cfg(feature = "tagged")]
+use binary_util::interfaces::{Reader, Writer};
+use binary_util::io::ByteReader;
+use binary_util::types::tagged::{Compound, Nbt, Tag, TAG_END, TAG_INT, TAG_INT_ARRAY, TAG_LIST};
+
+// The "hello world" test file from the NBT specification.
+const HELLO_WORLD: &[u8] = &[
+    0x0a, 0x00, 0x0b, b'h', b'e', b'l', b'l', b'o', b' ', b'w', b'o', b'r', b'l', b'd', 0x08, 0x00,
+    0x04, b'n', b'a', b'm', b'e', 0x00, 0x09, b'B', b'a', b'n', b'a', b'n', b'r', b'a', b'm', b'a',
+    0x00,
+];
+
+#[test]
+fn read_hello_world() {
+    let nbt = ByteReader::from(HELLO_WORLD).read_nbt().unwrap();
+    assert_eq!(nbt.name, "hello world");
+    assert_eq!(nbt.root.len(), 1);
+    assert_eq!(
+        nbt.root.get("name").and_then(Tag::as_str),
+        Some("Bananrama")
+    );
+    assert_eq!(nbt.write_to_bytes().unwrap().as_slice(), HELLO_WORLD);
+}
+
+#[test]
+fn build_and_round_trip() {
+    let level = Compound::new()
+        .with("flag", true)
+        .with("seed", -42i64)
+        .with("gravity", 0.08f64)
+        .with("heights", vec![1i32, 2, 3])
+        .with("blocks", vec![-1i8, 0, 1])
+        .with("longs", vec![i64::MAX, i64::MIN])
+        .with_compound("spawn", |c| c.with("x", 1i32).with("y", 64i16))
+        .with_list(
+            "players",
+            [
+                Compound::new().with("name", "a"),
+                Compound::new().with("name", "b"),
+            ],
+        )
+        .with_list("empty", Vec::<Tag>::new());
+    let nbt = Nbt::new("", level);
+
+    let bytes = nbt.write_to_bytes().unwrap();
+    let read = Nbt::read_from_slice(bytes.as_slice()).unwrap();
+    assert_eq!(read, nbt);
+    assert_eq!(read.root.get("flag"), Some(&Tag::Byte(1)));
+    assert_eq!(read.root.get("heights").map(Tag::id), Some(TAG_INT_ARRAY));
+    assert_eq!(
+        read.root
+            .get("spawn")
+            .and_then(Tag::as_compound)
+            .and_then(|c| c.get("y"))
+            .and_then(Tag::as_i64),
+        Some(64)
+    );
+    assert_eq!(
+        read.root
+            .get("players")
+            .and_then(Tag::as_list)
+            .map(<[Tag]>::len),
+        Some(2)
+    );
+
+    // a single unnamed tag, as sent over the network.
+    let tag = Tag::Compound(nbt.root.clone());
+    let bytes = tag.write_to_bytes().unwrap();
+    assert_eq!(Tag::read_from_slice(bytes.as_slice()).unwrap(), tag);
+}
+
+#[test]
+fn compound_keeps_insertion_order() {
+    let mut compound: Compound = [("b", 1i32), ("a", 2i32)].into_iter().collect();
+    assert_eq!(compound.insert("b", 3i32), Some(Tag::Int(1)));
+    assert_eq!(
+        compound.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+        ["b", "a"]
+    );
+    assert_eq!(compound.remove("b"), Some(Tag::Int(3)));
+    assert!(!compound.contains("b"));
+}
+
+#[test]
+fn mixed_list_is_rejected() {
+    let tag = Tag::List(vec![Tag::Int(1), Tag::Short(2)]);
+    assert!(tag.write_to_bytes().is_err());
+}
+
+#[test]
+fn invalid_payloads_are_rejected() {
+    // negative list length
+    assert!(Tag::read_from_slice(&[TAG_LIST, TAG_INT, 0xff, 0xff, 0xff, 0xff]).is_err());
+    // list of end tags with items
+    assert!(Tag::read_from_slice(&[TAG_LIST, TAG_END, 0, 0, 0, 1]).is_err());
+    // unknown tag id
+    assert!(Tag::read_from_slice(&[13, 0]).is_err());
+    // root is not a compound
+    assert!(Nbt::read_from_slice(&[TAG_INT, 0, 0, 0, 0, 0, 1]).is_err());
+    // huge array length with no data does not allocate up front
+    assert!(Tag::read_from_slice(&[11, 0x7f, 0xff, 0xff, 0xff]).is_err());
+    // a compound naming two tags "a"
+    let duplicate = [
+        10, TAG_INT, 0, 1, b'a', 0, 0, 0, 1, TAG_INT, 0, 1, b'a', 0, 0, 0, 2, TAG_END,
+    ];
+    let err = Tag::read_from_slice(&duplicate).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn nesting_is_limited_by_max_depth() {
+    let mut tag = Tag::Compound(Compound::new());
+    for _ in 0..8 {
+        tag = Tag::List(vec![tag]);
+    }
+    let bytes = tag.write_to_bytes().unwrap();
+
+    let mut buf = ByteReader::from(bytes.as_slice()).with_max_depth(4);
+    assert!(Tag::read(&mut buf).is_err());
+    let mut buf = ByteReader::from(bytes.as_slice()).with_max_depth(16);
+    assert_eq!(Tag::read(&mut buf).unwrap(), tag);
+}
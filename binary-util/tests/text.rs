@@ -0,0 +1,78 @@
+use binary_util::io::{ByteReader, ByteWriter};
+use std::io::ErrorKind;
+
+#[test]
+fn read_line_strips_endings() {
+    let mut reader = ByteReader::from(&b"QUERY\r\nPING\n"[..]);
+    assert_eq!(reader.read_line().unwrap(), "QUERY");
+    assert_eq!(reader.read_line().unwrap(), "PING");
+    assert!(reader.as_slice().is_empty());
+}
+
+#[test]
+fn read_line_incomplete_is_not_consumed() {
+    let mut reader = ByteReader::from(&b"partial"[..]);
+    let err = reader.read_line().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    assert_eq!(reader.as_slice(), b"partial");
+}
+
+#[test]
+fn read_line_invalid_utf8() {
+    let mut reader = ByteReader::from(&[0xff, 0xfe, b'\n'][..]);
+    assert_eq!(
+        reader.read_line().unwrap_err().kind(),
+        ErrorKind::InvalidData
+    );
+}
+
+#[test]
+fn lines_yields_trailing_fragment() {
+    let mut reader = ByteReader::from(&b"a\nb\r\n\nc"[..]);
+    let lines = reader.lines().collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(lines, vec!["a", "b", "", "c"]);
+}
+
+#[test]
+fn read_ascii_u32_stops_at_non_digit() {
+    let mut reader = ByteReader::from(&b"1024 rest"[..]);
+    assert_eq!(reader.read_ascii_u32().unwrap(), 1024);
+    assert_eq!(reader.as_slice(), b" rest");
+}
+
+#[test]
+fn read_ascii_u32_errors() {
+    let mut reader = ByteReader::from(&b"abc"[..]);
+    assert_eq!(
+        reader.read_ascii_u32().unwrap_err().kind(),
+        ErrorKind::InvalidData
+    );
+    assert_eq!(reader.as_slice(), b"abc");
+
+    let mut reader = ByteReader::from(&b"4294967296"[..]);
+    assert_eq!(
+        reader.read_ascii_u32().unwrap_err().kind(),
+        ErrorKind::InvalidData
+    );
+
+    let mut reader = ByteReader::from(&b"4294967295"[..]);
+    assert_eq!(reader.read_ascii_u32().unwrap(), u32::MAX);
+}
+
+#[test]
+fn mixed_text_and_binary() {
+    let mut writer = ByteWriter::new();
+    writer.write_line("LEN").unwrap();
+    writer.write_ascii_u32(5).unwrap();
+    writer.write_line("").unwrap();
+    writer.write(&[1, 2, 3, 4, 5]).unwrap();
+
+    let mut reader = ByteReader::from(writer.as_slice());
+    assert_eq!(reader.read_line().unwrap(), "LEN");
+    let len = reader.read_ascii_u32().unwrap();
+    assert_eq!(reader.read_line().unwrap(), "");
+    assert_eq!(
+        &reader.read_bytes(len as usize).unwrap()[..],
+        &[1, 2, 3, 4, 5]
+    );
+}
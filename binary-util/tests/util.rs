@@ -0,0 +1,26 @@
+use binary_util::interfaces::{Reader, Writer};
+use binary_util::util::{ct_eq, Secret};
+
+#[test]
+fn ct_eq_compares_every_byte() {
+    assert!(ct_eq(&[], &[]));
+    assert!(ct_eq(&[1, 2, 3], &[1, 2, 3]));
+    assert!(!ct_eq(&[1, 2, 3], &[0, 2, 3]));
+    assert!(!ct_eq(&[1, 2, 3], &[1, 2, 4]));
+    assert!(!ct_eq(&[1, 2, 3], &[1, 2]));
+}
+
+#[test]
+fn secret_is_encoded_like_inner() {
+    let secret = Secret::new(vec![0xde_u8, 0xad]);
+    let buf = secret.write_to_bytes().unwrap();
+    assert_eq!(
+        buf.as_slice(),
+        vec![0xde_u8, 0xad].write_to_bytes().unwrap().as_slice()
+    );
+
+    let read = Secret::<Vec<u8>>::read_from_slice(buf.as_slice()).unwrap();
+    assert_eq!(read, secret);
+    assert_ne!(read, Secret::new(vec![0xde, 0xae]));
+    assert_eq!(format!("{:?}", read), "Secret([REDACTED])");
+}
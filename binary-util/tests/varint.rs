@@ -1,4 +1,4 @@
-use binary_util::io::{ByteReader, ByteWriter};
+use binary_util::io::{BinaryErrorExt, ByteReader, ByteWriter};
 
 pub const FIVE_BYTE_VARINT: &[u8] = &[255, 255, 255, 255, 7]; // 2147483647
 pub const THREE_BYTE_VARINT: &[u8] = &[255, 255, 127]; // 2097151
@@ -111,3 +111,105 @@ fn var_int_32_overflow() {
     let mut buf = ByteWriter::new();
     buf.write_var_i32(i32::MAX).unwrap();
 }
+
+#[test]
+fn var_u32_len() {
+    assert_eq!(ByteWriter::var_u32_len(0), 1);
+    assert_eq!(ByteWriter::var_u32_len(127), 1);
+    assert_eq!(ByteWriter::var_u32_len(255), 2);
+    assert_eq!(ByteWriter::var_u32_len(2097151), 3);
+    assert_eq!(ByteWriter::var_u32_len(2147483647), 5);
+}
+
+#[test]
+fn write_var_u32_at() {
+    let mut buf = ByteWriter::new();
+    buf.write_u8(1).unwrap();
+    buf.write(&[0; 5]).unwrap();
+    buf.write_u8(2).unwrap();
+    buf.write_var_u32_at(1, 255).unwrap();
+    assert_eq!(buf.as_slice(), &[1, 255, 129, 128, 128, 0, 2]);
+
+    let mut reader = ByteReader::from(buf.as_slice());
+    assert_eq!(reader.read_u8().unwrap(), 1);
+    assert_eq!(reader.read_var_u32().unwrap(), 255);
+    assert_eq!(reader.read_u8().unwrap(), 2);
+
+    // the slot must be within the written buffer
+    let err = buf.write_var_u32_at(3, 255).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    assert_eq!(err.offset(), Some(3));
+}
+
+#[test]
+fn insert_var_u32_at() {
+    let mut buf = ByteWriter::new();
+    buf.write_u8(1).unwrap();
+    buf.write(&[2; 255]).unwrap();
+    buf.insert_var_u32_at(1, 255).unwrap();
+    assert_eq!(&buf.as_slice()[..4], &[1, 255, 1, 2]);
+    assert_eq!(buf.len(), 1 + 2 + 255);
+
+    // the canonical prefix is accepted by hardened readers.
+    let mut reader = ByteReader::hardened(buf.as_slice());
+    assert_eq!(reader.read_u8().unwrap(), 1);
+    assert_eq!(reader.read_var_u32().unwrap(), 255);
+    assert_eq!(&reader.read_bytes(255).unwrap()[..], &[2; 255][..]);
+
+    buf.insert_var_u32_at(buf.len(), 0).unwrap();
+    assert_eq!(buf.as_slice().last(), Some(&0));
+    assert_eq!(
+        buf.insert_var_u32_at(buf.len() + 1, 0)
+            .unwrap_err()
+            .offset(),
+        Some(buf.len() + 1)
+    );
+}
+
+#[test]
+fn big_endian_var_ints() {
+    let mut buf = ByteWriter::new();
+    buf.write_var_u32_be(300).unwrap();
+    buf.write_var_u32_be(u32::MAX).unwrap();
+    buf.write_var_i32_be(-1).unwrap();
+    buf.write_var_u64_be(u64::MAX).unwrap();
+    buf.write_var_i64_be(i64::MIN).unwrap();
+    assert_eq!(&buf.as_slice()[..8], &[130, 44, 143, 255, 255, 255, 127, 1]);
+
+    let mut reader = ByteReader::from(buf.as_slice());
+    assert_eq!(reader.read_var_u32_be().unwrap(), 300);
+    assert_eq!(reader.read_var_u32_be().unwrap(), u32::MAX);
+    assert_eq!(reader.read_var_i32_be().unwrap(), -1);
+    assert_eq!(reader.read_var_u64_be().unwrap(), u64::MAX);
+    assert_eq!(reader.read_var_i64_be().unwrap(), i64::MIN);
+
+    // an incomplete var-int is not consumed.
+    let mut reader = ByteReader::from(&[130][..]);
+    assert!(reader.read_var_u32_be().is_err());
+    assert_eq!(reader.as_slice(), &[130]);
+    assert!(ByteReader::from(&[255; 6][..]).read_var_u32_be().is_err());
+}
+
+#[test]
+fn var_int_order_types() {
+    use binary_util::interfaces::{Reader, Writer};
+    use binary_util::types::{vari32, varu32, BE, LE};
+
+    assert_eq!(
+        LE(varu32(300)).write_to_bytes().unwrap().as_slice(),
+        varu32(300).write_to_bytes().unwrap().as_slice()
+    );
+    let buf = BE(varu32(300)).write_to_bytes().unwrap();
+    assert_eq!(buf.as_slice(), &[130, 44]);
+    assert_eq!(
+        BE::<varu32>::read_from_slice(buf.as_slice()).unwrap(),
+        BE(varu32(300))
+    );
+
+    let buf = BE(vari32(-65)).write_to_bytes().unwrap();
+    assert_eq!(buf.as_slice(), &[129, 1]);
+    assert_eq!(
+        BE::<vari32>::read_from_slice(buf.as_slice()).unwrap().0,
+        vari32(-65)
+    );
+}
@@ -0,0 +1,77 @@
+use binary_util::io::{ByteReader, ByteWriter, ReadExt, WriteExt};
+use binary_util::varint::{self, Decode, Decoder, MAX_VAR_U64_LEN};
+
+#[test]
+fn decoder_byte_at_a_time() {
+    let mut writer = ByteWriter::new();
+    writer.write_var_u64(u64::MAX).unwrap();
+    writer.write_var_u32(300).unwrap();
+
+    let mut decoder = Decoder::u64();
+    let mut values = Vec::new();
+    for byte in &writer.as_slice()[..MAX_VAR_U64_LEN] {
+        if let Decode::Complete(value) = decoder.push(*byte).unwrap() {
+            values.push(value);
+        } else {
+            assert!(!decoder.is_empty());
+        }
+    }
+    assert_eq!(values, vec![u64::MAX]);
+    assert!(decoder.is_empty());
+
+    let mut decoder = Decoder::u32();
+    assert_eq!(decoder.push(172).unwrap(), Decode::NeedMore);
+    assert_eq!(decoder.len(), 1);
+    assert_eq!(decoder.push(2).unwrap(), Decode::Complete(300));
+}
+
+#[test]
+fn decoder_overflow_resets() {
+    let mut decoder = Decoder::u32();
+    for _ in 0..4 {
+        assert_eq!(decoder.push(0xFF).unwrap(), Decode::NeedMore);
+    }
+    assert!(decoder.push(0xFF).is_err());
+    assert!(decoder.is_empty());
+    assert_eq!(decoder.push(5).unwrap(), Decode::Complete(5));
+}
+
+#[test]
+fn encode_matches_writer() {
+    for num in [0, 1, 127, 128, 300, u32::MAX as u64, u64::MAX] {
+        let mut out = [0; MAX_VAR_U64_LEN];
+        let encoded = varint::encode(num, &mut out);
+        assert_eq!(encoded.len(), varint::encoded_len(num));
+
+        let mut buf: Vec<u8> = Vec::new();
+        buf.write_var_u64(num).unwrap();
+        assert_eq!(encoded, &buf[..]);
+        assert_eq!((&buf[..]).read_var_u64().unwrap(), num);
+    }
+}
+
+#[test]
+fn zigzag_matches_signed_var_ints() {
+    for num in [0, -1, 1, i32::MIN, i32::MAX] {
+        let mut writer = ByteWriter::new();
+        writer.write_var_i32(num).unwrap();
+        writer.write_var_i64(num as i64).unwrap();
+        assert_eq!(
+            varint::zigzag_decode(varint::zigzag_encode(num as i64)),
+            num as i64
+        );
+
+        let mut reader = ByteReader::from(writer.as_slice());
+        assert_eq!(reader.read_var_i32().unwrap(), num);
+        assert_eq!(reader.read_var_i64().unwrap(), num as i64);
+    }
+}
+
+#[test]
+fn var_int_write_is_atomic() {
+    let mut buf = [0u8; 2];
+    let mut slice = &mut buf[..];
+    assert!(slice.write_var_u32(u32::MAX).is_err());
+    assert_eq!(slice.len(), 2);
+    assert_eq!(buf, [0, 0]);
+}
@@ -0,0 +1,51 @@
+use binary_util::interfaces::{Reader, Writer};
+use binary_util::io::{ByteReader, ByteWriter};
+use binary_util::types::{i40, i48, i56, u40, u48, u56, BE, LE};
+use binary_util::BinaryIo;
+
+#[derive(BinaryIo, Debug, PartialEq)]
+struct Timestamps {
+    a: u40,
+    b: LE<u48>,
+    c: BE<u56>,
+}
+
+#[test]
+fn wide_uint_encode() {
+    let packet = Timestamps {
+        a: u40(0x01_0203_0405),
+        b: LE(u48(0x0102_0304_0506)),
+        c: BE(u56(0x01_0203_0405_0607)),
+    };
+
+    let bytes = packet.write_to_bytes().unwrap();
+    assert_eq!(
+        bytes.as_slice(),
+        &[1, 2, 3, 4, 5, 6, 5, 4, 3, 2, 1, 1, 2, 3, 4, 5, 6, 7]
+    );
+
+    let mut reader = ByteReader::from(bytes.as_slice());
+    assert_eq!(Timestamps::read(&mut reader).unwrap(), packet);
+}
+
+#[test]
+fn wide_int_sign_extension() {
+    let mut writer = ByteWriter::new();
+    i40(-2).write(&mut writer).unwrap();
+    LE(i48(-3)).write(&mut writer).unwrap();
+    i56(i56::MIN).write(&mut writer).unwrap();
+    assert_eq!(writer.as_slice().len(), 5 + 6 + 7);
+    assert_eq!(&writer.as_slice()[..5], &[0xff, 0xff, 0xff, 0xff, 0xfe]);
+
+    let mut reader = ByteReader::from(writer.as_slice());
+    assert_eq!(i40::read(&mut reader).unwrap(), i40(-2));
+    assert_eq!(LE::<i48>::read(&mut reader).unwrap().0, i48(-3));
+    assert_eq!(i56::read(&mut reader).unwrap(), i56(i56::MIN));
+}
+
+#[test]
+fn wide_int_range() {
+    assert_eq!(u48::MAX, (1 << 48) - 1);
+    assert_eq!(i40::MIN, -(1 << 39));
+    assert!(std::panic::catch_unwind(|| u40::new(1 << 40)).is_err());
+}
@@ -0,0 +1,105 @@
+use std::io::Write;
+
+use binary_util::interfaces::{Reader, Writer};
+use binary_util::types::Raw;
+use binary_util::BinaryIo;
+
+/// A sink which records the size of every write it receives.
+#[derive(Default)]
+struct Sink {
+    data: Vec<u8>,
+    writes: Vec<usize>,
+}
+
+impl Write for Sink {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.data.extend_from_slice(buf);
+        self.writes.push(buf.len());
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn streamed<T: Writer>(value: &T) -> Sink {
+    let mut sink = Sink::default();
+    value.write_into_stream(&mut sink).unwrap();
+    assert_eq!(sink.data, value.write_to_bytes().unwrap().as_slice());
+    sink
+}
+
+#[derive(BinaryIo)]
+struct Position(i32, i32);
+
+#[derive(BinaryIo)]
+struct LevelChunk {
+    position: Position,
+    #[bool_width(bit)]
+    cached: bool,
+    #[bool_width(bit)]
+    lit: bool,
+    #[len_of(biomes)]
+    biome_count: u16,
+    biomes: Vec<u8>,
+    blocks: Vec<u8>,
+    entities: Option<String>,
+    data: Raw,
+}
+
+#[test]
+fn derived_struct_streams_fields() {
+    let chunk = LevelChunk {
+        position: Position(-4, 9),
+        cached: true,
+        lit: false,
+        biome_count: 0,
+        biomes: vec![1, 2, 3],
+        blocks: vec![7; 20_000],
+        entities: Some("zombie".into()),
+        data: Raw::new(vec![9; 100_000].into()),
+    };
+    let sink = streamed(&chunk);
+    // the raw payload is written to the sink as is, and the blocks in chunks rather than all at once.
+    assert!(sink.writes.contains(&100_000));
+    assert!(sink.writes.iter().all(|&len| len <= 100_000));
+    assert!(sink.writes.iter().filter(|&&len| len >= 8 * 1024).count() >= 3);
+
+    let read = LevelChunk::read_from_slice(&sink.data).unwrap();
+    assert_eq!(read.biome_count, 3);
+    assert_eq!(read.entities.as_deref(), Some("zombie"));
+
+    // references and boxes stream the value the same way, even behind `dyn Writer`.
+    let borrowed: &dyn Writer = &chunk;
+    assert_eq!(streamed(&borrowed).writes, sink.writes);
+    assert_eq!(streamed(&&chunk).writes, sink.writes);
+    let boxed: Box<dyn Writer> = Box::new(chunk);
+    assert_eq!(streamed(&boxed).writes, sink.writes);
+}
+
+#[derive(BinaryIo)]
+struct Checksummed {
+    payload: Vec<u8>,
+    #[crc_of(payload, crc32)]
+    crc: u32,
+}
+
+#[test]
+fn position_dependent_structs_are_buffered() {
+    let value = Checksummed {
+        payload: vec![1, 2, 3, 4],
+        crc: 0,
+    };
+    let sink = streamed(&value);
+    assert_eq!(sink.writes.len(), 1);
+    assert_ne!(Checksummed::read_from_slice(&sink.data).unwrap().crc, 0);
+}
+
+#[test]
+fn byte_payloads_are_not_copied() {
+    let bytes = bytes::Bytes::from(vec![3; 300]);
+    let sink = streamed(&bytes);
+    assert_eq!(sink.writes, vec![2, 300]);
+    assert_eq!(streamed(&None::<u8>).data, vec![0]);
+}
@@ -0,0 +1,31 @@
+use binary_util::interfaces::Writer;
+use binary_util::io::ByteWriter;
+use binary_util::types::WriterFn;
+
+#[test]
+fn writes_with_closure() {
+    let name = "abc".to_string();
+    let payload = WriterFn::new(|buf: &mut ByteWriter| {
+        buf.write_u8(1)?;
+        name.write(buf)
+    });
+    assert_eq!(
+        payload.write_to_bytes().unwrap().as_slice(),
+        &[1, 3, b'a', b'b', b'c']
+    );
+    assert_eq!(format!("{:?}", payload), "WriterFn");
+
+    // written anywhere a writer is expected.
+    let mut buf = ByteWriter::new();
+    buf.write_type(&Some(payload)).unwrap();
+    assert_eq!(buf.as_slice(), &[1, 1, 3, b'a', b'b', b'c']);
+}
+
+#[test]
+fn errors_are_returned() {
+    let failing = WriterFn::new(|_: &mut ByteWriter| Err(std::io::Error::other("no")));
+    assert!(failing.write_to_bytes().is_err());
+    assert!(vec![Box::new(failing) as Box<dyn Writer>]
+        .write_to_bytes()
+        .is_err());
+}